@@ -0,0 +1,9 @@
+-- Per-partition consumer offsets for streaming sources (e.g., Kafka).
+-- next_offset is the first offset that has not yet been cached.
+CREATE TABLE table_offsets (
+    table_id INTEGER NOT NULL,
+    partition_id INTEGER NOT NULL,
+    next_offset BIGINT NOT NULL,
+    FOREIGN KEY (table_id) REFERENCES tables(id),
+    PRIMARY KEY (table_id, partition_id)
+);
@@ -0,0 +1,17 @@
+-- How schema refresh handles breaking changes (dropped or retyped columns):
+-- 'block', 'auto_apply' or 'apply_and_purge'.
+ALTER TABLE connections ADD COLUMN schema_change_policy TEXT NOT NULL DEFAULT 'auto_apply';
+
+-- Schema history per table. changes_json holds the column-level diff against the
+-- previous version; status is 'applied', or 'blocked' when the policy held it back.
+CREATE TABLE table_schema_versions (
+    id SERIAL PRIMARY KEY,
+    table_id INTEGER NOT NULL,
+    version INTEGER NOT NULL,
+    arrow_schema_json TEXT NOT NULL,
+    changes_json TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (table_id) REFERENCES tables(id),
+    UNIQUE (table_id, version)
+);
@@ -0,0 +1,3 @@
+-- When schema refresh last found the table missing from the source. NULL while the
+-- source still has the table; stale tables keep their metadata until pruned.
+ALTER TABLE tables ADD COLUMN stale_since TIMESTAMP;
@@ -0,0 +1,5 @@
+-- Size estimates reported by the source at discovery, used for query planning.
+ALTER TABLE tables ADD COLUMN estimated_rows BIGINT;
+ALTER TABLE tables ADD COLUMN estimated_bytes BIGINT;
+-- Exact row count and column min/max of the cached parquet, read from its footers (JSON).
+ALTER TABLE tables ADD COLUMN cache_stats_json TEXT;
@@ -0,0 +1,4 @@
+-- When a query last scanned the table
+ALTER TABLE tables ADD COLUMN last_accessed_at TIMESTAMP;
+-- How many times queries scanned the table
+ALTER TABLE tables ADD COLUMN access_count BIGINT NOT NULL DEFAULT 0;
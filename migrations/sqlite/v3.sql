@@ -0,0 +1,3 @@
+-- Source snapshot (e.g., Iceberg snapshot ID) that the cached parquet reflects.
+-- NULL when the table is not cached or the source has no snapshots.
+ALTER TABLE tables ADD COLUMN source_snapshot_id BIGINT;
@@ -0,0 +1,3 @@
+-- Source table type as reported in information_schema.tables ('BASE TABLE', 'VIEW',
+-- 'MATERIALIZED VIEW', ...). Drives per-type caching policy.
+ALTER TABLE tables ADD COLUMN table_type TEXT NOT NULL DEFAULT 'BASE TABLE';
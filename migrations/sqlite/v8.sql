@@ -0,0 +1,21 @@
+-- Named snapshots pinning the cached versions of a set of tables. Refresh keeps pinned
+-- version directories instead of deleting them, until the snapshot is expired.
+CREATE TABLE cache_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Table versions pinned by a snapshot, with the schema they were written with.
+CREATE TABLE cache_snapshot_tables (
+    snapshot_id INTEGER NOT NULL,
+    connection_id INTEGER NOT NULL,
+    schema_name TEXT NOT NULL,
+    table_name TEXT NOT NULL,
+    parquet_path TEXT NOT NULL,
+    arrow_schema_json TEXT NOT NULL,
+    FOREIGN KEY (snapshot_id) REFERENCES cache_snapshots(id),
+    PRIMARY KEY (snapshot_id, connection_id, schema_name, table_name)
+);
+
+CREATE INDEX idx_cache_snapshot_tables_path ON cache_snapshot_tables(parquet_path);
@@ -0,0 +1,3 @@
+-- Table patterns (JSON array) fetched into the cache in the background after discovery
+-- and on startup. NULL means no warm-up.
+ALTER TABLE connections ADD COLUMN warm_up_json TEXT;
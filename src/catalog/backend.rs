@@ -21,7 +21,10 @@
 //! let connections = backend.list_connections().await?;
 //! ```
 
-use crate::catalog::manager::{ConnectionInfo, TableInfo};
+use crate::catalog::manager::{
+    CacheSnapshot, ConnectionInfo, PartitionOffset, SchemaChangePolicy, SchemaVersion,
+    SchemaVersionStatus, SnapshotTable, TableInfo, TableKind,
+};
 use anyhow::{anyhow, Result};
 use sqlx::{
     query, query_as, query_scalar, ColumnIndex, Database, Decode, Encode, Executor, FromRow,
@@ -78,10 +81,19 @@ where
     DB: CatalogDatabase,
     ConnectionInfo: for<'r> FromRow<'r, DB::Row>,
     TableInfo: for<'r> FromRow<'r, DB::Row>,
+    PartitionOffset: for<'r> FromRow<'r, DB::Row>,
+    SchemaVersion: for<'r> FromRow<'r, DB::Row>,
+    CacheSnapshot: for<'r> FromRow<'r, DB::Row>,
+    SnapshotTable: for<'r> FromRow<'r, DB::Row>,
     for<'q> &'q str: Encode<'q, DB> + Type<DB>,
     for<'q> String: Encode<'q, DB> + Type<DB>,
     for<'q> i32: Encode<'q, DB> + Type<DB>,
+    for<'q> i64: Encode<'q, DB> + Type<DB>,
+    for<'q> Option<&'q str>: Encode<'q, DB> + Type<DB>,
+    for<'q> Option<i64>: Encode<'q, DB> + Type<DB>,
     for<'r> i32: Decode<'r, DB>,
+    for<'r> i64: Decode<'r, DB>,
+    for<'r> String: Decode<'r, DB>,
     for<'q> <DB as Database>::Arguments<'q>: IntoArguments<'q, DB> + Send,
     for<'c> &'c Pool<DB>: Executor<'c, Database = DB>,
     usize: ColumnIndex<DB::Row>,
@@ -248,7 +260,10 @@ where
     pub async fn list_tables(&self, connection_id: Option<i32>) -> Result<Vec<TableInfo>> {
         let mut sql = String::from(
             "SELECT id, connection_id, schema_name, table_name, parquet_path, \
-             CAST(last_sync AS TEXT) as last_sync, arrow_schema_json \
+             CAST(last_sync AS TEXT) as last_sync, arrow_schema_json, \
+             CAST(stale_since AS TEXT) as stale_since, table_type, \
+             estimated_rows, estimated_bytes, cache_stats_json, \
+             CAST(last_accessed_at AS TEXT) as last_accessed_at, access_count \
              FROM tables",
         );
 
@@ -275,7 +290,10 @@ where
     ) -> Result<Option<TableInfo>> {
         let sql = format!(
             "SELECT id, connection_id, schema_name, table_name, parquet_path, \
-             CAST(last_sync AS TEXT) as last_sync, arrow_schema_json \
+             CAST(last_sync AS TEXT) as last_sync, arrow_schema_json, \
+             CAST(stale_since AS TEXT) as stale_since, table_type, \
+             estimated_rows, estimated_bytes, cache_stats_json, \
+             CAST(last_accessed_at AS TEXT) as last_accessed_at, access_count \
              FROM tables WHERE connection_id = {} AND schema_name = {} AND table_name = {}",
             DB::bind_param(1),
             DB::bind_param(2),
@@ -320,12 +338,23 @@ where
             .ok_or_else(|| anyhow!("Table '{}.{}' not found", schema_name, table_name))?;
 
         let sql = format!(
-            "UPDATE tables SET parquet_path = NULL, last_sync = NULL WHERE id = {}",
+            "UPDATE tables SET parquet_path = NULL, last_sync = NULL, source_snapshot_id = NULL, \
+             cache_stats_json = NULL WHERE id = {}",
             DB::bind_param(1)
         );
 
         query(&sql).bind(table.id).execute(&self.pool).await?;
 
+        let offsets_sql = format!(
+            "DELETE FROM table_offsets WHERE table_id = {}",
+            DB::bind_param(1)
+        );
+
+        query(&offsets_sql)
+            .bind(table.id)
+            .execute(&self.pool)
+            .await?;
+
         Ok(table)
     }
 
@@ -336,14 +365,14 @@ where
             .ok_or_else(|| anyhow!("Connection '{}' not found", name))?;
 
         let sql = format!(
-            "UPDATE tables SET parquet_path = NULL, last_sync = NULL \
-             WHERE connection_id = {}",
+            "UPDATE tables SET parquet_path = NULL, last_sync = NULL, source_snapshot_id = NULL, \
+             cache_stats_json = NULL WHERE connection_id = {}",
             DB::bind_param(1)
         );
 
         query(&sql).bind(connection.id).execute(&self.pool).await?;
 
-        Ok(())
+        self.delete_connection_offsets(connection.id).await
     }
 
     pub async fn delete_connection(&self, name: &str) -> Result<()> {
@@ -352,6 +381,20 @@ where
             .await?
             .ok_or_else(|| anyhow!("Connection '{}' not found", name))?;
 
+        self.delete_connection_offsets(connection.id).await?;
+        self.delete_connection_schema_versions(connection.id)
+            .await?;
+
+        let delete_pins_sql = format!(
+            "DELETE FROM cache_snapshot_tables WHERE connection_id = {}",
+            DB::bind_param(1)
+        );
+
+        query(&delete_pins_sql)
+            .bind(connection.id)
+            .execute(&self.pool)
+            .await?;
+
         let delete_tables_sql = format!(
             "DELETE FROM tables WHERE connection_id = {}",
             DB::bind_param(1)
@@ -373,6 +416,36 @@ where
         Ok(())
     }
 
+    pub async fn update_connection(
+        &self,
+        id: i32,
+        name: &str,
+        source_type: &str,
+        config_json: &str,
+    ) -> Result<()> {
+        if self.get_connection_by_id(id).await?.is_none() {
+            return Err(anyhow!("Connection {} not found", id));
+        }
+
+        let sql = format!(
+            "UPDATE connections SET name = {}, source_type = {}, config_json = {} WHERE id = {}",
+            DB::bind_param(1),
+            DB::bind_param(2),
+            DB::bind_param(3),
+            DB::bind_param(4)
+        );
+
+        query(&sql)
+            .bind(name)
+            .bind(source_type)
+            .bind(config_json)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn get_connection_by_id(&self, id: i32) -> Result<Option<ConnectionInfo>> {
         let sql = format!(
             "SELECT id, external_id, name, source_type, config_json FROM connections WHERE id = {}",
@@ -385,6 +458,446 @@ where
             .map_err(Into::into)
     }
 
+    pub async fn get_table_offsets(&self, table_id: i32) -> Result<Vec<PartitionOffset>> {
+        let sql = format!(
+            "SELECT partition_id, next_offset FROM table_offsets \
+             WHERE table_id = {} ORDER BY partition_id",
+            DB::bind_param(1)
+        );
+
+        query_as::<DB, PartitionOffset>(&sql)
+            .bind(table_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn update_table_offsets(
+        &self,
+        table_id: i32,
+        offsets: &[PartitionOffset],
+    ) -> Result<()> {
+        let sql = format!(
+            "INSERT INTO table_offsets (table_id, partition_id, next_offset) \
+             VALUES ({}, {}, {}) \
+             ON CONFLICT (table_id, partition_id) \
+             DO UPDATE SET next_offset = excluded.next_offset",
+            DB::bind_param(1),
+            DB::bind_param(2),
+            DB::bind_param(3)
+        );
+
+        for offset in offsets {
+            query(&sql)
+                .bind(table_id)
+                .bind(offset.partition_id)
+                .bind(offset.next_offset)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_table_snapshot_id(&self, table_id: i32) -> Result<Option<i64>> {
+        let sql = format!(
+            "SELECT source_snapshot_id FROM tables WHERE id = {}",
+            DB::bind_param(1)
+        );
+
+        query_scalar::<DB, Option<i64>>(&sql)
+            .bind(table_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map(Option::flatten)
+            .map_err(Into::into)
+    }
+
+    pub async fn update_table_snapshot_id(
+        &self,
+        table_id: i32,
+        snapshot_id: Option<i64>,
+    ) -> Result<()> {
+        let sql = format!(
+            "UPDATE tables SET source_snapshot_id = {} WHERE id = {}",
+            DB::bind_param(1),
+            DB::bind_param(2)
+        );
+
+        query(&sql)
+            .bind(snapshot_id)
+            .bind(table_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_schema_change_policy(&self, connection_id: i32) -> Result<SchemaChangePolicy> {
+        let sql = format!(
+            "SELECT schema_change_policy FROM connections WHERE id = {}",
+            DB::bind_param(1)
+        );
+
+        let policy = query_scalar::<DB, String>(&sql)
+            .bind(connection_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| anyhow!("Connection {} not found", connection_id))?;
+        policy.parse()
+    }
+
+    pub async fn set_schema_change_policy(
+        &self,
+        connection_id: i32,
+        policy: SchemaChangePolicy,
+    ) -> Result<()> {
+        let sql = format!(
+            "UPDATE connections SET schema_change_policy = {} WHERE id = {}",
+            DB::bind_param(1),
+            DB::bind_param(2)
+        );
+
+        query(&sql)
+            .bind(policy.as_str())
+            .bind(connection_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_warm_up_json(&self, connection_id: i32) -> Result<Option<String>> {
+        let sql = format!(
+            "SELECT warm_up_json FROM connections WHERE id = {}",
+            DB::bind_param(1)
+        );
+
+        let warm_up = query_scalar::<DB, Option<String>>(&sql)
+            .bind(connection_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| anyhow!("Connection {} not found", connection_id))?;
+        Ok(warm_up)
+    }
+
+    pub async fn set_warm_up_json(
+        &self,
+        connection_id: i32,
+        warm_up_json: Option<&str>,
+    ) -> Result<()> {
+        let sql = format!(
+            "UPDATE connections SET warm_up_json = {} WHERE id = {}",
+            DB::bind_param(1),
+            DB::bind_param(2)
+        );
+
+        query(&sql)
+            .bind(warm_up_json)
+            .bind(connection_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn add_schema_version(
+        &self,
+        table_id: i32,
+        arrow_schema_json: &str,
+        changes_json: &str,
+        status: SchemaVersionStatus,
+    ) -> Result<i32> {
+        let max_sql = format!(
+            "SELECT MAX(version) FROM table_schema_versions WHERE table_id = {}",
+            DB::bind_param(1)
+        );
+        let version = query_scalar::<DB, Option<i32>>(&max_sql)
+            .bind(table_id)
+            .fetch_one(&self.pool)
+            .await?
+            .unwrap_or(0)
+            + 1;
+
+        let insert_sql = format!(
+            "INSERT INTO table_schema_versions \
+             (table_id, version, arrow_schema_json, changes_json, status) \
+             VALUES ({}, {}, {}, {}, {})",
+            DB::bind_param(1),
+            DB::bind_param(2),
+            DB::bind_param(3),
+            DB::bind_param(4),
+            DB::bind_param(5)
+        );
+
+        query(&insert_sql)
+            .bind(table_id)
+            .bind(version)
+            .bind(arrow_schema_json)
+            .bind(changes_json)
+            .bind(status.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(version)
+    }
+
+    pub async fn list_schema_versions(
+        &self,
+        connection_id: Option<i32>,
+    ) -> Result<Vec<SchemaVersion>> {
+        let mut sql = String::from(
+            "SELECT v.id, v.table_id, t.connection_id, t.schema_name, t.table_name, v.version, \
+             v.arrow_schema_json, v.changes_json, v.status, \
+             CAST(v.created_at AS TEXT) as created_at \
+             FROM table_schema_versions v JOIN tables t ON t.id = v.table_id",
+        );
+
+        if connection_id.is_some() {
+            sql.push_str(" WHERE t.connection_id = ");
+            sql.push_str(DB::bind_param(1).as_ref());
+        }
+
+        sql.push_str(" ORDER BY t.schema_name, t.table_name, v.version");
+
+        let mut stmt = query_as::<DB, SchemaVersion>(&sql);
+        if let Some(conn_id) = connection_id {
+            stmt = stmt.bind(conn_id);
+        }
+
+        stmt.fetch_all(&self.pool).await.map_err(Into::into)
+    }
+
+    pub async fn create_cache_snapshot(&self, name: &str, tables: &[TableInfo]) -> Result<i32> {
+        let insert_sql = format!(
+            "INSERT INTO cache_snapshots (name) VALUES ({})",
+            DB::bind_param(1)
+        );
+        query(&insert_sql).bind(name).execute(&self.pool).await?;
+
+        let select_sql = format!(
+            "SELECT id FROM cache_snapshots WHERE name = {}",
+            DB::bind_param(1)
+        );
+        let snapshot_id = query_scalar::<DB, i32>(&select_sql)
+            .bind(name)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let pin_sql = format!(
+            "INSERT INTO cache_snapshot_tables \
+             (snapshot_id, connection_id, schema_name, table_name, parquet_path, arrow_schema_json) \
+             VALUES ({}, {}, {}, {}, {}, {})",
+            DB::bind_param(1),
+            DB::bind_param(2),
+            DB::bind_param(3),
+            DB::bind_param(4),
+            DB::bind_param(5),
+            DB::bind_param(6)
+        );
+
+        for table in tables {
+            let (Some(parquet_path), Some(arrow_schema_json)) =
+                (&table.parquet_path, &table.arrow_schema_json)
+            else {
+                continue;
+            };
+
+            query(&pin_sql)
+                .bind(snapshot_id)
+                .bind(table.connection_id)
+                .bind(table.schema_name.as_str())
+                .bind(table.table_name.as_str())
+                .bind(parquet_path.as_str())
+                .bind(arrow_schema_json.as_str())
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(snapshot_id)
+    }
+
+    pub async fn get_cache_snapshot(&self, name: &str) -> Result<Option<CacheSnapshot>> {
+        let sql = format!(
+            "SELECT id, name, CAST(created_at AS TEXT) as created_at \
+             FROM cache_snapshots WHERE name = {}",
+            DB::bind_param(1)
+        );
+
+        query_as::<DB, CacheSnapshot>(&sql)
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn list_cache_snapshots(&self) -> Result<Vec<CacheSnapshot>> {
+        query_as::<DB, CacheSnapshot>(
+            "SELECT id, name, CAST(created_at AS TEXT) as created_at \
+             FROM cache_snapshots ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    pub async fn list_snapshot_tables(
+        &self,
+        snapshot_id: Option<i32>,
+    ) -> Result<Vec<SnapshotTable>> {
+        let mut sql = String::from(
+            "SELECT snapshot_id, connection_id, schema_name, table_name, parquet_path, \
+             arrow_schema_json FROM cache_snapshot_tables",
+        );
+
+        if snapshot_id.is_some() {
+            sql.push_str(" WHERE snapshot_id = ");
+            sql.push_str(DB::bind_param(1).as_ref());
+        }
+
+        sql.push_str(" ORDER BY snapshot_id, connection_id, schema_name, table_name");
+
+        let mut stmt = query_as::<DB, SnapshotTable>(&sql);
+        if let Some(id) = snapshot_id {
+            stmt = stmt.bind(id);
+        }
+
+        stmt.fetch_all(&self.pool).await.map_err(Into::into)
+    }
+
+    pub async fn delete_cache_snapshot(&self, snapshot_id: i32) -> Result<()> {
+        for (table, column) in [
+            ("cache_snapshot_tables", "snapshot_id"),
+            ("cache_snapshots", "id"),
+        ] {
+            let sql = format!(
+                "DELETE FROM {} WHERE {} = {}",
+                table,
+                column,
+                DB::bind_param(1)
+            );
+            query(&sql).bind(snapshot_id).execute(&self.pool).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn set_table_stale(&self, table_id: i32, stale: bool) -> Result<()> {
+        let stale_since = if stale {
+            "COALESCE(stale_since, CURRENT_TIMESTAMP)"
+        } else {
+            "NULL"
+        };
+        let sql = format!(
+            "UPDATE tables SET stale_since = {} WHERE id = {}",
+            stale_since,
+            DB::bind_param(1)
+        );
+
+        query(&sql).bind(table_id).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    pub async fn set_table_type(&self, table_id: i32, kind: TableKind) -> Result<()> {
+        let sql = format!(
+            "UPDATE tables SET table_type = {} WHERE id = {}",
+            DB::bind_param(1),
+            DB::bind_param(2)
+        );
+
+        query(&sql)
+            .bind(kind.as_str())
+            .bind(table_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_table_estimates(
+        &self,
+        table_id: i32,
+        rows: Option<i64>,
+        bytes: Option<i64>,
+    ) -> Result<()> {
+        let sql = format!(
+            "UPDATE tables SET estimated_rows = {}, estimated_bytes = {} WHERE id = {}",
+            DB::bind_param(1),
+            DB::bind_param(2),
+            DB::bind_param(3)
+        );
+
+        query(&sql)
+            .bind(rows)
+            .bind(bytes)
+            .bind(table_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_table_cache_stats(
+        &self,
+        table_id: i32,
+        stats_json: Option<&str>,
+    ) -> Result<()> {
+        let sql = format!(
+            "UPDATE tables SET cache_stats_json = {} WHERE id = {}",
+            DB::bind_param(1),
+            DB::bind_param(2)
+        );
+
+        query(&sql)
+            .bind(stats_json)
+            .bind(table_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_table(&self, table_id: i32) -> Result<()> {
+        for table in ["table_offsets", "table_schema_versions"] {
+            let sql = format!(
+                "DELETE FROM {} WHERE table_id = {}",
+                table,
+                DB::bind_param(1)
+            );
+            query(&sql).bind(table_id).execute(&self.pool).await?;
+        }
+
+        let sql = format!("DELETE FROM tables WHERE id = {}", DB::bind_param(1));
+        query(&sql).bind(table_id).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    async fn delete_connection_schema_versions(&self, connection_id: i32) -> Result<()> {
+        let sql = format!(
+            "DELETE FROM table_schema_versions WHERE table_id IN \
+             (SELECT id FROM tables WHERE connection_id = {})",
+            DB::bind_param(1)
+        );
+
+        query(&sql).bind(connection_id).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    async fn delete_connection_offsets(&self, connection_id: i32) -> Result<()> {
+        let sql = format!(
+            "DELETE FROM table_offsets WHERE table_id IN \
+             (SELECT id FROM tables WHERE connection_id = {})",
+            DB::bind_param(1)
+        );
+
+        query(&sql).bind(connection_id).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
     pub async fn remove_pending_deletion(&self, id: i32) -> Result<()> {
         let sql = format!(
             "DELETE FROM pending_deletions WHERE id = {}",
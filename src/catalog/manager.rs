@@ -36,6 +36,171 @@ pub struct TableInfo {
     pub parquet_path: Option<String>,
     pub last_sync: Option<String>,
     pub arrow_schema_json: Option<String>,
+    /// When schema refresh first found the table missing from the source, if it still is.
+    pub stale_since: Option<String>,
+    /// Source table type, as an information_schema name (see [`TableKind::as_str`]).
+    pub table_type: String,
+    /// Row count estimate reported by the source at discovery.
+    pub estimated_rows: Option<i64>,
+    /// Storage size estimate in bytes reported by the source at discovery.
+    pub estimated_bytes: Option<i64>,
+    /// Exact statistics of the cached parquet, as written by the fetch orchestrator.
+    pub cache_stats_json: Option<String>,
+    /// When a query last scanned the table, as of the last flush of recorded accesses.
+    pub last_accessed_at: Option<String>,
+    /// Number of query scans of the table, as of the last flush of recorded accesses.
+    pub access_count: i64,
+}
+
+impl TableInfo {
+    pub fn kind(&self) -> TableKind {
+        TableKind::from_source(&self.table_type)
+    }
+}
+
+/// What kind of relation a table is in its source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableKind {
+    #[default]
+    BaseTable,
+    View,
+    MaterializedView,
+    /// Backed by data the source does not manage (external or foreign tables).
+    External,
+    /// Append-only stream, e.g. a Kafka topic.
+    Stream,
+}
+
+impl TableKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TableKind::BaseTable => "BASE TABLE",
+            TableKind::View => "VIEW",
+            TableKind::MaterializedView => "MATERIALIZED VIEW",
+            TableKind::External => "EXTERNAL TABLE",
+            TableKind::Stream => "STREAM",
+        }
+    }
+
+    /// Normalize a table type as reported by a source. Unknown types are treated as base tables.
+    pub fn from_source(table_type: &str) -> Self {
+        match table_type.trim().to_ascii_uppercase().as_str() {
+            "VIEW" | "SYSTEM VIEW" => TableKind::View,
+            "MATERIALIZED VIEW" | "MATERIALIZED_VIEW" => TableKind::MaterializedView,
+            "EXTERNAL" | "EXTERNAL TABLE" | "FOREIGN" | "FOREIGN TABLE" => TableKind::External,
+            "STREAM" => TableKind::Stream,
+            _ => TableKind::BaseTable,
+        }
+    }
+}
+
+/// Consumer position within one partition of a streaming table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct PartitionOffset {
+    pub partition_id: i32,
+    /// First offset that has not been cached yet.
+    pub next_offset: i64,
+}
+
+/// How schema refresh handles breaking changes (dropped or retyped columns) on a connection.
+/// Additive changes are always applied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaChangePolicy {
+    /// Keep the current schema and record the change as blocked.
+    Block,
+    /// Apply the new schema and keep cached data.
+    #[default]
+    AutoApply,
+    /// Apply the new schema and purge the table's cached data.
+    ApplyAndPurge,
+}
+
+impl SchemaChangePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaChangePolicy::Block => "block",
+            SchemaChangePolicy::AutoApply => "auto_apply",
+            SchemaChangePolicy::ApplyAndPurge => "apply_and_purge",
+        }
+    }
+}
+
+impl std::str::FromStr for SchemaChangePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "block" => Ok(SchemaChangePolicy::Block),
+            "auto_apply" => Ok(SchemaChangePolicy::AutoApply),
+            "apply_and_purge" => Ok(SchemaChangePolicy::ApplyAndPurge),
+            other => Err(anyhow::anyhow!("Unknown schema change policy '{}'", other)),
+        }
+    }
+}
+
+/// Whether a recorded schema version was applied to the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaVersionStatus {
+    Applied,
+    /// Held back by the connection's `Block` policy.
+    Blocked,
+}
+
+impl SchemaVersionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaVersionStatus::Applied => "applied",
+            SchemaVersionStatus::Blocked => "blocked",
+        }
+    }
+}
+
+/// One entry in a table's schema history.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SchemaVersion {
+    pub id: i32,
+    pub table_id: i32,
+    pub connection_id: i32,
+    pub schema_name: String,
+    pub table_name: String,
+    /// 1-based, increasing per table.
+    pub version: i32,
+    pub arrow_schema_json: String,
+    /// JSON array of column changes against the previous version.
+    pub changes_json: String,
+    /// "applied" or "blocked"
+    pub status: String,
+    pub created_at: Option<String>,
+}
+
+/// A named snapshot pinning the cached versions of a set of tables.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CacheSnapshot {
+    pub id: i32,
+    pub name: String,
+    pub created_at: Option<String>,
+}
+
+/// A table version pinned by a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SnapshotTable {
+    pub snapshot_id: i32,
+    pub connection_id: i32,
+    pub schema_name: String,
+    pub table_name: String,
+    pub parquet_path: String,
+    /// Schema of the pinned version, which later refreshes may have changed since.
+    pub arrow_schema_json: String,
+}
+
+/// Query scans of a table collected in memory since the last flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableAccess {
+    pub table_id: i32,
+    pub count: i64,
+    pub last_accessed_at: DateTime<Utc>,
 }
 
 /// Record for deferred file deletion (survives restarts)
@@ -93,7 +258,8 @@ pub trait CatalogManager: Debug + Send + Sync {
     ) -> Result<Option<TableInfo>>;
     async fn update_table_sync(&self, table_id: i32, parquet_path: &str) -> Result<()>;
 
-    /// Clear table cache metadata (set paths to NULL) without deleting files.
+    /// Clear table cache metadata (set paths to NULL and reset stream offsets, source
+    /// snapshot and cache statistics) without deleting files.
     async fn clear_table_cache_metadata(
         &self,
         connection_id: i32,
@@ -101,19 +267,111 @@ pub trait CatalogManager: Debug + Send + Sync {
         table_name: &str,
     ) -> Result<TableInfo>;
 
-    /// Clear cache metadata for all tables in a connection (set paths to NULL).
+    /// Clear cache metadata for all tables in a connection (set paths to NULL and
+    /// reset stream offsets, source snapshots and cache statistics).
     async fn clear_connection_cache_metadata(&self, name: &str) -> Result<()>;
 
     /// Delete connection and all associated table rows from metadata.
     async fn delete_connection(&self, name: &str) -> Result<()>;
 
+    /// Replace a connection's name and configuration. Its tables and cache are kept.
+    async fn update_connection(
+        &self,
+        id: i32,
+        name: &str,
+        source_type: &str,
+        config_json: &str,
+    ) -> Result<()>;
+
     /// Get connection by internal ID.
     async fn get_connection_by_id(&self, id: i32) -> Result<Option<ConnectionInfo>>;
 
-    // NOTE: Stale table detection (tables removed from remote source) is intentionally
-    // not implemented. The naive approach of comparing discovered vs existing tables
-    // is error-prone and doesn't handle data cleanup properly. Stale tables will
-    // remain in the catalog until a more robust solution is implemented.
+    /// Get the stored per-partition offsets for a streaming table.
+    /// Returns an empty list if the table has never been fetched.
+    async fn get_table_offsets(&self, table_id: i32) -> Result<Vec<PartitionOffset>>;
+
+    /// Insert or update per-partition offsets for a streaming table.
+    /// Partitions not present in `offsets` are left untouched.
+    async fn update_table_offsets(&self, table_id: i32, offsets: &[PartitionOffset]) -> Result<()>;
+
+    /// Get the source snapshot ID (e.g., Iceberg snapshot) that the cached data reflects.
+    /// Returns None if the table is not cached or its source has no snapshots.
+    async fn get_table_snapshot_id(&self, table_id: i32) -> Result<Option<i64>>;
+
+    /// Record the source snapshot ID that the cached data reflects.
+    async fn update_table_snapshot_id(&self, table_id: i32, snapshot_id: Option<i64>)
+        -> Result<()>;
+
+    /// Get how schema refresh handles breaking changes for a connection.
+    async fn get_schema_change_policy(&self, connection_id: i32) -> Result<SchemaChangePolicy>;
+
+    /// Set how schema refresh handles breaking changes for a connection.
+    async fn set_schema_change_policy(
+        &self,
+        connection_id: i32,
+        policy: SchemaChangePolicy,
+    ) -> Result<()>;
+
+    /// Get the JSON-encoded warm-up patterns for a connection, if any.
+    async fn get_warm_up_json(&self, connection_id: i32) -> Result<Option<String>>;
+
+    /// Set or clear the JSON-encoded warm-up patterns for a connection.
+    async fn set_warm_up_json(&self, connection_id: i32, warm_up_json: Option<&str>) -> Result<()>;
+
+    /// Append a version to a table's schema history. Returns the new version number.
+    async fn add_schema_version(
+        &self,
+        table_id: i32,
+        arrow_schema_json: &str,
+        changes_json: &str,
+        status: SchemaVersionStatus,
+    ) -> Result<i32>;
+
+    /// List schema history, optionally filtered by connection, ordered by table and version.
+    async fn list_schema_versions(&self, connection_id: Option<i32>) -> Result<Vec<SchemaVersion>>;
+
+    /// Mark a table as missing from its source, or clear the mark when it reappears.
+    /// Marking an already stale table keeps its original `stale_since`.
+    async fn set_table_stale(&self, table_id: i32, stale: bool) -> Result<()>;
+
+    /// Record the table's type in its source.
+    async fn set_table_type(&self, table_id: i32, kind: TableKind) -> Result<()>;
+
+    /// Record the source's row count and size estimates for a table.
+    async fn update_table_estimates(
+        &self,
+        table_id: i32,
+        rows: Option<i64>,
+        bytes: Option<i64>,
+    ) -> Result<()>;
+
+    /// Record (or clear) the statistics of a table's cached parquet.
+    async fn update_table_cache_stats(&self, table_id: i32, stats_json: Option<&str>)
+        -> Result<()>;
+
+    /// Add collected scans to the tables' access counts and move their last access
+    /// time forward, in one transaction.
+    async fn record_table_accesses(&self, accesses: &[TableAccess]) -> Result<()>;
+
+    /// Delete a table's metadata, including its stream offsets and schema history.
+    /// Cached files are left for the caller to clean up.
+    async fn delete_table(&self, table_id: i32) -> Result<()>;
+
+    /// Create a snapshot pinning the current cached versions of `tables`. Tables that are
+    /// not cached are skipped. Returns the snapshot ID.
+    async fn create_cache_snapshot(&self, name: &str, tables: &[TableInfo]) -> Result<i32>;
+
+    /// Get a snapshot by name.
+    async fn get_cache_snapshot(&self, name: &str) -> Result<Option<CacheSnapshot>>;
+
+    /// List snapshots ordered by name.
+    async fn list_cache_snapshots(&self) -> Result<Vec<CacheSnapshot>>;
+
+    /// List the table versions pinned by one snapshot, or by all snapshots.
+    async fn list_snapshot_tables(&self, snapshot_id: Option<i32>) -> Result<Vec<SnapshotTable>>;
+
+    /// Delete a snapshot. Its pinned files are left for the caller to clean up.
+    async fn delete_cache_snapshot(&self, snapshot_id: i32) -> Result<()>;
 
     /// Schedule a file path for deletion after a grace period.
     async fn schedule_file_deletion(&self, path: &str, delete_after: DateTime<Utc>) -> Result<()>;
@@ -121,6 +379,9 @@ pub trait CatalogManager: Debug + Send + Sync {
     /// Get all pending file deletions that are due for cleanup.
     async fn get_pending_deletions(&self) -> Result<Vec<PendingDeletion>>;
 
+    /// Get all scheduled file deletions, including those not yet due.
+    async fn list_scheduled_deletions(&self) -> Result<Vec<PendingDeletion>>;
+
     /// Increment the retry count for a failed deletion. Returns the new count.
     async fn increment_deletion_retry(&self, id: i32) -> Result<i32>;
 
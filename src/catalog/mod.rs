@@ -1,12 +1,18 @@
 mod backend;
 mod migrations;
 mod postgres_manager;
+mod schema_history;
 mod sqlite_manager;
 
 mod manager;
 
 pub use manager::{
-    CatalogManager, ConnectionInfo, OptimisticLock, PendingDeletion, QueryResult, TableInfo,
+    CacheSnapshot, CatalogManager, ConnectionInfo, OptimisticLock, PartitionOffset,
+    PendingDeletion, QueryResult, SchemaChangePolicy, SchemaVersion, SchemaVersionStatus,
+    SnapshotTable, TableAccess, TableInfo, TableKind,
 };
 pub use postgres_manager::PostgresCatalogManager;
+pub use schema_history::{
+    diff_schemas, is_breaking, record_schema_version, ColumnChange, ColumnChangeType,
+};
 pub use sqlite_manager::SqliteCatalogManager;
@@ -1,6 +1,8 @@
 use crate::catalog::backend::CatalogBackend;
 use crate::catalog::manager::{
-    CatalogManager, ConnectionInfo, OptimisticLock, PendingDeletion, QueryResult, TableInfo,
+    CacheSnapshot, CatalogManager, ConnectionInfo, OptimisticLock, PartitionOffset,
+    PendingDeletion, QueryResult, SchemaChangePolicy, SchemaVersion, SchemaVersionStatus,
+    SnapshotTable, TableAccess, TableInfo, TableKind,
 };
 use crate::catalog::migrations::{
     run_migrations, wrap_migration_sql, CatalogMigrations, Migration, POSTGRES_MIGRATIONS,
@@ -140,10 +142,155 @@ impl CatalogManager for PostgresCatalogManager {
         self.backend.delete_connection(name).await
     }
 
+    async fn update_connection(
+        &self,
+        id: i32,
+        name: &str,
+        source_type: &str,
+        config_json: &str,
+    ) -> Result<()> {
+        self.backend
+            .update_connection(id, name, source_type, config_json)
+            .await
+    }
+
+    async fn get_table_offsets(&self, table_id: i32) -> Result<Vec<PartitionOffset>> {
+        self.backend.get_table_offsets(table_id).await
+    }
+
+    async fn update_table_offsets(&self, table_id: i32, offsets: &[PartitionOffset]) -> Result<()> {
+        self.backend.update_table_offsets(table_id, offsets).await
+    }
+
+    async fn get_table_snapshot_id(&self, table_id: i32) -> Result<Option<i64>> {
+        self.backend.get_table_snapshot_id(table_id).await
+    }
+
+    async fn update_table_snapshot_id(
+        &self,
+        table_id: i32,
+        snapshot_id: Option<i64>,
+    ) -> Result<()> {
+        self.backend
+            .update_table_snapshot_id(table_id, snapshot_id)
+            .await
+    }
+
+    async fn get_schema_change_policy(&self, connection_id: i32) -> Result<SchemaChangePolicy> {
+        self.backend.get_schema_change_policy(connection_id).await
+    }
+
+    async fn set_schema_change_policy(
+        &self,
+        connection_id: i32,
+        policy: SchemaChangePolicy,
+    ) -> Result<()> {
+        self.backend
+            .set_schema_change_policy(connection_id, policy)
+            .await
+    }
+
+    async fn get_warm_up_json(&self, connection_id: i32) -> Result<Option<String>> {
+        self.backend.get_warm_up_json(connection_id).await
+    }
+
+    async fn set_warm_up_json(&self, connection_id: i32, warm_up_json: Option<&str>) -> Result<()> {
+        self.backend
+            .set_warm_up_json(connection_id, warm_up_json)
+            .await
+    }
+
+    async fn add_schema_version(
+        &self,
+        table_id: i32,
+        arrow_schema_json: &str,
+        changes_json: &str,
+        status: SchemaVersionStatus,
+    ) -> Result<i32> {
+        self.backend
+            .add_schema_version(table_id, arrow_schema_json, changes_json, status)
+            .await
+    }
+
+    async fn list_schema_versions(&self, connection_id: Option<i32>) -> Result<Vec<SchemaVersion>> {
+        self.backend.list_schema_versions(connection_id).await
+    }
+
+    async fn set_table_stale(&self, table_id: i32, stale: bool) -> Result<()> {
+        self.backend.set_table_stale(table_id, stale).await
+    }
+
+    async fn set_table_type(&self, table_id: i32, kind: TableKind) -> Result<()> {
+        self.backend.set_table_type(table_id, kind).await
+    }
+
+    async fn update_table_estimates(
+        &self,
+        table_id: i32,
+        rows: Option<i64>,
+        bytes: Option<i64>,
+    ) -> Result<()> {
+        self.backend
+            .update_table_estimates(table_id, rows, bytes)
+            .await
+    }
+
+    async fn update_table_cache_stats(
+        &self,
+        table_id: i32,
+        stats_json: Option<&str>,
+    ) -> Result<()> {
+        self.backend
+            .update_table_cache_stats(table_id, stats_json)
+            .await
+    }
+
+    async fn record_table_accesses(&self, accesses: &[TableAccess]) -> Result<()> {
+        // last_accessed_at is a TIMESTAMP without time zone, holding UTC
+        let mut tx = self.backend.pool().begin().await?;
+        for access in accesses {
+            sqlx::query(
+                "UPDATE tables SET access_count = access_count + $1, \
+                 last_accessed_at = GREATEST(last_accessed_at, $2) WHERE id = $3",
+            )
+            .bind(access.count)
+            .bind(access.last_accessed_at.naive_utc())
+            .bind(access.table_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_table(&self, table_id: i32) -> Result<()> {
+        self.backend.delete_table(table_id).await
+    }
+
     async fn get_connection_by_id(&self, id: i32) -> Result<Option<ConnectionInfo>> {
         self.backend.get_connection_by_id(id).await
     }
 
+    async fn create_cache_snapshot(&self, name: &str, tables: &[TableInfo]) -> Result<i32> {
+        self.backend.create_cache_snapshot(name, tables).await
+    }
+
+    async fn get_cache_snapshot(&self, name: &str) -> Result<Option<CacheSnapshot>> {
+        self.backend.get_cache_snapshot(name).await
+    }
+
+    async fn list_cache_snapshots(&self) -> Result<Vec<CacheSnapshot>> {
+        self.backend.list_cache_snapshots().await
+    }
+
+    async fn list_snapshot_tables(&self, snapshot_id: Option<i32>) -> Result<Vec<SnapshotTable>> {
+        self.backend.list_snapshot_tables(snapshot_id).await
+    }
+
+    async fn delete_cache_snapshot(&self, snapshot_id: i32) -> Result<()> {
+        self.backend.delete_cache_snapshot(snapshot_id).await
+    }
+
     async fn schedule_file_deletion(&self, path: &str, delete_after: DateTime<Utc>) -> Result<()> {
         // Use native TIMESTAMPTZ binding for Postgres (not RFC3339 string)
         // ON CONFLICT DO NOTHING silently ignores duplicates when path already exists
@@ -168,6 +315,13 @@ impl CatalogManager for PostgresCatalogManager {
         .map_err(Into::into)
     }
 
+    async fn list_scheduled_deletions(&self) -> Result<Vec<PendingDeletion>> {
+        sqlx::query_as("SELECT id, path, delete_after, retry_count FROM pending_deletions")
+            .fetch_all(self.backend.pool())
+            .await
+            .map_err(Into::into)
+    }
+
     async fn increment_deletion_retry(&self, id: i32) -> Result<i32> {
         let new_count: (i32,) = sqlx::query_as(
             "UPDATE pending_deletions SET retry_count = retry_count + 1 WHERE id = $1 RETURNING retry_count",
@@ -0,0 +1,220 @@
+//! Column-level schema diffs and schema history recording.
+//!
+//! Every schema change a table goes through is appended to its history with the diff against
+//! the previous version. Dropped and retyped columns are breaking: queries written against
+//! the old schema may fail. Added columns are not.
+
+use anyhow::Result;
+use datafusion::arrow::datatypes::{Field, Schema};
+use serde::{Deserialize, Serialize};
+
+use super::{CatalogManager, SchemaVersionStatus, TableInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnChangeType {
+    Added,
+    Dropped,
+    /// Data type or nullability changed
+    Retyped,
+}
+
+impl ColumnChangeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnChangeType::Added => "added",
+            ColumnChangeType::Dropped => "dropped",
+            ColumnChangeType::Retyped => "retyped",
+        }
+    }
+}
+
+/// A change to one column between two schema versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnChange {
+    pub change: ColumnChangeType,
+    pub column: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_type: Option<String>,
+}
+
+/// Diff two schemas by column name: dropped and retyped columns in old order, then added
+/// columns in new order.
+pub fn diff_schemas(old: &Schema, new: &Schema) -> Vec<ColumnChange> {
+    let mut changes = Vec::new();
+
+    for old_field in old.fields() {
+        match new.field_with_name(old_field.name()) {
+            Ok(new_field) => {
+                let (old_type, new_type) = (column_type(old_field), column_type(new_field));
+                if old_type != new_type {
+                    changes.push(ColumnChange {
+                        change: ColumnChangeType::Retyped,
+                        column: old_field.name().clone(),
+                        old_type: Some(old_type),
+                        new_type: Some(new_type),
+                    });
+                }
+            }
+            Err(_) => changes.push(ColumnChange {
+                change: ColumnChangeType::Dropped,
+                column: old_field.name().clone(),
+                old_type: Some(column_type(old_field)),
+                new_type: None,
+            }),
+        }
+    }
+
+    for new_field in new.fields() {
+        if old.field_with_name(new_field.name()).is_err() {
+            changes.push(ColumnChange {
+                change: ColumnChangeType::Added,
+                column: new_field.name().clone(),
+                old_type: None,
+                new_type: Some(column_type(new_field)),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Whether any change can break queries written against the old schema.
+pub fn is_breaking(changes: &[ColumnChange]) -> bool {
+    changes.iter().any(|c| c.change != ColumnChangeType::Added)
+}
+
+fn column_type(field: &Field) -> String {
+    if field.is_nullable() {
+        field.data_type().to_string()
+    } else {
+        format!("{} NOT NULL", field.data_type())
+    }
+}
+
+/// Append a schema version for `table`. Tables whose history predates this version get
+/// their current schema recorded first, so the history always starts from a baseline.
+/// A blocked change that is already the latest blocked version is not recorded again.
+pub async fn record_schema_version(
+    catalog: &dyn CatalogManager,
+    table: &TableInfo,
+    arrow_schema_json: &str,
+    changes: &[ColumnChange],
+    status: SchemaVersionStatus,
+) -> Result<()> {
+    let history: Vec<_> = catalog
+        .list_schema_versions(Some(table.connection_id))
+        .await?
+        .into_iter()
+        .filter(|v| v.table_id == table.id)
+        .collect();
+
+    if let Some(latest) = history.last() {
+        if status == SchemaVersionStatus::Blocked
+            && latest.status == SchemaVersionStatus::Blocked.as_str()
+            && latest.arrow_schema_json == arrow_schema_json
+        {
+            return Ok(());
+        }
+    } else if let Some(current) = &table.arrow_schema_json {
+        catalog
+            .add_schema_version(table.id, current, "[]", SchemaVersionStatus::Applied)
+            .await?;
+    }
+
+    catalog
+        .add_schema_version(
+            table.id,
+            arrow_schema_json,
+            &serde_json::to_string(changes)?,
+            status,
+        )
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::datatypes::DataType;
+
+    #[test]
+    fn test_diff_schemas_reports_each_change() {
+        let old = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("legacy", DataType::Utf8, true),
+        ]);
+        let new = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("email", DataType::Utf8, true),
+        ]);
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                ColumnChange {
+                    change: ColumnChangeType::Retyped,
+                    column: "id".to_string(),
+                    old_type: Some("Int32 NOT NULL".to_string()),
+                    new_type: Some("Int64 NOT NULL".to_string()),
+                },
+                ColumnChange {
+                    change: ColumnChangeType::Dropped,
+                    column: "legacy".to_string(),
+                    old_type: Some("Utf8".to_string()),
+                    new_type: None,
+                },
+                ColumnChange {
+                    change: ColumnChangeType::Added,
+                    column: "email".to_string(),
+                    old_type: None,
+                    new_type: Some("Utf8".to_string()),
+                },
+            ]
+        );
+        assert!(is_breaking(&changes));
+    }
+
+    #[test]
+    fn test_added_columns_are_not_breaking() {
+        let old = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        let new = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("email", DataType::Utf8, true),
+        ]);
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert!(!is_breaking(&changes));
+        assert!(diff_schemas(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_nullability_change_is_retyped() {
+        let old = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        let new = Schema::new(vec![Field::new("id", DataType::Int32, true)]);
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(changes[0].change, ColumnChangeType::Retyped);
+        assert_eq!(changes[0].new_type.as_deref(), Some("Int32"));
+    }
+
+    #[test]
+    fn test_column_change_json_shape() {
+        let change = ColumnChange {
+            change: ColumnChangeType::Added,
+            column: "email".to_string(),
+            old_type: None,
+            new_type: Some("Utf8".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&change).unwrap(),
+            serde_json::json!({"change": "added", "column": "email", "new_type": "Utf8"})
+        );
+    }
+}
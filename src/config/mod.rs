@@ -10,6 +10,8 @@ pub struct AppConfig {
     pub paths: PathsConfig,
     #[serde(default)]
     pub secrets: SecretsConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -46,6 +48,25 @@ pub struct StorageConfig {
     pub bucket: Option<String>,
     pub region: Option<String>,
     pub endpoint: Option<String>,
+    /// Azure storage account name. Defaults to `AZURE_STORAGE_ACCOUNT_NAME`.
+    pub account: Option<String>,
+    /// Key prefix within the S3 bucket that cache files are stored under.
+    pub prefix: Option<String>,
+    /// S3 server-side encryption: `sse-s3` or `sse-kms`.
+    pub encryption: Option<String>,
+    /// KMS key for `sse-kms`. Defaults to the bucket's key.
+    pub kms_key_id: Option<String>,
+    /// AWS profile to read S3 credentials from, instead of the standard credential chain.
+    pub profile: Option<String>,
+    /// Name of a secret holding S3 credentials as JSON with `access_key_id`,
+    /// `secret_access_key` and optionally `session_token`.
+    pub credentials_secret: Option<String>,
+    /// Address the S3 bucket virtual-hosted style (`{bucket}.{host}`).
+    pub virtual_hosted_style: Option<bool>,
+    /// Local directory for keeping S3 cache files on disk. Disabled if not set.
+    pub local_cache_dir: Option<String>,
+    /// Disk budget for `local_cache_dir` in bytes. Defaults to 10 GiB.
+    pub local_cache_size: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -64,6 +85,22 @@ pub struct SecretsConfig {
     pub encryption_key: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct CacheConfig {
+    /// Verify cached files on startup, resetting tables whose files are missing or corrupt.
+    #[serde(default)]
+    pub verify_on_startup: bool,
+    /// With `verify_on_startup`, schedule cache directories no table references for deletion.
+    #[serde(default)]
+    pub delete_orphans: bool,
+    /// Seconds between cache garbage collection runs; unset disables the collector.
+    #[serde(default)]
+    pub gc_interval_secs: Option<u64>,
+    /// Seconds since its last write before an unreferenced cache version is collected.
+    #[serde(default)]
+    pub gc_min_age_secs: Option<u64>,
+}
+
 impl AppConfig {
     /// Load configuration from file and environment variables
     pub fn load(config_path: &str) -> Result<Self> {
@@ -118,6 +155,33 @@ impl AppConfig {
                 if self.storage.bucket.is_none() {
                     anyhow::bail!("S3 storage requires 'bucket'");
                 }
+                match self.storage.encryption.as_deref() {
+                    None | Some("sse-kms") => {}
+                    Some("sse-s3") if self.storage.kms_key_id.is_some() => {
+                        anyhow::bail!("S3 'kms_key_id' requires encryption = \"sse-kms\"")
+                    }
+                    Some("sse-s3") => {}
+                    Some(other) => anyhow::bail!(
+                        "Invalid S3 encryption '{}': expected \"sse-s3\" or \"sse-kms\"",
+                        other
+                    ),
+                }
+                if self.storage.kms_key_id.is_some() && self.storage.encryption.is_none() {
+                    anyhow::bail!("S3 'kms_key_id' requires encryption = \"sse-kms\"");
+                }
+                if self.storage.profile.is_some() && self.storage.credentials_secret.is_some() {
+                    anyhow::bail!("S3 storage accepts either 'profile' or 'credentials_secret'");
+                }
+            }
+            "gcs" => {
+                if self.storage.bucket.is_none() {
+                    anyhow::bail!("GCS storage requires 'bucket'");
+                }
+            }
+            "azure" => {
+                if self.storage.bucket.is_none() {
+                    anyhow::bail!("Azure storage requires 'bucket' (the container name)");
+                }
             }
             "filesystem" => {
                 // Filesystem storage uses paths config, no additional validation needed
@@ -33,6 +33,10 @@ pub enum DataFetchError {
     #[error("schema serialization failed: {0}")]
     SchemaSerialization(String),
 
+    /// Fetched data cannot be reconciled with the table schema recorded in the catalog
+    #[error("schema mismatch for {table}: {details}")]
+    SchemaMismatch { table: String, details: String },
+
     /// Requested table not found in catalog
     #[error("table not found: {schema}.{table} (connection {connection_id})")]
     TableNotFound {
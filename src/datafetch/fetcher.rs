@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 
 use super::native::StreamingParquetWriter;
-use super::{DataFetchError, TableMetadata};
+use super::{DataFetchError, SnapshotRefresh, TableMetadata, TimeTravel};
+use crate::catalog::PartitionOffset;
 use crate::secrets::SecretManager;
 use crate::source::Source;
 
@@ -15,6 +16,21 @@ pub trait DataFetcher: Send + Sync + std::fmt::Debug {
         secrets: &SecretManager,
     ) -> Result<Vec<TableMetadata>, DataFetchError>;
 
+    /// Discover one table (with columns), or None if the source no longer has it.
+    async fn discover_table(
+        &self,
+        source: &Source,
+        secrets: &SecretManager,
+        schema: &str,
+        table: &str,
+    ) -> Result<Option<TableMetadata>, DataFetchError> {
+        Ok(self
+            .discover_tables(source, secrets)
+            .await?
+            .into_iter()
+            .find(|t| t.schema_name == schema && t.table_name == table))
+    }
+
     /// Fetch table data and write to the provided Parquet writer.
     /// The writer is pre-initialized with the destination path.
     /// Driver must call: writer.init(schema) -> writer.write_batch()* (but NOT close())
@@ -27,4 +43,78 @@ pub trait DataFetcher: Send + Sync + std::fmt::Debug {
         table: &str,
         writer: &mut StreamingParquetWriter,
     ) -> Result<(), DataFetchError>;
+
+    /// Fetch records appended to a streaming table since the given offsets and write
+    /// them to the provided Parquet writer. Partitions missing from `offsets` are read
+    /// from the earliest available record.
+    ///
+    /// Returns the offsets to resume from on the next fetch. Only streaming sources
+    /// (see `Source::is_streaming`) support this.
+    async fn fetch_table_since(
+        &self,
+        source: &Source,
+        _secrets: &SecretManager,
+        _schema: &str,
+        _table: &str,
+        _offsets: &[PartitionOffset],
+        _writer: &mut StreamingParquetWriter,
+    ) -> Result<Vec<PartitionOffset>, DataFetchError> {
+        Err(DataFetchError::UnsupportedDriver(source.source_type()))
+    }
+
+    /// Compare a snapshot-versioned table's current snapshot against the one that was
+    /// last cached and decide how it should be refreshed. Pass None for `cached_snapshot`
+    /// when nothing is cached yet. Only sources with snapshots (see `Source::has_snapshots`)
+    /// support this.
+    async fn plan_snapshot_refresh(
+        &self,
+        source: &Source,
+        _secrets: &SecretManager,
+        _schema: &str,
+        _table: &str,
+        _cached_snapshot: Option<i64>,
+    ) -> Result<SnapshotRefresh, DataFetchError> {
+        Err(DataFetchError::UnsupportedDriver(source.source_type()))
+    }
+
+    /// Fetch the data described by a refresh plan and write it to the provided Parquet
+    /// writer: the whole table at the planned snapshot for `Full`, or only the newly added
+    /// data files for `Append`. Writes nothing for `Unchanged`.
+    async fn fetch_table_snapshot(
+        &self,
+        source: &Source,
+        _secrets: &SecretManager,
+        _schema: &str,
+        _table: &str,
+        _plan: &SnapshotRefresh,
+        _writer: &mut StreamingParquetWriter,
+    ) -> Result<(), DataFetchError> {
+        Err(DataFetchError::UnsupportedDriver(source.source_type()))
+    }
+
+    /// When the source last refreshed a materialized view, in microseconds since the epoch.
+    /// Returns None when the source does not track refreshes.
+    async fn source_refreshed_at(
+        &self,
+        _source: &Source,
+        _secrets: &SecretManager,
+        _schema: &str,
+        _table: &str,
+    ) -> Result<Option<i64>, DataFetchError> {
+        Ok(None)
+    }
+
+    /// Fetch a table as it was at an earlier point in its history and write it to the
+    /// provided Parquet writer.
+    async fn fetch_table_as_of(
+        &self,
+        source: &Source,
+        _secrets: &SecretManager,
+        _schema: &str,
+        _table: &str,
+        _as_of: &TimeTravel,
+        _writer: &mut StreamingParquetWriter,
+    ) -> Result<(), DataFetchError> {
+        Err(DataFetchError::UnsupportedDriver(source.source_type()))
+    }
 }
@@ -2,10 +2,18 @@ mod error;
 mod fetcher;
 pub mod native;
 mod orchestrator;
+mod reconcile;
+mod statistics;
 mod types;
 
 pub use error::DataFetchError;
 pub use fetcher::DataFetcher;
 pub use native::{NativeFetcher, StreamingParquetWriter};
-pub use orchestrator::FetchOrchestrator;
-pub use types::{deserialize_arrow_schema, ColumnMetadata, TableMetadata};
+pub(crate) use orchestrator::cached_version;
+pub use orchestrator::{FetchOrchestrator, TIME_TRAVEL_RETENTION};
+pub use reconcile::SchemaMismatch;
+pub use statistics::{table_statistics, CacheStatistics, CachedColumnStatistics};
+pub use types::{
+    deserialize_arrow_schema, CachePolicy, ColumnMetadata, SnapshotRefresh, TableMetadata,
+    TimeTravel,
+};
@@ -0,0 +1,752 @@
+//! BigQuery native driver implementation.
+//!
+//! Discovery queries `INFORMATION_SCHEMA.COLUMNS` and `TABLES` through the REST API. Table
+//! data is read with the Storage Read API in Arrow format: each serialized record batch is
+//! decoded with the session schema and handed to the Parquet writer as-is. Both endpoints can
+//! be pointed at the open-source BigQuery emulator.
+
+use datafusion::arrow::datatypes::{DataType, Field, Fields, IntervalUnit, Schema, TimeUnit};
+use datafusion::arrow::ipc::reader::StreamReader;
+use datafusion::arrow::record_batch::RecordBatch;
+use gcp_auth::{CustomServiceAccount, TokenProvider};
+use google_cloud_googleapis::cloud::bigquery::storage::v1::big_query_read_client::BigQueryReadClient;
+use google_cloud_googleapis::cloud::bigquery::storage::v1::{
+    read_rows_response, read_session, ArrowSchema, CreateReadSessionRequest, DataFormat,
+    ReadRowsRequest, ReadSession,
+};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::io::Cursor;
+use std::sync::Arc;
+use tonic::transport::{Channel, ClientTlsConfig};
+
+use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
+use crate::secrets::SecretManager;
+use crate::source::{Credential, Source, TableMatcher};
+
+use super::StreamingParquetWriter;
+
+const DEFAULT_API_ENDPOINT: &str = "https://bigquery.googleapis.com";
+const DEFAULT_STORAGE_ENDPOINT: &str = "https://bigquerystorage.googleapis.com";
+const BIGQUERY_SCOPE: &str = "https://www.googleapis.com/auth/bigquery";
+
+/// How long a single REST query call waits for the job before returning (then we poll).
+const QUERY_TIMEOUT_MS: u64 = 30_000;
+
+/// Upper bound on a single ReadRows response (the service caps them at 128 MiB).
+const MAX_MESSAGE_SIZE: usize = 128 * 1024 * 1024;
+
+/// Resolved connection settings for a BigQuery source
+struct BigqueryConfig {
+    project: String,
+    dataset: String,
+    api_endpoint: String,
+    storage_endpoint: String,
+    /// `Authorization` header value; `None` when no credential is configured (emulator)
+    authorization: Option<String>,
+}
+
+impl BigqueryConfig {
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.authorization {
+            Some(value) => request.header(reqwest::header::AUTHORIZATION, value),
+            None => request,
+        }
+    }
+
+    /// Wrap a Storage API message with auth and the routing header the service requires.
+    fn grpc_request<T>(
+        &self,
+        message: T,
+        routing_key: &str,
+        routing_value: &str,
+    ) -> Result<tonic::Request<T>, DataFetchError> {
+        let mut request = tonic::Request::new(message);
+        let metadata = request.metadata_mut();
+
+        let routing = format!("{}={}", routing_key, urlencoding::encode(routing_value));
+        metadata.insert(
+            "x-goog-request-params",
+            routing.parse().map_err(|_| {
+                DataFetchError::Query(format!("Invalid routing header: {}", routing))
+            })?,
+        );
+        if let Some(value) = &self.authorization {
+            metadata.insert(
+                "authorization",
+                value.parse().map_err(|_| {
+                    DataFetchError::Connection("Invalid BigQuery access token".to_string())
+                })?,
+            );
+        }
+
+        Ok(request)
+    }
+}
+
+async fn build_config(
+    source: &Source,
+    secrets: &SecretManager,
+) -> Result<BigqueryConfig, DataFetchError> {
+    let (project, dataset, credential, api_endpoint, storage_endpoint) = match source {
+        Source::Bigquery {
+            project,
+            dataset,
+            credential,
+            api_endpoint,
+            storage_endpoint,
+            ..
+        } => (project, dataset, credential, api_endpoint, storage_endpoint),
+        _ => {
+            return Err(DataFetchError::Connection(
+                "Expected BigQuery source".to_string(),
+            ))
+        }
+    };
+
+    // The credential is a service account key; without one requests are sent
+    // unauthenticated, which is what the emulator expects.
+    let authorization = match credential {
+        Credential::None => None,
+        Credential::SecretRef { .. } => {
+            let key_json = credential
+                .resolve(secrets)
+                .await
+                .map_err(|e| DataFetchError::Connection(e.to_string()))?;
+            let account = CustomServiceAccount::from_json(&key_json).map_err(|e| {
+                DataFetchError::Connection(format!("Invalid service account key: {}", e))
+            })?;
+            let token = account.token(&[BIGQUERY_SCOPE]).await.map_err(|e| {
+                DataFetchError::Connection(format!("Failed to obtain access token: {}", e))
+            })?;
+            Some(format!("Bearer {}", token.as_str()))
+        }
+    };
+
+    Ok(BigqueryConfig {
+        project: project.clone(),
+        dataset: dataset.clone(),
+        api_endpoint: api_endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_API_ENDPOINT)
+            .trim_end_matches('/')
+            .to_string(),
+        storage_endpoint: storage_endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_STORAGE_ENDPOINT)
+            .trim_end_matches('/')
+            .to_string(),
+        authorization,
+    })
+}
+
+/// Response of `jobs.query` and `jobs.getQueryResults`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryResponse {
+    #[serde(default)]
+    job_complete: bool,
+    job_reference: Option<JobReference>,
+    #[serde(default)]
+    rows: Vec<QueryRow>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobReference {
+    job_id: String,
+    location: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct QueryRow {
+    f: Vec<QueryCell>,
+}
+
+#[derive(Deserialize)]
+struct QueryCell {
+    v: serde_json::Value,
+}
+
+impl QueryRow {
+    /// String value of a cell; the REST API encodes every scalar as a string.
+    fn get(&self, index: usize) -> Option<&str> {
+        self.f.get(index).and_then(|cell| cell.v.as_str())
+    }
+}
+
+async fn send<T: DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T, DataFetchError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| DataFetchError::Connection(format!("BigQuery request failed: {}", e)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(DataFetchError::Query(format!(
+            "BigQuery API returned {}: {}",
+            status, body
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| DataFetchError::Query(format!("Invalid BigQuery response: {}", e)))
+}
+
+/// Run a GoogleSQL query and collect every result row, polling until the job completes.
+async fn run_query(
+    http: &reqwest::Client,
+    config: &BigqueryConfig,
+    sql: &str,
+) -> Result<Vec<QueryRow>, DataFetchError> {
+    let url = format!(
+        "{}/bigquery/v2/projects/{}/queries",
+        config.api_endpoint, config.project
+    );
+    let body = serde_json::json!({
+        "query": sql,
+        "useLegacySql": false,
+        "timeoutMs": QUERY_TIMEOUT_MS,
+    });
+
+    let mut response: QueryResponse = send(config.authorize(http.post(&url)).json(&body)).await?;
+    let mut rows = std::mem::take(&mut response.rows);
+
+    while !response.job_complete || response.page_token.is_some() {
+        let job = response.job_reference.as_ref().ok_or_else(|| {
+            DataFetchError::Query("BigQuery response has no job reference".to_string())
+        })?;
+
+        let mut request = http
+            .get(format!("{}/{}", url, job.job_id))
+            .query(&[("timeoutMs", QUERY_TIMEOUT_MS.to_string())]);
+        if let Some(location) = &job.location {
+            request = request.query(&[("location", location)]);
+        }
+        if let Some(page_token) = &response.page_token {
+            request = request.query(&[("pageToken", page_token)]);
+        }
+
+        let mut next: QueryResponse = send(config.authorize(request)).await?;
+        rows.append(&mut next.rows);
+        if next.job_reference.is_none() {
+            next.job_reference = response.job_reference.take();
+        }
+        response = next;
+    }
+
+    Ok(rows)
+}
+
+/// Quote a `project.dataset` path for GoogleSQL.
+fn quote_path(project: &str, dataset: &str) -> String {
+    format!(
+        "`{}.{}`",
+        project.replace('`', "\\`"),
+        dataset.replace('`', "\\`")
+    )
+}
+
+/// Discover tables and columns from the configured BigQuery dataset
+pub async fn discover_tables(
+    source: &Source,
+    secrets: &SecretManager,
+    matcher: &TableMatcher,
+) -> Result<Vec<TableMetadata>, DataFetchError> {
+    let config = build_config(source, secrets).await?;
+    let http = reqwest::Client::new();
+
+    let query = format!(
+        r#"
+        SELECT
+            c.table_catalog,
+            c.table_schema,
+            c.table_name,
+            c.column_name,
+            c.data_type,
+            c.is_nullable,
+            c.ordinal_position,
+            t.table_type
+        FROM {path}.INFORMATION_SCHEMA.COLUMNS c
+        JOIN {path}.INFORMATION_SCHEMA.TABLES t
+          ON t.table_schema = c.table_schema AND t.table_name = c.table_name
+        ORDER BY c.table_name, c.ordinal_position
+        "#,
+        path = quote_path(&config.project, &config.dataset)
+    );
+
+    let rows = run_query(&http, &config, &query)
+        .await
+        .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+
+    let mut tables: Vec<TableMetadata> = Vec::new();
+
+    for row in rows {
+        let catalog = row.get(0).map(str::to_string);
+        let schema_name = row.get(1).unwrap_or_default().to_string();
+        let table_name = row.get(2).unwrap_or_default().to_string();
+        if !matcher.matches(&schema_name, &table_name) {
+            continue;
+        }
+
+        let column = ColumnMetadata {
+            name: row.get(3).unwrap_or_default().to_string(),
+            data_type: bigquery_type_to_arrow(row.get(4).unwrap_or("STRING")),
+            nullable: row
+                .get(5)
+                .map(|s| s.eq_ignore_ascii_case("YES"))
+                .unwrap_or(true),
+            ordinal_position: row.get(6).and_then(|s| s.parse().ok()).unwrap_or(0),
+        };
+
+        // Rows are ordered by table, so a new table always starts a new entry
+        match tables.last_mut() {
+            Some(existing)
+                if existing.schema_name == schema_name && existing.table_name == table_name =>
+            {
+                existing.columns.push(column);
+            }
+            _ => tables.push(TableMetadata {
+                catalog_name: catalog,
+                schema_name,
+                table_name,
+                table_type: row.get(7).unwrap_or("BASE TABLE").to_string(),
+                columns: vec![column],
+                row_count: None,
+                size_bytes: None,
+            }),
+        }
+    }
+
+    Ok(tables)
+}
+
+/// Last refresh of a materialized view, in microseconds since the epoch. None for other
+/// tables and for materialized views that were never refreshed.
+pub async fn source_refreshed_at(
+    source: &Source,
+    secrets: &SecretManager,
+    table: &str,
+) -> Result<Option<i64>, DataFetchError> {
+    let config = build_config(source, secrets).await?;
+    let http = reqwest::Client::new();
+
+    let query = format!(
+        "SELECT UNIX_MICROS(last_refresh_time) FROM {}.INFORMATION_SCHEMA.MATERIALIZED_VIEWS \
+         WHERE table_name = '{}'",
+        quote_path(&config.project, &config.dataset),
+        table.replace('\\', "\\\\").replace('\'', "\\'")
+    );
+
+    let rows = run_query(&http, &config, &query).await?;
+    Ok(rows
+        .first()
+        .and_then(|row| row.get(0))
+        .and_then(|s| s.parse().ok()))
+}
+
+async fn connect_storage(
+    config: &BigqueryConfig,
+) -> Result<BigQueryReadClient<Channel>, DataFetchError> {
+    let mut endpoint = Channel::from_shared(config.storage_endpoint.clone())
+        .map_err(|e| DataFetchError::Connection(format!("Invalid storage endpoint: {}", e)))?;
+    if config.storage_endpoint.starts_with("https://") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| DataFetchError::Connection(format!("TLS configuration failed: {}", e)))?;
+    }
+
+    let channel = endpoint.connect().await.map_err(|e| {
+        DataFetchError::Connection(format!(
+            "Failed to connect to BigQuery Storage API at {}: {}",
+            config.storage_endpoint, e
+        ))
+    })?;
+
+    Ok(BigQueryReadClient::new(channel).max_decoding_message_size(MAX_MESSAGE_SIZE))
+}
+
+/// Fetch table data through the Storage Read API and write to Parquet
+pub async fn fetch_table(
+    source: &Source,
+    secrets: &SecretManager,
+    _catalog: Option<&str>,
+    schema: &str,
+    table: &str,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    let config = build_config(source, secrets).await?;
+    let mut client = connect_storage(&config).await?;
+
+    let table_path = format!(
+        "projects/{}/datasets/{}/tables/{}",
+        config.project, schema, table
+    );
+
+    // A single stream: batches are written sequentially, so more streams would not help
+    let request = CreateReadSessionRequest {
+        parent: format!("projects/{}", config.project),
+        read_session: Some(ReadSession {
+            table: table_path.clone(),
+            data_format: DataFormat::Arrow as i32,
+            ..Default::default()
+        }),
+        max_stream_count: 1,
+        ..Default::default()
+    };
+    let session = client
+        .create_read_session(config.grpc_request(request, "read_session.table", &table_path)?)
+        .await
+        .map_err(|e| {
+            DataFetchError::Query(format!(
+                "Failed to create read session for {}: {}",
+                table_path,
+                e.message()
+            ))
+        })?
+        .into_inner();
+
+    let serialized_schema = match session.schema {
+        Some(read_session::Schema::ArrowSchema(ArrowSchema { serialized_schema })) => {
+            serialized_schema
+        }
+        _ => {
+            return Err(DataFetchError::Query(format!(
+                "Read session for {} returned no Arrow schema",
+                table_path
+            )))
+        }
+    };
+    let arrow_schema = decode_schema(&serialized_schema)?;
+    writer.init(&arrow_schema)?;
+
+    for stream in &session.streams {
+        let request = ReadRowsRequest {
+            read_stream: stream.name.clone(),
+            offset: 0,
+        };
+        let mut responses = client
+            .read_rows(config.grpc_request(request, "read_stream", &stream.name)?)
+            .await
+            .map_err(|e| DataFetchError::Query(format!("ReadRows failed: {}", e.message())))?
+            .into_inner();
+
+        while let Some(response) = responses
+            .message()
+            .await
+            .map_err(|e| DataFetchError::Query(format!("ReadRows failed: {}", e.message())))?
+        {
+            if let Some(read_rows_response::Rows::ArrowRecordBatch(batch)) = response.rows {
+                for batch in decode_batches(&serialized_schema, &batch.serialized_record_batch)? {
+                    writer.write_batch(&batch).await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Decode the IPC schema message of a read session.
+fn decode_schema(serialized_schema: &[u8]) -> Result<Schema, DataFetchError> {
+    let reader = StreamReader::try_new(Cursor::new(serialized_schema), None)
+        .map_err(|e| DataFetchError::Query(format!("Invalid Arrow schema: {}", e)))?;
+    Ok(reader.schema().as_ref().clone())
+}
+
+/// Decode a serialized record batch message. The Storage API sends batches without the
+/// schema, so the schema message is prepended to form a readable IPC stream.
+fn decode_batches(
+    serialized_schema: &[u8],
+    serialized_batch: &[u8],
+) -> Result<Vec<RecordBatch>, DataFetchError> {
+    let mut stream = Vec::with_capacity(serialized_schema.len() + serialized_batch.len());
+    stream.extend_from_slice(serialized_schema);
+    stream.extend_from_slice(serialized_batch);
+
+    StreamReader::try_new(Cursor::new(stream), None)
+        .map_err(|e| DataFetchError::Query(format!("IPC read error: {}", e)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| DataFetchError::Query(format!("IPC read error: {}", e)))
+}
+
+/// Convert a BigQuery `INFORMATION_SCHEMA` type to the Arrow type the Storage Read API
+/// produces for it.
+fn bigquery_type_to_arrow(bq_type: &str) -> DataType {
+    let bq_type = bq_type.trim();
+
+    if let Some(inner) = type_parameter(bq_type, "ARRAY") {
+        return DataType::List(Arc::new(Field::new(
+            "item",
+            bigquery_type_to_arrow(inner),
+            true,
+        )));
+    }
+    if let Some(inner) = type_parameter(bq_type, "STRUCT") {
+        return DataType::Struct(struct_fields(inner));
+    }
+    if let Some(inner) = type_parameter(bq_type, "RANGE") {
+        let element = bigquery_type_to_arrow(inner);
+        return DataType::Struct(Fields::from(vec![
+            Field::new("start", element.clone(), true),
+            Field::new("end", element, true),
+        ]));
+    }
+
+    // Handle parameterized types like STRING(255), NUMERIC(10, 2)
+    let type_upper = bq_type.to_uppercase();
+    let base_type = type_upper.split('(').next().unwrap_or(&type_upper).trim();
+
+    match base_type {
+        "BOOL" | "BOOLEAN" => DataType::Boolean,
+        "INT64" | "INT" | "INTEGER" | "SMALLINT" | "BIGINT" | "TINYINT" | "BYTEINT" => {
+            DataType::Int64
+        }
+        "FLOAT64" | "FLOAT" => DataType::Float64,
+        "NUMERIC" | "DECIMAL" => DataType::Decimal128(38, 9),
+        "BIGNUMERIC" | "BIGDECIMAL" => DataType::Decimal256(76, 38),
+        "STRING" => DataType::Utf8,
+        "BYTES" => DataType::Binary,
+        "DATE" => DataType::Date32,
+        "TIME" => DataType::Time64(TimeUnit::Microsecond),
+        "DATETIME" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        "TIMESTAMP" => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        "INTERVAL" => DataType::Interval(IntervalUnit::MonthDayNano),
+        // JSON is read as its text and GEOGRAPHY as WKT
+        _ => DataType::Utf8,
+    }
+}
+
+/// The parameter of a type like `ARRAY<INT64>`, with the type name matched case-insensitively.
+fn type_parameter<'a>(bq_type: &'a str, name: &str) -> Option<&'a str> {
+    let rest = match bq_type.get(..name.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(name) => &bq_type[name.len()..],
+        _ => return None,
+    };
+    rest.trim_start().strip_prefix('<')?.strip_suffix('>')
+}
+
+/// The fields of a `STRUCT<name TYPE, ...>` parameter list. Field names keep their case.
+fn struct_fields(params: &str) -> Fields {
+    let mut fields = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in params.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                fields.extend(struct_field(&params[start..i]));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.extend(struct_field(&params[start..]));
+    Fields::from(fields)
+}
+
+fn struct_field(definition: &str) -> Option<Field> {
+    let (name, bq_type) = definition.trim().split_once(char::is_whitespace)?;
+    Some(Field::new(
+        name.trim_matches('`'),
+        bigquery_type_to_arrow(bq_type),
+        true,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::{Int64Array, StringArray};
+    use datafusion::arrow::ipc::writer::{
+        write_message, DictionaryTracker, IpcDataGenerator, IpcWriteOptions,
+    };
+
+    #[test]
+    fn test_bigquery_type_to_arrow_scalars() {
+        assert_eq!(bigquery_type_to_arrow("INT64"), DataType::Int64);
+        assert_eq!(bigquery_type_to_arrow("FLOAT64"), DataType::Float64);
+        assert_eq!(bigquery_type_to_arrow("BOOL"), DataType::Boolean);
+        assert_eq!(bigquery_type_to_arrow("STRING(255)"), DataType::Utf8);
+        assert_eq!(bigquery_type_to_arrow("BYTES"), DataType::Binary);
+        assert_eq!(bigquery_type_to_arrow("DATE"), DataType::Date32);
+        assert_eq!(bigquery_type_to_arrow("JSON"), DataType::Utf8);
+        assert_eq!(bigquery_type_to_arrow("GEOGRAPHY"), DataType::Utf8);
+    }
+
+    #[test]
+    fn test_bigquery_type_to_arrow_numeric() {
+        assert_eq!(
+            bigquery_type_to_arrow("NUMERIC"),
+            DataType::Decimal128(38, 9)
+        );
+        assert_eq!(
+            bigquery_type_to_arrow("NUMERIC(10, 2)"),
+            DataType::Decimal128(38, 9)
+        );
+        assert_eq!(
+            bigquery_type_to_arrow("BIGNUMERIC"),
+            DataType::Decimal256(76, 38)
+        );
+    }
+
+    #[test]
+    fn test_bigquery_type_to_arrow_datetime() {
+        assert_eq!(
+            bigquery_type_to_arrow("TIME"),
+            DataType::Time64(TimeUnit::Microsecond)
+        );
+        assert_eq!(
+            bigquery_type_to_arrow("DATETIME"),
+            DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(
+            bigquery_type_to_arrow("TIMESTAMP"),
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+    }
+
+    #[test]
+    fn test_bigquery_type_to_arrow_array() {
+        assert_eq!(
+            bigquery_type_to_arrow("ARRAY<INT64>"),
+            DataType::List(Arc::new(Field::new("item", DataType::Int64, true)))
+        );
+    }
+
+    #[test]
+    fn test_bigquery_type_to_arrow_struct() {
+        let address = Fields::from(vec![
+            Field::new("City", DataType::Utf8, true),
+            Field::new("zip", DataType::Int64, true),
+        ]);
+        assert_eq!(
+            bigquery_type_to_arrow(
+                "STRUCT<id INT64, Address STRUCT<City STRING(20), zip INT64>, \
+                 amounts ARRAY<NUMERIC(10, 2)>>"
+            ),
+            DataType::Struct(Fields::from(vec![
+                Field::new("id", DataType::Int64, true),
+                Field::new("Address", DataType::Struct(address), true),
+                Field::new(
+                    "amounts",
+                    DataType::List(Arc::new(Field::new(
+                        "item",
+                        DataType::Decimal128(38, 9),
+                        true
+                    ))),
+                    true
+                ),
+            ]))
+        );
+        assert_eq!(
+            bigquery_type_to_arrow("RANGE<DATE>"),
+            DataType::Struct(Fields::from(vec![
+                Field::new("start", DataType::Date32, true),
+                Field::new("end", DataType::Date32, true),
+            ]))
+        );
+        assert_eq!(
+            bigquery_type_to_arrow("INTERVAL"),
+            DataType::Interval(IntervalUnit::MonthDayNano)
+        );
+    }
+
+    #[test]
+    fn test_struct_column_matches_discovered_schema() {
+        use datafusion::arrow::array::StructArray;
+
+        // A STRUCT column as the Storage Read API sends it
+        let DataType::Struct(fields) = bigquery_type_to_arrow("STRUCT<a INT64, b STRING>") else {
+            panic!("STRUCT should map to a struct type");
+        };
+        let point = StructArray::new(
+            fields.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["x", "y"])),
+            ],
+            None,
+        );
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "point",
+            DataType::Struct(fields),
+            true,
+        )]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(point)]).unwrap();
+
+        let discovered = Schema::new(vec![Field::new(
+            "point",
+            bigquery_type_to_arrow("STRUCT<a INT64, b STRING>"),
+            true,
+        )]);
+        assert!(crate::datafetch::reconcile::reconcile_schemas(
+            batch.schema().as_ref(),
+            &discovered
+        )
+        .is_ok());
+        assert!(!crate::datafetch::reconcile::needs_cast(
+            batch.schema().as_ref(),
+            &discovered
+        ));
+    }
+
+    #[test]
+    fn test_quote_path() {
+        assert_eq!(quote_path("my-project", "sales"), "`my-project.sales`");
+        assert_eq!(quote_path("p", "a`b"), "`p.a\\`b`");
+    }
+
+    #[test]
+    fn test_query_row_get() {
+        let row: QueryRow =
+            serde_json::from_str(r#"{"f": [{"v": "orders"}, {"v": null}, {"v": "3"}]}"#).unwrap();
+        assert_eq!(row.get(0), Some("orders"));
+        assert_eq!(row.get(1), None);
+        assert_eq!(row.get(2), Some("3"));
+        assert_eq!(row.get(3), None);
+    }
+
+    #[test]
+    fn test_decode_separately_serialized_batches() {
+        // The Storage API sends the schema once and each batch as a bare IPC message
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+            ],
+        )
+        .unwrap();
+
+        let generator = IpcDataGenerator::default();
+        let options = IpcWriteOptions::default();
+        let mut tracker = DictionaryTracker::new(false);
+
+        let mut serialized_schema = Vec::new();
+        let encoded =
+            generator.schema_to_bytes_with_dictionary_tracker(&schema, &mut tracker, &options);
+        write_message(&mut serialized_schema, encoded, &options).unwrap();
+
+        let mut serialized_batch = Vec::new();
+        let (_, encoded) = generator
+            .encoded_batch(&batch, &mut tracker, &options)
+            .unwrap();
+        write_message(&mut serialized_batch, encoded, &options).unwrap();
+
+        assert_eq!(decode_schema(&serialized_schema).unwrap(), *schema);
+
+        let decoded = decode_batches(&serialized_schema, &serialized_batch).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0], batch);
+    }
+}
@@ -0,0 +1,248 @@
+//! Decimal type mapping shared by the SQL drivers.
+//!
+//! Declared `DECIMAL(p, s)` columns map to `Decimal128` when the precision fits in 38 digits
+//! and to `Decimal256` up to 76 digits. Unconstrained or wider numerics have no faithful
+//! fixed-point Arrow type and are kept as text.
+
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use datafusion::arrow::array::{ArrayBuilder, Decimal128Builder, Decimal256Builder};
+use datafusion::arrow::datatypes::{
+    i256, DataType, DECIMAL128_MAX_PRECISION, DECIMAL256_MAX_PRECISION,
+};
+
+use crate::datafetch::DataFetchError;
+
+/// Arrow type for a declared precision and scale, or `None` when it does not fit.
+pub fn decimal_type(precision: u32, scale: u32) -> Option<DataType> {
+    if precision == 0 || scale > precision {
+        return None;
+    }
+    if precision <= DECIMAL128_MAX_PRECISION as u32 {
+        Some(DataType::Decimal128(precision as u8, scale as i8))
+    } else if precision <= DECIMAL256_MAX_PRECISION as u32 {
+        Some(DataType::Decimal256(precision as u8, scale as i8))
+    } else {
+        None
+    }
+}
+
+/// Parse the `(precision, scale)` parameters of a type like `numeric(10,2)`,
+/// `DECIMAL(10, 2) unsigned` or `NUMBER(38)`. A missing scale means 0.
+pub fn parse_precision_scale(type_str: &str) -> Option<(u32, u32)> {
+    let params = type_str.split_once('(')?.1.split_once(')')?.0;
+    let mut parts = params.split(',').map(str::trim);
+    let precision = parts.next()?.parse().ok()?;
+    let scale = match parts.next() {
+        Some(scale) => scale.parse().ok()?,
+        None => 0,
+    };
+    Some((precision, scale))
+}
+
+/// Arrow type for a parameterized decimal type string; `Utf8` when unconstrained or too wide.
+pub fn decimal_type_from_str(type_str: &str) -> DataType {
+    parse_precision_scale(type_str)
+        .and_then(|(precision, scale)| decimal_type(precision, scale))
+        .unwrap_or(DataType::Utf8)
+}
+
+/// Builder for a `Decimal128` or `Decimal256` column.
+pub fn make_decimal_builder(data_type: &DataType, capacity: usize) -> Box<dyn ArrayBuilder> {
+    match data_type {
+        DataType::Decimal256(_, _) => {
+            Box::new(Decimal256Builder::with_capacity(capacity).with_data_type(data_type.clone()))
+        }
+        _ => Box::new(Decimal128Builder::with_capacity(capacity).with_data_type(data_type.clone())),
+    }
+}
+
+/// Append a value of `column` to a builder created by [`make_decimal_builder`] for
+/// `data_type`. Values are rescaled to the column scale. A value with more fractional digits
+/// than the scale, or more digits than the precision, is an error rather than being rounded
+/// or dropped.
+pub fn append_decimal(
+    builder: &mut Box<dyn ArrayBuilder>,
+    data_type: &DataType,
+    column: &str,
+    value: Option<BigDecimal>,
+) -> Result<(), DataFetchError> {
+    let (precision, scale) = match data_type {
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+            (*precision, *scale)
+        }
+        _ => unreachable!("append_decimal called for non-decimal type {}", data_type),
+    };
+    let unscaled = value
+        .map(|v| unscaled(&v, precision, scale).map_err(|e| decimal_error(column, &v, e)))
+        .transpose()?;
+
+    match data_type {
+        DataType::Decimal256(_, _) => {
+            let b = builder
+                .as_any_mut()
+                .downcast_mut::<Decimal256Builder>()
+                .unwrap();
+            // Fits, since the precision is at most 76 digits
+            b.append_option(unscaled.and_then(|v| i256::from_string(&v.to_string())));
+        }
+        _ => {
+            let b = builder
+                .as_any_mut()
+                .downcast_mut::<Decimal128Builder>()
+                .unwrap();
+            // Fits, since the precision is at most 38 digits
+            b.append_option(unscaled.and_then(|v| v.to_i128()));
+        }
+    }
+    Ok(())
+}
+
+/// The integer `value * 10^scale`, if it is exact and has at most `precision` digits.
+fn unscaled(value: &BigDecimal, precision: u8, scale: i8) -> Result<BigInt, String> {
+    let rescaled = value.with_scale(scale as i64);
+    if &rescaled != value {
+        return Err(format!("has more than {} fractional digits", scale));
+    }
+    let (unscaled, _) = rescaled.as_bigint_and_exponent();
+    if unscaled.magnitude().to_string().len() > precision as usize {
+        return Err(format!("has more than {} digits", precision));
+    }
+    Ok(unscaled)
+}
+
+fn decimal_error(column: &str, value: &BigDecimal, reason: String) -> DataFetchError {
+    DataFetchError::Query(format!(
+        "value {} of column '{}' does not fit its decimal type: it {}",
+        value, column, reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::{Array, Decimal128Array, Decimal256Array};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_decimal_type_by_precision() {
+        assert_eq!(decimal_type(10, 2), Some(DataType::Decimal128(10, 2)));
+        assert_eq!(decimal_type(38, 0), Some(DataType::Decimal128(38, 0)));
+        assert_eq!(decimal_type(39, 4), Some(DataType::Decimal256(39, 4)));
+        assert_eq!(decimal_type(76, 10), Some(DataType::Decimal256(76, 10)));
+        assert_eq!(decimal_type(77, 0), None);
+        assert_eq!(decimal_type(5, 6), None);
+        assert_eq!(decimal_type(0, 0), None);
+    }
+
+    #[test]
+    fn test_parse_precision_scale() {
+        assert_eq!(parse_precision_scale("numeric(10,2)"), Some((10, 2)));
+        assert_eq!(
+            parse_precision_scale("DECIMAL(12, 4) unsigned"),
+            Some((12, 4))
+        );
+        assert_eq!(parse_precision_scale("NUMBER(38)"), Some((38, 0)));
+        assert_eq!(parse_precision_scale("numeric"), None);
+        assert_eq!(parse_precision_scale("decimal(x,2)"), None);
+    }
+
+    #[test]
+    fn test_decimal_type_from_str_fallback() {
+        assert_eq!(
+            decimal_type_from_str("decimal(10,2)"),
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(decimal_type_from_str("numeric"), DataType::Utf8);
+        assert_eq!(decimal_type_from_str("numeric(1000,2)"), DataType::Utf8);
+    }
+
+    #[test]
+    fn test_append_decimal128_rescales() {
+        let data_type = DataType::Decimal128(10, 2);
+        let mut builder = make_decimal_builder(&data_type, 4);
+        append_decimal(
+            &mut builder,
+            &data_type,
+            "amount",
+            Some(BigDecimal::from_str("123.45").unwrap()),
+        )
+        .unwrap();
+        append_decimal(
+            &mut builder,
+            &data_type,
+            "amount",
+            Some(BigDecimal::from_str("-7.5").unwrap()),
+        )
+        .unwrap();
+        append_decimal(&mut builder, &data_type, "amount", None).unwrap();
+
+        let array = builder.finish();
+        let array = array.as_any().downcast_ref::<Decimal128Array>().unwrap();
+        assert_eq!(array.data_type(), &data_type);
+        assert_eq!(array.value(0), 12345);
+        assert_eq!(array.value(1), -750);
+        assert!(array.is_null(2));
+        assert_eq!(array.value_as_string(0), "123.45");
+    }
+
+    #[test]
+    fn test_append_decimal256() {
+        let data_type = DataType::Decimal256(50, 3);
+        let mut builder = make_decimal_builder(&data_type, 1);
+        let value = "12345678901234567890123456789012345678901234.567";
+        append_decimal(
+            &mut builder,
+            &data_type,
+            "amount",
+            Some(BigDecimal::from_str(value).unwrap()),
+        )
+        .unwrap();
+
+        let array = builder.finish();
+        let array = array.as_any().downcast_ref::<Decimal256Array>().unwrap();
+        assert_eq!(array.value_as_string(0), value);
+    }
+
+    #[test]
+    fn test_append_decimal_rejects_values_that_do_not_fit() {
+        let data_type = DataType::Decimal128(5, 2);
+        let mut builder = make_decimal_builder(&data_type, 2);
+
+        let err = append_decimal(
+            &mut builder,
+            &data_type,
+            "amount",
+            Some(BigDecimal::from_str("1234.5").unwrap()),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("1234.5") && err.contains("'amount'"),
+            "{}",
+            err
+        );
+
+        let err = append_decimal(
+            &mut builder,
+            &data_type,
+            "amount",
+            Some(BigDecimal::from_str("1.234").unwrap()),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("fractional digits"), "{}", err);
+
+        // Trailing zeros beyond the scale lose nothing
+        append_decimal(
+            &mut builder,
+            &data_type,
+            "amount",
+            Some(BigDecimal::from_str("-999.990").unwrap()),
+        )
+        .unwrap();
+        let array = builder.finish();
+        let array = array.as_any().downcast_ref::<Decimal128Array>().unwrap();
+        assert_eq!(array.value(0), -99999);
+    }
+}
@@ -0,0 +1,303 @@
+//! Delta Lake native driver implementation.
+//!
+//! A Delta source points at either a single table (a directory containing `_delta_log`)
+//! or a lake root whose immediate subdirectories are tables. All tables are exposed in
+//! the `default` schema. Reads go through delta-rs' DataFusion provider so that
+//! partition values are materialized and deletion vectors are applied.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use datafusion::prelude::SessionContext;
+use deltalake::{DeltaTable, DeltaTableBuilder};
+use futures::StreamExt;
+use object_store::path::Path as ObjectPath;
+use url::Url;
+
+use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
+use crate::secrets::SecretManager;
+use crate::source::{AwsCredentials, Credential, Source, TableMatcher};
+
+use super::StreamingParquetWriter;
+
+/// Schema name under which all Delta tables are exposed.
+const DEFAULT_SCHEMA: &str = "default";
+
+/// Directory that marks a Delta table root.
+const DELTA_LOG_DIR: &str = "_delta_log";
+
+// Storage option keys understood by delta-rs' S3 backend
+const AWS_ACCESS_KEY_ID: &str = "AWS_ACCESS_KEY_ID";
+const AWS_SECRET_ACCESS_KEY: &str = "AWS_SECRET_ACCESS_KEY";
+const AWS_SESSION_TOKEN: &str = "AWS_SESSION_TOKEN";
+
+/// Connection settings shared by discovery and fetch.
+struct DeltaConfig {
+    root: Url,
+    storage_options: HashMap<String, String>,
+    version: Option<i64>,
+    timestamp: Option<String>,
+}
+
+async fn build_config(
+    source: &Source,
+    secrets: &SecretManager,
+) -> Result<DeltaConfig, DataFetchError> {
+    let (location, credential, version, timestamp) = match source {
+        Source::Delta {
+            location,
+            credential,
+            version,
+            timestamp,
+            ..
+        } => (location, credential, *version, timestamp.clone()),
+        _ => unreachable!("build_config called with non-Delta source"),
+    };
+
+    if version.is_some() && timestamp.is_some() {
+        return Err(DataFetchError::Connection(
+            "only one of version or timestamp may be set for Delta time travel".to_string(),
+        ));
+    }
+
+    let mut storage_options = HashMap::new();
+    if let Credential::SecretRef { .. } = credential {
+        let creds_json = credential
+            .resolve(secrets)
+            .await
+            .map_err(|e| DataFetchError::Connection(e.to_string()))?;
+        let aws_creds: AwsCredentials = serde_json::from_str(&creds_json).map_err(|e| {
+            DataFetchError::Connection(format!("Invalid AWS credentials JSON: {}", e))
+        })?;
+        storage_options.insert(AWS_ACCESS_KEY_ID.to_string(), aws_creds.access_key_id);
+        storage_options.insert(
+            AWS_SECRET_ACCESS_KEY.to_string(),
+            aws_creds.secret_access_key,
+        );
+        if let Some(session_token) = aws_creds.session_token {
+            storage_options.insert(AWS_SESSION_TOKEN.to_string(), session_token);
+        }
+    }
+
+    Ok(DeltaConfig {
+        root: location_to_url(location)?,
+        storage_options,
+        version,
+        timestamp,
+    })
+}
+
+/// Normalize a location into a directory URL. Bare paths are treated as local files.
+fn location_to_url(location: &str) -> Result<Url, DataFetchError> {
+    let mut url = match Url::parse(location) {
+        Ok(url) if url.scheme().len() > 1 => url,
+        // Not a URL, or a Windows drive letter parsed as a scheme
+        _ => {
+            let path = std::path::absolute(location).map_err(|e| {
+                DataFetchError::Connection(format!("invalid location '{}': {}", location, e))
+            })?;
+            Url::from_directory_path(&path).map_err(|_| {
+                DataFetchError::Connection(format!("invalid location '{}'", location))
+            })?
+        }
+    };
+
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+
+    Ok(url)
+}
+
+/// Resolve the tables under the configured root as (table name, table URL) pairs. Only
+/// directories whose name passes `keep` are probed for a `_delta_log`.
+async fn table_locations(
+    config: &DeltaConfig,
+    keep: impl Fn(&str) -> bool,
+) -> Result<Vec<(String, Url)>, DataFetchError> {
+    let (store, root_path) =
+        object_store::parse_url_opts(&config.root, config.storage_options.iter())
+            .map_err(|e| DataFetchError::Connection(e.to_string()))?;
+
+    // The root itself is a table
+    if has_delta_log(store.as_ref(), &root_path).await? {
+        let name = root_path
+            .parts()
+            .last()
+            .map(|p| p.as_ref().to_string())
+            .ok_or_else(|| {
+                DataFetchError::Discovery("cannot derive a table name from the root".to_string())
+            })?;
+        if !keep(&name) {
+            return Ok(Vec::new());
+        }
+        return Ok(vec![(name, config.root.clone())]);
+    }
+
+    let listing = store
+        .list_with_delimiter(Some(&root_path))
+        .await
+        .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+
+    let mut tables = Vec::new();
+    for prefix in listing.common_prefixes {
+        let Some(name) = prefix.filename().map(str::to_string) else {
+            continue;
+        };
+        if name.starts_with('_') || name.starts_with('.') || !keep(&name) {
+            continue;
+        }
+        if has_delta_log(store.as_ref(), &prefix).await? {
+            let url = config
+                .root
+                .join(&format!("{}/", name))
+                .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+            tables.push((name, url));
+        }
+    }
+
+    tables.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(tables)
+}
+
+async fn has_delta_log(
+    store: &dyn object_store::ObjectStore,
+    dir: &ObjectPath,
+) -> Result<bool, DataFetchError> {
+    let log_dir = dir.child(DELTA_LOG_DIR);
+    let mut entries = store.list(Some(&log_dir));
+    match entries.next().await {
+        Some(Ok(_)) => Ok(true),
+        Some(Err(object_store::Error::NotFound { .. })) | None => Ok(false),
+        Some(Err(e)) => Err(DataFetchError::Discovery(e.to_string())),
+    }
+}
+
+/// Load a table, applying the configured time travel target.
+async fn load_table(config: &DeltaConfig, url: &Url) -> Result<DeltaTable, DataFetchError> {
+    let mut builder = DeltaTableBuilder::from_uri(url.clone())
+        .map_err(|e| DataFetchError::Connection(e.to_string()))?
+        .with_storage_options(config.storage_options.clone());
+
+    if let Some(version) = config.version {
+        builder = builder.with_version(version);
+    } else if let Some(timestamp) = &config.timestamp {
+        builder = builder
+            .with_datestring(timestamp)
+            .map_err(|e| DataFetchError::Connection(format!("invalid timestamp: {}", e)))?;
+    }
+
+    builder
+        .load()
+        .await
+        .map_err(|e| DataFetchError::Query(e.to_string()))
+}
+
+/// Discover Delta tables and read their schemas from `_delta_log`.
+pub async fn discover_tables(
+    source: &Source,
+    secrets: &SecretManager,
+    matcher: &TableMatcher,
+) -> Result<Vec<TableMetadata>, DataFetchError> {
+    deltalake::aws::register_handlers(None);
+
+    let config = build_config(source, secrets).await?;
+    let mut tables = Vec::new();
+
+    for (name, url) in
+        table_locations(&config, |name| matcher.matches(DEFAULT_SCHEMA, name)).await?
+    {
+        let table = load_table(&config, &url).await?;
+        let schema = table
+            .snapshot()
+            .map(|s| s.snapshot().arrow_schema())
+            .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| ColumnMetadata {
+                name: field.name().clone(),
+                data_type: field.data_type().clone(),
+                nullable: field.is_nullable(),
+                ordinal_position: i as i32,
+            })
+            .collect();
+
+        tables.push(TableMetadata {
+            catalog_name: None,
+            schema_name: DEFAULT_SCHEMA.to_string(),
+            table_name: name,
+            table_type: "BASE TABLE".to_string(),
+            columns,
+            row_count: None,
+            size_bytes: None,
+        });
+    }
+
+    Ok(tables)
+}
+
+/// Fetch table data and write to Parquet using streaming.
+pub async fn fetch_table(
+    source: &Source,
+    secrets: &SecretManager,
+    _catalog: Option<&str>,
+    _schema: &str,
+    table: &str,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    deltalake::aws::register_handlers(None);
+
+    let config = build_config(source, secrets).await?;
+    let url = table_locations(&config, |name| name == table)
+        .await?
+        .into_iter()
+        .find(|(name, _)| name == table)
+        .map(|(_, url)| url)
+        .ok_or_else(|| DataFetchError::Query(format!("Delta table '{}' not found", table)))?;
+
+    let delta_table = load_table(&config, &url).await?;
+
+    // The DataFusion provider handles partition columns and deletion vectors for us
+    let ctx = SessionContext::new();
+    let df = ctx
+        .read_table(Arc::new(delta_table))
+        .map_err(|e| DataFetchError::Query(e.to_string()))?;
+
+    writer.init(df.schema().as_arrow())?;
+
+    let mut stream = df
+        .execute_stream()
+        .await
+        .map_err(|e| DataFetchError::Query(e.to_string()))?;
+
+    while let Some(batch) = stream.next().await {
+        let batch = batch.map_err(|e| DataFetchError::Query(e.to_string()))?;
+        writer.write_batch(&batch).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_to_url_local_path() {
+        let url = location_to_url("/data/lake").unwrap();
+        assert_eq!(url.as_str(), "file:///data/lake/");
+    }
+
+    #[test]
+    fn test_location_to_url_remote() {
+        let url = location_to_url("s3://bucket/lake").unwrap();
+        assert_eq!(url.as_str(), "s3://bucket/lake/");
+
+        let url = location_to_url("s3://bucket/lake/").unwrap();
+        assert_eq!(url.as_str(), "s3://bucket/lake/");
+    }
+}
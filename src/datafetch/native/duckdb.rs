@@ -6,20 +6,23 @@ use urlencoding::encode;
 
 use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
 use crate::secrets::SecretManager;
-use crate::source::Source;
+use crate::source::{Source, TableMatcher};
 
+use super::decimal::{decimal_type, parse_precision_scale};
 use super::StreamingParquetWriter;
 
 /// Discover tables and columns from DuckDB/MotherDuck
 pub async fn discover_tables(
     source: &Source,
     secrets: &SecretManager,
+    matcher: &TableMatcher,
 ) -> Result<Vec<TableMetadata>, DataFetchError> {
     let connection_string = resolve_connection_string(source, secrets).await?;
     let catalog = source.catalog().map(|s| s.to_string());
+    let matcher = matcher.clone();
 
     tokio::task::spawn_blocking(move || {
-        discover_tables_sync(&connection_string, catalog.as_deref())
+        discover_tables_sync(&connection_string, catalog.as_deref(), &matcher)
     })
     .await
     .map_err(|e| DataFetchError::Connection(e.to_string()))?
@@ -31,10 +34,11 @@ pub async fn resolve_connection_string(
     secrets: &SecretManager,
 ) -> Result<String, DataFetchError> {
     match source {
-        Source::Duckdb { path } => Ok(path.clone()),
+        Source::Duckdb { path, .. } => Ok(path.clone()),
         Source::Motherduck {
             database,
             credential,
+            ..
         } => {
             let token = credential
                 .resolve(secrets)
@@ -55,6 +59,7 @@ pub async fn resolve_connection_string(
 fn discover_tables_sync(
     connection_string: &str,
     catalog: Option<&str>,
+    matcher: &TableMatcher,
 ) -> Result<Vec<TableMetadata>, DataFetchError> {
     let conn = Connection::open(connection_string)
         .map_err(|e| DataFetchError::Connection(e.to_string()))?;
@@ -68,12 +73,17 @@ fn discover_tables_sync(
             c.column_name,
             c.data_type,
             c.is_nullable,
-            c.ordinal_position
+            c.ordinal_position,
+            d.estimated_size
         FROM information_schema.tables t
         JOIN information_schema.columns c
             ON t.table_catalog = c.table_catalog
             AND t.table_schema = c.table_schema
             AND t.table_name = c.table_name
+        LEFT JOIN duckdb_tables() d
+            ON d.database_name = t.table_catalog
+            AND d.schema_name = t.table_schema
+            AND d.table_name = t.table_name
         WHERE t.table_schema NOT IN ('information_schema', 'pg_catalog')
           AND ($1 IS NULL OR t.table_catalog = $1)
         ORDER BY t.table_schema, t.table_name, c.ordinal_position
@@ -94,6 +104,7 @@ fn discover_tables_sync(
                 row.get::<_, String>(5)?,         // data_type
                 row.get::<_, String>(6)?,         // is_nullable
                 row.get::<_, i32>(7)?,            // ordinal_position
+                row.get::<_, Option<i64>>(8)?,    // estimated row count
             ))
         })
         .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
@@ -101,8 +112,21 @@ fn discover_tables_sync(
     let mut table_map: HashMap<(Option<String>, String, String), TableMetadata> = HashMap::new();
 
     for row_result in rows {
-        let (catalog, schema, table, table_type, col_name, data_type, is_nullable, ordinal) =
-            row_result.map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+        let (
+            catalog,
+            schema,
+            table,
+            table_type,
+            col_name,
+            data_type,
+            is_nullable,
+            ordinal,
+            row_count,
+        ) = row_result.map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+
+        if !matcher.matches(&schema, &table) {
+            continue;
+        }
 
         let column = ColumnMetadata {
             name: col_name,
@@ -122,6 +146,8 @@ fn discover_tables_sync(
                 table_name: table,
                 table_type,
                 columns: vec![column],
+                row_count: row_count.and_then(|n| u64::try_from(n).ok()),
+                size_bytes: None,
             });
     }
 
@@ -176,7 +202,7 @@ pub async fn fetch_table(
     while let Some(msg) = rx.recv().await {
         match msg {
             FetchMessage::Batch(batch) => {
-                writer.write_batch(&batch)?;
+                writer.write_batch(&batch).await?;
                 wrote_any = true;
             }
             FetchMessage::Schema(_) => {
@@ -188,7 +214,7 @@ pub async fn fetch_table(
     // Handle empty table
     if !wrote_any {
         let empty_batch = RecordBatch::new_empty(Arc::new(arrow_schema));
-        writer.write_batch(&empty_batch)?;
+        writer.write_batch(&empty_batch).await?;
     }
 
     // Wait for blocking task to complete and propagate any errors
@@ -262,7 +288,10 @@ fn duckdb_type_to_arrow(duckdb_type: &str) -> datafusion::arrow::datatypes::Data
         "UBIGINT" => DataType::UInt64,
         "REAL" | "FLOAT4" | "FLOAT" => DataType::Float32,
         "DOUBLE" | "FLOAT8" => DataType::Float64,
-        "DECIMAL" | "NUMERIC" => DataType::Decimal128(38, 10),
+        // DuckDB reports the declared width, e.g. DECIMAL(10,2); its default is DECIMAL(18,3)
+        "DECIMAL" | "NUMERIC" => parse_precision_scale(&type_upper)
+            .and_then(|(precision, scale)| decimal_type(precision, scale))
+            .unwrap_or(DataType::Decimal128(18, 3)),
         "VARCHAR" | "TEXT" | "STRING" | "CHAR" | "BPCHAR" => DataType::Utf8,
         "BLOB" | "BYTEA" | "BINARY" | "VARBINARY" => DataType::Binary,
         "DATE" => DataType::Date32,
@@ -295,4 +324,19 @@ mod tests {
         assert!(matches!(duckdb_type_to_arrow("BOOLEAN"), DataType::Boolean));
         assert!(matches!(duckdb_type_to_arrow("BIGINT"), DataType::Int64));
     }
+
+    #[test]
+    fn test_duckdb_decimal_mapping() {
+        use datafusion::arrow::datatypes::DataType;
+
+        assert_eq!(
+            duckdb_type_to_arrow("DECIMAL(10,2)"),
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            duckdb_type_to_arrow("DECIMAL(38,10)"),
+            DataType::Decimal128(38, 10)
+        );
+        assert_eq!(duckdb_type_to_arrow("DECIMAL"), DataType::Decimal128(18, 3));
+    }
 }
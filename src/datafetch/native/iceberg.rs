@@ -4,17 +4,26 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
-use iceberg::spec::{PrimitiveType, Type};
+use iceberg::io::FileIO;
+use iceberg::spec::{
+    ManifestContentType, ManifestStatus, Operation, PrimitiveType, SnapshotRef,
+    TableMetadata as IcebergTableMetadata, Type,
+};
+use iceberg::table::Table;
 use iceberg::{Catalog, CatalogBuilder, NamespaceIdent, TableIdent};
 use iceberg_catalog_glue::GlueCatalogBuilder;
 use iceberg_catalog_rest::RestCatalogBuilder;
+use iceberg_catalog_sql::{SqlBindStyle, SqlCatalog, SqlCatalogConfig};
 use std::future::Future;
 use std::pin::Pin;
 
-use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
+use crate::datafetch::{
+    ColumnMetadata, DataFetchError, SnapshotRefresh, TableMetadata, TimeTravel,
+};
 use crate::secrets::SecretManager;
-use crate::source::{AwsCredentials, Credential, IcebergCatalogType, Source};
+use crate::source::{AwsCredentials, Credential, IcebergCatalogType, Source, TableMatcher};
 
+use super::iceberg_fs_catalog::FilesystemCatalog;
 use super::StreamingParquetWriter;
 
 // Re-exports of arrow 55 for iceberg compatibility
@@ -101,6 +110,58 @@ async fn build_catalog(
 
             Ok(Arc::new(catalog))
         }
+
+        IcebergCatalogType::Sql { uri, credential } => {
+            let mut uri = uri.clone();
+
+            // Add the database password to the URL if credential provided
+            if let Credential::SecretRef { .. } = credential {
+                let password = credential
+                    .resolve(secrets)
+                    .await
+                    .map_err(|e| DataFetchError::Connection(e.to_string()))?;
+                let mut url = url::Url::parse(&uri)
+                    .map_err(|e| DataFetchError::Connection(format!("Invalid SQL URI: {}", e)))?;
+                url.set_password(Some(&password)).map_err(|_| {
+                    DataFetchError::Connection("SQL URI does not accept a password".to_string())
+                })?;
+                uri = url.to_string();
+            }
+
+            let file_io = FileIO::from_path(warehouse)
+                .and_then(|builder| builder.build())
+                .map_err(|e| DataFetchError::Connection(e.to_string()))?;
+            let config = SqlCatalogConfig::builder()
+                .sql_bind_style(sql_bind_style(&uri))
+                .uri(uri)
+                .name("sql".to_string())
+                .warehouse_location(warehouse.clone())
+                .file_io(file_io)
+                .build();
+
+            sqlx::any::install_default_drivers();
+            let catalog = SqlCatalog::new(config)
+                .await
+                .map_err(|e| DataFetchError::Connection(e.to_string()))?;
+
+            Ok(Arc::new(catalog))
+        }
+
+        IcebergCatalogType::Filesystem { path } => {
+            let catalog = FilesystemCatalog::new(path)
+                .map_err(|e| DataFetchError::Connection(e.to_string()))?;
+
+            Ok(Arc::new(catalog))
+        }
+    }
+}
+
+/// Placeholder style for the SQL catalog's queries: Postgres uses `$1`, others `?`.
+fn sql_bind_style(uri: &str) -> SqlBindStyle {
+    if uri.starts_with("postgres://") || uri.starts_with("postgresql://") {
+        SqlBindStyle::DollarNumeric
+    } else {
+        SqlBindStyle::QMark
     }
 }
 
@@ -109,6 +170,7 @@ async fn build_catalog(
 pub async fn discover_tables(
     source: &Source,
     secrets: &SecretManager,
+    matcher: &TableMatcher,
 ) -> Result<Vec<TableMetadata>, DataFetchError> {
     let catalog = build_catalog(source, secrets).await?;
 
@@ -134,12 +196,21 @@ pub async fn discover_tables(
     let mut tables = Vec::new();
 
     for ns in namespaces {
+        let schema_name = ns.as_ref().join(".");
+        if !matcher.matches_schema(&schema_name) {
+            continue;
+        }
+
         let table_idents = catalog
             .list_tables(&ns)
             .await
             .map_err(|e| DataFetchError::Query(e.to_string()))?;
 
-        for table_ident in table_idents {
+        // Only tables the filter keeps are loaded
+        for table_ident in table_idents
+            .into_iter()
+            .filter(|ident| matcher.matches(&schema_name, ident.name()))
+        {
             let table = catalog
                 .load_table(&table_ident)
                 .await
@@ -160,12 +231,23 @@ pub async fn discover_tables(
                 })
                 .collect();
 
+            // The current snapshot's summary keeps running totals for the whole table
+            let total = |key: &str| -> Option<u64> {
+                table
+                    .metadata()
+                    .current_snapshot()
+                    .and_then(|s| s.summary().additional_properties.get(key))
+                    .and_then(|v| v.parse().ok())
+            };
+
             tables.push(TableMetadata {
                 catalog_name: None,
-                schema_name: ns.as_ref().join("."),
+                schema_name: schema_name.clone(),
                 table_name: table_ident.name().to_string(),
                 table_type: "BASE TABLE".to_string(),
                 columns,
+                row_count: total("total-records"),
+                size_bytes: total("total-files-size"),
             });
         }
     }
@@ -200,20 +282,13 @@ fn discover_all_namespaces<'a>(
     })
 }
 
-/// Fetch table data and write to Parquet using streaming.
-///
-/// Note: Due to arrow version mismatch between iceberg (arrow 55) and datafusion (arrow 56),
-/// we use IPC serialization to bridge the versions.
-pub async fn fetch_table(
+/// Load a table from the catalog. `schema` is the dot-separated namespace.
+async fn load_table(
     source: &Source,
     secrets: &SecretManager,
-    _catalog: Option<&str>,
     schema: &str,
     table: &str,
-    writer: &mut StreamingParquetWriter,
-) -> Result<(), DataFetchError> {
-    use futures::StreamExt;
-
+) -> Result<Table, DataFetchError> {
     let catalog = build_catalog(source, secrets).await?;
 
     let parts: Vec<&str> = schema.split('.').collect();
@@ -221,14 +296,182 @@ pub async fn fetch_table(
         NamespaceIdent::from_strs(&parts).map_err(|e| DataFetchError::Query(e.to_string()))?;
     let table_ident = TableIdent::new(namespace, table.to_string());
 
-    let iceberg_table = catalog
+    catalog
         .load_table(&table_ident)
         .await
-        .map_err(|e| DataFetchError::Query(e.to_string()))?;
+        .map_err(|e| DataFetchError::Query(e.to_string()))
+}
+
+/// Fetch table data and write to Parquet using streaming.
+pub async fn fetch_table(
+    source: &Source,
+    secrets: &SecretManager,
+    _catalog: Option<&str>,
+    schema: &str,
+    table: &str,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    let iceberg_table = load_table(source, secrets, schema, table).await?;
+    scan_to_writer(&iceberg_table, None, writer).await
+}
+
+/// Decide how to refresh a table whose cache reflects `cached_snapshot`.
+///
+/// A refresh is an append when every snapshot between the cached one and the current one
+/// is an `append` operation that kept the same schema. Anything else (overwrites, deletes,
+/// schema changes, expired or rolled-back history) requires a full re-read.
+pub async fn plan_snapshot_refresh(
+    source: &Source,
+    secrets: &SecretManager,
+    schema: &str,
+    table: &str,
+    cached_snapshot: Option<i64>,
+) -> Result<SnapshotRefresh, DataFetchError> {
+    let iceberg_table = load_table(source, secrets, schema, table).await?;
+    Ok(plan_refresh(iceberg_table.metadata(), cached_snapshot))
+}
+
+fn plan_refresh(metadata: &IcebergTableMetadata, cached_snapshot: Option<i64>) -> SnapshotRefresh {
+    let Some(current) = metadata.current_snapshot() else {
+        return SnapshotRefresh::Full { snapshot_id: None };
+    };
+    let snapshot_id = current.snapshot_id();
+
+    let Some(cached_id) = cached_snapshot else {
+        return SnapshotRefresh::Full {
+            snapshot_id: Some(snapshot_id),
+        };
+    };
+    if cached_id == snapshot_id {
+        return SnapshotRefresh::Unchanged { snapshot_id };
+    }
+
+    let cached_schema = metadata
+        .snapshot_by_id(cached_id)
+        .and_then(|s| s.schema_id());
+
+    match appended_snapshots(metadata, cached_id) {
+        Some(appended)
+            if cached_schema.is_some()
+                && appended.iter().all(|s| s.schema_id() == cached_schema) =>
+        {
+            SnapshotRefresh::Append {
+                from_snapshot_id: cached_id,
+                snapshot_id,
+            }
+        }
+        _ => SnapshotRefresh::Full {
+            snapshot_id: Some(snapshot_id),
+        },
+    }
+}
+
+/// Walk back from the current snapshot to `from_snapshot_id`, returning the snapshots in
+/// between (newest first) if all of them are appends. Returns None if any snapshot is not
+/// an append or `from_snapshot_id` is not an ancestor of the current snapshot.
+fn appended_snapshots(
+    metadata: &IcebergTableMetadata,
+    from_snapshot_id: i64,
+) -> Option<Vec<SnapshotRef>> {
+    let mut snapshots = Vec::new();
+    let mut next = metadata.current_snapshot().cloned();
+
+    while let Some(snapshot) = next {
+        if snapshot.snapshot_id() == from_snapshot_id {
+            return Some(snapshots);
+        }
+        if snapshot.summary().operation != Operation::Append {
+            return None;
+        }
+        next = snapshot
+            .parent_snapshot_id()
+            .and_then(|id| metadata.snapshot_by_id(id).cloned());
+        snapshots.push(snapshot);
+    }
+
+    None
+}
+
+/// Fetch the data described by a refresh plan.
+pub async fn fetch_table_snapshot(
+    source: &Source,
+    secrets: &SecretManager,
+    schema: &str,
+    table: &str,
+    plan: &SnapshotRefresh,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    let iceberg_table = load_table(source, secrets, schema, table).await?;
+
+    match *plan {
+        SnapshotRefresh::Unchanged { .. } => Ok(()),
+        SnapshotRefresh::Full { snapshot_id } => {
+            scan_to_writer(&iceberg_table, snapshot_id, writer).await
+        }
+        SnapshotRefresh::Append {
+            from_snapshot_id,
+            snapshot_id,
+        } => append_to_writer(&iceberg_table, from_snapshot_id, snapshot_id, writer).await,
+    }
+}
 
-    // Build a scan for the current snapshot
-    let scan = iceberg_table
-        .scan()
+/// Fetch the table as of an earlier snapshot or point in time.
+pub async fn fetch_table_as_of(
+    source: &Source,
+    secrets: &SecretManager,
+    schema: &str,
+    table: &str,
+    as_of: &TimeTravel,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    let iceberg_table = load_table(source, secrets, schema, table).await?;
+    let snapshot_id = resolve_time_travel(iceberg_table.metadata(), as_of)?;
+    scan_to_writer(&iceberg_table, Some(snapshot_id), writer).await
+}
+
+/// Resolve a time travel target to a snapshot ID. Timestamps resolve to the snapshot that
+/// was current at that instant according to the table's snapshot log.
+fn resolve_time_travel(
+    metadata: &IcebergTableMetadata,
+    as_of: &TimeTravel,
+) -> Result<i64, DataFetchError> {
+    match as_of {
+        TimeTravel::Snapshot(id) => metadata
+            .snapshot_by_id(*id)
+            .map(|s| s.snapshot_id())
+            .ok_or_else(|| DataFetchError::Query(format!("snapshot {} not found", id))),
+        TimeTravel::Timestamp(ts) => {
+            let ts_ms = ts.timestamp_millis();
+            metadata
+                .history()
+                .iter()
+                .filter(|entry| entry.timestamp_ms <= ts_ms)
+                .max_by_key(|entry| entry.timestamp_ms)
+                .map(|entry| entry.snapshot_id)
+                .ok_or_else(|| {
+                    DataFetchError::Query(format!("no snapshot exists at or before {}", ts))
+                })
+        }
+    }
+}
+
+/// Scan the table at the given snapshot (or the current one) into the writer.
+///
+/// Note: Due to arrow version mismatch between iceberg (arrow 55) and datafusion (arrow 56),
+/// we use IPC serialization to bridge the versions.
+async fn scan_to_writer(
+    iceberg_table: &Table,
+    snapshot_id: Option<i64>,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    use futures::StreamExt;
+
+    // Build a scan for the requested snapshot, defaulting to the current one
+    let mut scan_builder = iceberg_table.scan();
+    if let Some(id) = snapshot_id {
+        scan_builder = scan_builder.snapshot_id(id);
+    }
+    let scan = scan_builder
         .build()
         .map_err(|e| DataFetchError::Query(e.to_string()))?;
 
@@ -254,19 +497,152 @@ pub async fn fetch_table(
             writer_initialized = true;
         }
 
-        writer.write_batch(&datafusion_batch)?;
+        writer.write_batch(&datafusion_batch).await?;
     }
 
     // Handle empty tables - initialize writer with manually converted schema
     if !writer_initialized {
-        let iceberg_schema = iceberg_table.metadata().current_schema();
-        let arrow_schema = iceberg_schema_to_datafusion_arrow(iceberg_schema)?;
+        let iceberg_schema = snapshot_schema(iceberg_table, snapshot_id)?;
+        let arrow_schema = iceberg_schema_to_datafusion_arrow(&iceberg_schema)?;
         writer.init(&arrow_schema)?;
     }
 
     Ok(())
 }
 
+/// Schema of the given snapshot, or the table's current schema.
+fn snapshot_schema(
+    iceberg_table: &Table,
+    snapshot_id: Option<i64>,
+) -> Result<iceberg::spec::SchemaRef, DataFetchError> {
+    let metadata = iceberg_table.metadata();
+    match snapshot_id.and_then(|id| metadata.snapshot_by_id(id)) {
+        Some(snapshot) => snapshot
+            .schema(metadata)
+            .map_err(|e| DataFetchError::Query(e.to_string())),
+        None => Ok(metadata.current_schema().clone()),
+    }
+}
+
+/// Write only the data files added by the append snapshots after `from_snapshot_id`.
+///
+/// Append snapshots never add delete files, so the added data files can be read as-is.
+/// They are plain Parquet, which lets us read them with datafusion's reader directly
+/// rather than going through the arrow 55 IPC bridge.
+async fn append_to_writer(
+    iceberg_table: &Table,
+    from_snapshot_id: i64,
+    snapshot_id: i64,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let metadata = iceberg_table.metadata();
+    let file_io = iceberg_table.file_io();
+
+    let snapshots = appended_snapshots(metadata, from_snapshot_id)
+        .filter(|s| s.first().map(|s| s.snapshot_id()) == Some(snapshot_id))
+        .ok_or_else(|| {
+            DataFetchError::Query(format!(
+                "snapshot {} is not an append-only descendant of {}",
+                snapshot_id, from_snapshot_id
+            ))
+        })?;
+
+    let iceberg_schema = snapshot_schema(iceberg_table, Some(snapshot_id))?;
+    let target_schema = Arc::new(iceberg_schema_to_datafusion_arrow(&iceberg_schema)?);
+    writer.init(&target_schema)?;
+
+    // Oldest first, so rows land in commit order
+    for snapshot in snapshots.iter().rev() {
+        for path in added_data_files(snapshot, metadata, file_io).await? {
+            let bytes = file_io
+                .new_input(&path)
+                .map_err(|e| DataFetchError::Query(e.to_string()))?
+                .read()
+                .await
+                .map_err(|e| DataFetchError::Query(format!("failed to read {}: {}", path, e)))?;
+
+            let reader = ParquetRecordBatchReaderBuilder::try_new(bytes)
+                .and_then(|builder| builder.build())
+                .map_err(|e| DataFetchError::Query(format!("failed to read {}: {}", path, e)))?;
+
+            for batch in reader {
+                let batch = batch.map_err(|e| DataFetchError::Query(e.to_string()))?;
+                writer
+                    .write_batch(&project_batch(&batch, &target_schema)?)
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Paths of the data files a snapshot added.
+async fn added_data_files(
+    snapshot: &SnapshotRef,
+    metadata: &IcebergTableMetadata,
+    file_io: &FileIO,
+) -> Result<Vec<String>, DataFetchError> {
+    let manifest_list = snapshot
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| DataFetchError::Query(e.to_string()))?;
+
+    let mut paths = Vec::new();
+    for manifest_file in manifest_list.entries() {
+        if manifest_file.added_snapshot_id != snapshot.snapshot_id()
+            || manifest_file.content != ManifestContentType::Data
+        {
+            continue;
+        }
+
+        let manifest = manifest_file
+            .load_manifest(file_io)
+            .await
+            .map_err(|e| DataFetchError::Query(e.to_string()))?;
+
+        paths.extend(
+            manifest
+                .entries()
+                .iter()
+                .filter(|entry| {
+                    entry.status() == ManifestStatus::Added
+                        && entry.snapshot_id() == Some(snapshot.snapshot_id())
+                })
+                .map(|entry| entry.file_path().to_string()),
+        );
+    }
+
+    Ok(paths)
+}
+
+/// Reorder and cast a data file batch to the table schema, matching columns by name.
+/// Columns missing from the file are filled with nulls.
+fn project_batch(
+    batch: &datafusion::arrow::record_batch::RecordBatch,
+    target: &Arc<Schema>,
+) -> Result<datafusion::arrow::record_batch::RecordBatch, DataFetchError> {
+    use datafusion::arrow::array::new_null_array;
+    use datafusion::arrow::compute::cast;
+
+    let columns = target
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(column) if column.data_type() == field.data_type() => Ok(column.clone()),
+            Some(column) => {
+                cast(column, field.data_type()).map_err(|e| DataFetchError::Query(e.to_string()))
+            }
+            None => Ok(new_null_array(field.data_type(), batch.num_rows())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    datafusion::arrow::record_batch::RecordBatch::try_new(target.clone(), columns)
+        .map_err(|e| DataFetchError::Query(e.to_string()))
+}
+
 /// Convert an iceberg arrow RecordBatch (arrow 55) to datafusion arrow RecordBatch (arrow 56)
 /// using IPC serialization as a bridge between arrow versions.
 fn convert_arrow_batch(
@@ -393,6 +769,26 @@ fn iceberg_type_to_arrow(iceberg_type: &Type) -> DataType {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sql_bind_style() {
+        assert_eq!(
+            sql_bind_style("postgres://user@localhost/catalog"),
+            SqlBindStyle::DollarNumeric
+        );
+        assert_eq!(
+            sql_bind_style("postgresql://localhost/catalog"),
+            SqlBindStyle::DollarNumeric
+        );
+        assert_eq!(
+            sql_bind_style("sqlite:/tmp/catalog.db"),
+            SqlBindStyle::QMark
+        );
+        assert_eq!(
+            sql_bind_style("mysql://localhost/catalog"),
+            SqlBindStyle::QMark
+        );
+    }
+
     #[test]
     fn test_iceberg_type_to_arrow_primitives() {
         assert!(matches!(
@@ -0,0 +1,327 @@
+//! Read-only Hadoop-style Iceberg catalog over a local filesystem warehouse.
+//!
+//! Namespaces are directories and tables are directories containing a `metadata/`
+//! folder: `{warehouse}/{namespace...}/{table}/metadata/`. The current metadata file is
+//! taken from `version-hint.text` when present (Hadoop catalog layout), otherwise the
+//! newest `*.metadata.json` file is used.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use iceberg::io::{FileIO, FileIOBuilder};
+use iceberg::table::{StaticTable, Table};
+use iceberg::{
+    Catalog, Error, ErrorKind, Namespace, NamespaceIdent, Result, TableCommit, TableCreation,
+    TableIdent,
+};
+
+const METADATA_DIR: &str = "metadata";
+const VERSION_HINT_FILE: &str = "version-hint.text";
+const METADATA_FILE_SUFFIX: &str = ".metadata.json";
+
+#[derive(Debug)]
+pub struct FilesystemCatalog {
+    warehouse: PathBuf,
+    file_io: FileIO,
+}
+
+impl FilesystemCatalog {
+    pub fn new(warehouse: impl Into<PathBuf>) -> Result<Self> {
+        let warehouse = warehouse.into();
+        if !warehouse.is_dir() {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!("warehouse '{}' is not a directory", warehouse.display()),
+            ));
+        }
+
+        Ok(Self {
+            warehouse,
+            file_io: FileIOBuilder::new_fs_io().build()?,
+        })
+    }
+
+    fn namespace_path(&self, namespace: &NamespaceIdent) -> PathBuf {
+        namespace
+            .iter()
+            .fold(self.warehouse.clone(), |path, part| path.join(part))
+    }
+
+    fn table_path(&self, table: &TableIdent) -> PathBuf {
+        self.namespace_path(table.namespace()).join(table.name())
+    }
+
+    /// Subdirectories of `dir`, split into (namespaces, tables).
+    async fn children(&self, dir: &Path) -> Result<(Vec<String>, Vec<String>)> {
+        let mut namespaces = Vec::new();
+        let mut tables = Vec::new();
+
+        let mut entries = tokio::fs::read_dir(dir).await.map_err(io_error)?;
+        while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+            if !entry.file_type().await.map_err(io_error)?.is_dir() {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if name.starts_with('.') || name.starts_with('_') || name == METADATA_DIR {
+                continue;
+            }
+
+            if is_table_dir(&entry.path()).await {
+                tables.push(name);
+            } else {
+                namespaces.push(name);
+            }
+        }
+
+        namespaces.sort();
+        tables.sort();
+        Ok((namespaces, tables))
+    }
+}
+
+async fn is_table_dir(dir: &Path) -> bool {
+    tokio::fs::metadata(dir.join(METADATA_DIR))
+        .await
+        .is_ok_and(|m| m.is_dir())
+}
+
+/// Locate the current metadata file of a table directory.
+async fn current_metadata_file(table_dir: &Path) -> Result<PathBuf> {
+    let metadata_dir = table_dir.join(METADATA_DIR);
+
+    if let Ok(hint) = tokio::fs::read_to_string(metadata_dir.join(VERSION_HINT_FILE)).await {
+        let path = metadata_dir.join(format!("v{}{}", hint.trim(), METADATA_FILE_SUFFIX));
+        if tokio::fs::metadata(&path).await.is_ok() {
+            return Ok(path);
+        }
+    }
+
+    // Without a hint, pick the newest metadata file by version. Names are either
+    // `v{N}.metadata.json` (Hadoop) or `{NNNNN}-{uuid}.metadata.json` (other catalogs).
+    let mut newest: Option<(u64, String)> = None;
+    let mut entries = tokio::fs::read_dir(&metadata_dir).await.map_err(io_error)?;
+    while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let Some(version) = metadata_file_version(&name) else {
+            continue;
+        };
+        if newest
+            .as_ref()
+            .is_none_or(|(v, n)| (version, &name) > (*v, n))
+        {
+            newest = Some((version, name));
+        }
+    }
+
+    newest
+        .map(|(_, name)| metadata_dir.join(name))
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("no metadata file found in {}", metadata_dir.display()),
+            )
+        })
+}
+
+/// Version number encoded in a metadata file name, if it is one.
+fn metadata_file_version(name: &str) -> Option<u64> {
+    let stem = name.strip_suffix(METADATA_FILE_SUFFIX)?;
+    let version = stem.strip_prefix('v').unwrap_or(stem);
+    let version = version.split('-').next()?;
+    version.parse().ok()
+}
+
+fn io_error(e: std::io::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "filesystem catalog I/O failed").with_source(e)
+}
+
+fn read_only(operation: &str) -> Error {
+    Error::new(
+        ErrorKind::FeatureUnsupported,
+        format!(
+            "filesystem catalog is read-only: {} is not supported",
+            operation
+        ),
+    )
+}
+
+#[async_trait]
+impl Catalog for FilesystemCatalog {
+    async fn list_namespaces(
+        &self,
+        parent: Option<&NamespaceIdent>,
+    ) -> Result<Vec<NamespaceIdent>> {
+        let dir = match parent {
+            Some(ns) => self.namespace_path(ns),
+            None => self.warehouse.clone(),
+        };
+        let (namespaces, _) = self.children(&dir).await?;
+
+        namespaces
+            .into_iter()
+            .map(|name| {
+                let mut parts: Vec<String> = parent
+                    .map(|ns| ns.iter().cloned().collect())
+                    .unwrap_or_default();
+                parts.push(name);
+                NamespaceIdent::from_vec(parts)
+            })
+            .collect()
+    }
+
+    async fn create_namespace(
+        &self,
+        _namespace: &NamespaceIdent,
+        _properties: HashMap<String, String>,
+    ) -> Result<Namespace> {
+        Err(read_only("create_namespace"))
+    }
+
+    async fn get_namespace(&self, namespace: &NamespaceIdent) -> Result<Namespace> {
+        if self.namespace_exists(namespace).await? {
+            Ok(Namespace::new(namespace.clone()))
+        } else {
+            Err(Error::new(
+                ErrorKind::NamespaceNotFound,
+                format!("namespace {:?} not found", namespace),
+            ))
+        }
+    }
+
+    async fn namespace_exists(&self, namespace: &NamespaceIdent) -> Result<bool> {
+        let path = self.namespace_path(namespace);
+        Ok(path.is_dir() && !is_table_dir(&path).await)
+    }
+
+    async fn update_namespace(
+        &self,
+        _namespace: &NamespaceIdent,
+        _properties: HashMap<String, String>,
+    ) -> Result<()> {
+        Err(read_only("update_namespace"))
+    }
+
+    async fn drop_namespace(&self, _namespace: &NamespaceIdent) -> Result<()> {
+        Err(read_only("drop_namespace"))
+    }
+
+    async fn list_tables(&self, namespace: &NamespaceIdent) -> Result<Vec<TableIdent>> {
+        let (_, tables) = self.children(&self.namespace_path(namespace)).await?;
+        Ok(tables
+            .into_iter()
+            .map(|name| TableIdent::new(namespace.clone(), name))
+            .collect())
+    }
+
+    async fn create_table(
+        &self,
+        _namespace: &NamespaceIdent,
+        _creation: TableCreation,
+    ) -> Result<Table> {
+        Err(read_only("create_table"))
+    }
+
+    async fn load_table(&self, table: &TableIdent) -> Result<Table> {
+        let table_dir = self.table_path(table);
+        if !is_table_dir(&table_dir).await {
+            return Err(Error::new(
+                ErrorKind::TableNotFound,
+                format!("table {} not found", table),
+            ));
+        }
+
+        let metadata_file = current_metadata_file(&table_dir).await?;
+        let static_table = StaticTable::from_metadata_file(
+            &metadata_file.to_string_lossy(),
+            table.clone(),
+            self.file_io.clone(),
+        )
+        .await?;
+
+        Ok(static_table.into_table())
+    }
+
+    async fn drop_table(&self, _table: &TableIdent) -> Result<()> {
+        Err(read_only("drop_table"))
+    }
+
+    async fn table_exists(&self, table: &TableIdent) -> Result<bool> {
+        Ok(is_table_dir(&self.table_path(table)).await)
+    }
+
+    async fn rename_table(&self, _src: &TableIdent, _dest: &TableIdent) -> Result<()> {
+        Err(read_only("rename_table"))
+    }
+
+    async fn register_table(
+        &self,
+        _table: &TableIdent,
+        _metadata_location: String,
+    ) -> Result<Table> {
+        Err(read_only("register_table"))
+    }
+
+    async fn update_table(&self, _commit: TableCommit) -> Result<Table> {
+        Err(read_only("update_table"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_file_version() {
+        assert_eq!(metadata_file_version("v3.metadata.json"), Some(3));
+        assert_eq!(
+            metadata_file_version("00012-6c2e8f1a-9b1d-4c57-8f6e-2a3b4c5d6e7f.metadata.json"),
+            Some(12)
+        );
+        assert_eq!(metadata_file_version("version-hint.text"), None);
+        assert_eq!(metadata_file_version("snap-1-1-abc.avro"), None);
+    }
+
+    #[tokio::test]
+    async fn test_version_hint_takes_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let metadata_dir = dir.path().join(METADATA_DIR);
+        std::fs::create_dir_all(&metadata_dir).unwrap();
+        std::fs::write(metadata_dir.join("v1.metadata.json"), "{}").unwrap();
+        std::fs::write(metadata_dir.join("v2.metadata.json"), "{}").unwrap();
+        std::fs::write(metadata_dir.join(VERSION_HINT_FILE), "1\n").unwrap();
+
+        let current = current_metadata_file(dir.path()).await.unwrap();
+        assert!(current.ends_with("v1.metadata.json"));
+
+        std::fs::remove_file(metadata_dir.join(VERSION_HINT_FILE)).unwrap();
+        let current = current_metadata_file(dir.path()).await.unwrap();
+        assert!(current.ends_with("v2.metadata.json"));
+    }
+
+    #[tokio::test]
+    async fn test_lists_namespaces_and_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("db/events").join(METADATA_DIR)).unwrap();
+        std::fs::create_dir_all(dir.path().join("db/nested/users").join(METADATA_DIR)).unwrap();
+
+        let catalog = FilesystemCatalog::new(dir.path()).unwrap();
+
+        let top = catalog.list_namespaces(None).await.unwrap();
+        assert_eq!(top, vec![NamespaceIdent::new("db".to_string())]);
+
+        let nested = catalog.list_namespaces(Some(&top[0])).await.unwrap();
+        assert_eq!(
+            nested,
+            vec![NamespaceIdent::from_strs(["db", "nested"]).unwrap()]
+        );
+
+        let tables = catalog.list_tables(&top[0]).await.unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].name(), "events");
+    }
+}
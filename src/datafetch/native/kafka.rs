@@ -0,0 +1,742 @@
+//! Kafka native driver implementation.
+//!
+//! Each topic is exposed as a table in the `topics` schema. Rows carry envelope columns
+//! (`_partition`, `_offset`, `_timestamp`, `_key`) followed by the decoded message
+//! fields. Message values are decoded from JSON, or from Confluent wire-format Avro
+//! using schemas resolved from a schema registry.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apache_avro::Schema as AvroSchema;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use rskafka::client::partition::{OffsetAt, PartitionClient, UnknownTopicHandling};
+use rskafka::client::{Client, ClientBuilder};
+use rskafka::record::RecordAndOffset;
+use serde_json::{Map, Value};
+
+use crate::catalog::PartitionOffset;
+use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
+use crate::secrets::SecretManager;
+use crate::source::{KafkaFormat, Source, TableMatcher};
+
+use super::StreamingParquetWriter;
+
+/// Schema name under which all topics are exposed.
+const TOPICS_SCHEMA: &str = "topics";
+
+/// Number of most recent messages per partition sampled to infer JSON schemas.
+const JSON_SAMPLE_SIZE: i64 = 100;
+
+/// Upper bound on the bytes returned by a single fetch request.
+const FETCH_MAX_BYTES: i32 = 1024 * 1024;
+
+/// How long the broker may wait for `FETCH_MAX_BYTES` to accumulate.
+const FETCH_MAX_WAIT_MS: i32 = 500;
+
+/// Rows decoded before a record batch is written to parquet.
+const BATCH_SIZE: usize = 8192;
+
+/// Confluent wire format: magic byte followed by a big-endian 4-byte schema id.
+const AVRO_MAGIC_BYTE: u8 = 0;
+const AVRO_HEADER_LEN: usize = 5;
+
+async fn connect(source: &Source) -> Result<Client, DataFetchError> {
+    let bootstrap_servers = match source {
+        Source::Kafka {
+            bootstrap_servers, ..
+        } => bootstrap_servers,
+        _ => unreachable!("connect called with non-Kafka source"),
+    };
+
+    let brokers = parse_brokers(bootstrap_servers)?;
+
+    ClientBuilder::new(brokers)
+        .build()
+        .await
+        .map_err(|e| DataFetchError::Connection(e.to_string()))
+}
+
+/// Split the bootstrap server list into broker addresses. Brokers may carry a
+/// `PLAINTEXT://` prefix; brokers that require SASL or TLS are refused, since
+/// connections are made without authentication or encryption.
+fn parse_brokers(bootstrap_servers: &str) -> Result<Vec<String>, DataFetchError> {
+    let mut brokers = Vec::new();
+    for server in bootstrap_servers.split(',').map(str::trim) {
+        if server.is_empty() {
+            continue;
+        }
+        let address = match server.split_once("://") {
+            None => server,
+            Some((protocol, address)) if protocol.eq_ignore_ascii_case("plaintext") => address,
+            Some((protocol, _)) => {
+                return Err(DataFetchError::Connection(format!(
+                    "broker '{}' uses {}, but only PLAINTEXT Kafka brokers are supported \
+                     (SASL and TLS are not)",
+                    server, protocol
+                )));
+            }
+        };
+        brokers.push(address.to_string());
+    }
+
+    if brokers.is_empty() {
+        return Err(DataFetchError::Connection(
+            "no bootstrap servers configured".to_string(),
+        ));
+    }
+    Ok(brokers)
+}
+
+/// Discover topics and infer a schema for each.
+pub async fn discover_tables(
+    source: &Source,
+    _secrets: &SecretManager,
+    matcher: &TableMatcher,
+) -> Result<Vec<TableMetadata>, DataFetchError> {
+    let client = connect(source).await?;
+
+    let mut topics = client
+        .list_topics()
+        .await
+        .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+    topics.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut tables = Vec::new();
+
+    for topic in topics {
+        // Skip internal bookkeeping topics such as the schema registry's `_schemas`, and
+        // filtered topics before sampling them
+        if topic.name.starts_with('_') || !matcher.matches(TOPICS_SCHEMA, &topic.name) {
+            continue;
+        }
+
+        let partitions: Vec<i32> = topic.partitions.iter().copied().collect();
+        let schema = topic_schema(&client, source, &topic.name, &partitions).await?;
+
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| ColumnMetadata {
+                name: field.name().clone(),
+                data_type: field.data_type().clone(),
+                nullable: field.is_nullable(),
+                ordinal_position: i as i32,
+            })
+            .collect();
+
+        tables.push(TableMetadata {
+            catalog_name: None,
+            schema_name: TOPICS_SCHEMA.to_string(),
+            table_name: topic.name,
+            table_type: "STREAM".to_string(),
+            columns,
+            row_count: None,
+            size_bytes: None,
+        });
+    }
+
+    Ok(tables)
+}
+
+/// Fetch every record currently in the topic.
+pub async fn fetch_table(
+    source: &Source,
+    secrets: &SecretManager,
+    _catalog: Option<&str>,
+    schema: &str,
+    table: &str,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    fetch_table_since(source, secrets, schema, table, &[], writer).await?;
+    Ok(())
+}
+
+/// Fetch records appended since the given offsets, up to the high watermark observed
+/// when the fetch started. Returns the offsets to resume from next time.
+///
+/// Records are decoded into the writer's expected schema, which the catalog fixed when
+/// the topic was discovered. Inferring it again from recent messages would let a sample
+/// with different fields stop every later append; fields not in the schema are dropped.
+pub async fn fetch_table_since(
+    source: &Source,
+    _secrets: &SecretManager,
+    _schema: &str,
+    table: &str,
+    offsets: &[PartitionOffset],
+    writer: &mut StreamingParquetWriter,
+) -> Result<Vec<PartitionOffset>, DataFetchError> {
+    let client = connect(source).await?;
+
+    let topic = client
+        .list_topics()
+        .await
+        .map_err(|e| DataFetchError::Query(e.to_string()))?
+        .into_iter()
+        .find(|t| t.name == table)
+        .ok_or_else(|| DataFetchError::Query(format!("topic '{}' not found", table)))?;
+    let partitions: Vec<i32> = topic.partitions.iter().copied().collect();
+
+    let arrow_schema = match writer.expected_schema() {
+        Some(schema) => schema.clone(),
+        None => Arc::new(topic_schema(&client, source, table, &partitions).await?),
+    };
+    writer.init(&arrow_schema)?;
+
+    let mut decoder = MessageDecoder::new(source)?;
+    let stored: HashMap<i32, i64> = offsets
+        .iter()
+        .map(|o| (o.partition_id, o.next_offset))
+        .collect();
+
+    let mut new_offsets = Vec::with_capacity(partitions.len());
+    let mut rows = Vec::with_capacity(BATCH_SIZE);
+
+    for partition in partitions {
+        let client = partition_client(&client, table, partition).await?;
+        let earliest = get_offset(&client, OffsetAt::Earliest).await?;
+        let high_watermark = get_offset(&client, OffsetAt::Latest).await?;
+
+        let mut next_offset = match stored.get(&partition) {
+            Some(&offset) if offset < earliest => {
+                tracing::warn!(
+                    topic = table,
+                    partition,
+                    stored_offset = offset,
+                    earliest,
+                    "Stored offset is no longer retained; skipping to earliest available record"
+                );
+                earliest
+            }
+            Some(&offset) => offset,
+            None => earliest,
+        };
+
+        while next_offset < high_watermark {
+            let (records, fetched_watermark) = client
+                .fetch_records(next_offset, 1..FETCH_MAX_BYTES, FETCH_MAX_WAIT_MS)
+                .await
+                .map_err(|e| DataFetchError::Query(e.to_string()))?;
+
+            // Gaps made of transaction markers or compacted records yield no data; skip
+            // past them rather than retrying the same offset forever
+            let Some(last) = records.last() else {
+                let skip_to = fetched_watermark.min(high_watermark);
+                if skip_to <= next_offset {
+                    break;
+                }
+                tracing::debug!(
+                    topic = table,
+                    partition,
+                    from = next_offset,
+                    to = skip_to,
+                    "Skipping offsets without records"
+                );
+                next_offset = skip_to;
+                continue;
+            };
+            next_offset = last.offset + 1;
+
+            for record in records {
+                rows.push(decoder.decode(partition, &record).await?);
+                if rows.len() >= BATCH_SIZE {
+                    write_rows(&arrow_schema, &mut rows, writer).await?;
+                }
+            }
+        }
+
+        new_offsets.push(PartitionOffset {
+            partition_id: partition,
+            next_offset,
+        });
+    }
+
+    write_rows(&arrow_schema, &mut rows, writer).await?;
+
+    Ok(new_offsets)
+}
+
+async fn partition_client(
+    client: &Client,
+    topic: &str,
+    partition: i32,
+) -> Result<PartitionClient, DataFetchError> {
+    client
+        .partition_client(topic, partition, UnknownTopicHandling::Error)
+        .await
+        .map_err(|e| DataFetchError::Connection(e.to_string()))
+}
+
+async fn get_offset(client: &PartitionClient, at: OffsetAt) -> Result<i64, DataFetchError> {
+    client
+        .get_offset(at)
+        .await
+        .map_err(|e| DataFetchError::Query(e.to_string()))
+}
+
+/// Build the Arrow schema for a topic: envelope columns followed by value fields.
+async fn topic_schema(
+    client: &Client,
+    source: &Source,
+    topic: &str,
+    partitions: &[i32],
+) -> Result<Schema, DataFetchError> {
+    let (format, registry_url) = match source {
+        Source::Kafka {
+            format,
+            schema_registry_url,
+            ..
+        } => (*format, schema_registry_url.as_deref()),
+        _ => unreachable!("topic_schema called with non-Kafka source"),
+    };
+
+    let value_fields = match format {
+        KafkaFormat::Json => {
+            let samples = sample_values(client, topic, partitions).await?;
+            let inferred = arrow_json::reader::infer_json_schema_from_iterator(
+                samples
+                    .iter()
+                    .map(Ok::<_, datafusion::arrow::error::ArrowError>),
+            )
+            .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+            inferred
+                .fields()
+                .iter()
+                .map(|f| Field::new(f.name(), f.data_type().clone(), true))
+                .collect()
+        }
+        KafkaFormat::Avro => {
+            let registry = SchemaRegistry::new(require_registry_url(registry_url)?);
+            let schema = registry.latest_value_schema(topic).await?;
+            avro_record_fields(&schema)?
+        }
+    };
+
+    let mut fields = envelope_fields();
+    fields.extend(
+        value_fields
+            .into_iter()
+            .filter(|f: &Field| !is_envelope_column(f.name())),
+    );
+
+    Ok(Schema::new(fields))
+}
+
+fn envelope_fields() -> Vec<Field> {
+    vec![
+        Field::new("_partition", DataType::Int32, false),
+        Field::new("_offset", DataType::Int64, false),
+        Field::new(
+            "_timestamp",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("_key", DataType::Utf8, true),
+    ]
+}
+
+fn is_envelope_column(name: &str) -> bool {
+    matches!(name, "_partition" | "_offset" | "_timestamp" | "_key")
+}
+
+/// Read up to `JSON_SAMPLE_SIZE` of the most recent JSON objects from each partition.
+async fn sample_values(
+    client: &Client,
+    topic: &str,
+    partitions: &[i32],
+) -> Result<Vec<Value>, DataFetchError> {
+    let mut samples = Vec::new();
+
+    for &partition in partitions {
+        let client = partition_client(client, topic, partition).await?;
+        let earliest = get_offset(&client, OffsetAt::Earliest).await?;
+        let latest = get_offset(&client, OffsetAt::Latest).await?;
+        let start = earliest.max(latest - JSON_SAMPLE_SIZE);
+
+        if start >= latest {
+            continue;
+        }
+
+        let (records, _) = client
+            .fetch_records(start, 1..FETCH_MAX_BYTES, FETCH_MAX_WAIT_MS)
+            .await
+            .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+
+        samples.extend(
+            records
+                .into_iter()
+                .filter_map(|r| r.record.value)
+                .filter_map(|v| serde_json::from_slice::<Value>(&v).ok())
+                .filter(Value::is_object),
+        );
+    }
+
+    Ok(samples)
+}
+
+fn require_registry_url(url: Option<&str>) -> Result<&str, DataFetchError> {
+    url.ok_or_else(|| {
+        DataFetchError::Connection("schema_registry_url is required for Avro topics".to_string())
+    })
+}
+
+/// Map the top-level Avro record schema of a topic to Arrow fields.
+fn avro_record_fields(schema: &AvroSchema) -> Result<Vec<Field>, DataFetchError> {
+    match schema {
+        AvroSchema::Record(record) => Ok(record
+            .fields
+            .iter()
+            .map(|f| {
+                let (data_type, nullable) = avro_type_to_arrow(&f.schema);
+                Field::new(&f.name, data_type, nullable)
+            })
+            .collect()),
+        _ => Err(DataFetchError::Discovery(
+            "Avro value schema must be a record".to_string(),
+        )),
+    }
+}
+
+/// Convert an Avro schema to an Arrow DataType and nullability.
+///
+/// Types without a faithful JSON representation (bytes, fixed, decimal) and nested
+/// types (records, arrays, maps) are stored as their JSON text.
+fn avro_type_to_arrow(schema: &AvroSchema) -> (DataType, bool) {
+    let data_type = match schema {
+        AvroSchema::Union(union) => {
+            let non_null: Vec<&AvroSchema> = union
+                .variants()
+                .iter()
+                .filter(|s| !matches!(s, AvroSchema::Null))
+                .collect();
+            let data_type = match non_null.as_slice() {
+                [single] => avro_type_to_arrow(single).0,
+                _ => DataType::Utf8,
+            };
+            return (data_type, union.is_nullable());
+        }
+        AvroSchema::Null => return (DataType::Null, true),
+        AvroSchema::Boolean => DataType::Boolean,
+        AvroSchema::Int => DataType::Int32,
+        AvroSchema::Long => DataType::Int64,
+        AvroSchema::Float => DataType::Float32,
+        AvroSchema::Double => DataType::Float64,
+        AvroSchema::String | AvroSchema::Enum(_) | AvroSchema::Uuid => DataType::Utf8,
+        AvroSchema::Date => DataType::Date32,
+        AvroSchema::TimeMillis => DataType::Time32(TimeUnit::Millisecond),
+        AvroSchema::TimeMicros => DataType::Time64(TimeUnit::Microsecond),
+        AvroSchema::TimestampMillis => {
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+        }
+        AvroSchema::TimestampMicros => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        }
+        AvroSchema::TimestampNanos => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+        AvroSchema::LocalTimestampMillis => DataType::Timestamp(TimeUnit::Millisecond, None),
+        AvroSchema::LocalTimestampMicros => DataType::Timestamp(TimeUnit::Microsecond, None),
+        AvroSchema::LocalTimestampNanos => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        _ => DataType::Utf8,
+    };
+    (data_type, false)
+}
+
+/// Minimal Confluent schema registry client.
+struct SchemaRegistry {
+    http: reqwest::Client,
+    base_url: String,
+    schemas: HashMap<u32, AvroSchema>,
+}
+
+#[derive(serde::Deserialize)]
+struct RegistrySchemaResponse {
+    schema: String,
+}
+
+impl SchemaRegistry {
+    fn new(base_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            schemas: HashMap::new(),
+        }
+    }
+
+    /// Latest schema registered under the topic's value subject (`{topic}-value`).
+    async fn latest_value_schema(&self, topic: &str) -> Result<AvroSchema, DataFetchError> {
+        let url = format!(
+            "{}/subjects/{}-value/versions/latest",
+            self.base_url,
+            urlencoding::encode(topic)
+        );
+        self.get_schema(&url).await
+    }
+
+    /// Schema by global id, as embedded in Confluent wire-format messages.
+    async fn schema_by_id(&mut self, id: u32) -> Result<&AvroSchema, DataFetchError> {
+        if !self.schemas.contains_key(&id) {
+            let url = format!("{}/schemas/ids/{}", self.base_url, id);
+            let schema = self.get_schema(&url).await?;
+            self.schemas.insert(id, schema);
+        }
+        Ok(&self.schemas[&id])
+    }
+
+    async fn get_schema(&self, url: &str) -> Result<AvroSchema, DataFetchError> {
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| DataFetchError::Connection(format!("schema registry: {}", e)))?;
+        let body: RegistrySchemaResponse = response
+            .json()
+            .await
+            .map_err(|e| DataFetchError::Query(format!("schema registry: {}", e)))?;
+        AvroSchema::parse_str(&body.schema)
+            .map_err(|e| DataFetchError::Query(format!("invalid Avro schema: {}", e)))
+    }
+}
+
+/// Decodes Kafka records into JSON rows ready for Arrow conversion.
+struct MessageDecoder {
+    registry: Option<SchemaRegistry>,
+}
+
+impl MessageDecoder {
+    fn new(source: &Source) -> Result<Self, DataFetchError> {
+        let registry = match source {
+            Source::Kafka {
+                format: KafkaFormat::Avro,
+                schema_registry_url,
+                ..
+            } => Some(SchemaRegistry::new(require_registry_url(
+                schema_registry_url.as_deref(),
+            )?)),
+            _ => None,
+        };
+        Ok(Self { registry })
+    }
+
+    async fn decode(
+        &mut self,
+        partition: i32,
+        record: &RecordAndOffset,
+    ) -> Result<Map<String, Value>, DataFetchError> {
+        let mut row = match &record.record.value {
+            Some(bytes) => match &mut self.registry {
+                Some(registry) => decode_avro(registry, bytes).await?,
+                None => decode_json(bytes),
+            },
+            None => Map::new(),
+        };
+
+        row.insert("_partition".to_string(), partition.into());
+        row.insert("_offset".to_string(), record.offset.into());
+        row.insert(
+            "_timestamp".to_string(),
+            record.record.timestamp.timestamp_millis().into(),
+        );
+        row.insert(
+            "_key".to_string(),
+            record
+                .record
+                .key
+                .as_ref()
+                .map(|k| Value::String(String::from_utf8_lossy(k).into_owned()))
+                .unwrap_or(Value::Null),
+        );
+
+        Ok(row)
+    }
+}
+
+/// Parse a JSON object message. Messages that are not JSON objects yield only the
+/// envelope columns rather than failing the whole fetch.
+fn decode_json(bytes: &[u8]) -> Map<String, Value> {
+    match serde_json::from_slice::<Value>(bytes) {
+        Ok(Value::Object(map)) => map,
+        _ => {
+            tracing::warn!("Skipping payload of Kafka message that is not a JSON object");
+            Map::new()
+        }
+    }
+}
+
+async fn decode_avro(
+    registry: &mut SchemaRegistry,
+    bytes: &[u8],
+) -> Result<Map<String, Value>, DataFetchError> {
+    if bytes.len() < AVRO_HEADER_LEN || bytes[0] != AVRO_MAGIC_BYTE {
+        return Err(DataFetchError::Query(
+            "Kafka message is not in Confluent Avro wire format".to_string(),
+        ));
+    }
+    let schema_id = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+    let schema = registry.schema_by_id(schema_id).await?;
+
+    let mut payload = &bytes[AVRO_HEADER_LEN..];
+    let value = apache_avro::from_avro_datum(schema, &mut payload, None)
+        .map_err(|e| DataFetchError::Query(format!("Avro decode failed: {}", e)))?;
+
+    match Value::try_from(value) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(DataFetchError::Query(
+            "Avro message is not a record".to_string(),
+        )),
+        Err(e) => Err(DataFetchError::Query(format!("Avro decode failed: {}", e))),
+    }
+}
+
+/// Convert buffered rows into a record batch and write it.
+async fn write_rows(
+    schema: &Arc<Schema>,
+    rows: &mut Vec<Map<String, Value>>,
+    writer: &mut StreamingParquetWriter,
+) -> Result<(), DataFetchError> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    for row in rows.iter_mut() {
+        stringify_nested_values(schema, row);
+    }
+
+    let mut decoder = arrow_json::ReaderBuilder::new(schema.clone())
+        .with_batch_size(rows.len())
+        .build_decoder()
+        .map_err(|e| DataFetchError::Query(e.to_string()))?;
+    decoder
+        .serialize(rows)
+        .map_err(|e| DataFetchError::Query(e.to_string()))?;
+    rows.clear();
+    let batch = decoder
+        .flush()
+        .map_err(|e| DataFetchError::Query(e.to_string()))?;
+
+    if let Some(batch) = batch {
+        writer.write_batch(&batch).await?;
+    }
+
+    Ok(())
+}
+
+/// Replace non-string values in Utf8 columns with their JSON text so nested or
+/// binary data can be stored (and later queried with JSON functions).
+fn stringify_nested_values(schema: &Schema, row: &mut Map<String, Value>) {
+    for field in schema.fields() {
+        if field.data_type() != &DataType::Utf8 {
+            continue;
+        }
+        if let Some(value) = row.get_mut(field.name()) {
+            if !value.is_string() && !value.is_null() {
+                *value = Value::String(value.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avro_primitive_types() {
+        assert_eq!(
+            avro_type_to_arrow(&AvroSchema::Boolean),
+            (DataType::Boolean, false)
+        );
+        assert_eq!(
+            avro_type_to_arrow(&AvroSchema::Long),
+            (DataType::Int64, false)
+        );
+        assert_eq!(
+            avro_type_to_arrow(&AvroSchema::Double),
+            (DataType::Float64, false)
+        );
+        assert_eq!(
+            avro_type_to_arrow(&AvroSchema::Bytes),
+            (DataType::Utf8, false)
+        );
+    }
+
+    #[test]
+    fn test_avro_record_with_nullable_union() {
+        let schema = AvroSchema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "Event",
+                "fields": [
+                    {"name": "id", "type": "long"},
+                    {"name": "user", "type": ["null", "string"]},
+                    {"name": "at", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+                    {"name": "tags", "type": {"type": "array", "items": "string"}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let fields = avro_record_fields(&schema).unwrap();
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[0].data_type(), &DataType::Int64);
+        assert!(!fields[0].is_nullable());
+        assert_eq!(fields[1].data_type(), &DataType::Utf8);
+        assert!(fields[1].is_nullable());
+        assert_eq!(
+            fields[2].data_type(),
+            &DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+        );
+        assert_eq!(fields[3].data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn test_avro_non_record_schema_rejected() {
+        let schema = AvroSchema::parse_str(r#""string""#).unwrap();
+        assert!(avro_record_fields(&schema).is_err());
+    }
+
+    #[test]
+    fn test_parse_brokers() {
+        assert_eq!(
+            parse_brokers(" a:9092, PLAINTEXT://b:9092 ,").unwrap(),
+            vec!["a:9092", "b:9092"]
+        );
+        assert!(parse_brokers(" , ").is_err());
+
+        let err = parse_brokers("a:9092,SASL_SSL://b:9093").unwrap_err();
+        assert!(err.to_string().contains("SASL_SSL"));
+    }
+
+    #[test]
+    fn test_decode_json_non_object_keeps_envelope_only() {
+        assert!(decode_json(b"not json").is_empty());
+        assert!(decode_json(b"[1, 2]").is_empty());
+        assert_eq!(decode_json(br#"{"a": 1}"#).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_rows_stringifies_nested_values() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut writer = StreamingParquetWriter::new(temp_dir.path().join("data.parquet"));
+
+        let mut fields = envelope_fields();
+        fields.push(Field::new("payload", DataType::Utf8, true));
+        let schema = Arc::new(Schema::new(fields));
+        writer.init(&schema).unwrap();
+
+        let mut row = Map::new();
+        row.insert("_partition".to_string(), 0.into());
+        row.insert("_offset".to_string(), 7.into());
+        row.insert("_timestamp".to_string(), 1_700_000_000_000i64.into());
+        row.insert("_key".to_string(), Value::Null);
+        row.insert("payload".to_string(), serde_json::json!({"nested": [1, 2]}));
+        let mut rows = vec![row];
+
+        write_rows(&schema, &mut rows, &mut writer).await.unwrap();
+        assert!(rows.is_empty());
+
+        let (_, row_count) = writer.close().await.unwrap();
+        assert_eq!(row_count, 1);
+    }
+}
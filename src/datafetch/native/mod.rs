@@ -1,5 +1,10 @@
+mod bigquery;
+mod decimal;
+mod delta;
 mod duckdb;
 mod iceberg;
+mod iceberg_fs_catalog;
+mod kafka;
 mod mysql;
 mod parquet_writer;
 mod postgres;
@@ -9,9 +14,10 @@ pub use parquet_writer::StreamingParquetWriter;
 
 use async_trait::async_trait;
 
-use crate::datafetch::{DataFetchError, DataFetcher, TableMetadata};
+use crate::catalog::PartitionOffset;
+use crate::datafetch::{DataFetchError, DataFetcher, SnapshotRefresh, TableMetadata, TimeTravel};
 use crate::secrets::SecretManager;
-use crate::source::Source;
+use crate::source::{Source, TableMatcher};
 
 /// Native Rust driver-based data fetcher
 #[derive(Debug, Default)]
@@ -30,15 +36,26 @@ impl DataFetcher for NativeFetcher {
         source: &Source,
         secrets: &SecretManager,
     ) -> Result<Vec<TableMetadata>, DataFetchError> {
-        match source {
-            Source::Duckdb { .. } | Source::Motherduck { .. } => {
-                duckdb::discover_tables(source, secrets).await
-            }
-            Source::Postgres { .. } => postgres::discover_tables(source, secrets).await,
-            Source::Iceberg { .. } => iceberg::discover_tables(source, secrets).await,
-            Source::Mysql { .. } => mysql::discover_tables(source, secrets).await,
-            Source::Snowflake { .. } => snowflake::discover_tables(source, secrets).await,
-        }
+        let matcher = source
+            .table_filter()
+            .matcher()
+            .map_err(|e| DataFetchError::Discovery(e.to_string()))?;
+        discover_matching(source, secrets, &matcher).await
+    }
+
+    /// Only lists `schema.table`, so drivers skip sampling or loading any other table.
+    async fn discover_table(
+        &self,
+        source: &Source,
+        secrets: &SecretManager,
+        schema: &str,
+        table: &str,
+    ) -> Result<Option<TableMetadata>, DataFetchError> {
+        let matcher = TableMatcher::only(schema, table);
+        Ok(discover_matching(source, secrets, &matcher)
+            .await?
+            .into_iter()
+            .find(|t| t.schema_name == schema && t.table_name == table))
     }
 
     async fn fetch_table(
@@ -66,6 +83,116 @@ impl DataFetcher for NativeFetcher {
             Source::Snowflake { .. } => {
                 snowflake::fetch_table(source, secrets, catalog, schema, table, writer).await
             }
+            Source::Kafka { .. } => {
+                kafka::fetch_table(source, secrets, catalog, schema, table, writer).await
+            }
+            Source::Delta { .. } => {
+                delta::fetch_table(source, secrets, catalog, schema, table, writer).await
+            }
+            Source::Bigquery { .. } => {
+                bigquery::fetch_table(source, secrets, catalog, schema, table, writer).await
+            }
+        }
+    }
+
+    async fn fetch_table_since(
+        &self,
+        source: &Source,
+        secrets: &SecretManager,
+        schema: &str,
+        table: &str,
+        offsets: &[PartitionOffset],
+        writer: &mut StreamingParquetWriter,
+    ) -> Result<Vec<PartitionOffset>, DataFetchError> {
+        match source {
+            Source::Kafka { .. } => {
+                kafka::fetch_table_since(source, secrets, schema, table, offsets, writer).await
+            }
+            _ => Err(DataFetchError::UnsupportedDriver(source.source_type())),
+        }
+    }
+
+    async fn plan_snapshot_refresh(
+        &self,
+        source: &Source,
+        secrets: &SecretManager,
+        schema: &str,
+        table: &str,
+        cached_snapshot: Option<i64>,
+    ) -> Result<SnapshotRefresh, DataFetchError> {
+        match source {
+            Source::Iceberg { .. } => {
+                iceberg::plan_snapshot_refresh(source, secrets, schema, table, cached_snapshot)
+                    .await
+            }
+            _ => Err(DataFetchError::UnsupportedDriver(source.source_type())),
+        }
+    }
+
+    async fn fetch_table_snapshot(
+        &self,
+        source: &Source,
+        secrets: &SecretManager,
+        schema: &str,
+        table: &str,
+        plan: &SnapshotRefresh,
+        writer: &mut StreamingParquetWriter,
+    ) -> Result<(), DataFetchError> {
+        match source {
+            Source::Iceberg { .. } => {
+                iceberg::fetch_table_snapshot(source, secrets, schema, table, plan, writer).await
+            }
+            _ => Err(DataFetchError::UnsupportedDriver(source.source_type())),
+        }
+    }
+
+    async fn source_refreshed_at(
+        &self,
+        source: &Source,
+        secrets: &SecretManager,
+        _schema: &str,
+        table: &str,
+    ) -> Result<Option<i64>, DataFetchError> {
+        match source {
+            Source::Bigquery { .. } => bigquery::source_refreshed_at(source, secrets, table).await,
+            _ => Ok(None),
+        }
+    }
+
+    async fn fetch_table_as_of(
+        &self,
+        source: &Source,
+        secrets: &SecretManager,
+        schema: &str,
+        table: &str,
+        as_of: &TimeTravel,
+        writer: &mut StreamingParquetWriter,
+    ) -> Result<(), DataFetchError> {
+        match source {
+            Source::Iceberg { .. } => {
+                iceberg::fetch_table_as_of(source, secrets, schema, table, as_of, writer).await
+            }
+            _ => Err(DataFetchError::UnsupportedDriver(source.source_type())),
+        }
+    }
+}
+
+/// Discover the tables of `source` that `matcher` keeps.
+async fn discover_matching(
+    source: &Source,
+    secrets: &SecretManager,
+    matcher: &TableMatcher,
+) -> Result<Vec<TableMetadata>, DataFetchError> {
+    match source {
+        Source::Duckdb { .. } | Source::Motherduck { .. } => {
+            duckdb::discover_tables(source, secrets, matcher).await
         }
+        Source::Postgres { .. } => postgres::discover_tables(source, secrets, matcher).await,
+        Source::Iceberg { .. } => iceberg::discover_tables(source, secrets, matcher).await,
+        Source::Mysql { .. } => mysql::discover_tables(source, secrets, matcher).await,
+        Source::Snowflake { .. } => snowflake::discover_tables(source, secrets, matcher).await,
+        Source::Kafka { .. } => kafka::discover_tables(source, secrets, matcher).await,
+        Source::Delta { .. } => delta::discover_tables(source, secrets, matcher).await,
+        Source::Bigquery { .. } => bigquery::discover_tables(source, secrets, matcher).await,
     }
 }
@@ -1,5 +1,6 @@
 //! MySQL native driver implementation using sqlx
 
+use bigdecimal::BigDecimal;
 use datafusion::arrow::array::{
     ArrayBuilder, BinaryBuilder, BooleanBuilder, Date32Builder, Float32Builder, Float64Builder,
     Int16Builder, Int32Builder, Int64Builder, Int8Builder, StringBuilder,
@@ -15,8 +16,9 @@ use tracing::warn;
 
 use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
 use crate::secrets::SecretManager;
-use crate::source::Source;
+use crate::source::{Source, TableMatcher};
 
+use super::decimal::{append_decimal, decimal_type_from_str, make_decimal_builder};
 use super::StreamingParquetWriter;
 
 /// Build MySQL connection options from source configuration and resolved password.
@@ -48,6 +50,7 @@ pub async fn resolve_connect_options(
             user,
             database,
             credential,
+            ..
         } => (host, *port, user, database, credential),
         _ => {
             return Err(DataFetchError::Connection(
@@ -89,6 +92,7 @@ async fn connect_with_ssl_retry(
 pub async fn discover_tables(
     source: &Source,
     secrets: &SecretManager,
+    matcher: &TableMatcher,
 ) -> Result<Vec<TableMetadata>, DataFetchError> {
     let options = resolve_connect_options(source, secrets).await?;
     let mut conn = connect_with_ssl_retry(options).await?;
@@ -113,7 +117,9 @@ pub async fn discover_tables(
             CAST(c.COLUMN_NAME AS CHAR(64)) AS COLUMN_NAME,
             CAST(c.COLUMN_TYPE AS CHAR(255)) AS COLUMN_TYPE,
             CAST(c.IS_NULLABLE AS CHAR(3)) AS IS_NULLABLE,
-            c.ORDINAL_POSITION
+            c.ORDINAL_POSITION,
+            CAST(t.TABLE_ROWS AS SIGNED) AS TABLE_ROWS,
+            CAST(t.DATA_LENGTH + t.INDEX_LENGTH AS SIGNED) AS TABLE_BYTES
         FROM information_schema.TABLES t
         JOIN information_schema.COLUMNS c
             ON t.TABLE_CATALOG = c.TABLE_CATALOG
@@ -138,6 +144,13 @@ pub async fn discover_tables(
         let data_type: String = row.get(5);
         let is_nullable: String = row.get(6);
         let ordinal: u32 = row.get(7);
+        // InnoDB row counts are sampled estimates; views report NULL
+        let row_count: Option<i64> = row.get(8);
+        let size_bytes: Option<i64> = row.get(9);
+
+        if !matcher.matches(&schema, &table) {
+            continue;
+        }
 
         let column = ColumnMetadata {
             name: col_name,
@@ -159,6 +172,8 @@ pub async fn discover_tables(
                 table_name: table,
                 table_type,
                 columns: vec![column],
+                row_count: row_count.and_then(|n| u64::try_from(n).ok()),
+                size_bytes: size_bytes.and_then(|n| u64::try_from(n).ok()),
             });
         }
     }
@@ -176,7 +191,10 @@ pub async fn fetch_table(
     writer: &mut StreamingParquetWriter,
 ) -> Result<(), DataFetchError> {
     let options = resolve_connect_options(source, secrets).await?;
-    let mut conn = connect_with_ssl_retry(options.clone()).await?;
+    let mut conn = connect_with_ssl_retry(options).await?;
+
+    // Row metadata only reports DECIMAL, so precision and scale come from the declared types
+    let declared = declared_columns(&mut conn, schema, table).await?;
 
     // Build query - use backticks for MySQL identifier escaping
     let query = format!(
@@ -194,44 +212,20 @@ pub async fn fetch_table(
     let first_row = stream.next().await;
 
     let arrow_schema = match &first_row {
-        Some(Ok(row)) => schema_from_columns(row.columns()),
+        Some(Ok(row)) => schema_from_columns(row.columns(), &declared),
         Some(Err(e)) => return Err(DataFetchError::Query(e.to_string())),
         None => {
-            // Empty table: query information_schema for schema
-            // Need a new connection since stream borrows conn
-            let mut schema_conn = connect_with_ssl_retry(options).await?;
-            let schema_rows = sqlx::query(
-                r#"
-                SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE
-                FROM information_schema.COLUMNS
-                WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
-                ORDER BY ORDINAL_POSITION
-                "#,
-            )
-            .bind(schema)
-            .bind(table)
-            .fetch_all(&mut schema_conn)
-            .await?;
-
-            if schema_rows.is_empty() {
+            // Empty table: use the declared column types
+            if declared.is_empty() {
                 return Err(DataFetchError::Query(format!(
                     "Table {}.{} has no columns",
                     schema, table
                 )));
             }
 
-            let fields: Vec<Field> = schema_rows
+            let fields: Vec<Field> = declared
                 .iter()
-                .map(|row| {
-                    let col_name: String = row.get(0);
-                    let data_type: String = row.get(1);
-                    let is_nullable: String = row.get(2);
-                    Field::new(
-                        col_name,
-                        mysql_type_to_arrow(&data_type),
-                        is_nullable.to_uppercase() == "YES",
-                    )
-                })
+                .map(|col| Field::new(&col.name, col.data_type.clone(), col.nullable))
                 .collect();
 
             Schema::new(fields)
@@ -244,7 +238,7 @@ pub async fn fetch_table(
     // Handle empty table case
     if first_row.is_none() {
         let empty_batch = RecordBatch::new_empty(Arc::new(arrow_schema));
-        writer.write_batch(&empty_batch)?;
+        writer.write_batch(&empty_batch).await?;
         return Ok(());
     }
 
@@ -261,7 +255,7 @@ pub async fn fetch_table(
         // Write batch when full
         if batch_rows.len() >= BATCH_SIZE {
             let batch = rows_to_batch(&batch_rows, &arrow_schema)?;
-            writer.write_batch(&batch)?;
+            writer.write_batch(&batch).await?;
             batch_rows.clear();
         }
     }
@@ -269,7 +263,7 @@ pub async fn fetch_table(
     // Write any remaining rows
     if !batch_rows.is_empty() {
         let batch = rows_to_batch(&batch_rows, &arrow_schema)?;
-        writer.write_batch(&batch)?;
+        writer.write_batch(&batch).await?;
     }
 
     Ok(())
@@ -279,13 +273,57 @@ pub async fn fetch_table(
 // Arrow conversion utilities
 // ============================================================================
 
-/// Build Arrow Schema from sqlx column metadata
-fn schema_from_columns(columns: &[MySqlColumn]) -> Schema {
+/// Declared columns of a table from information_schema, in ordinal order
+async fn declared_columns(
+    conn: &mut MySqlConnection,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<ColumnMetadata>, DataFetchError> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            CAST(COLUMN_NAME AS CHAR(64)) AS COLUMN_NAME,
+            CAST(COLUMN_TYPE AS CHAR(255)) AS COLUMN_TYPE,
+            CAST(IS_NULLABLE AS CHAR(3)) AS IS_NULLABLE,
+            ORDINAL_POSITION
+        FROM information_schema.COLUMNS
+        WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
+        ORDER BY ORDINAL_POSITION
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let data_type: String = row.get(1);
+            let is_nullable: String = row.get(2);
+            let ordinal: u32 = row.get(3);
+            ColumnMetadata {
+                name: row.get(0),
+                data_type: mysql_type_to_arrow(&data_type),
+                nullable: is_nullable.to_uppercase() == "YES",
+                ordinal_position: ordinal as i32,
+            }
+        })
+        .collect())
+}
+
+/// Build Arrow Schema from sqlx column metadata. DECIMAL columns take their type from the
+/// matching declared column, since the row metadata carries no precision or scale.
+fn schema_from_columns(columns: &[MySqlColumn], declared: &[ColumnMetadata]) -> Schema {
     let fields: Vec<Field> = columns
         .iter()
         .map(|col| {
             let name = col.name();
-            let data_type = mysql_type_to_arrow(col.type_info().name());
+            let type_name = col.type_info().name();
+            let data_type = match declared.iter().find(|d| d.name == name) {
+                Some(d) if type_name == "DECIMAL" => d.data_type.clone(),
+                _ => mysql_type_to_arrow(type_name),
+            };
             Field::new(name, data_type, true) // Assume nullable
         })
         .collect();
@@ -347,8 +385,8 @@ fn mysql_type_to_arrow(mysql_type: &str) -> DataType {
         }
         "float" => DataType::Float32,
         "double" | "real" => DataType::Float64,
-        // Complex numeric types: fallback to Utf8 to preserve precision
-        "decimal" | "numeric" | "dec" | "fixed" => DataType::Utf8,
+        // Fixed-point types map to Arrow decimals; without precision they stay text
+        "decimal" | "numeric" | "dec" | "fixed" => decimal_type_from_str(&type_lower),
         "varchar" | "char" | "text" | "tinytext" | "mediumtext" | "longtext" | "enum" | "set" => {
             DataType::Utf8
         }
@@ -404,6 +442,9 @@ fn make_builder(data_type: &DataType, capacity: usize) -> Box<dyn ArrayBuilder>
         DataType::Binary => Box::new(BinaryBuilder::with_capacity(capacity, capacity * 32)),
         DataType::Date32 => Box::new(Date32Builder::with_capacity(capacity)),
         DataType::Timestamp(_, _) => Box::new(TimestampMicrosecondBuilder::with_capacity(capacity)),
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            make_decimal_builder(data_type, capacity)
+        }
         _ => Box::new(StringBuilder::with_capacity(capacity, capacity * 32)), // Fallback to string
     }
 }
@@ -488,6 +529,10 @@ fn append_value(
                 .unwrap();
             b.append_option(try_get_with_warning::<Vec<u8>>(row, idx, "Vec<u8>"));
         }
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            let value = try_get_with_warning::<BigDecimal>(row, idx, "BigDecimal");
+            append_decimal(builder, data_type, row.column(idx).name(), value)?;
+        }
         DataType::Date32 => {
             let b = builder
                 .as_any_mut()
@@ -634,16 +679,26 @@ mod tests {
     }
 
     #[test]
-    fn test_mysql_type_to_arrow_complex_types_fallback() {
-        // Complex numeric types should fall back to Utf8 to preserve precision
-        assert!(matches!(
+    fn test_mysql_type_to_arrow_decimal_types() {
+        assert_eq!(
             mysql_type_to_arrow("decimal(10,2)"),
-            DataType::Utf8
-        ));
-        assert!(matches!(
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(
             mysql_type_to_arrow("numeric(10,2)"),
-            DataType::Utf8
-        ));
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            mysql_type_to_arrow("decimal(12,4) unsigned"),
+            DataType::Decimal128(12, 4)
+        );
+        // MySQL allows up to 65 digits, which needs Decimal256
+        assert_eq!(
+            mysql_type_to_arrow("decimal(65,30)"),
+            DataType::Decimal256(65, 30)
+        );
+        // Bare type names (row metadata) carry no precision: keep as text
+        assert_eq!(mysql_type_to_arrow("DECIMAL"), DataType::Utf8);
     }
 
     #[test]
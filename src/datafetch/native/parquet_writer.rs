@@ -1,23 +1,62 @@
 //! Centralized streaming Parquet writer with configurable compression
 
-use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
 use datafusion::arrow::record_batch::RecordBatch;
-use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::parquet::arrow::{ArrowWriter, AsyncArrowWriter};
 use datafusion::parquet::basic::{Compression, ZstdLevel};
+use datafusion::parquet::errors::ParquetError;
 use datafusion::parquet::file::properties::{WriterProperties, WriterVersion};
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::datafetch::reconcile::{cast_batch, needs_cast, reconcile_schemas};
+use crate::datafetch::{DataFetchError, SchemaMismatch};
+use crate::storage::{CacheUpload, UploadWriter};
+
+/// Where the batches go: the local file, a multipart upload, or a query reading them as
+/// they are fetched.
+enum Sink {
+    File(Box<ArrowWriter<File>>),
+    Upload(Box<AsyncArrowWriter<UploadWriter>>, CacheUpload),
+    Channel(mpsc::Sender<RecordBatch>),
+}
 
-use crate::datafetch::DataFetchError;
+impl Sink {
+    async fn write(&mut self, batch: &RecordBatch) -> Result<(), ParquetError> {
+        match self {
+            Sink::File(writer) => writer.write(batch),
+            Sink::Upload(writer, _) => writer.write(batch).await,
+            Sink::Channel(sender) => sender
+                .send(batch.clone())
+                .await
+                .map_err(|_| ParquetError::General("the reader stopped reading".to_string())),
+        }
+    }
+}
 
 /// Streaming Parquet writer that writes batches incrementally to disk.
 ///
 /// Lifecycle: new(path) -> init(schema) -> write_batch()* -> close()
+///
+/// With an expected schema (see [`with_expected_schema`](Self::with_expected_schema)),
+/// batches are cast to it when the fetched schema reconciles. Otherwise the file keeps the
+/// fetched schema and the mismatch is available from [`take_schema_mismatch`](Self::take_schema_mismatch).
+///
+/// With an upload (see [`with_upload`](Self::with_upload)), the file is streamed to storage
+/// instead of written to `path`. With a sender (see [`with_sender`](Self::with_sender)), no
+/// file is written and the batches are sent on as they arrive.
 pub struct StreamingParquetWriter {
     path: PathBuf,
-    writer: Option<ArrowWriter<File>>,
+    writer: Option<Sink>,
+    upload: Option<CacheUpload>,
+    sender: Option<mpsc::Sender<RecordBatch>>,
     row_count: usize,
+    expected: Option<(String, SchemaRef)>,
+    cast_to: Option<SchemaRef>,
+    schema_mismatch: Option<SchemaMismatch>,
+    file_schema: Option<SchemaRef>,
 }
 
 impl StreamingParquetWriter {
@@ -27,59 +66,175 @@ impl StreamingParquetWriter {
         Self {
             path,
             writer: None,
+            upload: None,
+            sender: None,
             row_count: 0,
+            expected: None,
+            cast_to: None,
+            schema_mismatch: None,
+            file_schema: None,
         }
     }
 
+    /// Reconcile the fetched schema with the schema recorded in the catalog for `table`.
+    pub fn with_expected_schema(mut self, table: impl Into<String>, schema: SchemaRef) -> Self {
+        self.expected = Some((table.into(), schema));
+        self
+    }
+
+    /// Stream the file into `upload` instead of writing it to the local path. Files whose
+    /// schema does not reconcile are still written locally, so they can be cast, and the
+    /// upload is abandoned.
+    pub fn with_upload(mut self, upload: CacheUpload) -> Self {
+        self.upload = Some(upload);
+        self
+    }
+
+    /// Send the batches to `sender` instead of writing a file. Sending waits while the
+    /// receiver is behind, and fails once it is dropped. A fetched schema that does not
+    /// reconcile with the expected one fails `init`, since there is no file to cast.
+    pub fn with_sender(mut self, sender: mpsc::Sender<RecordBatch>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// The schema recorded in the catalog, when the writer reconciles with one.
+    pub fn expected_schema(&self) -> Option<&SchemaRef> {
+        self.expected.as_ref().map(|(_, schema)| schema)
+    }
+
+    /// The schema of the written file, once `init` was called.
+    pub fn schema(&self) -> Option<&SchemaRef> {
+        self.file_schema.as_ref()
+    }
+
+    /// The mismatch between the fetched and expected schemas, if `init` found one.
+    pub fn take_schema_mismatch(&mut self) -> Option<SchemaMismatch> {
+        self.schema_mismatch.take()
+    }
+
     /// Initialize the writer with the Arrow schema.
-    /// Creates the file and configures Parquet properties.
+    /// Creates the file (or starts streaming) and configures Parquet properties.
     pub fn init(&mut self, schema: &Schema) -> Result<(), DataFetchError> {
-        // Create parent directories if needed
-        if let Some(parent) = self.path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| {
-                DataFetchError::Storage(format!("Failed to create directory: {}", e))
-            })?;
+        let mut file_schema = Arc::new(schema.clone());
+        if let Some((_, expected)) = &self.expected {
+            match reconcile_schemas(schema, expected) {
+                Ok(()) if needs_cast(schema, expected) => {
+                    file_schema = expected.clone();
+                    self.cast_to = Some(expected.clone());
+                }
+                Ok(()) => {}
+                Err(mismatch) => self.schema_mismatch = Some(mismatch),
+            }
         }
 
-        let file = File::create(&self.path)
-            .map_err(|e| DataFetchError::Storage(format!("Failed to create file: {}", e)))?;
-
         // Centralized Parquet configuration
         let props = WriterProperties::builder()
             .set_writer_version(WriterVersion::PARQUET_2_0)
             .set_compression(Compression::ZSTD(ZstdLevel::try_new(3).unwrap()))
             .build();
 
-        let writer = ArrowWriter::try_new(file, Arc::new(schema.clone()), Some(props))
-            .map_err(|e| DataFetchError::Storage(e.to_string()))?;
+        self.file_schema = Some(file_schema.clone());
+        if let Some(sender) = self.sender.take() {
+            if let (Some(mismatch), Some((table, _))) = (&self.schema_mismatch, &self.expected) {
+                return Err(DataFetchError::SchemaMismatch {
+                    table: table.clone(),
+                    details: mismatch.to_string(),
+                });
+            }
+            self.writer = Some(Sink::Channel(sender));
+            return Ok(());
+        }
 
-        self.writer = Some(writer);
+        let sink = match self.upload.take() {
+            Some(upload) if self.schema_mismatch.is_none() => {
+                let target = upload
+                    .writer()
+                    .map_err(|e| DataFetchError::Storage(e.to_string()))?;
+                AsyncArrowWriter::try_new(target, file_schema, Some(props))
+                    .map(|writer| Sink::Upload(Box::new(writer), upload))
+            }
+            upload => {
+                if let Some(upload) = upload {
+                    upload.abandon();
+                }
+                ArrowWriter::try_new(self.create_file()?, file_schema, Some(props))
+                    .map(|writer| Sink::File(Box::new(writer)))
+            }
+        }
+        .map_err(|e| DataFetchError::Storage(e.to_string()))?;
+
+        self.writer = Some(sink);
         Ok(())
     }
 
-    /// Write a batch to the Parquet file.
-    pub fn write_batch(&mut self, batch: &RecordBatch) -> Result<(), DataFetchError> {
+    fn create_file(&self) -> Result<File, DataFetchError> {
+        // Create parent directories if needed
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                DataFetchError::Storage(format!("Failed to create directory: {}", e))
+            })?;
+        }
+
+        File::create(&self.path)
+            .map_err(|e| DataFetchError::Storage(format!("Failed to create file: {}", e)))
+    }
+
+    /// Write a batch to the Parquet file. Streamed files wait here while too many parts
+    /// are still being uploaded.
+    pub async fn write_batch(&mut self, batch: &RecordBatch) -> Result<(), DataFetchError> {
         let writer = self.writer.as_mut().ok_or_else(|| {
             DataFetchError::Storage("Writer not initialized - call init() first".into())
         })?;
 
         self.row_count += batch.num_rows();
 
+        if let Some(target) = &self.cast_to {
+            let batch =
+                cast_batch(batch, target).map_err(|details| DataFetchError::SchemaMismatch {
+                    table: self
+                        .expected
+                        .as_ref()
+                        .map(|(t, _)| t.clone())
+                        .unwrap_or_default(),
+                    details,
+                })?;
+            return writer
+                .write(&batch)
+                .await
+                .map_err(|e| DataFetchError::Storage(e.to_string()));
+        }
+
         writer
             .write(batch)
+            .await
             .map_err(|e| DataFetchError::Storage(e.to_string()))
     }
 
     /// Close the writer and return the path to the written file along with the row count.
-    pub fn close(mut self) -> Result<(PathBuf, usize), DataFetchError> {
-        let writer = self
+    /// Streamed files are complete once the upload is finished, which happens when the
+    /// cache write is finalized.
+    pub async fn close(mut self) -> Result<(PathBuf, usize), DataFetchError> {
+        let sink = self
             .writer
             .take()
             .ok_or_else(|| DataFetchError::Storage("Writer not initialized".into()))?;
 
-        writer
-            .close()
-            .map_err(|e| DataFetchError::Storage(e.to_string()))?;
+        let closed = match sink {
+            Sink::File(writer) => writer.close().map(|_| ()),
+            Sink::Channel(_) => Ok(()),
+            Sink::Upload(mut writer, upload) => {
+                // Keep the footer so statistics can be read without downloading the file
+                match writer.flush().await {
+                    Ok(()) => {
+                        upload.begin_footer();
+                        writer.close().await.map(|_| ())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+        };
+        closed.map_err(|e| DataFetchError::Storage(e.to_string()))?;
 
         Ok((self.path, self.row_count))
     }
@@ -97,8 +252,8 @@ mod tests {
     use datafusion::arrow::datatypes::{DataType, Field};
     use tempfile::tempdir;
 
-    #[test]
-    fn test_streaming_writer_lifecycle() {
+    #[tokio::test]
+    async fn test_streaming_writer_lifecycle() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("test.parquet");
 
@@ -120,17 +275,17 @@ mod tests {
         )
         .unwrap();
 
-        writer.write_batch(&batch1).unwrap();
-        writer.write_batch(&batch2).unwrap();
+        writer.write_batch(&batch1).await.unwrap();
+        writer.write_batch(&batch2).await.unwrap();
 
-        let (result_path, row_count) = writer.close().unwrap();
+        let (result_path, row_count) = writer.close().await.unwrap();
         assert_eq!(result_path, path);
         assert_eq!(row_count, 6); // 3 rows from each batch
         assert!(path.exists());
     }
 
-    #[test]
-    fn test_streaming_writer_empty_table() {
+    #[tokio::test]
+    async fn test_streaming_writer_empty_table() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("empty.parquet");
 
@@ -141,15 +296,15 @@ mod tests {
 
         // Write empty batch
         let empty_batch = RecordBatch::new_empty(Arc::new(schema));
-        writer.write_batch(&empty_batch).unwrap();
+        writer.write_batch(&empty_batch).await.unwrap();
 
-        let (result_path, row_count) = writer.close().unwrap();
+        let (result_path, row_count) = writer.close().await.unwrap();
         assert_eq!(row_count, 0);
         assert!(result_path.exists());
     }
 
-    #[test]
-    fn test_write_batch_before_init_fails() {
+    #[tokio::test]
+    async fn test_write_batch_before_init_fails() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("test.parquet");
 
@@ -163,26 +318,26 @@ mod tests {
         let mut writer = StreamingParquetWriter::new(path);
 
         // Attempt to write without calling init() first
-        let result = writer.write_batch(&batch);
+        let result = writer.write_batch(&batch).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not initialized"));
     }
 
-    #[test]
-    fn test_close_before_init_fails() {
+    #[tokio::test]
+    async fn test_close_before_init_fails() {
         let dir = tempdir().unwrap();
         let path = dir.path().join("test.parquet");
 
         let writer = StreamingParquetWriter::new(path);
 
         // Attempt to close without calling init() first
-        let result = writer.close();
+        let result = writer.close().await;
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not initialized"));
     }
 
-    #[test]
-    fn test_schema_only_no_batches_written() {
+    #[tokio::test]
+    async fn test_schema_only_no_batches_written() {
         // Tests the case where init() is called but no batches are written before close().
         // This can happen when DataFusion returns an empty Vec<RecordBatch>.
         let dir = tempdir().unwrap();
@@ -197,7 +352,7 @@ mod tests {
         writer.init(&schema).unwrap();
 
         // Close without writing any batches
-        let (result_path, row_count) = writer.close().unwrap();
+        let (result_path, row_count) = writer.close().await.unwrap();
         assert_eq!(row_count, 0);
         assert!(result_path.exists());
 
@@ -215,8 +370,135 @@ mod tests {
         assert_eq!(parquet_schema.column(1).name(), "name");
     }
 
-    #[test]
-    fn test_compression_applied() {
+    #[tokio::test]
+    async fn test_expected_schema_casts_batches() {
+        use datafusion::arrow::array::Int64Array;
+        use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cast.parquet");
+
+        let fetched = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+        let expected = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+
+        let mut writer =
+            StreamingParquetWriter::new(path.clone()).with_expected_schema("s.t", expected.clone());
+        writer.init(&fetched).unwrap();
+        let batch =
+            RecordBatch::try_new(fetched, vec![Arc::new(Int32Array::from(vec![1, 2]))]).unwrap();
+        writer.write_batch(&batch).await.unwrap();
+        assert!(writer.take_schema_mismatch().is_none());
+        writer.close().await.unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(batches[0].schema().fields(), expected.fields());
+        let ids = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(ids.values(), &[1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_expected_schema_mismatch_is_recorded() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mismatch.parquet");
+
+        let fetched = Schema::new(vec![Field::new("name", DataType::Utf8, true)]);
+        let expected = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+
+        let mut writer = StreamingParquetWriter::new(path).with_expected_schema("s.t", expected);
+        writer.init(&fetched).unwrap();
+
+        let mismatch = writer.take_schema_mismatch().unwrap();
+        assert_eq!(mismatch.fetched.as_ref(), &fetched);
+        assert!(mismatch.to_string().contains("column 'id' is missing"));
+    }
+
+    #[tokio::test]
+    async fn test_null_in_non_nullable_expected_column_fails() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nulls.parquet");
+
+        let fetched = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+        let expected = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+
+        let mut writer = StreamingParquetWriter::new(path).with_expected_schema("s.t", expected);
+        writer.init(&fetched).unwrap();
+        let batch = RecordBatch::try_new(
+            fetched,
+            vec![Arc::new(Int32Array::from(vec![Some(1), None]))],
+        )
+        .unwrap();
+
+        let err = writer.write_batch(&batch).await.unwrap_err();
+        assert!(matches!(err, DataFetchError::SchemaMismatch { ref table, .. } if table == "s.t"));
+    }
+
+    #[tokio::test]
+    async fn test_streams_into_upload() {
+        use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use object_store::{memory::InMemory, path::Path as ObjectPath, ObjectStore};
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("streamed.parquet");
+        let store = Arc::new(InMemory::new());
+        let object = ObjectPath::from("cache/1/s/t/v1/data.parquet");
+        let upload = CacheUpload::new(store.clone(), object.clone());
+
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let mut writer = StreamingParquetWriter::new(path.clone()).with_upload(upload.clone());
+        writer.init(&schema).unwrap();
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![1, 2, 3]))]).unwrap();
+        writer.write_batch(&batch).await.unwrap();
+        let (_, row_count) = writer.close().await.unwrap();
+        assert!(upload.finish().await.unwrap());
+
+        assert_eq!(row_count, 3);
+        assert!(!path.exists());
+        let data = store.get(&object).await.unwrap().bytes().await.unwrap();
+        assert!(data.ends_with(&upload.tail().unwrap()));
+        let reader = ParquetRecordBatchReaderBuilder::try_new(data)
+            .unwrap()
+            .build()
+            .unwrap();
+        let rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(rows, 3);
+    }
+
+    #[tokio::test]
+    async fn test_schema_mismatch_abandons_upload() {
+        use object_store::{memory::InMemory, path::Path as ObjectPath};
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mismatch.parquet");
+        let store = Arc::new(InMemory::new());
+        let object = ObjectPath::from("cache/1/s/t/v1/data.parquet");
+        let upload = CacheUpload::new(store.clone(), object.clone());
+
+        let fetched = Schema::new(vec![Field::new("name", DataType::Utf8, true)]);
+        let expected = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+        let mut writer = StreamingParquetWriter::new(path.clone())
+            .with_expected_schema("s.t", expected)
+            .with_upload(upload.clone());
+        writer.init(&fetched).unwrap();
+        assert!(writer.take_schema_mismatch().is_some());
+        writer.close().await.unwrap();
+
+        // The file is written locally so it can be cast
+        assert!(upload.is_abandoned());
+        assert!(!upload.finish().await.unwrap());
+        assert!(path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_compression_applied() {
         use datafusion::parquet::file::reader::{FileReader, SerializedFileReader};
         use std::fs::File;
 
@@ -247,8 +529,8 @@ mod tests {
         )
         .unwrap();
 
-        writer.write_batch(&batch).unwrap();
-        writer.close().unwrap();
+        writer.write_batch(&batch).await.unwrap();
+        writer.close().await.unwrap();
 
         // Read back and verify compression
         let file = File::open(&path).unwrap();
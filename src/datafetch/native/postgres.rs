@@ -1,23 +1,40 @@
 //! PostgreSQL native driver implementation using sqlx
 
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use datafusion::arrow::array::{
-    ArrayBuilder, BinaryBuilder, BooleanBuilder, Date32Builder, Float32Builder, Float64Builder,
-    Int16Builder, Int32Builder, Int64Builder, StringBuilder, TimestampMicrosecondBuilder,
+    ArrayBuilder, BinaryBuilder, BooleanBuilder, Date32Builder, FixedSizeBinaryBuilder,
+    Float32Builder, Float64Builder, Int16Builder, Int32Builder, Int64Builder,
+    IntervalMonthDayNanoBuilder, ListBuilder, StringBuilder, Time64MicrosecondBuilder,
+    TimestampMicrosecondBuilder,
 };
-use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::datatypes::{DataType, Field, IntervalMonthDayNano, IntervalUnit, Schema};
 use datafusion::arrow::record_batch::RecordBatch;
 use futures::StreamExt;
-use sqlx::postgres::{PgColumn, PgConnection, PgRow};
+use sqlx::postgres::types::PgInterval;
+use sqlx::postgres::{PgColumn, PgConnection, PgRow, Postgres};
 use sqlx::{Column, Connection, Row, TypeInfo};
 use std::sync::Arc;
 use urlencoding::encode;
+use uuid::Uuid;
 
 use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
 use crate::secrets::SecretManager;
-use crate::source::Source;
+use crate::source::{Source, TableMatcher};
 
+use super::decimal::{append_decimal, decimal_type_from_str, make_decimal_builder};
 use super::StreamingParquetWriter;
 
+/// Column type as reported by `information_schema.columns`, with precision and scale appended
+/// for constrained numerics (e.g. `numeric(10,2)`) so they map to a decimal type. Arrays are
+/// reported as plain `ARRAY`, so their `udt_name` (e.g. `_int4`) is used to get the element type.
+const COLUMN_TYPE_EXPR: &str = "CASE \
+    WHEN c.data_type = 'numeric' AND c.numeric_precision IS NOT NULL \
+        THEN format('numeric(%s,%s)', c.numeric_precision, c.numeric_scale) \
+    WHEN c.data_type = 'ARRAY' THEN c.udt_name \
+    ELSE c.data_type \
+END::text";
+
 /// Build a PostgreSQL connection string from source configuration and resolved password.
 fn build_connection_string(
     host: &str,
@@ -48,6 +65,7 @@ pub async fn resolve_connection_string(
             user,
             database,
             credential,
+            ..
         } => (host, *port, user, database, credential),
         _ => {
             return Err(DataFetchError::Connection(
@@ -95,11 +113,12 @@ async fn connect_with_ssl_retry(connection_string: &str) -> Result<PgConnection,
 pub async fn discover_tables(
     source: &Source,
     secrets: &SecretManager,
+    matcher: &TableMatcher,
 ) -> Result<Vec<TableMetadata>, DataFetchError> {
     let connection_string = resolve_connection_string(source, secrets).await?;
     let mut conn = connect_with_ssl_retry(&connection_string).await?;
 
-    let rows = sqlx::query(
+    let query = format!(
         r#"
         SELECT
             t.table_catalog,
@@ -107,20 +126,23 @@ pub async fn discover_tables(
             t.table_name,
             t.table_type,
             c.column_name,
-            c.data_type,
+            {COLUMN_TYPE_EXPR},
             c.is_nullable,
-            c.ordinal_position::int
+            c.ordinal_position::int,
+            CASE WHEN pc.reltuples >= 0 THEN pc.reltuples::bigint END,
+            CASE WHEN pc.relkind IN ('r', 'm') THEN pg_total_relation_size(pc.oid) END
         FROM information_schema.tables t
         JOIN information_schema.columns c
             ON t.table_catalog = c.table_catalog
             AND t.table_schema = c.table_schema
             AND t.table_name = c.table_name
+        LEFT JOIN pg_catalog.pg_class pc
+            ON pc.oid = to_regclass(quote_ident(t.table_schema) || '.' || quote_ident(t.table_name))
         WHERE t.table_schema NOT IN ('information_schema', 'pg_catalog')
         ORDER BY t.table_schema, t.table_name, c.ordinal_position
-        "#,
-    )
-    .fetch_all(&mut conn)
-    .await?;
+        "#
+    );
+    let rows = sqlx::query(&query).fetch_all(&mut conn).await?;
 
     let mut tables: Vec<TableMetadata> = Vec::new();
 
@@ -133,6 +155,13 @@ pub async fn discover_tables(
         let data_type: String = row.get(5);
         let is_nullable: String = row.get(6);
         let ordinal: i32 = row.get(7);
+        // reltuples is -1 until the table is first analyzed
+        let row_count: Option<i64> = row.get(8);
+        let size_bytes: Option<i64> = row.get(9);
+
+        if !matcher.matches(&schema, &table) {
+            continue;
+        }
 
         let column = ColumnMetadata {
             name: col_name,
@@ -154,6 +183,8 @@ pub async fn discover_tables(
                 table_name: table,
                 table_type,
                 columns: vec![column],
+                row_count: row_count.and_then(|n| u64::try_from(n).ok()),
+                size_bytes: size_bytes.and_then(|n| u64::try_from(n).ok()),
             });
         }
     }
@@ -173,6 +204,9 @@ pub async fn fetch_table(
     let connection_string = resolve_connection_string(source, secrets).await?;
     let mut conn = connect_with_ssl_retry(&connection_string).await?;
 
+    // Row metadata only reports NUMERIC, so precision and scale come from the declared types
+    let declared = declared_columns(&mut conn, schema, table).await?;
+
     // Build query - properly escape identifiers
     let query = format!(
         "SELECT * FROM \"{}\".\"{}\"",
@@ -189,44 +223,20 @@ pub async fn fetch_table(
     let first_row = stream.next().await;
 
     let arrow_schema = match &first_row {
-        Some(Ok(row)) => schema_from_columns(row.columns()),
+        Some(Ok(row)) => schema_from_columns(row.columns(), &declared),
         Some(Err(e)) => return Err(DataFetchError::Query(e.to_string())),
         None => {
-            // Empty table: query information_schema for schema
-            // Need a new connection since stream borrows conn
-            let mut schema_conn = connect_with_ssl_retry(&connection_string).await?;
-            let schema_rows = sqlx::query(
-                r#"
-                SELECT column_name, data_type, is_nullable
-                FROM information_schema.columns
-                WHERE table_schema = $1 AND table_name = $2
-                ORDER BY ordinal_position
-                "#,
-            )
-            .bind(schema)
-            .bind(table)
-            .fetch_all(&mut schema_conn)
-            .await?;
-
-            if schema_rows.is_empty() {
+            // Empty table: use the declared column types
+            if declared.is_empty() {
                 return Err(DataFetchError::Query(format!(
                     "Table {}.{} has no columns",
                     schema, table
                 )));
             }
 
-            let fields: Vec<Field> = schema_rows
+            let fields: Vec<Field> = declared
                 .iter()
-                .map(|row| {
-                    let col_name: String = row.get(0);
-                    let data_type: String = row.get(1);
-                    let is_nullable: String = row.get(2);
-                    Field::new(
-                        col_name,
-                        pg_type_to_arrow(&data_type),
-                        is_nullable.to_uppercase() == "YES",
-                    )
-                })
+                .map(|col| Field::new(&col.name, col.data_type.clone(), col.nullable))
                 .collect();
 
             Schema::new(fields)
@@ -239,7 +249,7 @@ pub async fn fetch_table(
     // Handle empty table case
     if first_row.is_none() {
         let empty_batch = RecordBatch::new_empty(Arc::new(arrow_schema));
-        writer.write_batch(&empty_batch)?;
+        writer.write_batch(&empty_batch).await?;
         return Ok(());
     }
 
@@ -256,7 +266,7 @@ pub async fn fetch_table(
         // Write batch when full
         if batch_rows.len() >= BATCH_SIZE {
             let batch = rows_to_batch(&batch_rows, &arrow_schema)?;
-            writer.write_batch(&batch)?;
+            writer.write_batch(&batch).await?;
             batch_rows.clear();
         }
     }
@@ -264,7 +274,7 @@ pub async fn fetch_table(
     // Write any remaining rows
     if !batch_rows.is_empty() {
         let batch = rows_to_batch(&batch_rows, &arrow_schema)?;
-        writer.write_batch(&batch)?;
+        writer.write_batch(&batch).await?;
     }
 
     Ok(())
@@ -274,13 +284,53 @@ pub async fn fetch_table(
 // Arrow conversion utilities
 // ============================================================================
 
-/// Build Arrow Schema from sqlx column metadata
-fn schema_from_columns(columns: &[PgColumn]) -> Schema {
+/// Declared columns of a table from information_schema, in ordinal order
+async fn declared_columns(
+    conn: &mut PgConnection,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<ColumnMetadata>, DataFetchError> {
+    let query = format!(
+        r#"
+        SELECT c.column_name, {COLUMN_TYPE_EXPR}, c.is_nullable, c.ordinal_position::int
+        FROM information_schema.columns c
+        WHERE c.table_schema = $1 AND c.table_name = $2
+        ORDER BY c.ordinal_position
+        "#
+    );
+    let rows = sqlx::query(&query)
+        .bind(schema)
+        .bind(table)
+        .fetch_all(conn)
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let data_type: String = row.get(1);
+            let is_nullable: String = row.get(2);
+            ColumnMetadata {
+                name: row.get(0),
+                data_type: pg_type_to_arrow(&data_type),
+                nullable: is_nullable.to_uppercase() == "YES",
+                ordinal_position: row.get(3),
+            }
+        })
+        .collect())
+}
+
+/// Build Arrow Schema from sqlx column metadata. NUMERIC columns take their type from the
+/// matching declared column, since the row metadata carries no precision or scale.
+fn schema_from_columns(columns: &[PgColumn], declared: &[ColumnMetadata]) -> Schema {
     let fields: Vec<Field> = columns
         .iter()
         .map(|col| {
             let name = col.name();
-            let data_type = pg_type_to_arrow(col.type_info().name());
+            let type_name = col.type_info().name();
+            let data_type = match declared.iter().find(|d| d.name == name) {
+                Some(d) if type_name == "NUMERIC" => d.data_type.clone(),
+                _ => pg_type_to_arrow(type_name),
+            };
             Field::new(name, data_type, true) // Assume nullable
         })
         .collect();
@@ -294,6 +344,24 @@ fn pg_type_to_arrow(pg_type: &str) -> DataType {
 
     let type_lower = pg_type.to_lowercase();
 
+    // Constrained numerics carry precision and scale, e.g. "numeric(10,2)"
+    if type_lower.starts_with("numeric(") || type_lower.starts_with("decimal(") {
+        return decimal_type_from_str(&type_lower);
+    }
+
+    // Arrays: "int4[]" in row metadata, "_int4" (udt_name) in information_schema
+    if let Some(element) = type_lower
+        .strip_suffix("[]")
+        .or_else(|| type_lower.strip_prefix('_'))
+    {
+        let item_type = match pg_type_to_arrow(element) {
+            // Array elements carry no precision, so decimals are kept as text
+            DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => DataType::Utf8,
+            item_type => item_type,
+        };
+        return DataType::List(Arc::new(Field::new("item", item_type, true)));
+    }
+
     match type_lower.as_str() {
         "bool" | "boolean" => DataType::Boolean,
         "int2" | "smallint" => DataType::Int16,
@@ -301,21 +369,22 @@ fn pg_type_to_arrow(pg_type: &str) -> DataType {
         "int8" | "bigint" => DataType::Int64,
         "float4" | "real" => DataType::Float32,
         "float8" | "double precision" => DataType::Float64,
-        // Complex types: fallback to Utf8 to avoid runtime panics
+        // Unconstrained numerics have no fixed scale: keep the exact text
         "numeric" | "decimal" => DataType::Utf8,
         "varchar" | "text" | "char" | "bpchar" | "name" => DataType::Utf8,
         "bytea" => DataType::Binary,
         "date" => DataType::Date32,
-        "time" | "time without time zone" => DataType::Utf8,
+        "time" | "time without time zone" => DataType::Time64(TimeUnit::Microsecond),
         "timestamp" | "timestamp without time zone" => {
             DataType::Timestamp(TimeUnit::Microsecond, None)
         }
         "timestamptz" | "timestamp with time zone" => {
             DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
         }
-        "uuid" => DataType::Utf8,
+        "uuid" => DataType::FixedSizeBinary(16),
+        // JSON is kept as text and queried with the registered json_* functions
         "json" | "jsonb" => DataType::Utf8,
-        "interval" => DataType::Utf8,
+        "interval" => DataType::Interval(IntervalUnit::MonthDayNano),
         "character varying" | "character" => DataType::Utf8,
         _ => DataType::Utf8, // Default fallback
     }
@@ -355,8 +424,20 @@ fn make_builder(data_type: &DataType, capacity: usize) -> Box<dyn ArrayBuilder>
         DataType::Float64 => Box::new(Float64Builder::with_capacity(capacity)),
         DataType::Utf8 => Box::new(StringBuilder::with_capacity(capacity, capacity * 32)),
         DataType::Binary => Box::new(BinaryBuilder::with_capacity(capacity, capacity * 32)),
+        DataType::FixedSizeBinary(size) => {
+            Box::new(FixedSizeBinaryBuilder::with_capacity(capacity, *size))
+        }
         DataType::Date32 => Box::new(Date32Builder::with_capacity(capacity)),
+        DataType::Time64(_) => Box::new(Time64MicrosecondBuilder::with_capacity(capacity)),
         DataType::Timestamp(_, _) => Box::new(TimestampMicrosecondBuilder::with_capacity(capacity)),
+        DataType::Interval(_) => Box::new(IntervalMonthDayNanoBuilder::with_capacity(capacity)),
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            make_decimal_builder(data_type, capacity)
+        }
+        DataType::List(field) => Box::new(
+            ListBuilder::with_capacity(make_builder(field.data_type(), capacity), capacity)
+                .with_field(field.clone()),
+        ),
         _ => Box::new(StringBuilder::with_capacity(capacity, capacity * 32)), // Fallback to string
     }
 }
@@ -406,7 +487,23 @@ fn append_value(
                 .as_any_mut()
                 .downcast_mut::<StringBuilder>()
                 .unwrap();
-            b.append_option(row.try_get::<String, _>(idx).ok());
+            // Types without a String decoding are rendered as their exact text
+            let value = match row.column(idx).type_info().name() {
+                "NUMERIC" => row
+                    .try_get::<BigDecimal, _>(idx)
+                    .ok()
+                    .map(|d| d.to_string()),
+                "JSON" | "JSONB" => row
+                    .try_get::<serde_json::Value, _>(idx)
+                    .ok()
+                    .map(|v| v.to_string()),
+                _ => row.try_get::<String, _>(idx).ok(),
+            };
+            b.append_option(value);
+        }
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            let value = row.try_get::<BigDecimal, _>(idx).ok();
+            append_decimal(builder, data_type, row.column(idx).name(), value)?;
         }
         DataType::Binary => {
             let b = builder
@@ -415,33 +512,56 @@ fn append_value(
                 .unwrap();
             b.append_option(row.try_get::<Vec<u8>, _>(idx).ok());
         }
+        DataType::FixedSizeBinary(_) => {
+            let b = builder
+                .as_any_mut()
+                .downcast_mut::<FixedSizeBinaryBuilder>()
+                .unwrap();
+            append_uuid(b, row.try_get::<Uuid, _>(idx).ok())?;
+        }
         DataType::Date32 => {
             let b = builder
                 .as_any_mut()
                 .downcast_mut::<Date32Builder>()
                 .unwrap();
-            // chrono::NaiveDate -> days since epoch
-            if let Ok(date) = row.try_get::<chrono::NaiveDate, _>(idx) {
-                let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
-                let days = (date - epoch).num_days() as i32;
-                b.append_value(days);
-            } else {
-                b.append_null();
-            }
+            b.append_option(row.try_get::<NaiveDate, _>(idx).ok().map(days_since_epoch));
         }
-        DataType::Timestamp(_, _) => {
+        DataType::Time64(_) => {
+            let b = builder
+                .as_any_mut()
+                .downcast_mut::<Time64MicrosecondBuilder>()
+                .unwrap();
+            b.append_option(
+                row.try_get::<NaiveTime, _>(idx)
+                    .ok()
+                    .map(micros_since_midnight),
+            );
+        }
+        DataType::Timestamp(_, tz) => {
             let b = builder
                 .as_any_mut()
                 .downcast_mut::<TimestampMicrosecondBuilder>()
                 .unwrap();
-            // chrono::NaiveDateTime -> microseconds since epoch
-            if let Ok(ts) = row.try_get::<chrono::NaiveDateTime, _>(idx) {
-                let micros = ts.and_utc().timestamp_micros();
-                b.append_value(micros);
+            // timestamptz decodes as an instant, timestamp as a naive date-time
+            let micros = if tz.is_some() {
+                row.try_get::<DateTime<Utc>, _>(idx)
+                    .ok()
+                    .map(|ts| ts.timestamp_micros())
             } else {
-                b.append_null();
-            }
+                row.try_get::<NaiveDateTime, _>(idx)
+                    .ok()
+                    .map(|ts| ts.and_utc().timestamp_micros())
+            };
+            b.append_option(micros);
+        }
+        DataType::Interval(_) => {
+            let b = builder
+                .as_any_mut()
+                .downcast_mut::<IntervalMonthDayNanoBuilder>()
+                .unwrap();
+            b.append_option(row.try_get::<PgInterval, _>(idx).ok().map(month_day_nano));
         }
+        DataType::List(field) => append_list_value(builder, row, idx, field.data_type())?,
         _ => {
             // Fallback: try to get as string
             let b = builder
@@ -454,9 +574,184 @@ fn append_value(
     Ok(())
 }
 
+/// Append a Postgres array to a list column, decoding elements by the list's item type.
+fn append_list_value(
+    builder: &mut Box<dyn ArrayBuilder>,
+    row: &PgRow,
+    idx: usize,
+    item_type: &DataType,
+) -> Result<(), DataFetchError> {
+    match item_type {
+        DataType::Boolean => append_list(builder, row, idx, |b: &mut BooleanBuilder, v| {
+            b.append_option(v)
+        }),
+        DataType::Int16 => append_list(builder, row, idx, |b: &mut Int16Builder, v| {
+            b.append_option(v)
+        }),
+        DataType::Int32 => append_list(builder, row, idx, |b: &mut Int32Builder, v| {
+            b.append_option(v)
+        }),
+        DataType::Int64 => append_list(builder, row, idx, |b: &mut Int64Builder, v| {
+            b.append_option(v)
+        }),
+        DataType::Float32 => append_list(builder, row, idx, |b: &mut Float32Builder, v| {
+            b.append_option(v)
+        }),
+        DataType::Float64 => append_list(builder, row, idx, |b: &mut Float64Builder, v| {
+            b.append_option(v)
+        }),
+        DataType::Utf8 => match row.column(idx).type_info().name() {
+            "NUMERIC[]" => append_list(
+                builder,
+                row,
+                idx,
+                |b: &mut StringBuilder, v: Option<BigDecimal>| {
+                    b.append_option(v.map(|d| d.to_string()))
+                },
+            ),
+            "JSON[]" | "JSONB[]" => append_list(
+                builder,
+                row,
+                idx,
+                |b: &mut StringBuilder, v: Option<serde_json::Value>| {
+                    b.append_option(v.map(|v| v.to_string()))
+                },
+            ),
+            _ => append_list(
+                builder,
+                row,
+                idx,
+                |b: &mut StringBuilder, v: Option<String>| b.append_option(v),
+            ),
+        },
+        DataType::Binary => append_list(
+            builder,
+            row,
+            idx,
+            |b: &mut BinaryBuilder, v: Option<Vec<u8>>| b.append_option(v),
+        ),
+        DataType::FixedSizeBinary(_) => {
+            let mut result = Ok(());
+            append_list(builder, row, idx, |b: &mut FixedSizeBinaryBuilder, v| {
+                if result.is_ok() {
+                    result = append_uuid(b, v);
+                }
+            })?;
+            result
+        }
+        DataType::Date32 => append_list(builder, row, idx, |b: &mut Date32Builder, v| {
+            b.append_option(v.map(days_since_epoch))
+        }),
+        DataType::Time64(_) => {
+            append_list(builder, row, idx, |b: &mut Time64MicrosecondBuilder, v| {
+                b.append_option(v.map(micros_since_midnight))
+            })
+        }
+        DataType::Timestamp(_, Some(_)) => append_list(
+            builder,
+            row,
+            idx,
+            |b: &mut TimestampMicrosecondBuilder, v: Option<DateTime<Utc>>| {
+                b.append_option(v.map(|ts| ts.timestamp_micros()))
+            },
+        ),
+        DataType::Timestamp(_, None) => append_list(
+            builder,
+            row,
+            idx,
+            |b: &mut TimestampMicrosecondBuilder, v: Option<NaiveDateTime>| {
+                b.append_option(v.map(|ts| ts.and_utc().timestamp_micros()))
+            },
+        ),
+        DataType::Interval(_) => append_list(
+            builder,
+            row,
+            idx,
+            |b: &mut IntervalMonthDayNanoBuilder, v| b.append_option(v.map(month_day_nano)),
+        ),
+        other => Err(DataFetchError::Query(format!(
+            "Unsupported Postgres array element type: {}",
+            other
+        ))),
+    }
+}
+
+/// Decode a one-dimensional array as `Vec<Option<T>>` and append it as one list entry;
+/// NULL arrays become NULL list entries. Arrays that cannot be decoded, such as
+/// multi-dimensional arrays or arrays of unsupported element types, are an error.
+fn append_list<'r, T, B>(
+    builder: &mut Box<dyn ArrayBuilder>,
+    row: &'r PgRow,
+    idx: usize,
+    mut append: impl FnMut(&mut B, Option<T>),
+) -> Result<(), DataFetchError>
+where
+    Vec<Option<T>>: sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres>,
+    B: ArrayBuilder,
+{
+    let list = builder
+        .as_any_mut()
+        .downcast_mut::<ListBuilder<Box<dyn ArrayBuilder>>>()
+        .unwrap();
+
+    let values = row.try_get::<Option<Vec<Option<T>>>, _>(idx).map_err(|e| {
+        let column = row.column(idx);
+        DataFetchError::Query(format!(
+            "Cannot read array column '{}' of type {}: {}",
+            column.name(),
+            column.type_info().name(),
+            e
+        ))
+    })?;
+    match values {
+        Some(values) => {
+            let items = list.values().as_any_mut().downcast_mut::<B>().unwrap();
+            for value in values {
+                append(items, value);
+            }
+            list.append(true);
+        }
+        None => list.append(false),
+    }
+    Ok(())
+}
+
+fn append_uuid(b: &mut FixedSizeBinaryBuilder, value: Option<Uuid>) -> Result<(), DataFetchError> {
+    match value {
+        Some(uuid) => b
+            .append_value(uuid.as_bytes())
+            .map_err(|e| DataFetchError::Query(e.to_string())),
+        None => {
+            b.append_null();
+            Ok(())
+        }
+    }
+}
+
+/// chrono::NaiveDate -> days since epoch
+fn days_since_epoch(date: NaiveDate) -> i32 {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    (date - epoch).num_days() as i32
+}
+
+/// chrono::NaiveTime -> microseconds since midnight
+fn micros_since_midnight(time: NaiveTime) -> i64 {
+    time.num_seconds_from_midnight() as i64 * 1_000_000 + (time.nanosecond() / 1_000) as i64
+}
+
+/// Postgres keeps months, days and microseconds apart, exactly like MonthDayNano
+fn month_day_nano(interval: PgInterval) -> IntervalMonthDayNano {
+    IntervalMonthDayNano::new(
+        interval.months,
+        interval.days,
+        interval.microseconds * 1_000,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use datafusion::arrow::datatypes::TimeUnit;
 
     #[test]
     fn test_pg_type_to_arrow_basic_types() {
@@ -487,7 +782,6 @@ mod tests {
             pg_type_to_arrow("character varying"),
             DataType::Utf8
         ));
-        assert!(matches!(pg_type_to_arrow("uuid"), DataType::Utf8));
         assert!(matches!(pg_type_to_arrow("json"), DataType::Utf8));
         assert!(matches!(pg_type_to_arrow("jsonb"), DataType::Utf8));
     }
@@ -495,7 +789,18 @@ mod tests {
     #[test]
     fn test_pg_type_to_arrow_date_time_types() {
         assert!(matches!(pg_type_to_arrow("date"), DataType::Date32));
-        assert!(matches!(pg_type_to_arrow("time"), DataType::Utf8));
+        assert_eq!(
+            pg_type_to_arrow("time"),
+            DataType::Time64(TimeUnit::Microsecond)
+        );
+        assert_eq!(
+            pg_type_to_arrow("time without time zone"),
+            DataType::Time64(TimeUnit::Microsecond)
+        );
+        assert_eq!(
+            pg_type_to_arrow("interval"),
+            DataType::Interval(IntervalUnit::MonthDayNano)
+        );
 
         match pg_type_to_arrow("timestamp") {
             DataType::Timestamp(unit, tz) => {
@@ -523,14 +828,109 @@ mod tests {
     #[test]
     fn test_pg_type_to_arrow_binary_types() {
         assert!(matches!(pg_type_to_arrow("bytea"), DataType::Binary));
+        assert_eq!(pg_type_to_arrow("uuid"), DataType::FixedSizeBinary(16));
+    }
+
+    #[test]
+    fn test_pg_type_to_arrow_arrays() {
+        let list_of = |item: DataType| DataType::List(Arc::new(Field::new("item", item, true)));
+
+        // Row metadata names
+        assert_eq!(pg_type_to_arrow("INT4[]"), list_of(DataType::Int32));
+        assert_eq!(pg_type_to_arrow("TEXT[]"), list_of(DataType::Utf8));
+        assert_eq!(
+            pg_type_to_arrow("UUID[]"),
+            list_of(DataType::FixedSizeBinary(16))
+        );
+
+        // information_schema udt_name
+        assert_eq!(pg_type_to_arrow("_int8"), list_of(DataType::Int64));
+        assert_eq!(pg_type_to_arrow("_bool"), list_of(DataType::Boolean));
+        assert_eq!(
+            pg_type_to_arrow("_timestamptz"),
+            list_of(DataType::Timestamp(
+                TimeUnit::Microsecond,
+                Some("UTC".into())
+            ))
+        );
+        assert_eq!(pg_type_to_arrow("_numeric"), list_of(DataType::Utf8));
+    }
+
+    #[test]
+    fn test_list_builder_for_array_type() {
+        use datafusion::arrow::array::{Array, Int32Array, ListArray};
+
+        let data_type = pg_type_to_arrow("_int4");
+        let mut builder = make_builder(&data_type, 2);
+        {
+            let list = builder
+                .as_any_mut()
+                .downcast_mut::<ListBuilder<Box<dyn ArrayBuilder>>>()
+                .unwrap();
+            let items = list
+                .values()
+                .as_any_mut()
+                .downcast_mut::<Int32Builder>()
+                .unwrap();
+            items.append_value(1);
+            items.append_null();
+            list.append(true);
+            list.append(false);
+        }
+
+        let array = builder.finish();
+        assert_eq!(array.data_type(), &data_type);
+        let array = array.as_any().downcast_ref::<ListArray>().unwrap();
+        let first = array.value(0);
+        let first = first.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(first.value(0), 1);
+        assert!(first.is_null(1));
+        assert!(array.is_null(1));
+    }
+
+    #[test]
+    fn test_time_and_interval_conversion() {
+        let time = NaiveTime::from_hms_micro_opt(1, 2, 3, 456).unwrap();
+        assert_eq!(micros_since_midnight(time), 3_723_000_456);
+
+        let interval = PgInterval {
+            months: 14,
+            days: 3,
+            microseconds: 1_500,
+        };
+        assert_eq!(
+            month_day_nano(interval),
+            IntervalMonthDayNano::new(14, 3, 1_500_000)
+        );
+
+        let date = NaiveDate::from_ymd_opt(1970, 1, 11).unwrap();
+        assert_eq!(days_since_epoch(date), 10);
     }
 
     #[test]
     fn test_pg_type_to_arrow_complex_types_fallback() {
-        // Complex types should fall back to Utf8 to avoid runtime panics
+        // Unconstrained numerics and other complex types fall back to Utf8
         assert!(matches!(pg_type_to_arrow("numeric"), DataType::Utf8));
         assert!(matches!(pg_type_to_arrow("decimal"), DataType::Utf8));
-        assert!(matches!(pg_type_to_arrow("interval"), DataType::Utf8));
+        assert!(matches!(pg_type_to_arrow("point"), DataType::Utf8));
+    }
+
+    #[test]
+    fn test_pg_type_to_arrow_constrained_numeric() {
+        assert_eq!(
+            pg_type_to_arrow("numeric(10,2)"),
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            pg_type_to_arrow("NUMERIC(38,0)"),
+            DataType::Decimal128(38, 0)
+        );
+        assert_eq!(
+            pg_type_to_arrow("numeric(60,10)"),
+            DataType::Decimal256(60, 10)
+        );
+        // Beyond Decimal256 precision there is no fixed-point type: keep as text
+        assert_eq!(pg_type_to_arrow("numeric(200,2)"), DataType::Utf8);
     }
 
     #[test]
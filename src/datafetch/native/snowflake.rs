@@ -11,14 +11,21 @@ use std::sync::Arc;
 
 use crate::datafetch::{ColumnMetadata, DataFetchError, TableMetadata};
 use crate::secrets::SecretManager;
-use crate::source::Source;
+use crate::source::{Source, TableMatcher};
 
+use super::decimal::{decimal_type, parse_precision_scale};
 use super::StreamingParquetWriter;
 
 // Re-exports of arrow 54 for snowflake-api compatibility
 use arrow_array_54 as arrow54_array;
 use arrow_ipc_54 as arrow54_ipc;
 
+/// information_schema reports fixed-point columns as plain `NUMBER`; append precision and
+/// scale so they map to the matching decimal type.
+const NUMBER_TYPE_EXPR: &str = "CASE WHEN data_type = 'NUMBER' \
+    THEN 'NUMBER(' || numeric_precision || ',' || numeric_scale || ')' \
+    ELSE data_type END";
+
 /// Credential format for password authentication
 #[derive(Deserialize)]
 struct PasswordCredential {
@@ -45,6 +52,7 @@ async fn build_client(
             schema,
             role,
             credential,
+            ..
         } => (account, user, warehouse, database, schema, role, credential),
         _ => {
             return Err(DataFetchError::Connection(
@@ -103,6 +111,7 @@ async fn build_client(
 pub async fn discover_tables(
     source: &Source,
     secrets: &SecretManager,
+    matcher: &TableMatcher,
 ) -> Result<Vec<TableMetadata>, DataFetchError> {
     let client = build_client(source, secrets).await?;
 
@@ -123,18 +132,22 @@ pub async fn discover_tables(
         format!(
             r#"
             SELECT
-                table_catalog,
-                table_schema,
-                table_name,
-                table_type,
-                column_name,
-                data_type,
-                is_nullable,
-                ordinal_position
-            FROM "{database}".information_schema.columns
-            WHERE table_schema NOT IN ('INFORMATION_SCHEMA')
-              AND table_schema = '{schema}'
-            ORDER BY table_schema, table_name, ordinal_position
+                c.table_catalog,
+                c.table_schema,
+                c.table_name,
+                t.table_type,
+                c.column_name,
+                {NUMBER_TYPE_EXPR} AS data_type,
+                c.is_nullable,
+                c.ordinal_position,
+                t.row_count,
+                t.bytes
+            FROM "{database}".information_schema.columns c
+            JOIN "{database}".information_schema.tables t
+                ON t.table_schema = c.table_schema AND t.table_name = c.table_name
+            WHERE c.table_schema NOT IN ('INFORMATION_SCHEMA')
+              AND c.table_schema = '{schema}'
+            ORDER BY c.table_schema, c.table_name, c.ordinal_position
             "#,
             database = database.replace('"', "\"\""),
             schema = schema.replace('\'', "''")
@@ -143,17 +156,21 @@ pub async fn discover_tables(
         format!(
             r#"
             SELECT
-                table_catalog,
-                table_schema,
-                table_name,
-                table_type,
-                column_name,
-                data_type,
-                is_nullable,
-                ordinal_position
-            FROM "{database}".information_schema.columns
-            WHERE table_schema NOT IN ('INFORMATION_SCHEMA')
-            ORDER BY table_schema, table_name, ordinal_position
+                c.table_catalog,
+                c.table_schema,
+                c.table_name,
+                t.table_type,
+                c.column_name,
+                {NUMBER_TYPE_EXPR} AS data_type,
+                c.is_nullable,
+                c.ordinal_position,
+                t.row_count,
+                t.bytes
+            FROM "{database}".information_schema.columns c
+            JOIN "{database}".information_schema.tables t
+                ON t.table_schema = c.table_schema AND t.table_name = c.table_name
+            WHERE c.table_schema NOT IN ('INFORMATION_SCHEMA')
+            ORDER BY c.table_schema, c.table_name, c.ordinal_position
             "#,
             database = database.replace('"', "\"\"")
         )
@@ -181,7 +198,7 @@ pub async fn discover_tables(
         let batch = convert_arrow_batch(&batch)?;
         let num_rows = batch.num_rows();
 
-        // Get columns by index (0-7 based on SELECT order)
+        // Get columns by index (0-9 based on SELECT order)
         let catalog_col = batch.column(0);
         let schema_col = batch.column(1);
         let table_col = batch.column(2);
@@ -190,11 +207,16 @@ pub async fn discover_tables(
         let data_type_col = batch.column(5);
         let nullable_col = batch.column(6);
         let ordinal_col = batch.column(7);
+        let row_count_col = batch.column(8);
+        let bytes_col = batch.column(9);
 
         for row in 0..num_rows {
             let catalog = get_string_value(catalog_col.as_ref(), row);
             let schema_name = get_string_value(schema_col.as_ref(), row).unwrap_or_default();
             let table_name = get_string_value(table_col.as_ref(), row).unwrap_or_default();
+            if !matcher.matches(&schema_name, &table_name) {
+                continue;
+            }
             let table_type = get_string_value(table_type_col.as_ref(), row)
                 .unwrap_or_else(|| "BASE TABLE".to_string());
             let col_name = get_string_value(col_name_col.as_ref(), row).unwrap_or_default();
@@ -204,6 +226,11 @@ pub async fn discover_tables(
                 .map(|s| s.to_uppercase() == "YES")
                 .unwrap_or(true);
             let ordinal = get_int_value(ordinal_col.as_ref(), row).unwrap_or(0) as i32;
+            // Views have no row count or size
+            let row_count =
+                get_int_value(row_count_col.as_ref(), row).and_then(|n| u64::try_from(n).ok());
+            let size_bytes =
+                get_int_value(bytes_col.as_ref(), row).and_then(|n| u64::try_from(n).ok());
 
             let column = ColumnMetadata {
                 name: col_name,
@@ -226,6 +253,8 @@ pub async fn discover_tables(
                     table_name,
                     table_type,
                     columns: vec![column],
+                    row_count,
+                    size_bytes,
                 });
             }
         }
@@ -279,7 +308,7 @@ pub async fn fetch_table(
             // Empty table - need to get schema from information_schema
             let schema_query = format!(
                 r#"
-                SELECT column_name, data_type, is_nullable
+                SELECT column_name, {NUMBER_TYPE_EXPR} AS data_type, is_nullable
                 FROM "{database}".information_schema.columns
                 WHERE table_schema = '{schema}' AND table_name = '{table}'
                 ORDER BY ordinal_position
@@ -306,7 +335,7 @@ pub async fn fetch_table(
 
             writer.init(&arrow_schema)?;
             let empty_batch = RecordBatch::new_empty(Arc::new(arrow_schema));
-            writer.write_batch(&empty_batch)?;
+            writer.write_batch(&empty_batch).await?;
             return Ok(());
         }
     };
@@ -315,13 +344,13 @@ pub async fn fetch_table(
     let mut initialized = false;
     for batch in batches {
         // Convert from snowflake-api's arrow 54 to datafusion's arrow 56
-        let converted_batch = convert_arrow_batch(&batch)?;
+        let converted_batch = fixed_point_to_decimal(convert_arrow_batch(&batch)?)?;
 
         if !initialized {
             writer.init(converted_batch.schema().as_ref())?;
             initialized = true;
         }
-        writer.write_batch(&converted_batch)?;
+        writer.write_batch(&converted_batch).await?;
     }
 
     Ok(())
@@ -360,6 +389,61 @@ fn convert_arrow_batch(
         .map_err(|e| DataFetchError::Query(format!("IPC read error: {}", e)))
 }
 
+/// Snowflake sends NUMBER columns as unscaled integers (of a width that can vary between
+/// chunks), with precision and scale in the field metadata. Rebuild them as Arrow decimals
+/// so values keep their scale and every batch has the same schema.
+fn fixed_point_to_decimal(batch: RecordBatch) -> Result<RecordBatch, DataFetchError> {
+    use datafusion::arrow::array::{Array, ArrayRef, AsArray};
+    use datafusion::arrow::compute::cast;
+    use datafusion::arrow::datatypes::Decimal128Type;
+
+    let schema = batch.schema();
+    let mut fields = Vec::with_capacity(schema.fields().len());
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(schema.fields().len());
+
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        let metadata = field.metadata();
+        let precision_scale = match metadata.get("logicalType").map(String::as_str) {
+            Some("FIXED") => metadata
+                .get("precision")
+                .zip(metadata.get("scale"))
+                .and_then(|(p, s)| Some((p.parse::<u8>().ok()?, s.parse::<i8>().ok()?))),
+            _ => None,
+        };
+
+        let Some((precision, scale)) = precision_scale.filter(|_| {
+            column.data_type().is_integer()
+                || matches!(column.data_type(), DataType::Decimal128(..))
+        }) else {
+            fields.push(field.as_ref().clone());
+            columns.push(column.clone());
+            continue;
+        };
+
+        // Scale 0 keeps the raw integer; the real scale is then applied without rescaling
+        let unscaled = cast(column, &DataType::Decimal128(38, 0))
+            .map_err(|e| DataFetchError::Query(format!("NUMBER conversion failed: {}", e)))?;
+        let decimal = unscaled
+            .as_primitive::<Decimal128Type>()
+            .clone()
+            .with_precision_and_scale(precision, scale)
+            .map_err(|e| DataFetchError::Query(format!("NUMBER conversion failed: {}", e)))?;
+
+        fields.push(
+            Field::new(
+                field.name(),
+                decimal.data_type().clone(),
+                field.is_nullable(),
+            )
+            .with_metadata(metadata.clone()),
+        );
+        columns.push(Arc::new(decimal));
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+        .map_err(|e| DataFetchError::Query(e.to_string()))
+}
+
 /// Convert Snowflake type string to Arrow DataType
 fn snowflake_type_to_arrow(sf_type: &str) -> DataType {
     let type_upper = sf_type.to_uppercase();
@@ -376,8 +460,10 @@ fn snowflake_type_to_arrow(sf_type: &str) -> DataType {
         "INT" | "INTEGER" => DataType::Int32,
         "BIGINT" => DataType::Int64,
 
-        // Numeric types - use Utf8 for precision preservation like Postgres
-        "NUMBER" | "DECIMAL" | "NUMERIC" => DataType::Utf8,
+        // Fixed-point types; NUMBER defaults to NUMBER(38,0)
+        "NUMBER" | "DECIMAL" | "NUMERIC" => parse_precision_scale(&type_upper)
+            .and_then(|(precision, scale)| decimal_type(precision, scale))
+            .unwrap_or(DataType::Decimal128(38, 0)),
 
         // Float types
         "FLOAT" | "FLOAT4" | "FLOAT8" | "DOUBLE" | "DOUBLE PRECISION" | "REAL" => DataType::Float64,
@@ -428,7 +514,7 @@ fn get_string_value(array: &dyn datafusion::arrow::array::Array, row: usize) ->
 
 /// Extract integer value from Arrow array
 fn get_int_value(array: &dyn datafusion::arrow::array::Array, row: usize) -> Option<i64> {
-    use datafusion::arrow::array::{Int16Array, Int32Array, Int64Array};
+    use datafusion::arrow::array::{Int16Array, Int32Array, Int64Array, Int8Array};
 
     if array.is_null(row) {
         return None;
@@ -443,6 +529,9 @@ fn get_int_value(array: &dyn datafusion::arrow::array::Array, row: usize) -> Opt
     if let Some(a) = array.as_any().downcast_ref::<Int16Array>() {
         return Some(a.value(row) as i64);
     }
+    if let Some(a) = array.as_any().downcast_ref::<Int8Array>() {
+        return Some(a.value(row) as i64);
+    }
 
     None
 }
@@ -537,13 +626,60 @@ mod tests {
 
     #[test]
     fn test_snowflake_type_to_arrow_numeric() {
-        // Numeric types fall back to Utf8 for precision preservation
-        assert!(matches!(snowflake_type_to_arrow("NUMBER"), DataType::Utf8));
-        assert!(matches!(
-            snowflake_type_to_arrow("NUMBER(38,0)"),
-            DataType::Utf8
-        ));
-        assert!(matches!(snowflake_type_to_arrow("DECIMAL"), DataType::Utf8));
+        assert_eq!(
+            snowflake_type_to_arrow("NUMBER"),
+            DataType::Decimal128(38, 0)
+        );
+        assert_eq!(
+            snowflake_type_to_arrow("NUMBER(10,2)"),
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            snowflake_type_to_arrow("DECIMAL(38,0)"),
+            DataType::Decimal128(38, 0)
+        );
+    }
+
+    #[test]
+    fn test_fixed_point_to_decimal() {
+        use datafusion::arrow::array::{Array, Decimal128Array, Int32Array, StringArray};
+        use std::collections::HashMap;
+
+        let fixed = |precision: &str, scale: &str| {
+            HashMap::from([
+                ("logicalType".to_string(), "FIXED".to_string()),
+                ("precision".to_string(), precision.to_string()),
+                ("scale".to_string(), scale.to_string()),
+            ])
+        };
+        let schema = Schema::new(vec![
+            Field::new("amount", DataType::Int32, true).with_metadata(fixed("10", "2")),
+            Field::new("id", DataType::Int32, false).with_metadata(fixed("38", "0")),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(12345), None])),
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+            ],
+        )
+        .unwrap();
+
+        let converted = fixed_point_to_decimal(batch).unwrap();
+        let schema = converted.schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Decimal128(10, 2));
+        assert_eq!(schema.field(1).data_type(), &DataType::Decimal128(38, 0));
+        assert_eq!(schema.field(2).data_type(), &DataType::Utf8);
+
+        let amounts = converted
+            .column(0)
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(amounts.value_as_string(0), "123.45");
+        assert!(amounts.is_null(1));
     }
 
     #[test]
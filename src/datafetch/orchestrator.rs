@@ -1,12 +1,29 @@
 use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+
 use super::native::StreamingParquetWriter;
-use super::{DataFetchError, DataFetcher, TableMetadata};
-use crate::catalog::CatalogManager;
+use super::reconcile::{cast_batch, needs_cast, reconcile_schemas};
+use super::statistics::CacheStatistics;
+use super::{
+    deserialize_arrow_schema, CachePolicy, DataFetchError, DataFetcher, SchemaMismatch,
+    SnapshotRefresh, TableMetadata, TimeTravel,
+};
+use crate::catalog::{
+    diff_schemas, is_breaking, record_schema_version, CatalogManager, SchemaChangePolicy,
+    SchemaVersionStatus, TableAccess, TableInfo, TableKind,
+};
 use crate::secrets::SecretManager;
 use crate::source::Source;
-use crate::storage::StorageManager;
+use crate::storage::{CacheUpload, CacheWriteHandle, StorageManager};
+
+/// How long the version fetched for a time travel query is kept. Queries still scanning it
+/// after that fail.
+pub const TIME_TRAVEL_RETENTION: chrono::TimeDelta = chrono::TimeDelta::hours(1);
 
 /// Orchestrates the full table fetch workflow: fetch from source → write to storage → update catalog.
 #[derive(Debug)]
@@ -15,8 +32,17 @@ pub struct FetchOrchestrator {
     storage: Arc<dyn StorageManager>,
     catalog: Arc<dyn CatalogManager>,
     secret_manager: Arc<SecretManager>,
+    /// Overrides of `CachePolicy::default_for` per table kind.
+    cache_policies: HashMap<TableKind, CachePolicy>,
+    /// Per-table locks serialising writes to a table's cache, keyed by
+    /// (connection_id, schema, table).
+    table_locks: TableLocks,
+    /// Table scans not yet written to the catalog, by table ID.
+    table_accesses: std::sync::Mutex<HashMap<i32, TableAccess>>,
 }
 
+type TableLocks = std::sync::Mutex<HashMap<(i32, String, String), Arc<tokio::sync::Mutex<()>>>>;
+
 impl FetchOrchestrator {
     pub fn new(
         fetcher: Arc<dyn DataFetcher>,
@@ -29,7 +55,95 @@ impl FetchOrchestrator {
             storage,
             catalog,
             secret_manager,
+            cache_policies: HashMap::new(),
+            table_locks: Default::default(),
+            table_accesses: Default::default(),
+        }
+    }
+
+    /// Override the cache policy for one table kind.
+    pub fn with_cache_policy(mut self, kind: TableKind, policy: CachePolicy) -> Self {
+        self.cache_policies.insert(kind, policy);
+        self
+    }
+
+    /// Cache policy that applies to tables of this kind.
+    pub fn cache_policy(&self, kind: TableKind) -> CachePolicy {
+        self.cache_policies
+            .get(&kind)
+            .copied()
+            .unwrap_or_else(|| CachePolicy::default_for(kind))
+    }
+
+    /// Note that a query scanned a table. Scans are collected in memory and written to the
+    /// catalog in batches by [`flush_table_accesses`](Self::flush_table_accesses), so
+    /// queries don't wait on a catalog write.
+    pub fn record_table_access(&self, table_id: i32) {
+        let now = chrono::Utc::now();
+        self.table_accesses
+            .lock()
+            .unwrap()
+            .entry(table_id)
+            .and_modify(|access| {
+                access.count += 1;
+                access.last_accessed_at = now;
+            })
+            .or_insert(TableAccess {
+                table_id,
+                count: 1,
+                last_accessed_at: now,
+            });
+    }
+
+    /// Write the scans collected since the last flush to the catalog. If that fails they
+    /// are kept for the next flush.
+    pub async fn flush_table_accesses(&self) -> Result<()> {
+        let accesses: Vec<TableAccess> = std::mem::take(&mut *self.table_accesses.lock().unwrap())
+            .into_values()
+            .collect();
+        if accesses.is_empty() {
+            return Ok(());
+        }
+
+        if let Err(e) = self.catalog.record_table_accesses(&accesses).await {
+            let mut pending = self.table_accesses.lock().unwrap();
+            for access in accesses {
+                pending
+                    .entry(access.table_id)
+                    .and_modify(|newer| {
+                        newer.count += access.count;
+                        newer.last_accessed_at =
+                            newer.last_accessed_at.max(access.last_accessed_at);
+                    })
+                    .or_insert(access);
+            }
+            return Err(e);
         }
+        Ok(())
+    }
+
+    /// Wait for exclusive access to a table's cache. Writes that hold it see each other's
+    /// catalog updates, so appends resume where the previous one stopped.
+    async fn lock_table(
+        &self,
+        connection_id: i32,
+        schema_name: &str,
+        table_name: &str,
+    ) -> tokio::sync::OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.table_locks.lock().unwrap();
+            // Forget locks that nobody holds or waits for
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks
+                .entry((
+                    connection_id,
+                    schema_name.to_string(),
+                    table_name.to_string(),
+                ))
+                .or_default()
+                .clone()
+        };
+        lock.lock_owned().await
     }
 
     /// Fetch table data from source, write to cache storage, and update catalog metadata.
@@ -42,32 +156,110 @@ impl FetchOrchestrator {
         schema_name: &str,
         table_name: &str,
     ) -> Result<(String, usize)> {
+        let _lock = self
+            .lock_table(connection_id, schema_name, table_name)
+            .await;
+        self.cache_table_locked(source, connection_id, schema_name, table_name)
+            .await
+    }
+
+    /// Cache the table unless it is cached already, and return its cached path. Callers
+    /// racing to cache the same table wait for the first one and reuse its version instead
+    /// of writing their own.
+    pub async fn ensure_cached(
+        &self,
+        source: &Source,
+        connection_id: i32,
+        schema_name: &str,
+        table_name: &str,
+    ) -> Result<String> {
+        let _lock = self
+            .lock_table(connection_id, schema_name, table_name)
+            .await;
+
+        let cached = self
+            .catalog
+            .get_table(connection_id, schema_name, table_name)
+            .await?
+            .and_then(|info| info.parquet_path);
+        if let Some(path) = cached {
+            return Ok(path);
+        }
+
+        self.cache_table_locked(source, connection_id, schema_name, table_name)
+            .await
+            .map(|(url, _)| url)
+    }
+
+    /// Body of [`cache_table`](Self::cache_table), run with the table lock held.
+    async fn cache_table_locked(
+        &self,
+        source: &Source,
+        connection_id: i32,
+        schema_name: &str,
+        table_name: &str,
+    ) -> Result<(String, usize)> {
+        if source.is_streaming() {
+            return self
+                .append_table(source, connection_id, schema_name, table_name)
+                .await
+                .map(|(url, _, row_count)| (url, row_count));
+        }
+
+        let info = self
+            .catalog
+            .get_table(connection_id, schema_name, table_name)
+            .await
+            .ok()
+            .flatten();
+
+        if let Some(info) = &info {
+            if self.cache_policy(info.kind()) == CachePolicy::Live {
+                return Err(reject_live(info));
+            }
+        }
+        // Taken before the fetch, so a source refresh during the fetch is picked up next time
+        let refreshed_at = match &info {
+            Some(info) => self.source_refreshed_at(source, info).await?,
+            None => None,
+        };
+
         // Prepare cache write location
-        let handle = self
+        let mut handle = self
             .storage
             .prepare_cache_write(connection_id, schema_name, table_name);
+        self.begin_upload(&mut handle).await;
+
+        // Snapshot sources pin the fetch to one snapshot so the catalog can record it
+        let plan = if source.has_snapshots() {
+            Some(
+                self.plan_snapshot_refresh(source, schema_name, table_name, None)
+                    .await?,
+            )
+        } else {
+            None
+        };
 
         // Create writer
-        let mut writer = StreamingParquetWriter::new(handle.local_path.clone());
+        let mut writer = cache_writer(&handle, info.as_ref());
 
         // Fetch the table data into writer
-        self.fetcher
-            .fetch_table(
-                source,
-                &self.secret_manager,
-                None, // catalog
-                schema_name,
-                table_name,
-                &mut writer,
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to fetch table: {}", e))?;
+        self.fetch_into(source, schema_name, table_name, plan.as_ref(), &mut writer)
+            .await?;
 
         // Close writer and get row count
+        let mismatch = writer.take_schema_mismatch();
         let (_, row_count) = writer
             .close()
+            .await
             .map_err(|e| anyhow::anyhow!("Failed to close writer: {}", e))?;
 
+        if let (Some(info), Some(mismatch)) = (&info, mismatch) {
+            self.adopt_source_schema(source, info, mismatch, &handle.local_path)
+                .await?;
+        }
+        let stats = written_file_statistics(&handle);
+
         // Finalize cache write (uploads to S3 if needed, returns URL)
         let parquet_url = self
             .storage
@@ -75,13 +267,40 @@ impl FetchOrchestrator {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to finalize cache write: {}", e))?;
 
-        // Update catalog with new path
-        if let Ok(Some(info)) = self
-            .catalog
-            .get_table(connection_id, schema_name, table_name)
-            .await
-        {
-            let _ = self.catalog.update_table_sync(info.id, &parquet_url).await;
+        // Update catalog with new path, removing the new version if that fails
+        if let Some(info) = info {
+            let catalog_result = match plan {
+                Some(plan) => {
+                    self.commit_snapshot(info.id, None, &plan, &parquet_url)
+                        .await
+                }
+                None => self.catalog.update_table_sync(info.id, &parquet_url).await,
+            };
+            if let Err(e) = catalog_result {
+                if let Err(cleanup_err) = self.storage.delete_prefix(&parquet_url).await {
+                    tracing::warn!(
+                        "Failed to clean up orphaned directory {} after catalog update failure: {}",
+                        parquet_url,
+                        cleanup_err
+                    );
+                }
+                return Err(anyhow::anyhow!("Failed to update catalog: {}", e));
+            }
+            if refreshed_at.is_some() {
+                if let Err(e) = self
+                    .catalog
+                    .update_table_snapshot_id(info.id, refreshed_at)
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to record source refresh for {}: {}",
+                        qualified_name(&info),
+                        e
+                    );
+                }
+            }
+            self.record_cache_stats(&info, &parquet_url, stats, false)
+                .await;
         }
 
         Ok((parquet_url, row_count))
@@ -111,6 +330,10 @@ impl FetchOrchestrator {
         schema_name: &str,
         table_name: &str,
     ) -> Result<(String, Option<String>, usize)> {
+        let _lock = self
+            .lock_table(connection_id, schema_name, table_name)
+            .await;
+
         // 1. Verify table exists in catalog before doing any expensive I/O.
         // This catches typos early and avoids wasted fetches.
         let old_info = self
@@ -125,30 +348,75 @@ impl FetchOrchestrator {
         })?;
         let old_path = old_info.parquet_path.clone();
 
+        if self.cache_policy(old_info.kind()) == CachePolicy::Live {
+            return Err(reject_live(&old_info));
+        }
+
+        // Streaming tables append the records that arrived since the last fetch
+        if source.is_streaming() {
+            return self
+                .append_table(source, connection_id, schema_name, table_name)
+                .await;
+        }
+
+        // Snapshot tables skip unchanged snapshots and append only new data files.
+        // The recorded snapshot is only meaningful while its cached files exist.
+        let cached_snapshot = match (source.has_snapshots(), &old_path) {
+            (true, Some(_)) => self.catalog.get_table_snapshot_id(old_info.id).await?,
+            _ => None,
+        };
+        let plan = if source.has_snapshots() {
+            let plan = self
+                .plan_snapshot_refresh(source, schema_name, table_name, cached_snapshot)
+                .await?;
+            match (&plan, &old_path) {
+                (SnapshotRefresh::Unchanged { .. }, Some(path)) => {
+                    return Ok((path.clone(), None, 0));
+                }
+                (SnapshotRefresh::Append { .. }, Some(_)) => {
+                    return self
+                        .append_snapshot(source, &old_info, cached_snapshot, &plan)
+                        .await;
+                }
+                _ => Some(plan),
+            }
+        } else {
+            None
+        };
+
+        // Tables refreshed by their source (materialized views) skip refreshes the source
+        // has not reported since the cached copy. The marker shares the snapshot column.
+        let refreshed_at = self.source_refreshed_at(source, &old_info).await?;
+        if let (Some(refreshed_at), Some(path)) = (refreshed_at, &old_path) {
+            if self.catalog.get_table_snapshot_id(old_info.id).await? == Some(refreshed_at) {
+                return Ok((path.clone(), None, 0));
+            }
+        }
+
         // 2. Prepare cache write (generates versioned path)
-        let handle = self
+        let mut handle = self
             .storage
             .prepare_cache_write(connection_id, schema_name, table_name);
+        self.begin_upload(&mut handle).await;
 
-        // 3. Fetch and write to new path
-        let mut writer = StreamingParquetWriter::new(handle.local_path.clone());
-        self.fetcher
-            .fetch_table(
-                source,
-                &self.secret_manager,
-                None,
-                schema_name,
-                table_name,
-                &mut writer,
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to fetch table: {}", e))?;
+        // 3. Fetch and write to new path, cast to the catalog schema
+        let mut writer = cache_writer(&handle, Some(&old_info));
+        self.fetch_into(source, schema_name, table_name, plan.as_ref(), &mut writer)
+            .await?;
 
         // 4. Close writer and get row count
+        let mismatch = writer.take_schema_mismatch();
         let (_, row_count) = writer
             .close()
+            .await
             .map_err(|e| anyhow::anyhow!("Failed to close writer: {}", e))?;
 
+        if let Some(mismatch) = mismatch {
+            self.adopt_source_schema(source, &old_info, mismatch, &handle.local_path)
+                .await?;
+        }
+        let stats = written_file_statistics(&handle);
+
         // 5. Finalize (upload to S3 if needed)
         let new_url = self
             .storage
@@ -159,9 +427,16 @@ impl FetchOrchestrator {
         // 6. Atomic catalog update with cleanup on failure
         // If this fails after finalize_cache_write succeeds, we have orphaned files.
         // Clean them up to prevent storage leaks.
-        let catalog_result = self
-            .refresh_table_catalog_update(connection_id, schema_name, table_name, &new_url)
-            .await;
+        let catalog_result = match &plan {
+            Some(plan) => self
+                .commit_snapshot(old_info.id, cached_snapshot, plan, &new_url)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to update catalog: {}", e)),
+            None => {
+                self.refresh_table_catalog_update(connection_id, schema_name, table_name, &new_url)
+                    .await
+            }
+        };
 
         if let Err(e) = catalog_result {
             // Clean up orphaned versioned directory - delete the newly written data
@@ -175,122 +450,1110 @@ impl FetchOrchestrator {
             return Err(e);
         }
 
+        self.record_cache_stats(&old_info, &new_url, stats, false)
+            .await;
+
+        // A missing marker only costs one unnecessary refetch
+        if refreshed_at.is_some() {
+            if let Err(e) = self
+                .catalog
+                .update_table_snapshot_id(old_info.id, refreshed_at)
+                .await
+            {
+                tracing::warn!(
+                    "Failed to record source refresh for {}: {}",
+                    qualified_name(&old_info),
+                    e
+                );
+            }
+        }
+
         Ok((new_url, old_path, row_count))
     }
 
-    /// Helper to perform catalog update for refresh_table.
-    /// Separated to allow cleanup on failure.
-    async fn refresh_table_catalog_update(
+    /// Fetch new records for a streaming table, resuming from the offsets stored in
+    /// the catalog, and append them as a new parquet file.
+    ///
+    /// The first fetch creates a versioned directory like a regular cache write. Written
+    /// versions never change, so later fetches write a new version holding the previous
+    /// version's files plus one file for the micro-batch. The caller holds the table lock,
+    /// so concurrent appends cannot read the same offsets. Offsets are persisted before
+    /// `last_sync` is bumped; if either catalog update fails the new version is removed.
+    /// Returns (url, replaced_url, rows_appended).
+    async fn append_table(
         &self,
+        source: &Source,
         connection_id: i32,
         schema_name: &str,
         table_name: &str,
-        new_url: &str,
-    ) -> Result<()> {
+    ) -> Result<(String, Option<String>, usize)> {
         let info = self
             .catalog
             .get_table(connection_id, schema_name, table_name)
             .await?
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Table {}.{} not found during refresh",
-                    schema_name,
-                    table_name
-                )
+            .ok_or_else(|| DataFetchError::TableNotFound {
+                connection_id,
+                schema: schema_name.to_string(),
+                table: table_name.to_string(),
             })?;
+        let offsets = self.catalog.get_table_offsets(info.id).await?;
 
-        self.catalog
-            .update_table_sync(info.id, new_url)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to update catalog: {}", e))
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::catalog::{
-        CatalogManager, ConnectionInfo, OptimisticLock, PendingDeletion, TableInfo,
-    };
-    use crate::datafetch::{ColumnMetadata, DataFetchError, DataFetcher, TableMetadata};
-    use crate::secrets::{SecretMetadata, SecretStatus};
-    use crate::storage::{CacheWriteHandle, StorageManager};
-    use async_trait::async_trait;
-    use chrono::{DateTime, Utc};
-    use datafusion::arrow::datatypes::DataType as ArrowDataType;
-    use datafusion::prelude::SessionContext;
-    use std::collections::HashMap;
-    use std::path::PathBuf;
-    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-    use std::sync::Mutex;
+        let base_version = info.parquet_path.as_deref().and_then(cached_version);
+        let mut handle = match base_version {
+            Some(version) => {
+                self.storage
+                    .prepare_append_write(connection_id, schema_name, table_name, version)
+            }
+            None => self
+                .storage
+                .prepare_cache_write(connection_id, schema_name, table_name),
+        };
+        self.begin_upload(&mut handle).await;
 
-    /// Mock fetcher that produces minimal parquet data
-    #[derive(Debug)]
-    struct MockFetcher;
+        let mut writer = cache_writer(&handle, Some(&info));
+        let new_offsets = self
+            .fetcher
+            .fetch_table_since(
+                source,
+                &self.secret_manager,
+                schema_name,
+                table_name,
+                &offsets,
+                &mut writer,
+            )
+            .await
+            .map_err(fetch_error)?;
 
-    #[async_trait]
-    impl DataFetcher for MockFetcher {
-        async fn discover_tables(
-            &self,
-            _source: &Source,
-            _secret_manager: &SecretManager,
-        ) -> Result<Vec<TableMetadata>, DataFetchError> {
-            Ok(vec![TableMetadata {
-                catalog_name: None,
-                schema_name: "test".to_string(),
-                table_name: "orders".to_string(),
-                table_type: "BASE TABLE".to_string(),
-                columns: vec![ColumnMetadata {
-                    name: "id".to_string(),
-                    data_type: ArrowDataType::Int32,
-                    nullable: false,
-                    ordinal_position: 0,
-                }],
-            }])
+        let mismatch = writer.take_schema_mismatch();
+        let (_, row_count) = writer
+            .close()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to close writer: {}", e))?;
+        if let Some(mismatch) = mismatch {
+            return Err(reject_append(&info, mismatch, &handle.local_path));
         }
 
-        async fn fetch_table(
-            &self,
-            _source: &Source,
-            _secret_manager: &SecretManager,
-            _catalog: Option<&str>,
-            _schema: &str,
-            _table: &str,
-            writer: &mut super::StreamingParquetWriter,
-        ) -> Result<(), DataFetchError> {
-            use datafusion::arrow::array::Int32Array;
-            use datafusion::arrow::datatypes::{DataType, Field, Schema};
-            use datafusion::arrow::record_batch::RecordBatch;
-
-            let schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        // Nothing new on the stream: keep the existing version and skip the empty batch.
+        if let (0, Some(path)) = (row_count, &info.parquet_path) {
+            discard_write(&handle);
+            self.catalog
+                .update_table_offsets(info.id, &new_offsets)
+                .await?;
+            return Ok((path.clone(), None, 0));
+        }
 
-            // Initialize the writer with the schema before writing
-            writer.init(&schema)?;
+        let stats = written_file_statistics(&handle);
+        let url = self
+            .storage
+            .finalize_cache_write(&handle)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to finalize cache write: {}", e))?;
 
-            let batch = RecordBatch::try_new(
-                Arc::new(schema),
-                vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
-            )
-            .map_err(|e| DataFetchError::Query(e.to_string()))?;
+        let catalog_result = match self
+            .catalog
+            .update_table_offsets(info.id, &new_offsets)
+            .await
+        {
+            Ok(()) => {
+                let sync_result = self.catalog.update_table_sync(info.id, &url).await;
+                if sync_result.is_err() {
+                    // Roll offsets back so the removed batch is fetched again next time
+                    if let Err(e) = self.catalog.update_table_offsets(info.id, &offsets).await {
+                        tracing::warn!(
+                            "Failed to restore offsets for {}.{}: {}",
+                            schema_name,
+                            table_name,
+                            e
+                        );
+                    }
+                }
+                sync_result
+            }
+            Err(e) => Err(e),
+        };
 
-            writer.write_batch(&batch)?;
-            Ok(())
+        if let Err(e) = catalog_result {
+            if let Err(cleanup_err) = self.storage.delete_prefix(&url).await {
+                tracing::warn!(
+                    "Failed to clean up orphaned directory {} after catalog update failure: {}",
+                    url,
+                    cleanup_err
+                );
+            }
+            return Err(anyhow::anyhow!("Failed to update catalog: {}", e));
         }
-    }
 
-    /// Mock storage that tracks file operations
-    #[derive(Debug)]
-    struct MockStorage {
-        base_path: PathBuf,
-        deleted_urls: Mutex<Vec<String>>,
-        version_counter: AtomicUsize,
+        self.record_cache_stats(&info, &url, stats, base_version.is_some())
+            .await;
+
+        Ok((url, info.parquet_path, row_count))
     }
 
-    impl MockStorage {
-        fn new(base_path: PathBuf) -> Self {
-            Self {
-                base_path,
-                deleted_urls: Mutex::new(Vec::new()),
+    /// Fetch only the data files added since the cached snapshot and append them as a new
+    /// parquet file. Like streaming appends, this writes a new version holding the current
+    /// version's files plus the new one. The caller holds the table lock.
+    /// Returns (url, replaced_url, rows_appended).
+    async fn append_snapshot(
+        &self,
+        source: &Source,
+        info: &TableInfo,
+        cached_snapshot: Option<i64>,
+        plan: &SnapshotRefresh,
+    ) -> Result<(String, Option<String>, usize)> {
+        let path = info
+            .parquet_path
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Cannot append to a table that is not cached"))?;
+        let version =
+            cached_version(path).ok_or_else(|| anyhow::anyhow!("Invalid cache path: {}", path))?;
+
+        let mut handle = self.storage.prepare_append_write(
+            info.connection_id,
+            &info.schema_name,
+            &info.table_name,
+            version,
+        );
+        self.begin_upload(&mut handle).await;
+        let mut writer = cache_writer(&handle, Some(info));
+        self.fetch_into(
+            source,
+            &info.schema_name,
+            &info.table_name,
+            Some(plan),
+            &mut writer,
+        )
+        .await?;
+
+        let mismatch = writer.take_schema_mismatch();
+        let (_, row_count) = writer
+            .close()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to close writer: {}", e))?;
+        if let Some(mismatch) = mismatch {
+            return Err(reject_append(info, mismatch, &handle.local_path));
+        }
+
+        // Appends that added no rows only need the new snapshot recorded
+        if row_count == 0 {
+            discard_write(&handle);
+            self.catalog
+                .update_table_snapshot_id(info.id, plan.snapshot_id())
+                .await?;
+            return Ok((path.to_string(), None, 0));
+        }
+
+        let stats = written_file_statistics(&handle);
+        let url = self
+            .storage
+            .finalize_cache_write(&handle)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to finalize cache write: {}", e))?;
+
+        if let Err(e) = self
+            .commit_snapshot(info.id, cached_snapshot, plan, &url)
+            .await
+        {
+            if let Err(cleanup_err) = self.storage.delete_prefix(&url).await {
+                tracing::warn!(
+                    "Failed to clean up orphaned directory {} after catalog update failure: {}",
+                    url,
+                    cleanup_err
+                );
+            }
+            return Err(anyhow::anyhow!("Failed to update catalog: {}", e));
+        }
+
+        self.record_cache_stats(info, &url, stats, true).await;
+
+        Ok((url, Some(path.to_string()), row_count))
+    }
+
+    /// Stream the handle's file straight to storage when the backend supports it. Failing
+    /// to start the upload falls back to writing a local temp file.
+    async fn begin_upload(&self, handle: &mut CacheWriteHandle) {
+        match self.storage.begin_cache_upload(handle).await {
+            Ok(upload) => handle.upload = upload,
+            Err(e) => tracing::warn!(
+                "Failed to start upload for {}.{}, writing locally: {}",
+                handle.schema,
+                handle.table,
+                e
+            ),
+        }
+    }
+
+    /// Store the statistics of a table's cache after a successful write. `info` is the
+    /// table as it was before the write. Appends merge the new file into the statistics of
+    /// the version they carried over; if those are unknown, so are the merged ones.
+    async fn record_cache_stats(
+        &self,
+        info: &TableInfo,
+        url: &str,
+        file_stats: Option<CacheStatistics>,
+        appended: bool,
+    ) {
+        let stats = match (file_stats, appended) {
+            (Some(stats), false) => Some(stats.with_url(url)),
+            (Some(stats), true) => info
+                .cache_stats_json
+                .as_deref()
+                .and_then(|json| serde_json::from_str::<CacheStatistics>(json).ok())
+                .filter(|existing| info.parquet_path.as_deref() == Some(existing.url.as_str()))
+                .zip(catalog_schema(info))
+                .map(|(existing, schema)| existing.merge(stats.with_url(url), &schema)),
+            (None, _) => None,
+        };
+        let json = stats.and_then(|stats| serde_json::to_string(&stats).ok());
+
+        if let Err(e) = self
+            .catalog
+            .update_table_cache_stats(info.id, json.as_deref())
+            .await
+        {
+            tracing::warn!(
+                "Failed to record cache statistics for {}: {}",
+                qualified_name(info),
+                e
+            );
+        }
+    }
+
+    /// Record the snapshot a refresh cached, then point the table at the new data.
+    /// If the sync update fails the previous snapshot ID is restored, so the next refresh
+    /// does not mistake the stale cache for the new snapshot.
+    async fn commit_snapshot(
+        &self,
+        table_id: i32,
+        previous_snapshot: Option<i64>,
+        plan: &SnapshotRefresh,
+        url: &str,
+    ) -> Result<()> {
+        self.catalog
+            .update_table_snapshot_id(table_id, plan.snapshot_id())
+            .await?;
+
+        let sync_result = self.catalog.update_table_sync(table_id, url).await;
+        if sync_result.is_err() {
+            if let Err(e) = self
+                .catalog
+                .update_table_snapshot_id(table_id, previous_snapshot)
+                .await
+            {
+                tracing::warn!("Failed to restore snapshot for table {}: {}", table_id, e);
+            }
+        }
+        sync_result
+    }
+
+    /// The source's last refresh of a table cached with `OnSourceRefresh`; None for other
+    /// tables and for sources that do not report refreshes.
+    async fn source_refreshed_at(&self, source: &Source, info: &TableInfo) -> Result<Option<i64>> {
+        if source.has_snapshots() || self.cache_policy(info.kind()) != CachePolicy::OnSourceRefresh
+        {
+            return Ok(None);
+        }
+        self.fetcher
+            .source_refreshed_at(
+                source,
+                &self.secret_manager,
+                &info.schema_name,
+                &info.table_name,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to check source refresh: {}", e))
+    }
+
+    async fn plan_snapshot_refresh(
+        &self,
+        source: &Source,
+        schema_name: &str,
+        table_name: &str,
+        cached_snapshot: Option<i64>,
+    ) -> Result<SnapshotRefresh> {
+        self.fetcher
+            .plan_snapshot_refresh(
+                source,
+                &self.secret_manager,
+                schema_name,
+                table_name,
+                cached_snapshot,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to check table snapshot: {}", e))
+    }
+
+    /// Fetch table data into the writer, pinned to the planned snapshot when there is one.
+    async fn fetch_into(
+        &self,
+        source: &Source,
+        schema_name: &str,
+        table_name: &str,
+        plan: Option<&SnapshotRefresh>,
+        writer: &mut StreamingParquetWriter,
+    ) -> Result<()> {
+        let result = match plan {
+            Some(plan) => {
+                self.fetcher
+                    .fetch_table_snapshot(
+                        source,
+                        &self.secret_manager,
+                        schema_name,
+                        table_name,
+                        plan,
+                        writer,
+                    )
+                    .await
+            }
+            None => {
+                self.fetcher
+                    .fetch_table(
+                        source,
+                        &self.secret_manager,
+                        None, // catalog
+                        schema_name,
+                        table_name,
+                        writer,
+                    )
+                    .await
+            }
+        };
+        result.map_err(fetch_error)
+    }
+
+    /// Resolve a fetch whose schema does not reconcile with the catalog. If the source's
+    /// current schema matches what was fetched, the table changed at the source: the file is
+    /// cast to the rediscovered schema and the catalog adopts it. Otherwise the fetcher and
+    /// discovery disagree, the file is removed and a `SchemaMismatch` error returned.
+    /// Breaking changes are refused when the connection's policy blocks them; either way the
+    /// change is recorded in the table's schema history.
+    async fn adopt_source_schema(
+        &self,
+        source: &Source,
+        info: &TableInfo,
+        mismatch: SchemaMismatch,
+        local_path: &Path,
+    ) -> Result<()> {
+        let discovered = match self
+            .fetcher
+            .discover_table(
+                source,
+                &self.secret_manager,
+                &info.schema_name,
+                &info.table_name,
+            )
+            .await
+        {
+            Ok(table) => table.map(|t| t.to_arrow_schema()),
+            Err(e) => {
+                remove_local_file(local_path);
+                return Err(e.into());
+            }
+        };
+
+        let adopted = match discovered {
+            Some(schema) => match reconcile_schemas(&mismatch.fetched, &schema) {
+                Ok(()) if needs_cast(&mismatch.fetched, &schema) => {
+                    cast_parquet_file(local_path, &schema)
+                        .await
+                        .map(|()| schema)
+                }
+                Ok(()) => Ok(schema),
+                Err(mismatch) => Err(mismatch.to_string()),
+            },
+            None => Err(format!("{} (table no longer discovered)", mismatch)),
+        };
+
+        let schema = match adopted {
+            Ok(schema) => schema,
+            Err(details) => {
+                remove_local_file(local_path);
+                return Err(DataFetchError::SchemaMismatch {
+                    table: qualified_name(info),
+                    details,
+                }
+                .into());
+            }
+        };
+
+        let schema_json = serde_json::to_string(&schema)?;
+        let changes = info
+            .arrow_schema_json
+            .as_deref()
+            .and_then(|json| deserialize_arrow_schema(json).ok())
+            .map(|old| diff_schemas(&old, &schema))
+            .unwrap_or_default();
+
+        if is_breaking(&changes)
+            && self
+                .catalog
+                .get_schema_change_policy(info.connection_id)
+                .await?
+                == SchemaChangePolicy::Block
+        {
+            remove_local_file(local_path);
+            record_schema_version(
+                self.catalog.as_ref(),
+                info,
+                &schema_json,
+                &changes,
+                SchemaVersionStatus::Blocked,
+            )
+            .await?;
+            return Err(DataFetchError::SchemaMismatch {
+                table: qualified_name(info),
+                details: format!(
+                    "{} (breaking change blocked by the connection's schema change policy)",
+                    mismatch
+                ),
+            }
+            .into());
+        }
+
+        tracing::info!(
+            "Schema of {} changed at the source, updating catalog schema",
+            qualified_name(info)
+        );
+        self.catalog
+            .add_table(
+                info.connection_id,
+                &info.schema_name,
+                &info.table_name,
+                &schema_json,
+            )
+            .await?;
+        record_schema_version(
+            self.catalog.as_ref(),
+            info,
+            &schema_json,
+            &changes,
+            SchemaVersionStatus::Applied,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch a table as of an earlier snapshot or point in time into a cache version of its
+    /// own, for time travel queries to scan. The version is never recorded in the catalog and
+    /// is deleted after [`TIME_TRAVEL_RETENTION`]. Returns its URL and schema.
+    pub async fn cache_table_as_of(
+        &self,
+        source: &Source,
+        connection_id: i32,
+        schema_name: &str,
+        table_name: &str,
+        as_of: &TimeTravel,
+    ) -> Result<(String, SchemaRef)> {
+        let mut handle = self
+            .storage
+            .prepare_cache_write(connection_id, schema_name, table_name);
+        self.begin_upload(&mut handle).await;
+
+        let mut writer = cache_writer(&handle, None);
+        self.fetcher
+            .fetch_table_as_of(
+                source,
+                &self.secret_manager,
+                schema_name,
+                table_name,
+                as_of,
+                &mut writer,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch table: {}", e))?;
+        let schema = writer.schema().cloned().ok_or_else(|| {
+            anyhow::anyhow!("Fetch of {}.{} wrote no data", schema_name, table_name)
+        })?;
+        writer
+            .close()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to close writer: {}", e))?;
+
+        let url = self
+            .storage
+            .finalize_cache_write(&handle)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to finalize cache write: {}", e))?;
+        let delete_after = chrono::Utc::now() + TIME_TRAVEL_RETENTION;
+        if let Err(e) = self
+            .catalog
+            .schedule_file_deletion(&url, delete_after)
+            .await
+        {
+            tracing::warn!("Failed to schedule deletion of {}: {}", url, e);
+        }
+
+        Ok((url, schema))
+    }
+
+    /// Read a table straight from its source without caching it, for tables whose cache
+    /// policy is `Live`. Batches are cast to the catalog schema and sent to `sender` as they
+    /// are fetched; a source schema that no longer fits it is a `SchemaMismatch` until schema
+    /// refresh picks up the change.
+    pub async fn read_table_live(
+        &self,
+        source: &Source,
+        info: &TableInfo,
+        sender: tokio::sync::mpsc::Sender<RecordBatch>,
+    ) -> Result<()> {
+        let mut writer = writer_for(PathBuf::new(), Some(info)).with_sender(sender);
+        let result = self
+            .fetch_into(
+                source,
+                &info.schema_name,
+                &info.table_name,
+                None,
+                &mut writer,
+            )
+            .await;
+        if let Some(mismatch) = writer.take_schema_mismatch() {
+            return Err(DataFetchError::SchemaMismatch {
+                table: qualified_name(info),
+                details: format!(
+                    "{} (refresh the connection's schema to pick it up)",
+                    mismatch
+                ),
+            }
+            .into());
+        }
+        result?;
+        writer
+            .close()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to close writer: {}", e))?;
+        Ok(())
+    }
+
+    /// Helper to perform catalog update for refresh_table.
+    /// Separated to allow cleanup on failure.
+    async fn refresh_table_catalog_update(
+        &self,
+        connection_id: i32,
+        schema_name: &str,
+        table_name: &str,
+        new_url: &str,
+    ) -> Result<()> {
+        let info = self
+            .catalog
+            .get_table(connection_id, schema_name, table_name)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Table {}.{} not found during refresh",
+                    schema_name,
+                    table_name
+                )
+            })?;
+
+        self.catalog
+            .update_table_sync(info.id, new_url)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to update catalog: {}", e))
+    }
+}
+
+/// Writer for a table's cache file, reconciling with the catalog schema when there is one.
+fn writer_for(path: PathBuf, info: Option<&TableInfo>) -> StreamingParquetWriter {
+    let writer = StreamingParquetWriter::new(path);
+    let expected = info.and_then(|info| {
+        let json = info.arrow_schema_json.as_deref()?;
+        deserialize_arrow_schema(json)
+            .inspect_err(|e| {
+                tracing::warn!(
+                    "Ignoring unreadable catalog schema for {}: {}",
+                    qualified_name(info),
+                    e
+                )
+            })
+            .ok()
+    });
+    match (info, expected) {
+        (Some(info), Some(schema)) => writer.with_expected_schema(qualified_name(info), schema),
+        _ => writer,
+    }
+}
+
+/// Writer for a cache write, streaming into the handle's upload when it has one.
+fn cache_writer(handle: &CacheWriteHandle, info: Option<&TableInfo>) -> StreamingParquetWriter {
+    let writer = writer_for(handle.local_path.clone(), info);
+    match &handle.upload {
+        Some(upload) => writer.with_upload(upload.clone()),
+        None => writer,
+    }
+}
+
+/// The upload a cache write streamed into, if it was not written locally instead.
+fn streamed_upload(handle: &CacheWriteHandle) -> Option<&CacheUpload> {
+    handle
+        .upload
+        .as_ref()
+        .filter(|upload| !upload.is_abandoned())
+}
+
+/// Statistics of a freshly written file, read from the footer kept while streaming or from
+/// the local file. Failing to read them only costs planning accuracy.
+fn written_file_statistics(handle: &CacheWriteHandle) -> Option<CacheStatistics> {
+    let stats = match streamed_upload(handle) {
+        Some(upload) => upload
+            .tail()
+            .ok_or_else(|| anyhow::anyhow!("footer was not kept"))
+            .and_then(|tail| CacheStatistics::from_parquet_footer(&tail)),
+        None => CacheStatistics::from_parquet_file(&handle.local_path),
+    };
+    stats
+        .inspect_err(|e| {
+            tracing::warn!(
+                "Failed to read statistics of {}: {}",
+                handle.local_path.display(),
+                e
+            )
+        })
+        .ok()
+}
+
+/// Drop a written file that will not be kept: abort its upload or remove the local file.
+fn discard_write(handle: &CacheWriteHandle) {
+    match streamed_upload(handle) {
+        Some(upload) => upload.abandon(),
+        None => remove_local_file(&handle.local_path),
+    }
+}
+
+fn catalog_schema(info: &TableInfo) -> Option<SchemaRef> {
+    deserialize_arrow_schema(info.arrow_schema_json.as_deref()?).ok()
+}
+
+/// Tables read live have nothing to cache or refresh.
+fn reject_live(info: &TableInfo) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{} is a {} and is read live from its source; it is not cached",
+        qualified_name(info),
+        info.kind().as_str().to_lowercase()
+    )
+}
+
+/// Keep schema mismatches typed so callers can tell them apart from fetch failures.
+fn fetch_error(e: DataFetchError) -> anyhow::Error {
+    match e {
+        DataFetchError::SchemaMismatch { .. } => e.into(),
+        e => anyhow::anyhow!("Failed to fetch table: {}", e),
+    }
+}
+
+/// Appends add files next to ones written with the catalog schema, so they cannot change
+/// it; a full refresh picks up the new schema.
+fn reject_append(info: &TableInfo, mismatch: SchemaMismatch, local_path: &Path) -> anyhow::Error {
+    remove_local_file(local_path);
+    DataFetchError::SchemaMismatch {
+        table: qualified_name(info),
+        details: format!("{} (appends cannot change the table schema)", mismatch),
+    }
+    .into()
+}
+
+/// Rewrite a local parquet file with its batches cast to `schema`. Errors describe why the
+/// data does not fit.
+async fn cast_parquet_file(path: &Path, schema: &SchemaRef) -> Result<(), String> {
+    use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let cast_path = path.with_extension("cast.parquet");
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .and_then(|b| b.build())
+        .map_err(|e| e.to_string())?;
+
+    let mut writer = StreamingParquetWriter::new(cast_path.clone());
+    writer.init(schema).map_err(|e| e.to_string())?;
+    for batch in reader {
+        let batch = cast_batch(&batch.map_err(|e| e.to_string())?, schema)?;
+        writer
+            .write_batch(&batch)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    writer.close().await.map_err(|e| e.to_string())?;
+
+    std::fs::rename(&cast_path, path).map_err(|e| e.to_string())
+}
+
+fn remove_local_file(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        tracing::warn!("Failed to remove {}: {}", path.display(), e);
+    }
+}
+
+fn qualified_name(info: &TableInfo) -> String {
+    format!("{}.{}", info.schema_name, info.table_name)
+}
+
+/// Version directory name of a cached table path (the last path segment).
+pub(crate) fn cached_version(parquet_path: &str) -> Option<&str> {
+    parquet_path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::{
+        CacheSnapshot, CatalogManager, ConnectionInfo, OptimisticLock, PartitionOffset,
+        PendingDeletion, SchemaChangePolicy, SchemaVersion, SchemaVersionStatus, SnapshotTable,
+        TableInfo,
+    };
+    use crate::datafetch::{ColumnMetadata, DataFetchError, DataFetcher, TableMetadata};
+    use crate::secrets::{SecretMetadata, SecretStatus};
+    use crate::storage::{CacheWriteHandle, StorageManager};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use datafusion::arrow::datatypes::{DataType as ArrowDataType, Field, Schema};
+    use datafusion::prelude::SessionContext;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// Mock fetcher that produces minimal parquet data
+    #[derive(Debug)]
+    struct MockFetcher;
+
+    #[async_trait]
+    impl DataFetcher for MockFetcher {
+        async fn discover_tables(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+        ) -> Result<Vec<TableMetadata>, DataFetchError> {
+            Ok(vec![TableMetadata {
+                catalog_name: None,
+                schema_name: "test".to_string(),
+                table_name: "orders".to_string(),
+                table_type: "BASE TABLE".to_string(),
+                columns: vec![ColumnMetadata {
+                    name: "id".to_string(),
+                    data_type: ArrowDataType::Int32,
+                    nullable: false,
+                    ordinal_position: 0,
+                }],
+                row_count: None,
+                size_bytes: None,
+            }])
+        }
+
+        async fn fetch_table(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+            _catalog: Option<&str>,
+            _schema: &str,
+            _table: &str,
+            writer: &mut super::StreamingParquetWriter,
+        ) -> Result<(), DataFetchError> {
+            use datafusion::arrow::array::Int32Array;
+            use datafusion::arrow::datatypes::{DataType, Field, Schema};
+            use datafusion::arrow::record_batch::RecordBatch;
+
+            let schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+
+            // Initialize the writer with the schema before writing
+            writer.init(&schema)?;
+
+            let batch = RecordBatch::try_new(
+                Arc::new(schema),
+                vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+            )
+            .map_err(|e| DataFetchError::Query(e.to_string()))?;
+
+            writer.write_batch(&batch).await?;
+            Ok(())
+        }
+    }
+
+    /// Mock fetcher whose discovery disagrees with the data it fetches
+    #[derive(Debug)]
+    struct DriftingFetcher;
+
+    #[async_trait]
+    impl DataFetcher for DriftingFetcher {
+        async fn discover_tables(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+        ) -> Result<Vec<TableMetadata>, DataFetchError> {
+            Ok(vec![TableMetadata {
+                catalog_name: None,
+                schema_name: "test".to_string(),
+                table_name: "orders".to_string(),
+                table_type: "BASE TABLE".to_string(),
+                columns: vec![ColumnMetadata {
+                    name: "id".to_string(),
+                    data_type: ArrowDataType::Utf8,
+                    nullable: true,
+                    ordinal_position: 0,
+                }],
+                row_count: None,
+                size_bytes: None,
+            }])
+        }
+
+        async fn fetch_table(
+            &self,
+            source: &Source,
+            secret_manager: &SecretManager,
+            catalog: Option<&str>,
+            schema: &str,
+            table: &str,
+            writer: &mut super::StreamingParquetWriter,
+        ) -> Result<(), DataFetchError> {
+            MockFetcher
+                .fetch_table(source, secret_manager, catalog, schema, table, writer)
+                .await
+        }
+    }
+
+    /// Mock streaming fetcher that emits two records per partition past the stored offsets
+    #[derive(Debug)]
+    struct MockStreamingFetcher;
+
+    #[async_trait]
+    impl DataFetcher for MockStreamingFetcher {
+        async fn discover_tables(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+        ) -> Result<Vec<TableMetadata>, DataFetchError> {
+            Ok(vec![])
+        }
+
+        async fn fetch_table(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+            _catalog: Option<&str>,
+            _schema: &str,
+            _table: &str,
+            _writer: &mut super::StreamingParquetWriter,
+        ) -> Result<(), DataFetchError> {
+            Err(DataFetchError::Query("full fetch on a stream".to_string()))
+        }
+
+        async fn fetch_table_since(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+            _schema: &str,
+            _table: &str,
+            offsets: &[PartitionOffset],
+            writer: &mut super::StreamingParquetWriter,
+        ) -> Result<Vec<PartitionOffset>, DataFetchError> {
+            use datafusion::arrow::array::Int64Array;
+            use datafusion::arrow::datatypes::{DataType, Field, Schema};
+            use datafusion::arrow::record_batch::RecordBatch;
+
+            let start = offsets
+                .iter()
+                .find(|o| o.partition_id == 0)
+                .map(|o| o.next_offset)
+                .unwrap_or(0);
+            // Let concurrent fetches interleave like a real broker round-trip would
+            tokio::task::yield_now().await;
+
+            let schema = Schema::new(vec![Field::new("_offset", DataType::Int64, false)]);
+            writer.init(&schema)?;
+            let batch = RecordBatch::try_new(
+                Arc::new(schema),
+                vec![Arc::new(Int64Array::from(vec![start, start + 1]))],
+            )
+            .map_err(|e| DataFetchError::Query(e.to_string()))?;
+            writer.write_batch(&batch).await?;
+
+            Ok(vec![PartitionOffset {
+                partition_id: 0,
+                next_offset: start + 2,
+            }])
+        }
+    }
+
+    /// Mock fetcher for a materialized view whose source reports its last refresh.
+    /// Counts full fetches.
+    #[derive(Debug)]
+    struct MockMaterializedViewFetcher {
+        refreshed_at: std::sync::atomic::AtomicI64,
+        fetches: AtomicUsize,
+    }
+
+    impl MockMaterializedViewFetcher {
+        fn new(refreshed_at: i64) -> Self {
+            Self {
+                refreshed_at: std::sync::atomic::AtomicI64::new(refreshed_at),
+                fetches: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DataFetcher for MockMaterializedViewFetcher {
+        async fn discover_tables(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+        ) -> Result<Vec<TableMetadata>, DataFetchError> {
+            Ok(vec![])
+        }
+
+        async fn fetch_table(
+            &self,
+            source: &Source,
+            secret_manager: &SecretManager,
+            catalog: Option<&str>,
+            schema: &str,
+            table: &str,
+            writer: &mut super::StreamingParquetWriter,
+        ) -> Result<(), DataFetchError> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            MockFetcher
+                .fetch_table(source, secret_manager, catalog, schema, table, writer)
+                .await
+        }
+
+        async fn source_refreshed_at(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+            _schema: &str,
+            _table: &str,
+        ) -> Result<Option<i64>, DataFetchError> {
+            Ok(Some(self.refreshed_at.load(Ordering::SeqCst)))
+        }
+    }
+
+    /// Mock snapshot fetcher. The current snapshot is configurable, and whether newer
+    /// snapshots are append-only is controlled by `append_only`. Full reads write three
+    /// rows, appends write one.
+    #[derive(Debug)]
+    struct MockSnapshotFetcher {
+        current_snapshot: std::sync::atomic::AtomicI64,
+        append_only: AtomicBool,
+    }
+
+    impl MockSnapshotFetcher {
+        fn new(snapshot_id: i64) -> Self {
+            Self {
+                current_snapshot: std::sync::atomic::AtomicI64::new(snapshot_id),
+                append_only: AtomicBool::new(true),
+            }
+        }
+
+        fn commit(&self, snapshot_id: i64, append_only: bool) {
+            self.current_snapshot.store(snapshot_id, Ordering::SeqCst);
+            self.append_only.store(append_only, Ordering::SeqCst);
+        }
+
+        async fn write_rows(
+            writer: &mut super::StreamingParquetWriter,
+            ids: Vec<i32>,
+        ) -> Result<(), DataFetchError> {
+            use datafusion::arrow::array::Int32Array;
+            use datafusion::arrow::datatypes::{DataType, Field, Schema};
+            use datafusion::arrow::record_batch::RecordBatch;
+
+            let schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+            writer.init(&schema)?;
+            let batch =
+                RecordBatch::try_new(Arc::new(schema), vec![Arc::new(Int32Array::from(ids))])
+                    .map_err(|e| DataFetchError::Query(e.to_string()))?;
+            writer.write_batch(&batch).await
+        }
+    }
+
+    #[async_trait]
+    impl DataFetcher for MockSnapshotFetcher {
+        async fn discover_tables(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+        ) -> Result<Vec<TableMetadata>, DataFetchError> {
+            Ok(vec![])
+        }
+
+        async fn fetch_table(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+            _catalog: Option<&str>,
+            _schema: &str,
+            _table: &str,
+            _writer: &mut super::StreamingParquetWriter,
+        ) -> Result<(), DataFetchError> {
+            Err(DataFetchError::Query("unpinned fetch".to_string()))
+        }
+
+        async fn plan_snapshot_refresh(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+            _schema: &str,
+            _table: &str,
+            cached_snapshot: Option<i64>,
+        ) -> Result<SnapshotRefresh, DataFetchError> {
+            let snapshot_id = self.current_snapshot.load(Ordering::SeqCst);
+            Ok(match cached_snapshot {
+                Some(cached) if cached == snapshot_id => SnapshotRefresh::Unchanged { snapshot_id },
+                Some(cached) if self.append_only.load(Ordering::SeqCst) => {
+                    SnapshotRefresh::Append {
+                        from_snapshot_id: cached,
+                        snapshot_id,
+                    }
+                }
+                _ => SnapshotRefresh::Full {
+                    snapshot_id: Some(snapshot_id),
+                },
+            })
+        }
+
+        async fn fetch_table_snapshot(
+            &self,
+            _source: &Source,
+            _secret_manager: &SecretManager,
+            _schema: &str,
+            _table: &str,
+            plan: &SnapshotRefresh,
+            writer: &mut super::StreamingParquetWriter,
+        ) -> Result<(), DataFetchError> {
+            match plan {
+                SnapshotRefresh::Unchanged { .. } => Ok(()),
+                SnapshotRefresh::Append { .. } => Self::write_rows(writer, vec![4]).await,
+                SnapshotRefresh::Full { .. } => Self::write_rows(writer, vec![1, 2, 3]).await,
+            }
+        }
+
+        async fn fetch_table_as_of(
+            &self,
+            _source: &Source,
+            _secrets: &SecretManager,
+            _schema: &str,
+            _table: &str,
+            _as_of: &TimeTravel,
+            writer: &mut super::StreamingParquetWriter,
+        ) -> Result<(), DataFetchError> {
+            Self::write_rows(writer, vec![1, 2]).await
+        }
+    }
+
+    /// Mock storage that tracks file operations
+    #[derive(Debug)]
+    struct MockStorage {
+        base_path: PathBuf,
+        deleted_urls: Mutex<Vec<String>>,
+        version_counter: AtomicUsize,
+    }
+
+    impl MockStorage {
+        fn new(base_path: PathBuf) -> Self {
+            Self {
+                base_path,
+                deleted_urls: Mutex::new(Vec::new()),
                 version_counter: AtomicUsize::new(0),
             }
         }
@@ -298,6 +1561,35 @@ mod tests {
         fn get_deleted_urls(&self) -> Vec<String> {
             self.deleted_urls.lock().unwrap().clone()
         }
+
+        fn prepare_write(
+            &self,
+            connection_id: i32,
+            schema: &str,
+            table: &str,
+            file_name: &str,
+            base_version: Option<&str>,
+        ) -> CacheWriteHandle {
+            let version = format!("v{}", self.version_counter.fetch_add(1, Ordering::SeqCst));
+            let local_path = self
+                .base_path
+                .join(connection_id.to_string())
+                .join(schema)
+                .join(table)
+                .join(&version)
+                .join(file_name);
+
+            CacheWriteHandle {
+                local_path,
+                version,
+                base_version: base_version.map(str::to_string),
+                connection_id,
+                schema: schema.to_string(),
+                table: table.to_string(),
+                file_name: file_name.to_string(),
+                upload: None,
+            }
+        }
     }
 
     #[async_trait]
@@ -316,6 +1608,10 @@ mod tests {
             format!("{}/{}", self.base_path.display(), connection_id)
         }
 
+        fn cache_root(&self) -> String {
+            format!("file://{}", self.base_path.display())
+        }
+
         async fn read(&self, _url: &str) -> Result<Vec<u8>> {
             Ok(vec![])
         }
@@ -366,31 +1662,36 @@ mod tests {
             schema: &str,
             table: &str,
         ) -> CacheWriteHandle {
-            let version = format!("v{}", self.version_counter.fetch_add(1, Ordering::SeqCst));
-            let local_path = self
-                .base_path
-                .join(connection_id.to_string())
-                .join(schema)
-                .join(table)
-                .join(&version)
-                .join("data.parquet");
+            self.prepare_write(connection_id, schema, table, "data.parquet", None)
+        }
 
-            CacheWriteHandle {
-                local_path,
-                version,
-                connection_id,
-                schema: schema.to_string(),
-                table: table.to_string(),
-            }
+        fn prepare_append_write(
+            &self,
+            connection_id: i32,
+            schema: &str,
+            table: &str,
+            base_version: &str,
+        ) -> CacheWriteHandle {
+            let file_name = format!(
+                "part-{}.parquet",
+                self.version_counter.load(Ordering::SeqCst)
+            );
+            self.prepare_write(connection_id, schema, table, &file_name, Some(base_version))
         }
 
         async fn finalize_cache_write(&self, handle: &CacheWriteHandle) -> Result<String> {
-            let version_dir = self
+            let table_dir = self
                 .base_path
                 .join(handle.connection_id.to_string())
                 .join(&handle.schema)
-                .join(&handle.table)
-                .join(&handle.version);
+                .join(&handle.table);
+            let version_dir = table_dir.join(&handle.version);
+            if let Some(base) = &handle.base_version {
+                for entry in std::fs::read_dir(table_dir.join(base))? {
+                    let entry = entry?;
+                    std::fs::copy(entry.path(), version_dir.join(entry.file_name()))?;
+                }
+            }
             Ok(format!("file://{}", version_dir.display()))
         }
     }
@@ -399,6 +1700,10 @@ mod tests {
     #[derive(Debug)]
     struct MockCatalog {
         tables: Mutex<HashMap<(i32, String, String), TableInfo>>,
+        offsets: Mutex<HashMap<i32, Vec<PartitionOffset>>>,
+        snapshots: Mutex<HashMap<i32, i64>>,
+        policies: Mutex<HashMap<i32, SchemaChangePolicy>>,
+        versions: Mutex<Vec<SchemaVersion>>,
         fail_update: AtomicBool,
         next_id: AtomicUsize,
     }
@@ -407,6 +1712,10 @@ mod tests {
         fn new() -> Self {
             Self {
                 tables: Mutex::new(HashMap::new()),
+                offsets: Mutex::new(HashMap::new()),
+                snapshots: Mutex::new(HashMap::new()),
+                policies: Mutex::new(HashMap::new()),
+                versions: Mutex::new(Vec::new()),
                 fail_update: AtomicBool::new(false),
                 next_id: AtomicUsize::new(1),
             }
@@ -424,6 +1733,13 @@ mod tests {
                     parquet_path: None,
                     last_sync: None,
                     arrow_schema_json: None,
+                    stale_since: None,
+                    table_type: TableKind::BaseTable.as_str().to_string(),
+                    estimated_rows: None,
+                    estimated_bytes: None,
+                    cache_stats_json: None,
+                    last_accessed_at: None,
+                    access_count: 0,
                 },
             );
         }
@@ -431,6 +1747,13 @@ mod tests {
         fn set_fail_update(&self, fail: bool) {
             self.fail_update.store(fail, Ordering::SeqCst);
         }
+
+        fn set_schema(&self, connection_id: i32, schema: &str, table: &str, arrow_schema: &Schema) {
+            let key = (connection_id, schema.to_string(), table.to_string());
+            if let Some(info) = self.tables.lock().unwrap().get_mut(&key) {
+                info.arrow_schema_json = Some(serde_json::to_string(arrow_schema).unwrap());
+            }
+        }
     }
 
     #[async_trait]
@@ -468,10 +1791,20 @@ mod tests {
             connection_id: i32,
             schema_name: &str,
             table_name: &str,
-            _arrow_schema_json: &str,
+            arrow_schema_json: &str,
         ) -> Result<i32> {
-            self.add_table(connection_id, schema_name, table_name);
-            Ok(self.next_id.load(Ordering::SeqCst) as i32 - 1)
+            let key = (
+                connection_id,
+                schema_name.to_string(),
+                table_name.to_string(),
+            );
+            if self.tables.lock().unwrap().get(&key).is_none() {
+                self.add_table(connection_id, schema_name, table_name);
+            }
+            let mut tables = self.tables.lock().unwrap();
+            let info = tables.get_mut(&key).unwrap();
+            info.arrow_schema_json = Some(arrow_schema_json.to_string());
+            Ok(info.id)
         }
 
         async fn list_tables(&self, _connection_id: Option<i32>) -> Result<Vec<TableInfo>> {
@@ -496,10 +1829,15 @@ mod tests {
                 .cloned())
         }
 
-        async fn update_table_sync(&self, _table_id: i32, _parquet_path: &str) -> Result<()> {
+        async fn update_table_sync(&self, table_id: i32, parquet_path: &str) -> Result<()> {
             if self.fail_update.load(Ordering::SeqCst) {
                 return Err(anyhow::anyhow!("Simulated catalog update failure"));
             }
+            for info in self.tables.lock().unwrap().values_mut() {
+                if info.id == table_id {
+                    info.parquet_path = Some(parquet_path.to_string());
+                }
+            }
             Ok(())
         }
 
@@ -516,14 +1854,238 @@ mod tests {
             Ok(())
         }
 
-        async fn delete_connection(&self, _name: &str) -> Result<()> {
-            Ok(())
+        async fn delete_connection(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn update_connection(
+            &self,
+            _id: i32,
+            _name: &str,
+            _source_type: &str,
+            _config_json: &str,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_connection_by_id(&self, _id: i32) -> Result<Option<ConnectionInfo>> {
+            Ok(None)
+        }
+
+        async fn get_table_offsets(&self, table_id: i32) -> Result<Vec<PartitionOffset>> {
+            Ok(self
+                .offsets
+                .lock()
+                .unwrap()
+                .get(&table_id)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        async fn update_table_offsets(
+            &self,
+            table_id: i32,
+            offsets: &[PartitionOffset],
+        ) -> Result<()> {
+            let mut all = self.offsets.lock().unwrap();
+            let stored = all.entry(table_id).or_default();
+            for offset in offsets {
+                stored.retain(|o| o.partition_id != offset.partition_id);
+                stored.push(*offset);
+            }
+            Ok(())
+        }
+
+        async fn get_table_snapshot_id(&self, table_id: i32) -> Result<Option<i64>> {
+            Ok(self.snapshots.lock().unwrap().get(&table_id).copied())
+        }
+
+        async fn update_table_snapshot_id(
+            &self,
+            table_id: i32,
+            snapshot_id: Option<i64>,
+        ) -> Result<()> {
+            let mut snapshots = self.snapshots.lock().unwrap();
+            match snapshot_id {
+                Some(id) => snapshots.insert(table_id, id),
+                None => snapshots.remove(&table_id),
+            };
+            Ok(())
+        }
+
+        async fn get_schema_change_policy(&self, connection_id: i32) -> Result<SchemaChangePolicy> {
+            Ok(self
+                .policies
+                .lock()
+                .unwrap()
+                .get(&connection_id)
+                .copied()
+                .unwrap_or_default())
+        }
+
+        async fn set_schema_change_policy(
+            &self,
+            connection_id: i32,
+            policy: SchemaChangePolicy,
+        ) -> Result<()> {
+            self.policies.lock().unwrap().insert(connection_id, policy);
+            Ok(())
+        }
+
+        async fn get_warm_up_json(&self, _connection_id: i32) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn set_warm_up_json(
+            &self,
+            _connection_id: i32,
+            _warm_up_json: Option<&str>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn add_schema_version(
+            &self,
+            table_id: i32,
+            arrow_schema_json: &str,
+            changes_json: &str,
+            status: SchemaVersionStatus,
+        ) -> Result<i32> {
+            let table = self
+                .tables
+                .lock()
+                .unwrap()
+                .values()
+                .find(|t| t.id == table_id)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Unknown table {}", table_id))?;
+            let mut versions = self.versions.lock().unwrap();
+            let version = versions.iter().filter(|v| v.table_id == table_id).count() as i32 + 1;
+            let id = versions.len() as i32 + 1;
+            versions.push(SchemaVersion {
+                id,
+                table_id,
+                connection_id: table.connection_id,
+                schema_name: table.schema_name,
+                table_name: table.table_name,
+                version,
+                arrow_schema_json: arrow_schema_json.to_string(),
+                changes_json: changes_json.to_string(),
+                status: status.as_str().to_string(),
+                created_at: None,
+            });
+            Ok(version)
+        }
+
+        async fn list_schema_versions(
+            &self,
+            connection_id: Option<i32>,
+        ) -> Result<Vec<SchemaVersion>> {
+            Ok(self
+                .versions
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|v| connection_id.is_none_or(|id| v.connection_id == id))
+                .cloned()
+                .collect())
+        }
+
+        async fn set_table_stale(&self, table_id: i32, stale: bool) -> Result<()> {
+            for info in self.tables.lock().unwrap().values_mut() {
+                if info.id == table_id {
+                    info.stale_since = match (stale, info.stale_since.take()) {
+                        (true, since) => since.or_else(|| Some(Utc::now().to_rfc3339())),
+                        (false, _) => None,
+                    };
+                }
+            }
+            Ok(())
+        }
+
+        async fn update_table_estimates(
+            &self,
+            table_id: i32,
+            rows: Option<i64>,
+            bytes: Option<i64>,
+        ) -> Result<()> {
+            for info in self.tables.lock().unwrap().values_mut() {
+                if info.id == table_id {
+                    info.estimated_rows = rows;
+                    info.estimated_bytes = bytes;
+                }
+            }
+            Ok(())
+        }
+
+        async fn update_table_cache_stats(
+            &self,
+            table_id: i32,
+            stats_json: Option<&str>,
+        ) -> Result<()> {
+            for info in self.tables.lock().unwrap().values_mut() {
+                if info.id == table_id {
+                    info.cache_stats_json = stats_json.map(str::to_string);
+                }
+            }
+            Ok(())
+        }
+
+        async fn record_table_accesses(&self, accesses: &[TableAccess]) -> Result<()> {
+            if self.fail_update.load(Ordering::SeqCst) {
+                return Err(anyhow::anyhow!("Simulated catalog update failure"));
+            }
+            for info in self.tables.lock().unwrap().values_mut() {
+                if let Some(access) = accesses.iter().find(|a| a.table_id == info.id) {
+                    info.access_count += access.count;
+                    info.last_accessed_at = Some(access.last_accessed_at.to_rfc3339());
+                }
+            }
+            Ok(())
+        }
+
+        async fn set_table_type(&self, table_id: i32, kind: TableKind) -> Result<()> {
+            for info in self.tables.lock().unwrap().values_mut() {
+                if info.id == table_id {
+                    info.table_type = kind.as_str().to_string();
+                }
+            }
+            Ok(())
+        }
+
+        async fn delete_table(&self, table_id: i32) -> Result<()> {
+            self.tables.lock().unwrap().retain(|_, t| t.id != table_id);
+            self.offsets.lock().unwrap().remove(&table_id);
+            self.versions
+                .lock()
+                .unwrap()
+                .retain(|v| v.table_id != table_id);
+            Ok(())
+        }
+
+        async fn create_cache_snapshot(&self, _name: &str, _tables: &[TableInfo]) -> Result<i32> {
+            Ok(1)
         }
 
-        async fn get_connection_by_id(&self, _id: i32) -> Result<Option<ConnectionInfo>> {
+        async fn get_cache_snapshot(&self, _name: &str) -> Result<Option<CacheSnapshot>> {
             Ok(None)
         }
 
+        async fn list_cache_snapshots(&self) -> Result<Vec<CacheSnapshot>> {
+            Ok(vec![])
+        }
+
+        async fn list_snapshot_tables(
+            &self,
+            _snapshot_id: Option<i32>,
+        ) -> Result<Vec<SnapshotTable>> {
+            Ok(vec![])
+        }
+
+        async fn delete_cache_snapshot(&self, _snapshot_id: i32) -> Result<()> {
+            Ok(())
+        }
+
         async fn schedule_file_deletion(
             &self,
             _path: &str,
@@ -536,6 +2098,10 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn list_scheduled_deletions(&self) -> Result<Vec<PendingDeletion>> {
+            Ok(vec![])
+        }
+
         async fn increment_deletion_retry(&self, _id: i32) -> Result<i32> {
             Ok(1)
         }
@@ -642,131 +2208,989 @@ mod tests {
         let catalog = Arc::new(MockCatalog::new());
         let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
 
-        // Add a table to the mock catalog
-        catalog.add_table(1, "test", "orders");
-
-        // Configure catalog to fail on update
-        catalog.set_fail_update(true);
+        // Add a table to the mock catalog
+        catalog.add_table(1, "test", "orders");
+
+        // Configure catalog to fail on update
+        catalog.set_fail_update(true);
+
+        let orchestrator =
+            FetchOrchestrator::new(fetcher, storage.clone(), catalog, secret_manager);
+
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        // This should fail because catalog update is configured to fail
+        let result = orchestrator
+            .refresh_table(&source, 1, "test", "orders")
+            .await;
+
+        assert!(result.is_err(), "refresh_table should fail");
+        let err_msg = result.unwrap_err().to_string();
+        assert!(
+            err_msg.contains("Simulated catalog update failure"),
+            "Error should be from catalog, got: {}",
+            err_msg
+        );
+
+        // Verify that cleanup was attempted (storage.delete was called)
+        let deleted = storage.get_deleted_urls();
+        assert_eq!(
+            deleted.len(),
+            1,
+            "Should have deleted exactly one URL (the orphaned file)"
+        );
+
+        // Verify the deleted URL matches the expected pattern
+        let deleted_url = &deleted[0];
+        assert!(
+            deleted_url.contains("/1/test/orders/"),
+            "Deleted URL should be for the test table: {}",
+            deleted_url
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_table_fails_and_cleans_up_on_catalog_failure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let storage = Arc::new(MockStorage::new(cache_path.clone()));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+
+        catalog.add_table(1, "test", "orders");
+        catalog.set_fail_update(true);
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockFetcher),
+            storage.clone(),
+            catalog,
+            secret_manager,
+        );
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        // The cached path would never be recorded, so the fetch must not report success
+        let err = orchestrator
+            .cache_table(&source, 1, "test", "orders")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Simulated catalog update failure"),
+            "Error should be from catalog, got: {}",
+            err
+        );
+
+        let deleted = storage.get_deleted_urls();
+        assert_eq!(deleted.len(), 1);
+        assert!(!std::path::Path::new(deleted[0].strip_prefix("file://").unwrap()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_table_accesses_are_flushed_in_batches() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(MockStorage::new(temp_dir.path().join("cache")));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+        catalog.add_table(1, "test", "orders");
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockFetcher),
+            storage,
+            catalog.clone(),
+            secret_manager,
+        );
+        let access_count = || {
+            catalog.tables.lock().unwrap()[&(1, "test".to_string(), "orders".to_string())]
+                .access_count
+        };
+
+        for _ in 0..3 {
+            orchestrator.record_table_access(1);
+        }
+        assert_eq!(access_count(), 0, "accesses are only written on flush");
+
+        // A failed flush keeps the accesses for the next one
+        catalog.set_fail_update(true);
+        assert!(orchestrator.flush_table_accesses().await.is_err());
+        orchestrator.record_table_access(1);
+        catalog.set_fail_update(false);
+        orchestrator.flush_table_accesses().await.unwrap();
+        assert_eq!(access_count(), 4);
+
+        orchestrator.flush_table_accesses().await.unwrap();
+        assert_eq!(access_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_table_succeeds_without_cleanup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let fetcher = Arc::new(MockFetcher);
+        let storage = Arc::new(MockStorage::new(cache_path.clone()));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+
+        // Add a table to the mock catalog
+        catalog.add_table(1, "test", "orders");
+
+        // Catalog update should succeed (default)
+        let orchestrator =
+            FetchOrchestrator::new(fetcher, storage.clone(), catalog, secret_manager);
+
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        let result = orchestrator
+            .refresh_table(&source, 1, "test", "orders")
+            .await;
+
+        assert!(result.is_ok(), "refresh_table should succeed");
+
+        // Verify that no cleanup was performed
+        let deleted = storage.get_deleted_urls();
+        assert!(
+            deleted.is_empty(),
+            "Should not have deleted any URLs on success"
+        );
+
+        // Verify the returned URL and row count
+        let (new_url, _old_path, row_count) = result.unwrap();
+        assert!(
+            new_url.contains("/1/test/orders/"),
+            "New URL should be for the test table"
+        );
+        assert_eq!(row_count, 3, "Should have synced 3 rows from MockFetcher");
+    }
+
+    /// Read back every parquet file under a `file://` cache URL.
+    fn read_cached(url: &str) -> Vec<RecordBatch> {
+        use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let dir = PathBuf::from(url.trim_start_matches("file://"));
+        let mut batches = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let file = std::fs::File::open(entry.unwrap().path()).unwrap();
+            let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+                .unwrap()
+                .build()
+                .unwrap();
+            batches.extend(reader.map(|b| b.unwrap()));
+        }
+        batches
+    }
+
+    #[tokio::test]
+    async fn test_refresh_table_casts_to_catalog_schema() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        catalog.add_table(1, "test", "orders");
+        // Discovery widened the column; MockFetcher still produces Int32
+        let catalog_schema = Schema::new(vec![Field::new("id", ArrowDataType::Int64, false)]);
+        catalog.set_schema(1, "test", "orders", &catalog_schema);
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockFetcher),
+            Arc::new(MockStorage::new(cache_path)),
+            catalog.clone(),
+            Arc::new(create_test_secret_manager(temp_dir.path()).await),
+        );
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        let (url, _, row_count) = orchestrator
+            .refresh_table(&source, 1, "test", "orders")
+            .await
+            .unwrap();
+        assert_eq!(row_count, 3);
+
+        let batches = read_cached(&url);
+        assert_eq!(batches[0].schema().fields(), catalog_schema.fields());
+
+        // The catalog schema is kept
+        let info = catalog
+            .get_table(1, "test", "orders")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            info.arrow_schema_json,
+            Some(serde_json::to_string(&catalog_schema).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_table_adopts_changed_source_schema() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        catalog.add_table(1, "test", "orders");
+        // The catalog still describes an older version of the table
+        let old_schema = Schema::new(vec![Field::new("name", ArrowDataType::Utf8, true)]);
+        catalog.set_schema(1, "test", "orders", &old_schema);
+
+        let fetcher = Arc::new(MockFetcher);
+        let orchestrator = FetchOrchestrator::new(
+            fetcher.clone(),
+            Arc::new(MockStorage::new(cache_path)),
+            catalog.clone(),
+            Arc::new(create_test_secret_manager(temp_dir.path()).await),
+        );
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        orchestrator
+            .refresh_table(&source, 1, "test", "orders")
+            .await
+            .unwrap();
+
+        let discovered = fetcher
+            .discover_tables(&source, &create_test_secret_manager(temp_dir.path()).await)
+            .await
+            .unwrap();
+        let info = catalog
+            .get_table(1, "test", "orders")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            info.arrow_schema_json,
+            Some(serde_json::to_string(&discovered[0].to_arrow_schema()).unwrap())
+        );
+
+        // The old schema is kept as the baseline, followed by the applied change
+        let versions = catalog.list_schema_versions(Some(1)).await.unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].changes_json, "[]");
+        assert_eq!(versions[1].version, 2);
+        assert_eq!(versions[1].status, "applied");
+        assert_eq!(
+            versions[1].arrow_schema_json,
+            info.arrow_schema_json.unwrap()
+        );
+        assert!(versions[1]
+            .changes_json
+            .contains(r#""change":"dropped","column":"name""#));
+        assert!(versions[1]
+            .changes_json
+            .contains(r#""change":"added","column":"id""#));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_table_blocks_breaking_change_by_policy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        catalog.add_table(1, "test", "orders");
+        let old_schema = Schema::new(vec![Field::new("name", ArrowDataType::Utf8, true)]);
+        catalog.set_schema(1, "test", "orders", &old_schema);
+        catalog
+            .set_schema_change_policy(1, SchemaChangePolicy::Block)
+            .await
+            .unwrap();
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockFetcher),
+            Arc::new(MockStorage::new(cache_path)),
+            catalog.clone(),
+            Arc::new(create_test_secret_manager(temp_dir.path()).await),
+        );
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        for _ in 0..2 {
+            let err = orchestrator
+                .refresh_table(&source, 1, "test", "orders")
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<DataFetchError>(),
+                Some(DataFetchError::SchemaMismatch { .. })
+            ));
+        }
+
+        // The catalog keeps the old schema and the blocked change is recorded once
+        let info = catalog
+            .get_table(1, "test", "orders")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            info.arrow_schema_json,
+            Some(serde_json::to_string(&old_schema).unwrap())
+        );
+        let versions = catalog.list_schema_versions(Some(1)).await.unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[1].status, "blocked");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_table_rejects_unreconcilable_schema() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        catalog.add_table(1, "test", "orders");
+        let catalog_schema = Schema::new(vec![Field::new("id", ArrowDataType::Utf8, true)]);
+        catalog.set_schema(1, "test", "orders", &catalog_schema);
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(DriftingFetcher),
+            Arc::new(MockStorage::new(cache_path)),
+            catalog.clone(),
+            Arc::new(create_test_secret_manager(temp_dir.path()).await),
+        );
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        let err = orchestrator
+            .refresh_table(&source, 1, "test", "orders")
+            .await
+            .unwrap_err();
+        match err.downcast_ref::<DataFetchError>() {
+            Some(DataFetchError::SchemaMismatch { table, details }) => {
+                assert_eq!(table, "test.orders");
+                assert!(details.contains("column 'id' is Int32"));
+            }
+            other => panic!("expected SchemaMismatch, got {:?}", other),
+        }
+
+        // Neither the cached path nor the catalog schema changed
+        let info = catalog
+            .get_table(1, "test", "orders")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(info.parquet_path.is_none());
+        assert_eq!(
+            info.arrow_schema_json,
+            Some(serde_json::to_string(&catalog_schema).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_table_fails_when_table_not_found() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let fetcher = Arc::new(MockFetcher);
+        let storage = Arc::new(MockStorage::new(cache_path.clone()));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+
+        // Don't add the table - it should fail early with TableNotFound
 
         let orchestrator =
             FetchOrchestrator::new(fetcher, storage.clone(), catalog, secret_manager);
 
         let source = Source::Duckdb {
             path: ":memory:".to_string(),
+            table_filter: Default::default(),
         };
 
-        // This should fail because catalog update is configured to fail
         let result = orchestrator
             .refresh_table(&source, 1, "test", "orders")
             .await;
 
         assert!(result.is_err(), "refresh_table should fail");
-        let err_msg = result.unwrap_err().to_string();
+        let err = result.unwrap_err();
         assert!(
-            err_msg.contains("Simulated catalog update failure"),
-            "Error should be from catalog, got: {}",
-            err_msg
+            err.to_string().contains("not found"),
+            "Error should indicate table not found: {}",
+            err
         );
 
-        // Verify that cleanup was attempted (storage.delete was called)
+        // Verify no cleanup was needed - the early existence check prevents any I/O
         let deleted = storage.get_deleted_urls();
         assert_eq!(
             deleted.len(),
-            1,
-            "Should have deleted exactly one URL (the orphaned file)"
+            0,
+            "No cleanup should be needed since we fail before writing any data"
         );
+    }
 
-        // Verify the deleted URL matches the expected pattern
-        let deleted_url = &deleted[0];
-        assert!(
-            deleted_url.contains("/1/test/orders/"),
-            "Deleted URL should be for the test table: {}",
-            deleted_url
+    #[tokio::test]
+    async fn test_streaming_refresh_appends_into_new_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let fetcher = Arc::new(MockStreamingFetcher);
+        let storage = Arc::new(MockStorage::new(cache_path.clone()));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+
+        catalog.add_table(1, "topics", "events");
+
+        let orchestrator =
+            FetchOrchestrator::new(fetcher, storage.clone(), catalog.clone(), secret_manager);
+
+        let source = Source::Kafka {
+            bootstrap_servers: "localhost:9092".to_string(),
+            format: crate::source::KafkaFormat::Json,
+            schema_registry_url: None,
+            table_filter: Default::default(),
+        };
+
+        let (first_url, first_rows) = orchestrator
+            .cache_table(&source, 1, "topics", "events")
+            .await
+            .unwrap();
+        assert_eq!(first_rows, 2);
+
+        let (second_url, old_path, second_rows) = orchestrator
+            .refresh_table(&source, 1, "topics", "events")
+            .await
+            .unwrap();
+        assert_eq!(second_rows, 2);
+        assert_ne!(second_url, first_url, "Appends should write a new version");
+        assert_eq!(old_path.as_deref(), Some(first_url.as_str()));
+
+        let files_in = |url: &str| {
+            std::fs::read_dir(url.strip_prefix("file://").unwrap())
+                .unwrap()
+                .count()
+        };
+        assert_eq!(
+            files_in(&second_url),
+            2,
+            "Each micro-batch should be its own file"
+        );
+        assert_eq!(files_in(&first_url), 1, "The old version must not change");
+
+        let table_id = catalog
+            .get_table(1, "topics", "events")
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        let offsets = catalog.get_table_offsets(table_id).await.unwrap();
+        assert_eq!(
+            offsets,
+            vec![PartitionOffset {
+                partition_id: 0,
+                next_offset: 4
+            }]
         );
     }
 
     #[tokio::test]
-    async fn test_refresh_table_succeeds_without_cleanup() {
+    async fn test_appends_leave_pinned_versions_unchanged() {
+        use datafusion::datasource::file_format::parquet::ParquetFormat;
+        use datafusion::datasource::listing::{
+            ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+        };
+        use datafusion::prelude::SessionContext;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+        catalog.add_table(1, "topics", "events");
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockStreamingFetcher),
+            Arc::new(MockStorage::new(cache_path.clone())),
+            catalog,
+            secret_manager,
+        );
+        let source = Source::Kafka {
+            bootstrap_servers: "localhost:9092".to_string(),
+            format: crate::source::KafkaFormat::Json,
+            schema_registry_url: None,
+            table_filter: Default::default(),
+        };
+
+        // A cache snapshot pins the version dir that was current when it was taken
+        let (pinned_url, _) = orchestrator
+            .cache_table(&source, 1, "topics", "events")
+            .await
+            .unwrap();
+        let (current_url, _, _) = orchestrator
+            .refresh_table(&source, 1, "topics", "events")
+            .await
+            .unwrap();
+
+        let count_rows = |url: String| async move {
+            let config = ListingTableConfig::new(ListingTableUrl::parse(&url).unwrap())
+                .with_listing_options(ListingOptions::new(Arc::new(ParquetFormat::default())))
+                .infer_schema(&SessionContext::new().state())
+                .await
+                .unwrap();
+            let ctx = SessionContext::new();
+            ctx.register_table("t", Arc::new(ListingTable::try_new(config).unwrap()))
+                .unwrap();
+            ctx.table("t").await.unwrap().count().await.unwrap()
+        };
+        assert_eq!(
+            count_rows(pinned_url).await,
+            2,
+            "Rows appended after the pin must not be visible through it"
+        );
+        assert_eq!(count_rows(current_url).await, 4);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_streaming_refreshes_are_serialised() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let storage = Arc::new(MockStorage::new(cache_path.clone()));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+
+        catalog.add_table(1, "topics", "events");
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockStreamingFetcher),
+            storage,
+            catalog.clone(),
+            secret_manager,
+        );
+        let source = Source::Kafka {
+            bootstrap_servers: "localhost:9092".to_string(),
+            format: crate::source::KafkaFormat::Json,
+            schema_registry_url: None,
+            table_filter: Default::default(),
+        };
+
+        orchestrator
+            .cache_table(&source, 1, "topics", "events")
+            .await
+            .unwrap();
+        let (first, second) = tokio::join!(
+            orchestrator.refresh_table(&source, 1, "topics", "events"),
+            orchestrator.refresh_table(&source, 1, "topics", "events"),
+        );
+        first.unwrap();
+        second.unwrap();
+
+        // Each refresh resumed where the other stopped, so no batch was fetched twice
+        let info = catalog
+            .get_table(1, "topics", "events")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            catalog.get_table_offsets(info.id).await.unwrap(),
+            vec![PartitionOffset {
+                partition_id: 0,
+                next_offset: 6
+            }]
+        );
+        let url = info.parquet_path.unwrap();
+        let files = std::fs::read_dir(url.strip_prefix("file://").unwrap())
+            .unwrap()
+            .count();
+        assert_eq!(files, 3);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_ensure_cached_fetches_once() {
         let temp_dir = tempfile::tempdir().unwrap();
         let cache_path = temp_dir.path().join("cache");
         std::fs::create_dir_all(&cache_path).unwrap();
 
-        let fetcher = Arc::new(MockFetcher);
         let storage = Arc::new(MockStorage::new(cache_path.clone()));
         let catalog = Arc::new(MockCatalog::new());
         let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
 
-        // Add a table to the mock catalog
         catalog.add_table(1, "test", "orders");
 
-        // Catalog update should succeed (default)
         let orchestrator =
-            FetchOrchestrator::new(fetcher, storage.clone(), catalog, secret_manager);
-
+            FetchOrchestrator::new(Arc::new(MockFetcher), storage, catalog, secret_manager);
         let source = Source::Duckdb {
             path: ":memory:".to_string(),
+            table_filter: Default::default(),
         };
 
-        let result = orchestrator
-            .refresh_table(&source, 1, "test", "orders")
-            .await;
+        let (first, second) = tokio::join!(
+            orchestrator.ensure_cached(&source, 1, "test", "orders"),
+            orchestrator.ensure_cached(&source, 1, "test", "orders"),
+        );
+        assert_eq!(first.unwrap(), second.unwrap());
 
-        assert!(result.is_ok(), "refresh_table should succeed");
+        // The second caller reused the first one's version instead of writing its own
+        let versions = std::fs::read_dir(cache_path.join("1").join("test").join("orders"))
+            .unwrap()
+            .count();
+        assert_eq!(versions, 1);
+    }
 
-        // Verify that no cleanup was performed
-        let deleted = storage.get_deleted_urls();
-        assert!(
-            deleted.is_empty(),
-            "Should not have deleted any URLs on success"
+    fn iceberg_source() -> Source {
+        Source::Iceberg {
+            catalog_type: crate::source::IcebergCatalogType::Rest {
+                uri: "http://localhost:8181".to_string(),
+                credential: crate::source::Credential::None,
+            },
+            warehouse: "s3://warehouse".to_string(),
+            namespace: None,
+            table_filter: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_refresh_skips_unchanged_and_appends_new_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let fetcher = Arc::new(MockSnapshotFetcher::new(100));
+        let storage = Arc::new(MockStorage::new(cache_path.clone()));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+
+        catalog.add_table(1, "db", "events");
+
+        let orchestrator = FetchOrchestrator::new(
+            fetcher.clone(),
+            storage.clone(),
+            catalog.clone(),
+            secret_manager,
         );
+        let source = iceberg_source();
 
-        // Verify the returned URL and row count
-        let (new_url, _old_path, row_count) = result.unwrap();
-        assert!(
-            new_url.contains("/1/test/orders/"),
-            "New URL should be for the test table"
+        let (first_url, first_rows) = orchestrator
+            .cache_table(&source, 1, "db", "events")
+            .await
+            .unwrap();
+        assert_eq!(first_rows, 3);
+
+        let table_id = catalog
+            .get_table(1, "db", "events")
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        assert_eq!(
+            catalog.get_table_snapshot_id(table_id).await.unwrap(),
+            Some(100)
         );
-        assert_eq!(row_count, 3, "Should have synced 3 rows from MockFetcher");
+
+        // Same snapshot: nothing is fetched or replaced
+        let (url, old_path, rows) = orchestrator
+            .refresh_table(&source, 1, "db", "events")
+            .await
+            .unwrap();
+        assert_eq!(url, first_url);
+        assert!(old_path.is_none());
+        assert_eq!(rows, 0);
+
+        // Append-only snapshot: only new files are fetched, into a new version
+        fetcher.commit(101, true);
+        let (url, old_path, rows) = orchestrator
+            .refresh_table(&source, 1, "db", "events")
+            .await
+            .unwrap();
+        assert_ne!(url, first_url, "Appends should write a new version");
+        assert_eq!(old_path.as_deref(), Some(first_url.as_str()));
+        assert_eq!(rows, 1);
+        assert_eq!(
+            catalog.get_table_snapshot_id(table_id).await.unwrap(),
+            Some(101)
+        );
+
+        let files_in = |url: &str| {
+            std::fs::read_dir(url.strip_prefix("file://").unwrap())
+                .unwrap()
+                .count()
+        };
+        assert_eq!(files_in(&url), 2);
+        assert_eq!(files_in(&first_url), 1, "The old version must not change");
     }
 
     #[tokio::test]
-    async fn test_refresh_table_fails_when_table_not_found() {
+    async fn test_table_as_of_is_written_to_its_own_version() {
         let temp_dir = tempfile::tempdir().unwrap();
         let cache_path = temp_dir.path().join("cache");
         std::fs::create_dir_all(&cache_path).unwrap();
 
-        let fetcher = Arc::new(MockFetcher);
         let storage = Arc::new(MockStorage::new(cache_path.clone()));
         let catalog = Arc::new(MockCatalog::new());
         let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
 
-        // Don't add the table - it should fail early with TableNotFound
+        catalog.add_table(1, "db", "events");
 
-        let orchestrator =
-            FetchOrchestrator::new(fetcher, storage.clone(), catalog, secret_manager);
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockSnapshotFetcher::new(100)),
+            storage,
+            catalog.clone(),
+            secret_manager,
+        );
+
+        let (url, schema) = orchestrator
+            .cache_table_as_of(
+                &iceberg_source(),
+                1,
+                "db",
+                "events",
+                &TimeTravel::Snapshot(99),
+            )
+            .await
+            .unwrap();
+        assert_eq!(schema.field(0).name(), "id");
+
+        // Scanned from storage rather than held in memory, and not recorded as the cache
+        let ctx = SessionContext::new();
+        let count = ctx
+            .read_parquet(format!("{}/", url), Default::default())
+            .await
+            .unwrap()
+            .count()
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        let info = catalog.get_table(1, "db", "events").await.unwrap().unwrap();
+        assert!(info.parquet_path.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_refresh_rewrites_on_non_append_snapshot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let fetcher = Arc::new(MockSnapshotFetcher::new(100));
+        let storage = Arc::new(MockStorage::new(cache_path.clone()));
+        let catalog = Arc::new(MockCatalog::new());
+        let secret_manager = Arc::new(create_test_secret_manager(temp_dir.path()).await);
+
+        catalog.add_table(1, "db", "events");
+
+        let orchestrator = FetchOrchestrator::new(
+            fetcher.clone(),
+            storage.clone(),
+            catalog.clone(),
+            secret_manager,
+        );
+        let source = iceberg_source();
+
+        let (first_url, _) = orchestrator
+            .cache_table(&source, 1, "db", "events")
+            .await
+            .unwrap();
+
+        fetcher.commit(200, false);
+        let (url, old_path, rows) = orchestrator
+            .refresh_table(&source, 1, "db", "events")
+            .await
+            .unwrap();
+        assert_ne!(url, first_url, "Overwrites should write a new version");
+        assert_eq!(old_path.as_deref(), Some(first_url.as_str()));
+        assert_eq!(rows, 3);
+
+        let table_id = catalog
+            .get_table(1, "db", "events")
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        assert_eq!(
+            catalog.get_table_snapshot_id(table_id).await.unwrap(),
+            Some(200)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_views_are_read_live_and_never_cached() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        catalog.add_table(1, "test", "active_orders");
+        let table_id = catalog
+            .get_table(1, "test", "active_orders")
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        catalog
+            .set_table_type(table_id, TableKind::View)
+            .await
+            .unwrap();
 
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockFetcher),
+            Arc::new(MockStorage::new(cache_path.clone())),
+            catalog.clone(),
+            Arc::new(create_test_secret_manager(temp_dir.path()).await),
+        );
         let source = Source::Duckdb {
             path: ":memory:".to_string(),
+            table_filter: Default::default(),
         };
 
-        let result = orchestrator
-            .refresh_table(&source, 1, "test", "orders")
-            .await;
+        assert_eq!(
+            orchestrator.cache_policy(TableKind::View),
+            CachePolicy::Live
+        );
+        let err = orchestrator
+            .cache_table(&source, 1, "test", "active_orders")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("read live"), "{}", err);
+        assert!(orchestrator
+            .refresh_table(&source, 1, "test", "active_orders")
+            .await
+            .is_err());
 
-        assert!(result.is_err(), "refresh_table should fail");
-        let err = result.unwrap_err();
-        assert!(
-            err.to_string().contains("not found"),
-            "Error should indicate table not found: {}",
-            err
+        let info = catalog
+            .get_table(1, "test", "active_orders")
+            .await
+            .unwrap()
+            .unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(8);
+        orchestrator
+            .read_table_live(&source, &info, sender)
+            .await
+            .unwrap();
+        let mut rows = 0;
+        while let Ok(batch) = receiver.try_recv() {
+            rows += batch.num_rows();
+        }
+        assert_eq!(rows, 3);
+        assert!(info.parquet_path.is_none());
+
+        // Views can opt back into caching
+        let orchestrator = orchestrator.with_cache_policy(TableKind::View, CachePolicy::Cache);
+        let (_, rows) = orchestrator
+            .cache_table(&source, 1, "test", "active_orders")
+            .await
+            .unwrap();
+        assert_eq!(rows, 3);
+    }
+
+    #[tokio::test]
+    async fn test_materialized_view_refreshes_only_after_source_refresh() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        catalog.add_table(1, "test", "daily_totals");
+        let table_id = catalog
+            .get_table(1, "test", "daily_totals")
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        catalog
+            .set_table_type(table_id, TableKind::MaterializedView)
+            .await
+            .unwrap();
+
+        let fetcher = Arc::new(MockMaterializedViewFetcher::new(100));
+        let orchestrator = FetchOrchestrator::new(
+            fetcher.clone(),
+            Arc::new(MockStorage::new(cache_path)),
+            catalog.clone(),
+            Arc::new(create_test_secret_manager(temp_dir.path()).await),
         );
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
 
-        // Verify no cleanup was needed - the early existence check prevents any I/O
-        let deleted = storage.get_deleted_urls();
+        let (url, _) = orchestrator
+            .cache_table(&source, 1, "test", "daily_totals")
+            .await
+            .unwrap();
         assert_eq!(
-            deleted.len(),
-            0,
-            "No cleanup should be needed since we fail before writing any data"
+            catalog.get_table_snapshot_id(table_id).await.unwrap(),
+            Some(100)
+        );
+
+        // The source has not refreshed the view since it was cached
+        let (same_url, old_path, rows) = orchestrator
+            .refresh_table(&source, 1, "test", "daily_totals")
+            .await
+            .unwrap();
+        assert_eq!(same_url, url);
+        assert!(old_path.is_none());
+        assert_eq!(rows, 0);
+        assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 1);
+
+        fetcher.refreshed_at.store(200, Ordering::SeqCst);
+        let (new_url, old_path, rows) = orchestrator
+            .refresh_table(&source, 1, "test", "daily_totals")
+            .await
+            .unwrap();
+        assert_ne!(new_url, url);
+        assert_eq!(old_path, Some(url));
+        assert_eq!(rows, 3);
+        assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            catalog.get_table_snapshot_id(table_id).await.unwrap(),
+            Some(200)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_table_records_parquet_statistics() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_path).unwrap();
+
+        let catalog = Arc::new(MockCatalog::new());
+        catalog.add_table(1, "test", "orders");
+
+        let orchestrator = FetchOrchestrator::new(
+            Arc::new(MockFetcher),
+            Arc::new(MockStorage::new(cache_path)),
+            catalog.clone(),
+            Arc::new(create_test_secret_manager(temp_dir.path()).await),
         );
+        let source = Source::Duckdb {
+            path: ":memory:".to_string(),
+            table_filter: Default::default(),
+        };
+
+        let (url, _) = orchestrator
+            .cache_table(&source, 1, "test", "orders")
+            .await
+            .unwrap();
+
+        let info = catalog
+            .get_table(1, "test", "orders")
+            .await
+            .unwrap()
+            .unwrap();
+        let stats: CacheStatistics =
+            serde_json::from_str(info.cache_stats_json.as_deref().unwrap()).unwrap();
+        assert_eq!(stats.url, url);
+        assert_eq!(stats.num_rows, 3);
+        assert_eq!(stats.columns[0].min.as_deref(), Some("1"));
+        assert_eq!(stats.columns[0].max.as_deref(), Some("3"));
     }
 }
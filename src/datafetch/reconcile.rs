@@ -0,0 +1,301 @@
+//! Reconciliation of fetched schemas with the schema recorded in the catalog.
+//!
+//! Discovery and fetching derive their Arrow schemas independently: discovery from the
+//! source's metadata, fetchers from the runtime column types of the rows they read. The two
+//! can disagree on nullability, integer widths or timestamp time zones. Differences a
+//! lossless cast resolves are cast away while writing; anything else is a [`SchemaMismatch`].
+
+use std::fmt;
+
+use datafusion::arrow::array::ArrayRef;
+use datafusion::arrow::compute::{cast_with_options, CastOptions};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+
+/// A fetched schema that cannot be cast to the expected schema.
+#[derive(Debug, Clone)]
+pub struct SchemaMismatch {
+    /// The schema the fetcher produced
+    pub fetched: SchemaRef,
+    /// One entry per incompatible column
+    pub differences: Vec<String>,
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.differences.join("; "))
+    }
+}
+
+/// Check that every batch with the `fetched` schema can be cast to `expected`.
+///
+/// Columns are matched by name, so fetchers may return them in a different order. A column
+/// that is nullable in the fetched schema but not in the expected one is accepted; the
+/// cast fails if a NULL actually shows up.
+pub fn reconcile_schemas(fetched: &Schema, expected: &Schema) -> Result<(), SchemaMismatch> {
+    let mut differences = Vec::new();
+
+    for field in expected.fields() {
+        match fetched.field_with_name(field.name()) {
+            Ok(fetched_field) => {
+                if !is_lossless_cast(fetched_field.data_type(), field.data_type()) {
+                    differences.push(format!(
+                        "column '{}' is {} but the catalog expects {}",
+                        field.name(),
+                        fetched_field.data_type(),
+                        field.data_type()
+                    ));
+                }
+            }
+            Err(_) => differences.push(format!("column '{}' is missing", field.name())),
+        }
+    }
+    for field in fetched.fields() {
+        if expected.field_with_name(field.name()).is_err() {
+            differences.push(format!("column '{}' is not in the catalog", field.name()));
+        }
+    }
+
+    if differences.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaMismatch {
+            fetched: SchemaRef::new(fetched.clone()),
+            differences,
+        })
+    }
+}
+
+/// Whether batches with the `fetched` schema need casting to match `expected`.
+pub fn needs_cast(fetched: &Schema, expected: &Schema) -> bool {
+    fetched.fields() != expected.fields()
+}
+
+/// Cast a batch to `target`, which must have passed [`reconcile_schemas`]. Errors describe
+/// the offending column.
+pub fn cast_batch(batch: &RecordBatch, target: &SchemaRef) -> Result<RecordBatch, String> {
+    // Overflowing values are errors rather than silent NULLs
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+
+    let columns = target
+        .fields()
+        .iter()
+        .map(|field| {
+            let column = batch
+                .column_by_name(field.name())
+                .ok_or_else(|| format!("column '{}' is missing", field.name()))?;
+            if column.data_type() == field.data_type() {
+                return Ok(column.clone());
+            }
+            cast_with_options(column, field.data_type(), &options).map_err(|e| {
+                format!(
+                    "failed to cast column '{}' to {}: {}",
+                    field.name(),
+                    field.data_type(),
+                    e
+                )
+            })
+        })
+        .collect::<Result<Vec<ArrayRef>, _>>()?;
+
+    RecordBatch::try_new(target.clone(), columns)
+        .map_err(|e| format!("fetched rows do not fit the catalog schema: {}", e))
+}
+
+/// Casts that keep every value: integer and float widening, string and binary layout
+/// changes, finer timestamp units and decimals with room for the same digits.
+fn is_lossless_cast(from: &DataType, to: &DataType) -> bool {
+    use DataType::*;
+
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (Null, _) => true,
+        (Int8, Int16 | Int32 | Int64)
+        | (Int16, Int32 | Int64)
+        | (Int32, Int64)
+        | (UInt8, UInt16 | UInt32 | UInt64 | Int16 | Int32 | Int64)
+        | (UInt16, UInt32 | UInt64 | Int32 | Int64)
+        | (UInt32, UInt64 | Int64) => true,
+        (Float16, Float32 | Float64) | (Float32, Float64) => true,
+        (Int8 | Int16 | UInt8 | UInt16, Float32 | Float64) | (Int32 | UInt32, Float64) => true,
+        (Utf8, LargeUtf8 | Utf8View) | (LargeUtf8 | Utf8View, LargeUtf8 | Utf8View) => true,
+        (Binary, LargeBinary | BinaryView)
+        | (LargeBinary | BinaryView, LargeBinary | BinaryView) => true,
+        (Date32, Date64) => true,
+        (Timestamp(from_unit, from_tz), Timestamp(to_unit, to_tz)) => {
+            unit_rank(to_unit) >= unit_rank(from_unit)
+                && same_instants(from_tz.as_deref(), to_tz.as_deref())
+        }
+        (
+            Decimal128(from_p, from_s) | Decimal256(from_p, from_s),
+            Decimal128(to_p, to_s) | Decimal256(to_p, to_s),
+        ) => to_s >= from_s && (*to_p as i16 - *to_s as i16) >= (*from_p as i16 - *from_s as i16),
+        (List(from_item), List(to_item) | LargeList(to_item))
+        | (LargeList(from_item), LargeList(to_item)) => is_lossless_item_cast(from_item, to_item),
+        _ => false,
+    }
+}
+
+fn is_lossless_item_cast(from: &Field, to: &Field) -> bool {
+    (to.is_nullable() || !from.is_nullable()) && is_lossless_cast(from.data_type(), to.data_type())
+}
+
+fn unit_rank(unit: &TimeUnit) -> u8 {
+    match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 1,
+        TimeUnit::Microsecond => 2,
+        TimeUnit::Nanosecond => 3,
+    }
+}
+
+/// Timestamps with a time zone hold UTC instants, so relabeling the zone keeps them. A
+/// naive timestamp only denotes the same instant when the other side is UTC.
+fn same_instants(from_tz: Option<&str>, to_tz: Option<&str>) -> bool {
+    match (from_tz, to_tz) {
+        (Some(_), Some(_)) | (None, None) => true,
+        (Some(tz), None) | (None, Some(tz)) => {
+            matches!(tz, "UTC" | "utc" | "Etc/UTC" | "Z" | "+00:00")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::{Array, Int32Array, Int64Array, TimestampMicrosecondArray};
+    use std::sync::Arc;
+
+    fn schema(fields: Vec<Field>) -> Schema {
+        Schema::new(fields)
+    }
+
+    #[test]
+    fn test_identical_schemas_reconcile_without_cast() {
+        let s = schema(vec![Field::new("id", DataType::Int32, false)]);
+        assert!(reconcile_schemas(&s, &s).is_ok());
+        assert!(!needs_cast(&s, &s));
+    }
+
+    #[test]
+    fn test_widening_and_nullability_reconcile() {
+        let fetched = schema(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("id", DataType::Int32, true),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+        ]);
+        let expected = schema(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+        ]);
+        assert!(reconcile_schemas(&fetched, &expected).is_ok());
+        assert!(needs_cast(&fetched, &expected));
+    }
+
+    #[test]
+    fn test_incompatible_schemas_report_each_column() {
+        let fetched = schema(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("extra", DataType::Utf8, true),
+        ]);
+        let expected = schema(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+
+        let mismatch = reconcile_schemas(&fetched, &expected).unwrap_err();
+        assert_eq!(mismatch.differences.len(), 3);
+        let message = mismatch.to_string();
+        assert!(message.contains("column 'id' is Int64"));
+        assert!(message.contains("column 'name' is missing"));
+        assert!(message.contains("column 'extra' is not in the catalog"));
+    }
+
+    #[test]
+    fn test_lossless_cast_rules() {
+        assert!(is_lossless_cast(&DataType::Int16, &DataType::Int64));
+        assert!(!is_lossless_cast(&DataType::Int64, &DataType::Int32));
+        assert!(is_lossless_cast(&DataType::Float32, &DataType::Float64));
+        assert!(!is_lossless_cast(&DataType::Utf8, &DataType::Int32));
+        assert!(is_lossless_cast(&DataType::Null, &DataType::Utf8));
+        assert!(is_lossless_cast(
+            &DataType::Decimal128(10, 2),
+            &DataType::Decimal128(12, 4)
+        ));
+        assert!(!is_lossless_cast(
+            &DataType::Decimal128(10, 2),
+            &DataType::Decimal128(10, 4)
+        ));
+        assert!(!is_lossless_cast(
+            &DataType::Timestamp(TimeUnit::Microsecond, None),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("America/New_York".into()))
+        ));
+        assert!(!is_lossless_cast(
+            &DataType::Timestamp(TimeUnit::Nanosecond, None),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        ));
+    }
+
+    #[test]
+    fn test_cast_batch_reorders_and_widens() {
+        let fetched = Arc::new(schema(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+            Field::new("id", DataType::Int32, true),
+        ]));
+        let target = Arc::new(schema(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+        ]));
+        let batch = RecordBatch::try_new(
+            fetched,
+            vec![
+                Arc::new(TimestampMicrosecondArray::from(vec![Some(1_000), None])),
+                Arc::new(Int32Array::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+
+        let cast = cast_batch(&batch, &target).unwrap();
+        assert_eq!(cast.schema(), target);
+        let ids = cast
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(ids.values(), &[1, 2]);
+        let ts = cast
+            .column(1)
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(ts.value(0), 1_000);
+        assert!(ts.is_null(1));
+    }
+
+    #[test]
+    fn test_cast_batch_rejects_nulls_in_non_nullable_column() {
+        let fetched = Arc::new(schema(vec![Field::new("id", DataType::Int32, true)]));
+        let target = Arc::new(schema(vec![Field::new("id", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            fetched,
+            vec![Arc::new(Int32Array::from(vec![Some(1), None]))],
+        )
+        .unwrap();
+
+        assert!(cast_batch(&batch, &target).is_err());
+    }
+}
@@ -0,0 +1,467 @@
+//! Table statistics for query planning.
+//!
+//! Cached tables report exact statistics read from their parquet footers when the file is
+//! written: row count, and per-column null counts and min/max. They are stored in the
+//! catalog together with the cache URL they describe, so a table whose cache has moved on
+//! falls back to the row count and size estimates the source reported at discovery.
+
+use std::cmp::Ordering;
+use std::path::Path;
+
+use anyhow::Result;
+use datafusion::arrow::array::{Array, ArrayRef};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::common::stats::Precision;
+use datafusion::common::{ColumnStatistics, ScalarValue, Statistics};
+use datafusion::parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use datafusion::parquet::arrow::parquet_to_arrow_schema;
+use datafusion::parquet::file::metadata::{
+    ParquetMetaData, ParquetMetaDataReader, RowGroupMetaData,
+};
+use datafusion::parquet::schema::types::SchemaDescriptor;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::TableInfo;
+
+/// Length of the metadata size and magic bytes that end a parquet file
+const FOOTER_SIZE: usize = 8;
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Statistics of a table's cached parquet files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStatistics {
+    /// Cache URL these statistics describe
+    pub url: String,
+    pub num_rows: u64,
+    /// Uncompressed size of the data
+    pub total_byte_size: u64,
+    /// Compressed size of the data as stored. Unknown for statistics recorded before it
+    /// was tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_byte_size: Option<u64>,
+    /// Number of parquet files in the cache directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<u64>,
+    pub columns: Vec<CachedColumnStatistics>,
+}
+
+/// Statistics of one column. Bounds are kept in their display form and parsed back with
+/// the column's type; bounds that do not survive that round trip are not kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedColumnStatistics {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+}
+
+impl CacheStatistics {
+    /// Read statistics from the footer of a local parquet file. The URL is left empty until
+    /// the file has been moved to its final location.
+    pub fn from_parquet_file(path: &Path) -> Result<Self> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(path)?)?;
+        Ok(Self::from_metadata(builder.schema(), builder.metadata()))
+    }
+
+    /// Read statistics from the end of a parquet file that was streamed to storage. `tail`
+    /// must hold at least the whole footer.
+    pub fn from_parquet_footer(tail: &[u8]) -> Result<Self> {
+        let footer_start = tail
+            .len()
+            .checked_sub(FOOTER_SIZE)
+            .filter(|_| tail.ends_with(PARQUET_MAGIC))
+            .ok_or_else(|| anyhow::anyhow!("Not a parquet footer"))?;
+        let metadata_len =
+            u32::from_le_bytes(tail[footer_start..footer_start + 4].try_into()?) as usize;
+        let metadata_start = footer_start
+            .checked_sub(metadata_len)
+            .ok_or_else(|| anyhow::anyhow!("Parquet footer is incomplete"))?;
+
+        let metadata = ParquetMetaDataReader::decode_metadata(&tail[metadata_start..footer_start])?;
+        let file_metadata = metadata.file_metadata();
+        let schema = parquet_to_arrow_schema(
+            file_metadata.schema_descr(),
+            file_metadata.key_value_metadata(),
+        )?;
+        Ok(Self::from_metadata(&schema, &metadata))
+    }
+
+    fn from_metadata(schema: &Schema, metadata: &ParquetMetaData) -> Self {
+        let row_groups = metadata.row_groups();
+        let parquet_schema = metadata.file_metadata().schema_descr();
+
+        Self {
+            url: String::new(),
+            num_rows: row_groups.iter().map(|rg| rg.num_rows() as u64).sum(),
+            total_byte_size: row_groups
+                .iter()
+                .map(|rg| rg.total_byte_size() as u64)
+                .sum(),
+            compressed_byte_size: Some(
+                row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size() as u64)
+                    .sum(),
+            ),
+            file_count: Some(1),
+            columns: schema
+                .fields()
+                .iter()
+                .map(|field| column_statistics(field, schema, parquet_schema, row_groups))
+                .collect(),
+        }
+    }
+
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Statistics of a directory holding the files described by both `self` and `other`.
+    /// Columns missing from either side become unknown.
+    pub fn merge(self, other: Self, schema: &Schema) -> Self {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(
+                |field| match (self.column(field.name()), other.column(field.name())) {
+                    (Some(a), Some(b)) => CachedColumnStatistics {
+                        name: field.name().clone(),
+                        null_count: a.null_count.zip(b.null_count).map(|(a, b)| a + b),
+                        min: pick(&a.min, &b.min, field.data_type(), Ordering::Less),
+                        max: pick(&a.max, &b.max, field.data_type(), Ordering::Greater),
+                    },
+                    _ => CachedColumnStatistics::unknown(field.name()),
+                },
+            )
+            .collect();
+
+        Self {
+            url: other.url,
+            num_rows: self.num_rows + other.num_rows,
+            total_byte_size: self.total_byte_size + other.total_byte_size,
+            compressed_byte_size: self
+                .compressed_byte_size
+                .zip(other.compressed_byte_size)
+                .map(|(a, b)| a + b),
+            file_count: self.file_count.zip(other.file_count).map(|(a, b)| a + b),
+            columns,
+        }
+    }
+
+    fn column(&self, name: &str) -> Option<&CachedColumnStatistics> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// DataFusion statistics for `schema`, matching columns by name.
+    pub fn to_statistics(&self, schema: &Schema) -> Statistics {
+        let column_statistics = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let Some(column) = self.column(field.name()) else {
+                    return ColumnStatistics::new_unknown();
+                };
+                let bound = |value: &Option<String>| {
+                    match value.as_deref().and_then(|v| decode(v, field.data_type())) {
+                        // Parquet truncates long string statistics
+                        Some(value) if is_string(field.data_type()) => Precision::Inexact(value),
+                        Some(value) => Precision::Exact(value),
+                        None => Precision::Absent,
+                    }
+                };
+                ColumnStatistics {
+                    null_count: column
+                        .null_count
+                        .and_then(|n| usize::try_from(n).ok())
+                        .map_or(Precision::Absent, Precision::Exact),
+                    min_value: bound(&column.min),
+                    max_value: bound(&column.max),
+                    ..ColumnStatistics::new_unknown()
+                }
+            })
+            .collect();
+
+        Statistics {
+            num_rows: usize::try_from(self.num_rows).map_or(Precision::Absent, Precision::Exact),
+            total_byte_size: usize::try_from(self.total_byte_size)
+                .map_or(Precision::Absent, Precision::Inexact),
+            column_statistics,
+        }
+    }
+}
+
+impl CachedColumnStatistics {
+    fn unknown(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            null_count: None,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+/// Statistics for a table: exact ones while its cache matches the recorded statistics,
+/// otherwise the source's discovery estimates. None when neither is known.
+pub fn table_statistics(info: &TableInfo, schema: &Schema) -> Option<Statistics> {
+    let cached = info
+        .cache_stats_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<CacheStatistics>(json).ok())
+        .filter(|stats| info.parquet_path.as_deref() == Some(stats.url.as_str()));
+    if let Some(stats) = cached {
+        return Some(stats.to_statistics(schema));
+    }
+
+    if info.estimated_rows.is_none() && info.estimated_bytes.is_none() {
+        return None;
+    }
+    let estimate = |value: Option<i64>| {
+        value
+            .and_then(|v| usize::try_from(v).ok())
+            .map_or(Precision::Absent, Precision::Inexact)
+    };
+    Some(Statistics {
+        num_rows: estimate(info.estimated_rows),
+        total_byte_size: estimate(info.estimated_bytes),
+        column_statistics: Statistics::unknown_column(schema),
+    })
+}
+
+fn column_statistics(
+    field: &Field,
+    schema: &Schema,
+    parquet_schema: &SchemaDescriptor,
+    row_groups: &[RowGroupMetaData],
+) -> CachedColumnStatistics {
+    let Ok(converter) = StatisticsConverter::try_new(field.name(), schema, parquet_schema) else {
+        return CachedColumnStatistics::unknown(field.name());
+    };
+
+    // A null entry is a row group written without that statistic
+    let null_count = converter
+        .row_group_null_counts(row_groups)
+        .ok()
+        .filter(|counts| counts.null_count() == 0)
+        .map(|counts| counts.values().iter().sum());
+    let min = converter
+        .row_group_mins(row_groups)
+        .ok()
+        .and_then(|mins| extreme(&mins, Ordering::Less));
+    let max = converter
+        .row_group_maxes(row_groups)
+        .ok()
+        .and_then(|maxes| extreme(&maxes, Ordering::Greater));
+
+    CachedColumnStatistics {
+        name: field.name().clone(),
+        null_count,
+        min,
+        max,
+    }
+}
+
+/// The smallest (`Less`) or largest (`Greater`) of the per-row-group bounds, encoded. Any
+/// row group without a bound makes the result unknown.
+fn extreme(values: &ArrayRef, keep: Ordering) -> Option<String> {
+    if values.is_empty() || values.null_count() > 0 {
+        return None;
+    }
+
+    let mut best: Option<ScalarValue> = None;
+    for i in 0..values.len() {
+        let value = ScalarValue::try_from_array(values, i).ok()?;
+        best = match best {
+            Some(current) if value.partial_cmp(&current)? != keep => Some(current),
+            _ => Some(value),
+        };
+    }
+    encode(&best?)
+}
+
+fn pick(
+    a: &Option<String>,
+    b: &Option<String>,
+    data_type: &DataType,
+    keep: Ordering,
+) -> Option<String> {
+    let (a, b) = (a.as_ref()?, b.as_ref()?);
+    let ordering = decode(b, data_type)?.partial_cmp(&decode(a, data_type)?)?;
+    Some(if ordering == keep {
+        b.clone()
+    } else {
+        a.clone()
+    })
+}
+
+fn encode(value: &ScalarValue) -> Option<String> {
+    let text = value.to_string();
+    (decode(&text, &value.data_type()).as_ref() == Some(value)).then_some(text)
+}
+
+fn decode(text: &str, data_type: &DataType) -> Option<ScalarValue> {
+    ScalarValue::try_from_string(text.to_string(), data_type).ok()
+}
+
+fn is_string(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datafetch::StreamingParquetWriter;
+    use datafusion::arrow::array::{Int32Array, StringArray};
+    use datafusion::arrow::record_batch::RecordBatch;
+    use std::sync::Arc;
+
+    async fn write_file(path: &Path, ids: Vec<Option<i32>>, names: Vec<&str>) -> Schema {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int32Array::from(ids)),
+                Arc::new(StringArray::from(names)),
+            ],
+        )
+        .unwrap();
+
+        let mut writer = StreamingParquetWriter::new(path.to_path_buf());
+        writer.init(&schema).unwrap();
+        writer.write_batch(&batch).await.unwrap();
+        writer.close().await.unwrap();
+        schema
+    }
+
+    #[tokio::test]
+    async fn test_statistics_from_parquet_footer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        let schema = write_file(&path, vec![Some(3), None, Some(-7)], vec!["b", "a", "c"]).await;
+
+        let stats = CacheStatistics::from_parquet_file(&path).unwrap();
+        assert_eq!(stats.num_rows, 3);
+        assert_eq!(
+            stats.columns[0],
+            CachedColumnStatistics {
+                name: "id".to_string(),
+                null_count: Some(1),
+                min: Some("-7".to_string()),
+                max: Some("3".to_string()),
+            }
+        );
+
+        let statistics = stats.to_statistics(&schema);
+        assert_eq!(statistics.num_rows, Precision::Exact(3));
+        assert_eq!(
+            statistics.column_statistics[0].min_value,
+            Precision::Exact(ScalarValue::Int32(Some(-7)))
+        );
+        assert_eq!(
+            statistics.column_statistics[1].max_value,
+            Precision::Inexact(ScalarValue::Utf8(Some("c".to_string())))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_statistics_from_streamed_footer_match_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        write_file(&path, vec![Some(1), Some(2)], vec!["x", "y"]).await;
+
+        // Only the end of the file is kept while streaming
+        let data = std::fs::read(&path).unwrap();
+        let tail = &data[4..];
+        assert_eq!(
+            CacheStatistics::from_parquet_footer(tail).unwrap(),
+            CacheStatistics::from_parquet_file(&path).unwrap()
+        );
+        assert!(CacheStatistics::from_parquet_footer(&data[data.len() - 4..]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_merge_combines_appended_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let (first, second) = (dir.path().join("1.parquet"), dir.path().join("2.parquet"));
+        let schema = write_file(&first, vec![Some(1), Some(5)], vec!["a", "b"]).await;
+        write_file(&second, vec![Some(9), None], vec!["c", "d"]).await;
+
+        let merged = CacheStatistics::from_parquet_file(&first)
+            .unwrap()
+            .with_url("file:///cache/v1")
+            .merge(
+                CacheStatistics::from_parquet_file(&second)
+                    .unwrap()
+                    .with_url("file:///cache/v1"),
+                &schema,
+            );
+
+        assert_eq!(merged.num_rows, 4);
+        assert_eq!(merged.file_count, Some(2));
+        assert!(merged.compressed_byte_size.unwrap() > 0);
+        assert_eq!(merged.columns[0].null_count, Some(1));
+        assert_eq!(merged.columns[0].min.as_deref(), Some("1"));
+        assert_eq!(merged.columns[0].max.as_deref(), Some("9"));
+        assert_eq!(merged.columns[1].max.as_deref(), Some("d"));
+    }
+
+    #[test]
+    fn test_table_statistics_falls_back_to_estimates() {
+        let schema = Schema::new(vec![Field::new("id", DataType::Int32, true)]);
+        let mut info = TableInfo {
+            id: 1,
+            connection_id: 1,
+            schema_name: "public".to_string(),
+            table_name: "orders".to_string(),
+            parquet_path: None,
+            last_sync: None,
+            arrow_schema_json: None,
+            stale_since: None,
+            table_type: "BASE TABLE".to_string(),
+            estimated_rows: None,
+            estimated_bytes: None,
+            cache_stats_json: None,
+            last_accessed_at: None,
+            access_count: 0,
+        };
+        assert!(table_statistics(&info, &schema).is_none());
+
+        info.estimated_rows = Some(1000);
+        let stats = table_statistics(&info, &schema).unwrap();
+        assert_eq!(stats.num_rows, Precision::Inexact(1000));
+        assert_eq!(stats.total_byte_size, Precision::Absent);
+
+        // Statistics recorded for a previous cache version are ignored
+        let cached = CacheStatistics {
+            url: "file:///cache/v1".to_string(),
+            num_rows: 10,
+            total_byte_size: 100,
+            compressed_byte_size: None,
+            file_count: None,
+            columns: vec![],
+        };
+        info.cache_stats_json = Some(serde_json::to_string(&cached).unwrap());
+        info.parquet_path = Some("file:///cache/v2".to_string());
+        assert_eq!(
+            table_statistics(&info, &schema).unwrap().num_rows,
+            Precision::Inexact(1000)
+        );
+
+        info.parquet_path = Some("file:///cache/v1".to_string());
+        assert_eq!(
+            table_statistics(&info, &schema).unwrap().num_rows,
+            Precision::Exact(10)
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use datafusion::arrow::datatypes::{DataType as ArrowDataType, Field, Schema};
 use std::sync::Arc;
 
+use crate::catalog::TableKind;
+
 /// Metadata for a discovered table
 #[derive(Debug, Clone)]
 pub struct TableMetadata {
@@ -10,6 +13,10 @@ pub struct TableMetadata {
     pub table_name: String,
     pub table_type: String,
     pub columns: Vec<ColumnMetadata>,
+    /// The source's estimate of the table's row count, if it keeps one
+    pub row_count: Option<u64>,
+    /// The source's estimate of the table's storage size in bytes
+    pub size_bytes: Option<u64>,
 }
 
 /// Metadata for a table column
@@ -33,6 +40,67 @@ impl TableMetadata {
     }
 }
 
+/// How a snapshot-versioned table (e.g., Iceberg) should be refreshed, relative to the
+/// snapshot that is currently cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotRefresh {
+    /// The cached snapshot is still current; nothing needs to be fetched.
+    Unchanged { snapshot_id: i64 },
+    /// Every snapshot since the cached one only appended data files, so only those
+    /// files need to be fetched.
+    Append {
+        from_snapshot_id: i64,
+        snapshot_id: i64,
+    },
+    /// The table must be re-read in full. `snapshot_id` is None for tables that have
+    /// no snapshot yet.
+    Full { snapshot_id: Option<i64> },
+}
+
+impl SnapshotRefresh {
+    /// Snapshot the cache will reflect once this refresh is applied.
+    pub fn snapshot_id(&self) -> Option<i64> {
+        match self {
+            SnapshotRefresh::Unchanged { snapshot_id }
+            | SnapshotRefresh::Append { snapshot_id, .. } => Some(*snapshot_id),
+            SnapshotRefresh::Full { snapshot_id } => *snapshot_id,
+        }
+    }
+}
+
+/// Whether and when tables of one kind are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Cache on first query; every refresh re-fetches the table.
+    Cache,
+    /// Never cache; every query reads the table from the source.
+    Live,
+    /// Cache on first query; refreshes re-fetch only when the source reports the table was
+    /// refreshed since it was cached. Sources that cannot report this always re-fetch.
+    OnSourceRefresh,
+}
+
+impl CachePolicy {
+    /// Views are read live, materialized views follow their source refreshes, everything
+    /// else is cached.
+    pub fn default_for(kind: TableKind) -> Self {
+        match kind {
+            TableKind::View => CachePolicy::Live,
+            TableKind::MaterializedView => CachePolicy::OnSourceRefresh,
+            TableKind::BaseTable | TableKind::External | TableKind::Stream => CachePolicy::Cache,
+        }
+    }
+}
+
+/// Point in a table's history to read for time travel queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeTravel {
+    /// A specific snapshot ID
+    Snapshot(i64),
+    /// The snapshot that was current at this instant
+    Timestamp(DateTime<Utc>),
+}
+
 /// Deserialize an Arrow Schema from JSON string
 pub fn deserialize_arrow_schema(json: &str) -> Result<Arc<Schema>> {
     let schema: Schema = serde_json::from_str(json)?;
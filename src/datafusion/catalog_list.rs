@@ -0,0 +1,260 @@
+use super::block_on;
+use super::catalog_provider::RuntimeCatalogProvider;
+use crate::catalog::{CatalogManager, ConnectionInfo};
+use crate::datafetch::FetchOrchestrator;
+use crate::source::Source;
+use anyhow::Result;
+use datafusion::catalog::{CatalogProvider, CatalogProviderList};
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Catalog list that resolves connection catalogs from the catalog store.
+///
+/// The connections are read from the catalog store at most once per refresh interval, and
+/// immediately after [`invalidate`](Self::invalidate). Connections added, renamed or removed
+/// by other instances sharing the catalog store are therefore seen within one interval.
+/// A connection's provider is kept between lookups and rebuilt when its name,
+/// configuration or tables change, or when it is invalidated.
+/// Catalogs registered through [`CatalogProviderList::register_catalog`] (the `runtimedb`
+/// system catalog and DataFusion's default catalog) are held in memory and take precedence
+/// over connections of the same name.
+#[derive(Debug)]
+pub struct RuntimeCatalogProviderList {
+    catalog: Arc<dyn CatalogManager>,
+    orchestrator: Arc<FetchOrchestrator>,
+    refresh_interval: Duration,
+    registered: RwLock<HashMap<String, Arc<dyn CatalogProvider>>>,
+    /// Connections as last read from the catalog store; None when they must be read again
+    snapshot: RwLock<Option<Arc<ConnectionSnapshot>>>,
+    providers: RwLock<HashMap<i32, ConnectionCatalog>>,
+}
+
+#[derive(Debug)]
+struct ConnectionSnapshot {
+    loaded_at: Instant,
+    /// Connections by name, with the fingerprint of their tables
+    connections: HashMap<String, (ConnectionInfo, u64)>,
+}
+
+/// Provider of one connection, with the state it was built from.
+#[derive(Debug)]
+struct ConnectionCatalog {
+    name: String,
+    config_json: String,
+    tables_fingerprint: u64,
+    provider: Arc<dyn CatalogProvider>,
+}
+
+impl RuntimeCatalogProviderList {
+    pub fn new(
+        catalog: Arc<dyn CatalogManager>,
+        orchestrator: Arc<FetchOrchestrator>,
+        refresh_interval: Duration,
+    ) -> Self {
+        Self {
+            catalog,
+            orchestrator,
+            refresh_interval,
+            registered: RwLock::new(HashMap::new()),
+            snapshot: RwLock::new(None),
+            providers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Drop the kept provider of a connection, so the next lookup builds a fresh one
+    /// without open file handles or cached table providers, and read the connections
+    /// again on the next lookup.
+    pub fn invalidate(&self, connection_id: i32) {
+        self.providers
+            .write()
+            .expect("catalog lock poisoned")
+            .remove(&connection_id);
+        *self.snapshot.write().expect("catalog lock poisoned") = None;
+    }
+
+    /// Read the connections again if the refresh interval has passed. Called before a
+    /// query is planned, so failures to read the catalog store fail the query instead of
+    /// hiding its catalogs.
+    pub async fn ensure_fresh(&self) -> Result<()> {
+        if self.current_snapshot().is_none() {
+            self.refresh().await?;
+        }
+        Ok(())
+    }
+
+    /// The connections, unless they are due to be read again.
+    fn current_snapshot(&self) -> Option<Arc<ConnectionSnapshot>> {
+        self.snapshot
+            .read()
+            .expect("catalog lock poisoned")
+            .clone()
+            .filter(|snapshot| snapshot.loaded_at.elapsed() < self.refresh_interval)
+    }
+
+    /// Read the connections and their tables, and drop providers built from outdated state.
+    async fn refresh(&self) -> Result<Arc<ConnectionSnapshot>> {
+        let connections = self.catalog.list_connections().await?;
+        let mut tables_by_connection: HashMap<i32, Vec<_>> = HashMap::new();
+        for table in self.catalog.list_tables(None).await? {
+            tables_by_connection
+                .entry(table.connection_id)
+                .or_default()
+                .push(table);
+        }
+
+        let snapshot = Arc::new(ConnectionSnapshot {
+            loaded_at: Instant::now(),
+            connections: connections
+                .into_iter()
+                .map(|conn| {
+                    // Changes when tables are discovered, removed, re-created or change schema
+                    let mut tables = tables_by_connection.remove(&conn.id).unwrap_or_default();
+                    tables.sort_by_key(|t| t.id);
+                    let mut hasher = DefaultHasher::new();
+                    for table in &tables {
+                        (
+                            table.id,
+                            &table.schema_name,
+                            &table.table_name,
+                            &table.arrow_schema_json,
+                        )
+                            .hash(&mut hasher);
+                    }
+                    (conn.name.clone(), (conn, hasher.finish()))
+                })
+                .collect(),
+        });
+
+        self.providers
+            .write()
+            .expect("catalog lock poisoned")
+            .retain(|id, kept| {
+                snapshot
+                    .connections
+                    .get(&kept.name)
+                    .is_some_and(|(conn, tables)| {
+                        conn.id == *id
+                            && conn.config_json == kept.config_json
+                            && *tables == kept.tables_fingerprint
+                    })
+            });
+        *self.snapshot.write().expect("catalog lock poisoned") = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// The connections for a lookup, read again once they are outdated. Lookups are sync,
+    /// so this blocks; queries avoid it through [`ensure_fresh`](Self::ensure_fresh).
+    /// If they cannot be read, the last known connections are used.
+    fn snapshot(&self) -> Option<Arc<ConnectionSnapshot>> {
+        if let Some(snapshot) = self.current_snapshot() {
+            return Some(snapshot);
+        }
+        match block_on(self.refresh()) {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                tracing::warn!("Failed to read connections from the catalog store: {}", e);
+                self.snapshot.read().expect("catalog lock poisoned").clone()
+            }
+        }
+    }
+
+    fn connection_provider(
+        &self,
+        conn: &ConnectionInfo,
+        tables_fingerprint: u64,
+    ) -> Option<Arc<dyn CatalogProvider>> {
+        if let Some(kept) = self
+            .providers
+            .read()
+            .expect("catalog lock poisoned")
+            .get(&conn.id)
+            .filter(|kept| {
+                kept.name == conn.name
+                    && kept.config_json == conn.config_json
+                    && kept.tables_fingerprint == tables_fingerprint
+            })
+        {
+            return Some(kept.provider.clone());
+        }
+
+        let source: Source = match serde_json::from_str(&conn.config_json) {
+            Ok(source) => source,
+            Err(e) => {
+                tracing::warn!("Invalid configuration of connection '{}': {}", conn.name, e);
+                return None;
+            }
+        };
+        let provider = Arc::new(RuntimeCatalogProvider::new(
+            conn.id,
+            conn.name.clone(),
+            Arc::new(source),
+            self.catalog.clone(),
+            self.orchestrator.clone(),
+        )) as Arc<dyn CatalogProvider>;
+
+        self.providers
+            .write()
+            .expect("catalog lock poisoned")
+            .insert(
+                conn.id,
+                ConnectionCatalog {
+                    name: conn.name.clone(),
+                    config_json: conn.config_json.clone(),
+                    tables_fingerprint,
+                    provider: provider.clone(),
+                },
+            );
+        Some(provider)
+    }
+}
+
+impl CatalogProviderList for RuntimeCatalogProviderList {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn register_catalog(
+        &self,
+        name: String,
+        catalog: Arc<dyn CatalogProvider>,
+    ) -> Option<Arc<dyn CatalogProvider>> {
+        self.registered
+            .write()
+            .expect("catalog lock poisoned")
+            .insert(name, catalog)
+    }
+
+    fn catalog_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .registered
+            .read()
+            .expect("catalog lock poisoned")
+            .keys()
+            .cloned()
+            .collect();
+        if let Some(snapshot) = self.snapshot() {
+            names.extend(snapshot.connections.keys().cloned());
+        }
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn catalog(&self, name: &str) -> Option<Arc<dyn CatalogProvider>> {
+        if let Some(catalog) = self
+            .registered
+            .read()
+            .expect("catalog lock poisoned")
+            .get(name)
+        {
+            return Some(catalog.clone());
+        }
+
+        let snapshot = self.snapshot()?;
+        let (conn, tables_fingerprint) = snapshot.connections.get(name)?;
+        self.connection_provider(conn, *tables_fingerprint)
+    }
+}
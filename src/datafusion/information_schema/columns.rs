@@ -37,6 +37,8 @@ impl ColumnsTableProvider {
             Field::new("ordinal_position", DataType::Int32, false),
             Field::new("data_type", DataType::Utf8, false),
             Field::new("is_nullable", DataType::Utf8, false),
+            Field::new("numeric_precision", DataType::Int32, true),
+            Field::new("numeric_scale", DataType::Int32, true),
         ]))
     }
 
@@ -66,6 +68,8 @@ impl ColumnsTableProvider {
         let mut ordinal_builder = Int32Builder::new();
         let mut type_builder = StringBuilder::new();
         let mut nullable_builder = StringBuilder::new();
+        let mut precision_builder = Int32Builder::new();
+        let mut scale_builder = Int32Builder::new();
 
         for table in tables {
             let catalog_name = conn_map
@@ -89,6 +93,9 @@ impl ColumnsTableProvider {
                             } else {
                                 "NO"
                             });
+                            let (precision, scale) = numeric_precision_scale(field.data_type());
+                            precision_builder.append_option(precision);
+                            scale_builder.append_option(scale);
                         }
                     }
                     Err(e) => {
@@ -111,6 +118,8 @@ impl ColumnsTableProvider {
                 Arc::new(ordinal_builder.finish()),
                 Arc::new(type_builder.finish()),
                 Arc::new(nullable_builder.finish()),
+                Arc::new(precision_builder.finish()),
+                Arc::new(scale_builder.finish()),
             ],
         )?;
 
@@ -118,6 +127,17 @@ impl ColumnsTableProvider {
     }
 }
 
+/// Precision and scale reported for decimal columns; NULL for every other type.
+fn numeric_precision_scale(data_type: &DataType) -> (Option<i32>, Option<i32>) {
+    match data_type {
+        DataType::Decimal32(p, s)
+        | DataType::Decimal64(p, s)
+        | DataType::Decimal128(p, s)
+        | DataType::Decimal256(p, s) => (Some(*p as i32), Some(*s as i32)),
+        _ => (None, None),
+    }
+}
+
 #[async_trait]
 impl TableProvider for ColumnsTableProvider {
     fn as_any(&self) -> &dyn Any {
@@ -1,13 +1,18 @@
 mod columns;
+mod schema_changes;
+mod table_cache;
 mod tables;
 
 use crate::catalog::CatalogManager;
+use crate::datafetch::FetchOrchestrator;
 use async_trait::async_trait;
 use columns::ColumnsTableProvider;
 use datafusion::catalog::SchemaProvider;
 use datafusion::datasource::TableProvider;
+use schema_changes::SchemaChangesTableProvider;
 use std::any::Any;
 use std::sync::Arc;
+use table_cache::TableCacheTableProvider;
 use tables::TablesTableProvider;
 
 /// Schema provider for `runtimedb.information_schema`.
@@ -18,13 +23,17 @@ use tables::TablesTableProvider;
 pub struct InformationSchemaProvider {
     tables: Arc<TablesTableProvider>,
     columns: Arc<ColumnsTableProvider>,
+    schema_changes: Arc<SchemaChangesTableProvider>,
+    table_cache: Arc<TableCacheTableProvider>,
 }
 
 impl InformationSchemaProvider {
-    pub fn new(catalog: Arc<dyn CatalogManager>) -> Self {
+    pub fn new(catalog: Arc<dyn CatalogManager>, orchestrator: Arc<FetchOrchestrator>) -> Self {
         Self {
             tables: Arc::new(TablesTableProvider::new(catalog.clone())),
-            columns: Arc::new(ColumnsTableProvider::new(catalog)),
+            columns: Arc::new(ColumnsTableProvider::new(catalog.clone())),
+            schema_changes: Arc::new(SchemaChangesTableProvider::new(catalog.clone())),
+            table_cache: Arc::new(TableCacheTableProvider::new(catalog, orchestrator)),
         }
     }
 }
@@ -36,18 +45,28 @@ impl SchemaProvider for InformationSchemaProvider {
     }
 
     fn table_names(&self) -> Vec<String> {
-        vec!["tables".to_string(), "columns".to_string()]
+        vec![
+            "tables".to_string(),
+            "columns".to_string(),
+            "schema_changes".to_string(),
+            "table_cache".to_string(),
+        ]
     }
 
     async fn table(&self, name: &str) -> datafusion::error::Result<Option<Arc<dyn TableProvider>>> {
         match name {
             "tables" => Ok(Some(self.tables.clone())),
             "columns" => Ok(Some(self.columns.clone())),
+            "schema_changes" => Ok(Some(self.schema_changes.clone())),
+            "table_cache" => Ok(Some(self.table_cache.clone())),
             _ => Ok(None),
         }
     }
 
     fn table_exist(&self, name: &str) -> bool {
-        matches!(name, "tables" | "columns")
+        matches!(
+            name,
+            "tables" | "columns" | "schema_changes" | "table_cache"
+        )
     }
 }
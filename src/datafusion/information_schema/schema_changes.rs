@@ -0,0 +1,138 @@
+use crate::catalog::{CatalogManager, ColumnChange};
+use async_trait::async_trait;
+use datafusion::arrow::array::{Int32Builder, StringBuilder};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::catalog::Session;
+use datafusion::datasource::{MemTable, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::TableType;
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::prelude::Expr;
+use std::any::Any;
+use std::sync::Arc;
+
+/// Virtual table provider for `information_schema.schema_changes`.
+///
+/// One row per column change in each table's schema history, including changes that were
+/// blocked by the connection's schema change policy.
+#[derive(Debug)]
+pub struct SchemaChangesTableProvider {
+    catalog: Arc<dyn CatalogManager>,
+}
+
+impl SchemaChangesTableProvider {
+    pub fn new(catalog: Arc<dyn CatalogManager>) -> Self {
+        Self { catalog }
+    }
+
+    fn schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("table_catalog", DataType::Utf8, false),
+            Field::new("table_schema", DataType::Utf8, false),
+            Field::new("table_name", DataType::Utf8, false),
+            Field::new("version", DataType::Int32, false),
+            Field::new("status", DataType::Utf8, false),
+            Field::new("change_type", DataType::Utf8, false),
+            Field::new("column_name", DataType::Utf8, false),
+            Field::new("old_data_type", DataType::Utf8, true),
+            Field::new("new_data_type", DataType::Utf8, true),
+            Field::new("changed_at", DataType::Utf8, true),
+        ]))
+    }
+
+    async fn build_record_batch(&self) -> Result<RecordBatch> {
+        let connections = self
+            .catalog
+            .list_connections()
+            .await
+            .map_err(|e| DataFusionError::Execution(e.to_string()))?;
+
+        let conn_map: std::collections::HashMap<i32, String> =
+            connections.into_iter().map(|c| (c.id, c.name)).collect();
+
+        let versions = self
+            .catalog
+            .list_schema_versions(None)
+            .await
+            .map_err(|e| DataFusionError::Execution(e.to_string()))?;
+
+        let mut catalog_builder = StringBuilder::new();
+        let mut schema_builder = StringBuilder::new();
+        let mut name_builder = StringBuilder::new();
+        let mut version_builder = Int32Builder::new();
+        let mut status_builder = StringBuilder::new();
+        let mut change_builder = StringBuilder::new();
+        let mut column_builder = StringBuilder::new();
+        let mut old_type_builder = StringBuilder::new();
+        let mut new_type_builder = StringBuilder::new();
+        let mut changed_at_builder = StringBuilder::new();
+
+        for version in versions {
+            let catalog_name = conn_map
+                .get(&version.connection_id)
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string());
+            let changes: Vec<ColumnChange> = serde_json::from_str(&version.changes_json)
+                .map_err(|e| DataFusionError::Execution(e.to_string()))?;
+
+            for change in changes {
+                catalog_builder.append_value(&catalog_name);
+                schema_builder.append_value(&version.schema_name);
+                name_builder.append_value(&version.table_name);
+                version_builder.append_value(version.version);
+                status_builder.append_value(&version.status);
+                change_builder.append_value(change.change.as_str());
+                column_builder.append_value(&change.column);
+                old_type_builder.append_option(change.old_type);
+                new_type_builder.append_option(change.new_type);
+                changed_at_builder.append_option(version.created_at.as_deref());
+            }
+        }
+
+        let batch = RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(catalog_builder.finish()),
+                Arc::new(schema_builder.finish()),
+                Arc::new(name_builder.finish()),
+                Arc::new(version_builder.finish()),
+                Arc::new(status_builder.finish()),
+                Arc::new(change_builder.finish()),
+                Arc::new(column_builder.finish()),
+                Arc::new(old_type_builder.finish()),
+                Arc::new(new_type_builder.finish()),
+                Arc::new(changed_at_builder.finish()),
+            ],
+        )?;
+
+        Ok(batch)
+    }
+}
+
+#[async_trait]
+impl TableProvider for SchemaChangesTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        Self::schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let batch = self.build_record_batch().await?;
+        let mem_table = MemTable::try_new(Self::schema(), vec![vec![batch]])?;
+        mem_table.scan(state, projection, filters, limit).await
+    }
+}
@@ -0,0 +1,181 @@
+use crate::catalog::{CatalogManager, TableInfo};
+use crate::datafetch::{cached_version, CachePolicy, CacheStatistics, FetchOrchestrator};
+use crate::source::Source;
+use async_trait::async_trait;
+use datafusion::arrow::array::{BooleanBuilder, Int64Builder, StringBuilder};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::catalog::Session;
+use datafusion::datasource::{MemTable, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::TableType;
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::prelude::Expr;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Virtual table provider for `information_schema.table_cache`.
+///
+/// One row per table with the state of its cache: whether and when it was synced, the
+/// size and version of the cached data, when and how often it was queried and how it is
+/// synced.
+/// Sizes come from the statistics recorded when the cache was written and are null when
+/// those do not describe the current version.
+#[derive(Debug)]
+pub struct TableCacheTableProvider {
+    catalog: Arc<dyn CatalogManager>,
+    orchestrator: Arc<FetchOrchestrator>,
+}
+
+impl TableCacheTableProvider {
+    pub fn new(catalog: Arc<dyn CatalogManager>, orchestrator: Arc<FetchOrchestrator>) -> Self {
+        Self {
+            catalog,
+            orchestrator,
+        }
+    }
+
+    fn schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("table_catalog", DataType::Utf8, false),
+            Field::new("table_schema", DataType::Utf8, false),
+            Field::new("table_name", DataType::Utf8, false),
+            Field::new("is_cached", DataType::Boolean, false),
+            Field::new("sync_mode", DataType::Utf8, false),
+            Field::new("last_sync", DataType::Utf8, true),
+            Field::new("last_accessed_at", DataType::Utf8, true),
+            Field::new("access_count", DataType::Int64, false),
+            Field::new("cached_rows", DataType::Int64, true),
+            Field::new("cached_bytes", DataType::Int64, true),
+            Field::new("file_count", DataType::Int64, true),
+            Field::new("version_id", DataType::Utf8, true),
+        ]))
+    }
+
+    /// How refreshes bring the table up to date: `live` tables are never cached, `append`
+    /// adds new stream data, `snapshot` follows source snapshots, `on_source_refresh`
+    /// re-fetches only when the source changed and `full` re-fetches every time.
+    fn sync_mode(&self, source: Option<&Source>, table: &TableInfo) -> &'static str {
+        match self.orchestrator.cache_policy(table.kind()) {
+            CachePolicy::Live => "live",
+            _ if source.is_some_and(Source::is_streaming) => "append",
+            _ if source.is_some_and(Source::has_snapshots) => "snapshot",
+            CachePolicy::OnSourceRefresh => "on_source_refresh",
+            CachePolicy::Cache => "full",
+        }
+    }
+
+    async fn build_record_batch(&self) -> Result<RecordBatch> {
+        let connections = self
+            .catalog
+            .list_connections()
+            .await
+            .map_err(|e| DataFusionError::Execution(e.to_string()))?;
+
+        let conn_map: HashMap<i32, (String, Option<Source>)> = connections
+            .into_iter()
+            .map(|c| {
+                let source = serde_json::from_str(&c.config_json).ok();
+                (c.id, (c.name, source))
+            })
+            .collect();
+
+        // Include scans not yet written to the catalog
+        if let Err(e) = self.orchestrator.flush_table_accesses().await {
+            tracing::warn!("Failed to record table accesses: {}", e);
+        }
+
+        let tables = self
+            .catalog
+            .list_tables(None)
+            .await
+            .map_err(|e| DataFusionError::Execution(e.to_string()))?;
+
+        let mut catalog_builder = StringBuilder::new();
+        let mut schema_builder = StringBuilder::new();
+        let mut name_builder = StringBuilder::new();
+        let mut cached_builder = BooleanBuilder::new();
+        let mut mode_builder = StringBuilder::new();
+        let mut last_sync_builder = StringBuilder::new();
+        let mut accessed_builder = StringBuilder::new();
+        let mut access_count_builder = Int64Builder::new();
+        let mut rows_builder = Int64Builder::new();
+        let mut bytes_builder = Int64Builder::new();
+        let mut files_builder = Int64Builder::new();
+        let mut version_builder = StringBuilder::new();
+
+        for table in tables {
+            let (catalog_name, source) = conn_map
+                .get(&table.connection_id)
+                .map(|(name, source)| (name.as_str(), source.as_ref()))
+                .unwrap_or(("unknown", None));
+            let stats = table
+                .cache_stats_json
+                .as_deref()
+                .and_then(|json| serde_json::from_str::<CacheStatistics>(json).ok())
+                .filter(|stats| table.parquet_path.as_deref() == Some(stats.url.as_str()));
+            let count = |value: Option<u64>| value.and_then(|v| i64::try_from(v).ok());
+
+            catalog_builder.append_value(catalog_name);
+            schema_builder.append_value(&table.schema_name);
+            name_builder.append_value(&table.table_name);
+            cached_builder.append_value(table.parquet_path.is_some());
+            mode_builder.append_value(self.sync_mode(source, &table));
+            last_sync_builder.append_option(table.last_sync.as_deref());
+            accessed_builder.append_option(table.last_accessed_at.as_deref());
+            access_count_builder.append_value(table.access_count);
+            rows_builder.append_option(count(stats.as_ref().map(|s| s.num_rows)));
+            bytes_builder.append_option(count(stats.as_ref().and_then(|s| s.compressed_byte_size)));
+            files_builder.append_option(count(stats.as_ref().and_then(|s| s.file_count)));
+            version_builder.append_option(table.parquet_path.as_deref().and_then(cached_version));
+        }
+
+        let batch = RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(catalog_builder.finish()),
+                Arc::new(schema_builder.finish()),
+                Arc::new(name_builder.finish()),
+                Arc::new(cached_builder.finish()),
+                Arc::new(mode_builder.finish()),
+                Arc::new(last_sync_builder.finish()),
+                Arc::new(accessed_builder.finish()),
+                Arc::new(access_count_builder.finish()),
+                Arc::new(rows_builder.finish()),
+                Arc::new(bytes_builder.finish()),
+                Arc::new(files_builder.finish()),
+                Arc::new(version_builder.finish()),
+            ],
+        )?;
+
+        Ok(batch)
+    }
+}
+
+#[async_trait]
+impl TableProvider for TableCacheTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        Self::schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let batch = self.build_record_batch().await?;
+        let mem_table = MemTable::try_new(Self::schema(), vec![vec![batch]])?;
+        mem_table.scan(state, projection, filters, limit).await
+    }
+}
@@ -68,7 +68,7 @@ impl TablesTableProvider {
             catalog_builder.append_value(&catalog_name);
             schema_builder.append_value(&table.schema_name);
             name_builder.append_value(&table.table_name);
-            type_builder.append_value("BASE TABLE");
+            type_builder.append_value(&table.table_type);
         }
 
         let batch = RecordBatch::try_new(
@@ -1,15 +1,22 @@
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::catalog::streaming::StreamingTable;
 use datafusion::catalog::Session;
+use datafusion::common::Statistics;
 use datafusion::datasource::{TableProvider, TableType};
 use datafusion::error::DataFusionError;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
 use datafusion::logical_expr::{Expr, TableProviderFilterPushDown};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream;
 use datafusion::physical_plan::ExecutionPlan;
+use futures::StreamExt;
 use std::any::Any;
 use std::sync::Arc;
 
-use crate::catalog::CatalogManager;
-use crate::datafetch::FetchOrchestrator;
+use super::block_on;
+use crate::catalog::{CatalogManager, TableInfo};
+use crate::datafetch::{table_statistics, CachePolicy, FetchOrchestrator};
 use crate::source::Source;
 
 /// A lazy table provider that defers data fetching until scan() is called.
@@ -88,21 +95,39 @@ impl LazyTableProvider {
         table.scan(state, projection, filters, limit).await
     }
 
-    /// Fetch the table data and update catalog
+    /// Stream the table from its source, bypassing the cache. The fetch starts when the
+    /// plan is executed and is stopped when the query stops reading.
+    async fn read_live(
+        &self,
+        table_info: &TableInfo,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> datafusion::common::Result<Arc<dyn ExecutionPlan>> {
+        let partition = Arc::new(LivePartition {
+            schema: self.schema.clone(),
+            source: self.source.clone(),
+            orchestrator: self.orchestrator.clone(),
+            table_info: table_info.clone(),
+        });
+
+        StreamingTable::try_new(self.schema.clone(), vec![partition])?
+            .scan(state, projection, filters, limit)
+            .await
+    }
+
+    /// Fetch the table data and update catalog, unless a concurrent fetch cached it first
     async fn fetch_and_cache(&self) -> Result<String, DataFusionError> {
-        let (url, _row_count) = self
-            .orchestrator
-            .cache_table(
+        self.orchestrator
+            .ensure_cached(
                 &self.source,
                 self.connection_id,
                 &self.schema_name,
                 &self.table_name,
             )
             .await
-            .map_err(|e| {
-                DataFusionError::External(format!("Failed to cache table: {}", e).into())
-            })?;
-        Ok(url)
+            .map_err(|e| DataFusionError::External(format!("Failed to cache table: {}", e).into()))
     }
 }
 
@@ -121,6 +146,19 @@ impl TableProvider for LazyTableProvider {
         TableType::Base
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        // Exact for the current cache, otherwise the source's estimates.
+        // Uses block_on since TableProvider::statistics is sync
+        let info = block_on(self.catalog.get_table(
+            self.connection_id,
+            &self.schema_name,
+            &self.table_name,
+        ))
+        .ok()
+        .flatten()?;
+        table_statistics(&info, &self.schema)
+    }
+
     async fn scan(
         &self,
         state: &dyn Session,
@@ -140,16 +178,24 @@ impl TableProvider for LazyTableProvider {
                 DataFusionError::External("Table not found in catalog".to_string().into())
             })?;
 
-        let parquet_url = if let Some(path) = table_info.parquet_path {
-            // Already cached, use existing path
-            if path.starts_with("file://") || path.starts_with("s3://") {
-                path
-            } else {
-                format!("file://{}", path)
-            }
-        } else {
+        self.orchestrator.record_table_access(table_info.id);
+
+        if self.orchestrator.cache_policy(table_info.kind()) == CachePolicy::Live {
+            return self
+                .read_live(&table_info, state, projection, filters, limit)
+                .await;
+        }
+
+        let path = match table_info.parquet_path {
+            Some(path) => path,
             // Not cached, fetch now
-            self.fetch_and_cache().await?
+            None => self.fetch_and_cache().await?,
+        };
+        // Only bare local paths need a scheme
+        let parquet_url = if path.contains("://") {
+            path
+        } else {
+            format!("file://{}", path)
         };
 
         // Load the parquet file and create execution plan with projection, filter, and limit pushdown
@@ -165,3 +211,52 @@ impl TableProvider for LazyTableProvider {
         Ok(vec![TableProviderFilterPushDown::Inexact; filters.len()])
     }
 }
+
+/// The single partition of a live table scan, streaming batches as the source returns them.
+#[derive(Debug)]
+struct LivePartition {
+    schema: SchemaRef,
+    source: Arc<Source>,
+    orchestrator: Arc<FetchOrchestrator>,
+    table_info: TableInfo,
+}
+
+impl PartitionStream for LivePartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        // A small buffer keeps the fetch only a few batches ahead of the query
+        let (sender, receiver) = tokio::sync::mpsc::channel(2);
+        let orchestrator = self.orchestrator.clone();
+        let source = self.source.clone();
+        let table_info = self.table_info.clone();
+        let fetch = tokio::spawn(async move {
+            orchestrator
+                .read_table_live(&source, &table_info, sender)
+                .await
+        });
+
+        let batches = futures::stream::unfold(receiver, |mut receiver| async move {
+            let batch = receiver.recv().await?;
+            Some((Ok(batch), receiver))
+        });
+        // Once the fetch has sent its last batch, report how it ended
+        let outcome = futures::stream::once(async move {
+            match fetch.await {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(Err(DataFusionError::External(
+                    format!("Failed to read table: {}", e).into(),
+                ))),
+                Err(e) => Some(Err(DataFusionError::External(e.into()))),
+            }
+        })
+        .filter_map(futures::future::ready);
+
+        Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            batches.chain(outcome),
+        ))
+    }
+}
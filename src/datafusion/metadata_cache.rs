@@ -0,0 +1,593 @@
+//! Shared cache of file listings and parquet statistics for cached tables.
+//!
+//! Every scan of a cached table lists its version directory and reads the footer of each
+//! parquet file to collect statistics. Version directories are immutable once the catalog
+//! points at them (refreshes and appends write a new version instead of rewriting one), so
+//! listings are kept in memory keyed by directory. Statistics are keyed by the file's path
+//! together with its size, modification time and ETag, so a file rewritten in place (for
+//! example by another instance sharing the storage) is read again rather than served from
+//! outdated entries. The engine invalidates a version when a refresh swaps it out, and when
+//! its table is purged.
+
+use chrono::{DateTime, Utc};
+use datafusion::common::{ColumnStatistics, Statistics};
+use datafusion::datasource::listing::ListingTableUrl;
+use datafusion::execution::cache::cache_manager::CacheManagerConfig;
+use datafusion::execution::cache::CacheAccessor;
+use object_store::path::Path;
+use object_store::ObjectMeta;
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Default memory budget for cached listings and statistics (64 MiB).
+pub const DEFAULT_METADATA_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Hit-rate and size counters of a [`ParquetMetadataCache`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetadataCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub size_bytes: usize,
+    pub capacity_bytes: usize,
+}
+
+impl MetadataCacheStats {
+    /// Fraction of lookups served from the cache, or 0 before the first lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// The version of a file statistics were read from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FileVersion {
+    size: u64,
+    last_modified: DateTime<Utc>,
+    e_tag: Option<String>,
+}
+
+impl From<&ObjectMeta> for FileVersion {
+    fn from(meta: &ObjectMeta) -> Self {
+        Self {
+            size: meta.size,
+            last_modified: meta.last_modified,
+            e_tag: meta.e_tag.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Listing(Path),
+    Statistics(Path, FileVersion),
+}
+
+impl CacheKey {
+    fn path(&self) -> &Path {
+        match self {
+            CacheKey::Listing(path) | CacheKey::Statistics(path, _) => path,
+        }
+    }
+
+    fn size_bytes(&self) -> usize {
+        let version = match self {
+            CacheKey::Listing(_) => 0,
+            CacheKey::Statistics(_, version) => {
+                size_of::<FileVersion>() + version.e_tag.as_ref().map_or(0, String::len)
+            }
+        };
+        self.path().as_ref().len() + version
+    }
+}
+
+#[derive(Clone)]
+enum CachedValue {
+    Listing(Arc<Vec<ObjectMeta>>),
+    Statistics(Arc<Statistics>),
+}
+
+impl CachedValue {
+    fn size_bytes(&self) -> usize {
+        match self {
+            CachedValue::Listing(files) => files.iter().map(object_meta_size).sum(),
+            CachedValue::Statistics(stats) => {
+                size_of::<Statistics>()
+                    + stats
+                        .column_statistics
+                        .iter()
+                        .map(column_statistics_size)
+                        .sum::<usize>()
+            }
+        }
+    }
+}
+
+struct CacheEntry {
+    value: CachedValue,
+    size_bytes: usize,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    size_bytes: usize,
+    /// Logical clock for least-recently-used eviction.
+    clock: u64,
+}
+
+impl CacheState {
+    fn remove(&mut self, key: &CacheKey) -> Option<CachedValue> {
+        let entry = self.entries.remove(key)?;
+        self.size_bytes -= entry.size_bytes;
+        Some(entry.value)
+    }
+
+    fn evict_to(&mut self, capacity_bytes: usize) {
+        while self.size_bytes > capacity_bytes {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+    }
+}
+
+/// Size-bounded, least-recently-used cache of directory listings and per-file parquet
+/// statistics, shared by all scans of an engine's session.
+pub struct ParquetMetadataCache {
+    capacity_bytes: usize,
+    state: Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl std::fmt::Debug for ParquetMetadataCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParquetMetadataCache")
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl ParquetMetadataCache {
+    pub fn new(capacity_bytes: usize) -> Self {
+        Self {
+            capacity_bytes,
+            state: Mutex::new(CacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// DataFusion cache configuration that serves listings and file statistics from this
+    /// cache.
+    pub fn cache_manager_config(self: &Arc<Self>) -> CacheManagerConfig {
+        CacheManagerConfig::default()
+            .with_list_files_cache(Some(Arc::new(ListFilesCache(self.clone()))))
+            .with_files_statistics_cache(Some(Arc::new(FileStatisticsCache(self.clone()))))
+    }
+
+    pub fn stats(&self) -> MetadataCacheStats {
+        let state = self.state.lock().unwrap();
+        MetadataCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: state.entries.len(),
+            size_bytes: state.size_bytes,
+            capacity_bytes: self.capacity_bytes,
+        }
+    }
+
+    /// Drop everything cached for a cache URL: the listing of the directory itself and
+    /// anything cached beneath it.
+    pub fn invalidate(&self, url: &str) {
+        let prefix = match ListingTableUrl::parse(url) {
+            Ok(url) => url.prefix().clone(),
+            Err(e) => {
+                tracing::warn!("Clearing metadata cache, cannot parse {}: {}", url, e);
+                self.clear();
+                return;
+            }
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let stale: Vec<CacheKey> = state
+            .entries
+            .keys()
+            .filter(|key| key.path().prefix_matches(&prefix))
+            .cloned()
+            .collect();
+        for key in &stale {
+            state.remove(key);
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.clear();
+        state.size_bytes = 0;
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<CachedValue> {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+        state.entries.get_mut(key).map(|entry| {
+            entry.last_used = clock;
+            entry.value.clone()
+        })
+    }
+
+    fn record_lookup<T>(&self, value: Option<T>) -> Option<T> {
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    fn insert(&self, key: CacheKey, value: CachedValue) -> Option<CachedValue> {
+        let size_bytes = key.size_bytes() + value.size_bytes();
+        let mut state = self.state.lock().unwrap();
+        let previous = state.remove(&key);
+
+        // An entry larger than the whole cache would only evict everything else
+        if size_bytes > self.capacity_bytes {
+            return previous;
+        }
+
+        state.clock += 1;
+        let last_used = state.clock;
+        state.size_bytes += size_bytes;
+        state.entries.insert(
+            key,
+            CacheEntry {
+                value,
+                size_bytes,
+                last_used,
+            },
+        );
+        state.evict_to(self.capacity_bytes);
+        previous
+    }
+
+    fn remove(&self, key: &CacheKey) -> Option<CachedValue> {
+        self.state.lock().unwrap().remove(key)
+    }
+
+    /// Remove the entries of a kind for a path, returning one of their values.
+    fn remove_path(&self, path: &Path, kind: fn(&CacheKey) -> bool) -> Option<CachedValue> {
+        let mut state = self.state.lock().unwrap();
+        let keys: Vec<CacheKey> = state
+            .entries
+            .keys()
+            .filter(|key| kind(key) && key.path() == path)
+            .cloned()
+            .collect();
+        keys.iter().filter_map(|key| state.remove(key)).last()
+    }
+
+    fn contains_path(&self, path: &Path, kind: fn(&CacheKey) -> bool) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .entries
+            .keys()
+            .any(|key| kind(key) && key.path() == path)
+    }
+
+    fn count(&self, kind: fn(&CacheKey) -> bool) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .entries
+            .keys()
+            .filter(|key| kind(key))
+            .count()
+    }
+
+    fn clear_kind(&self, kind: fn(&CacheKey) -> bool) {
+        let mut state = self.state.lock().unwrap();
+        let keys: Vec<CacheKey> = state.entries.keys().filter(|k| kind(k)).cloned().collect();
+        for key in &keys {
+            state.remove(key);
+        }
+    }
+}
+
+fn object_meta_size(meta: &ObjectMeta) -> usize {
+    size_of::<ObjectMeta>()
+        + meta.location.as_ref().len()
+        + meta.e_tag.as_ref().map_or(0, String::len)
+        + meta.version.as_ref().map_or(0, String::len)
+}
+
+fn column_statistics_size(column: &ColumnStatistics) -> usize {
+    size_of::<ColumnStatistics>()
+        + column.min_value.get_value().map_or(0, |v| v.size())
+        + column.max_value.get_value().map_or(0, |v| v.size())
+        + column.sum_value.get_value().map_or(0, |v| v.size())
+}
+
+fn is_listing(key: &CacheKey) -> bool {
+    matches!(key, CacheKey::Listing(_))
+}
+
+fn is_statistics(key: &CacheKey) -> bool {
+    matches!(key, CacheKey::Statistics(_, _))
+}
+
+/// The cache as DataFusion's list-files cache, keyed by table directory.
+struct ListFilesCache(Arc<ParquetMetadataCache>);
+
+impl CacheAccessor<Path, Arc<Vec<ObjectMeta>>> for ListFilesCache {
+    type Extra = ObjectMeta;
+
+    fn get(&self, k: &Path) -> Option<Arc<Vec<ObjectMeta>>> {
+        let files = match self.0.get(&CacheKey::Listing(k.clone())) {
+            Some(CachedValue::Listing(files)) => Some(files),
+            _ => None,
+        };
+        self.0.record_lookup(files)
+    }
+
+    fn get_with_extra(&self, k: &Path, _e: &Self::Extra) -> Option<Arc<Vec<ObjectMeta>>> {
+        self.get(k)
+    }
+
+    fn put(&self, key: &Path, value: Arc<Vec<ObjectMeta>>) -> Option<Arc<Vec<ObjectMeta>>> {
+        match self
+            .0
+            .insert(CacheKey::Listing(key.clone()), CachedValue::Listing(value))
+        {
+            Some(CachedValue::Listing(files)) => Some(files),
+            _ => None,
+        }
+    }
+
+    fn put_with_extra(
+        &self,
+        key: &Path,
+        value: Arc<Vec<ObjectMeta>>,
+        _e: &Self::Extra,
+    ) -> Option<Arc<Vec<ObjectMeta>>> {
+        self.put(key, value)
+    }
+
+    fn remove(&mut self, k: &Path) -> Option<Arc<Vec<ObjectMeta>>> {
+        match self.0.remove(&CacheKey::Listing(k.clone())) {
+            Some(CachedValue::Listing(files)) => Some(files),
+            _ => None,
+        }
+    }
+
+    fn contains_key(&self, k: &Path) -> bool {
+        self.0
+            .state
+            .lock()
+            .unwrap()
+            .entries
+            .contains_key(&CacheKey::Listing(k.clone()))
+    }
+
+    fn len(&self) -> usize {
+        self.0.count(is_listing)
+    }
+
+    fn clear(&self) {
+        self.0.clear_kind(is_listing)
+    }
+
+    fn name(&self) -> String {
+        "ParquetMetadataCache::ListFiles".to_string()
+    }
+}
+
+/// The cache as DataFusion's file statistics cache, keyed by file and file version. Entries
+/// are only served for the exact file version they were read from.
+struct FileStatisticsCache(Arc<ParquetMetadataCache>);
+
+impl CacheAccessor<Path, Arc<Statistics>> for FileStatisticsCache {
+    type Extra = ObjectMeta;
+
+    /// Statistics are only valid together with the file metadata they were read from.
+    fn get(&self, _k: &Path) -> Option<Arc<Statistics>> {
+        None
+    }
+
+    fn get_with_extra(&self, k: &Path, e: &Self::Extra) -> Option<Arc<Statistics>> {
+        let stats = match self.0.get(&CacheKey::Statistics(k.clone(), e.into())) {
+            Some(CachedValue::Statistics(stats)) => Some(stats),
+            _ => None,
+        };
+        self.0.record_lookup(stats)
+    }
+
+    fn put(&self, _key: &Path, _value: Arc<Statistics>) -> Option<Arc<Statistics>> {
+        None
+    }
+
+    fn put_with_extra(
+        &self,
+        key: &Path,
+        value: Arc<Statistics>,
+        e: &Self::Extra,
+    ) -> Option<Arc<Statistics>> {
+        match self.0.insert(
+            CacheKey::Statistics(key.clone(), e.into()),
+            CachedValue::Statistics(value),
+        ) {
+            Some(CachedValue::Statistics(stats)) => Some(stats),
+            _ => None,
+        }
+    }
+
+    /// Removes the statistics of every version of the file.
+    fn remove(&mut self, k: &Path) -> Option<Arc<Statistics>> {
+        match self.0.remove_path(k, is_statistics) {
+            Some(CachedValue::Statistics(stats)) => Some(stats),
+            _ => None,
+        }
+    }
+
+    fn contains_key(&self, k: &Path) -> bool {
+        self.0.contains_path(k, is_statistics)
+    }
+
+    fn len(&self) -> usize {
+        self.0.count(is_statistics)
+    }
+
+    fn clear(&self) {
+        self.0.clear_kind(is_statistics)
+    }
+
+    fn name(&self) -> String {
+        "ParquetMetadataCache::FileStatistics".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use datafusion::common::stats::Precision;
+
+    fn meta(location: &str, size: u64) -> ObjectMeta {
+        ObjectMeta {
+            location: Path::from(location),
+            last_modified: Utc::now(),
+            size,
+            e_tag: None,
+            version: None,
+        }
+    }
+
+    fn statistics(rows: usize) -> Arc<Statistics> {
+        Arc::new(Statistics {
+            num_rows: Precision::Exact(rows),
+            total_byte_size: Precision::Absent,
+            column_statistics: vec![ColumnStatistics::new_unknown()],
+        })
+    }
+
+    #[test]
+    fn test_listing_hits_and_misses_are_counted() {
+        let cache = Arc::new(ParquetMetadataCache::new(DEFAULT_METADATA_CACHE_SIZE));
+        let listing = ListFilesCache(cache.clone());
+        let dir = Path::from("cache/1/public/orders/v1");
+
+        assert!(listing.get(&dir).is_none());
+        listing.put(
+            &dir,
+            Arc::new(vec![meta("cache/1/public/orders/v1/a.parquet", 10)]),
+        );
+        assert_eq!(listing.get(&dir).unwrap().len(), 1);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+        assert_eq!(stats.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_statistics_require_matching_file_metadata() {
+        let cache = Arc::new(ParquetMetadataCache::new(DEFAULT_METADATA_CACHE_SIZE));
+        let file_stats = FileStatisticsCache(cache.clone());
+        let file = meta("cache/1/public/orders/v1/a.parquet", 10);
+
+        file_stats.put_with_extra(&file.location, statistics(5), &file);
+        let hit = file_stats.get_with_extra(&file.location, &file).unwrap();
+        assert_eq!(hit.num_rows, Precision::Exact(5));
+
+        let rewritten = ObjectMeta {
+            size: 20,
+            ..file.clone()
+        };
+        assert!(file_stats
+            .get_with_extra(&file.location, &rewritten)
+            .is_none());
+
+        // Same size and modification time, but a different object
+        let replaced = ObjectMeta {
+            e_tag: Some("\"v2\"".to_string()),
+            ..file.clone()
+        };
+        assert!(file_stats
+            .get_with_extra(&file.location, &replaced)
+            .is_none());
+        file_stats.put_with_extra(&file.location, statistics(7), &replaced);
+        let hit = file_stats
+            .get_with_extra(&file.location, &replaced)
+            .unwrap();
+        assert_eq!(hit.num_rows, Precision::Exact(7));
+        assert_eq!(cache.stats().misses, 2);
+    }
+
+    #[test]
+    fn test_invalidate_drops_entries_under_version_only() {
+        let cache = Arc::new(ParquetMetadataCache::new(DEFAULT_METADATA_CACHE_SIZE));
+        let listing = ListFilesCache(cache.clone());
+        let file_stats = FileStatisticsCache(cache.clone());
+        let v1 = meta("cache/1/public/orders/v1/a.parquet", 10);
+        let v2 = meta("cache/1/public/orders/v2/a.parquet", 10);
+
+        listing.put(
+            &Path::from("cache/1/public/orders/v1"),
+            Arc::new(vec![v1.clone()]),
+        );
+        listing.put(
+            &Path::from("cache/1/public/orders/v2"),
+            Arc::new(vec![v2.clone()]),
+        );
+        file_stats.put_with_extra(&v1.location, statistics(1), &v1);
+        file_stats.put_with_extra(&v2.location, statistics(2), &v2);
+
+        cache.invalidate("s3://bucket/cache/1/public/orders/v1");
+
+        assert!(!listing.contains_key(&Path::from("cache/1/public/orders/v1")));
+        assert!(!file_stats.contains_key(&v1.location));
+        assert!(listing.contains_key(&Path::from("cache/1/public/orders/v2")));
+        assert!(file_stats.contains_key(&v2.location));
+        assert_eq!(cache.stats().entries, 2);
+    }
+
+    #[test]
+    fn test_least_recently_used_entries_are_evicted() {
+        let dir = |n: usize| Path::from(format!("cache/1/public/t{}/v1", n));
+        let files = |n: usize| {
+            Arc::new(vec![meta(
+                &format!("cache/1/public/t{}/v1/a.parquet", n),
+                1,
+            )])
+        };
+        let entry_size = dir(0).as_ref().len() + CachedValue::Listing(files(0)).size_bytes();
+
+        let cache = Arc::new(ParquetMetadataCache::new(entry_size * 2));
+        let listing = ListFilesCache(cache.clone());
+        listing.put(&dir(0), files(0));
+        listing.put(&dir(1), files(1));
+        listing.get(&dir(0));
+        listing.put(&dir(2), files(2));
+
+        assert!(listing.contains_key(&dir(0)));
+        assert!(!listing.contains_key(&dir(1)));
+        assert!(listing.contains_key(&dir(2)));
+        assert!(cache.stats().size_bytes <= entry_size * 2);
+    }
+}
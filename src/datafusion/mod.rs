@@ -1,8 +1,11 @@
+mod catalog_list;
 mod catalog_provider;
 mod information_schema;
 mod lazy_table_provider;
+mod metadata_cache;
 mod runtimedb_catalog;
 mod schema_provider;
+mod time_travel;
 
 use tokio::task::block_in_place;
 
@@ -18,8 +21,13 @@ where
     block_in_place(|| tokio::runtime::Handle::current().block_on(f))
 }
 
+pub use catalog_list::RuntimeCatalogProviderList;
 pub use catalog_provider::RuntimeCatalogProvider;
 pub use information_schema::InformationSchemaProvider;
 pub use lazy_table_provider::LazyTableProvider;
+pub use metadata_cache::{MetadataCacheStats, ParquetMetadataCache, DEFAULT_METADATA_CACHE_SIZE};
 pub use runtimedb_catalog::RuntimeDbCatalogProvider;
 pub use schema_provider::RuntimeSchemaProvider;
+pub use time_travel::{
+    TableAsOfFunction, TableAtSnapshotFunction, TABLE_AS_OF_FUNCTION, TABLE_AT_SNAPSHOT_FUNCTION,
+};
@@ -0,0 +1,239 @@
+use super::block_on;
+use crate::catalog::CatalogManager;
+use crate::datafetch::{deserialize_arrow_schema, FetchOrchestrator, TimeTravel};
+use crate::source::Source;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use datafusion::catalog::{TableFunctionImpl, TableProvider};
+use datafusion::common::{plan_datafusion_err, plan_err, ScalarValue};
+use datafusion::datasource::file_format::parquet::ParquetFormat;
+use datafusion::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::Expr;
+use std::sync::Arc;
+
+/// Name under which the function is registered with DataFusion.
+pub const TABLE_AS_OF_FUNCTION: &str = "table_as_of";
+
+/// Name under which the snapshot function is registered with DataFusion.
+pub const TABLE_AT_SNAPSHOT_FUNCTION: &str = "table_at_snapshot";
+
+/// Table function for time travel queries against snapshot-versioned sources (Iceberg).
+///
+/// ```sql
+/// SELECT * FROM table_as_of('lake', 'db', 'events', 4358109269873137077);
+/// SELECT * FROM table_as_of('lake', 'db', 'events', '2024-06-01T00:00:00Z');
+/// ```
+///
+/// An integer reads that snapshot ID; a string reads the snapshot that was current at that
+/// time. The data is fetched from the source while the query is planned, into a cache
+/// version of its own that the query scans and that is deleted after
+/// [`TIME_TRAVEL_RETENTION`](crate::datafetch::TIME_TRAVEL_RETENTION).
+#[derive(Debug)]
+pub struct TableAsOfFunction {
+    catalog: Arc<dyn CatalogManager>,
+    orchestrator: Arc<FetchOrchestrator>,
+}
+
+impl TableAsOfFunction {
+    pub fn new(catalog: Arc<dyn CatalogManager>, orchestrator: Arc<FetchOrchestrator>) -> Self {
+        Self {
+            catalog,
+            orchestrator,
+        }
+    }
+}
+
+impl TableFunctionImpl for TableAsOfFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let [connection, schema, table, as_of] = args else {
+            return plan_err!(
+                "{} expects (connection, schema, table, snapshot_id | timestamp)",
+                TABLE_AS_OF_FUNCTION
+            );
+        };
+        let connection = string_arg(TABLE_AS_OF_FUNCTION, connection, "connection")?;
+        let schema = string_arg(TABLE_AS_OF_FUNCTION, schema, "schema")?;
+        let table = string_arg(TABLE_AS_OF_FUNCTION, table, "table")?;
+        let as_of = parse_as_of(as_of)?;
+
+        let (url, arrow_schema) = block_on(async {
+            let conn = self
+                .catalog
+                .get_connection(&connection)
+                .await
+                .map_err(|e| DataFusionError::External(e.into()))?
+                .ok_or_else(|| plan_datafusion_err!("Connection '{}' not found", connection))?;
+            let source: Source = serde_json::from_str(&conn.config_json)
+                .map_err(|e| DataFusionError::External(e.into()))?;
+
+            if !source.has_snapshots() {
+                return plan_err!(
+                    "Connection '{}' ({}) does not support time travel",
+                    connection,
+                    source.source_type()
+                );
+            }
+
+            self.orchestrator
+                .cache_table_as_of(&source, conn.id, &schema, &table, &as_of)
+                .await
+                .map_err(|e| DataFusionError::External(e.into()))
+        })?;
+
+        let config = ListingTableConfig::new(ListingTableUrl::parse(&url)?)
+            .with_listing_options(ListingOptions::new(Arc::new(ParquetFormat::default())))
+            .with_schema(arrow_schema);
+        Ok(Arc::new(ListingTable::try_new(config)?))
+    }
+}
+
+/// Table function reading the cached version of a table pinned by a named cache snapshot.
+///
+/// ```sql
+/// SELECT * FROM table_at_snapshot('month_end', 'warehouse', 'sales', 'orders');
+/// ```
+///
+/// Works for any cached table, whatever its source. The data is read as the snapshot
+/// pinned it, with the schema it had then, even after refreshes replaced it. Cached
+/// versions are never modified once written (appends write a new version), so the
+/// pinned path always holds exactly the rows that were cached when the snapshot was taken.
+#[derive(Debug)]
+pub struct TableAtSnapshotFunction {
+    catalog: Arc<dyn CatalogManager>,
+}
+
+impl TableAtSnapshotFunction {
+    pub fn new(catalog: Arc<dyn CatalogManager>) -> Self {
+        Self { catalog }
+    }
+}
+
+impl TableFunctionImpl for TableAtSnapshotFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let [snapshot, connection, schema, table] = args else {
+            return plan_err!(
+                "{} expects (snapshot, connection, schema, table)",
+                TABLE_AT_SNAPSHOT_FUNCTION
+            );
+        };
+        let snapshot = string_arg(TABLE_AT_SNAPSHOT_FUNCTION, snapshot, "snapshot")?;
+        let connection = string_arg(TABLE_AT_SNAPSHOT_FUNCTION, connection, "connection")?;
+        let schema = string_arg(TABLE_AT_SNAPSHOT_FUNCTION, schema, "schema")?;
+        let table = string_arg(TABLE_AT_SNAPSHOT_FUNCTION, table, "table")?;
+
+        let pinned = block_on(async {
+            let external = |e: anyhow::Error| DataFusionError::External(e.into());
+            let snapshot_info = self
+                .catalog
+                .get_cache_snapshot(&snapshot)
+                .await
+                .map_err(external)?
+                .ok_or_else(|| plan_datafusion_err!("Snapshot '{}' not found", snapshot))?;
+            let conn = self
+                .catalog
+                .get_connection(&connection)
+                .await
+                .map_err(external)?
+                .ok_or_else(|| plan_datafusion_err!("Connection '{}' not found", connection))?;
+
+            self.catalog
+                .list_snapshot_tables(Some(snapshot_info.id))
+                .await
+                .map_err(external)?
+                .into_iter()
+                .find(|t| {
+                    t.connection_id == conn.id && t.schema_name == schema && t.table_name == table
+                })
+                .ok_or_else(|| {
+                    plan_datafusion_err!(
+                        "Snapshot '{}' does not include {}.{}.{}",
+                        snapshot,
+                        connection,
+                        schema,
+                        table
+                    )
+                })
+        })?;
+
+        let arrow_schema = deserialize_arrow_schema(&pinned.arrow_schema_json)
+            .map_err(|e| DataFusionError::External(e.into()))?;
+        let config = ListingTableConfig::new(ListingTableUrl::parse(&pinned.parquet_path)?)
+            .with_listing_options(ListingOptions::new(Arc::new(ParquetFormat::default())))
+            .with_schema(arrow_schema);
+
+        Ok(Arc::new(ListingTable::try_new(config)?))
+    }
+}
+
+fn string_arg(function: &str, expr: &Expr, name: &str) -> Result<String> {
+    match expr {
+        Expr::Literal(ScalarValue::Utf8(Some(value)), _)
+        | Expr::Literal(ScalarValue::LargeUtf8(Some(value)), _)
+        | Expr::Literal(ScalarValue::Utf8View(Some(value)), _) => Ok(value.clone()),
+        _ => plan_err!("{} argument '{}' must be a string literal", function, name),
+    }
+}
+
+/// Parse the time travel target: an integer snapshot ID, or an RFC 3339 / `YYYY-MM-DD HH:MM:SS`
+/// (UTC) timestamp string.
+fn parse_as_of(expr: &Expr) -> Result<TimeTravel> {
+    match expr {
+        Expr::Literal(ScalarValue::Int64(Some(id)), _) => Ok(TimeTravel::Snapshot(*id)),
+        Expr::Literal(_, _) => {
+            let value = string_arg(TABLE_AS_OF_FUNCTION, expr, "as_of")?;
+            parse_timestamp(&value)
+                .map(TimeTravel::Timestamp)
+                .ok_or_else(|| plan_datafusion_err!("Invalid timestamp '{}'", value))
+        }
+        _ => plan_err!(
+            "{} argument 'as_of' must be a snapshot ID or timestamp literal",
+            TABLE_AS_OF_FUNCTION
+        ),
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|ts| ts.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|ts| ts.and_utc())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::logical_expr::lit;
+
+    #[test]
+    fn test_parse_as_of_snapshot_id() {
+        assert_eq!(parse_as_of(&lit(42i64)).unwrap(), TimeTravel::Snapshot(42));
+    }
+
+    #[test]
+    fn test_parse_as_of_timestamp() {
+        let expected = DateTime::parse_from_rfc3339("2024-06-01T12:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_as_of(&lit("2024-06-01T12:30:00Z")).unwrap(),
+            TimeTravel::Timestamp(expected)
+        );
+        assert_eq!(
+            parse_as_of(&lit("2024-06-01 12:30:00")).unwrap(),
+            TimeTravel::Timestamp(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_as_of_rejects_invalid_values() {
+        assert!(parse_as_of(&lit("yesterday")).is_err());
+        assert!(parse_as_of(&lit(true)).is_err());
+    }
+}
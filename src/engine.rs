@@ -1,26 +1,43 @@
 use crate::catalog::{
-    CatalogManager, ConnectionInfo, QueryResult, SqliteCatalogManager, TableInfo,
+    diff_schemas, is_breaking, record_schema_version, CacheSnapshot, CatalogManager,
+    ConnectionInfo, QueryResult, SchemaChangePolicy, SchemaVersion, SchemaVersionStatus,
+    SnapshotTable, SqliteCatalogManager, TableInfo, TableKind,
 };
 use crate::datafetch::native::StreamingParquetWriter;
-use crate::datafetch::{FetchOrchestrator, NativeFetcher};
+use crate::datafetch::{deserialize_arrow_schema, CachePolicy, FetchOrchestrator, NativeFetcher};
 use crate::datafusion::{
-    block_on, InformationSchemaProvider, RuntimeCatalogProvider, RuntimeDbCatalogProvider,
+    block_on, InformationSchemaProvider, MetadataCacheStats, ParquetMetadataCache,
+    RuntimeCatalogProviderList, RuntimeDbCatalogProvider, TableAsOfFunction,
+    TableAtSnapshotFunction, DEFAULT_METADATA_CACHE_SIZE, TABLE_AS_OF_FUNCTION,
+    TABLE_AT_SNAPSHOT_FUNCTION,
 };
 use crate::http::models::{
-    ConnectionRefreshResult, ConnectionSchemaError, RefreshWarning, SchemaRefreshResult,
-    TableRefreshError, TableRefreshResult,
+    BrokenCacheEntry, CacheGcResult, CacheVerificationResult, ConnectionRefreshResult,
+    ConnectionSchemaError, RefreshWarning, SchemaRefreshResult, StaleTable, TableRefreshError,
+    TableRefreshResult, UnreferencedCacheVersion, WarmUpProgress, WarmUpState,
 };
 use crate::secrets::{EncryptedCatalogBackend, SecretManager, ENCRYPTED_PROVIDER_TYPE};
-use crate::source::Source;
-use crate::storage::{FilesystemStorage, StorageManager};
+use crate::source::{Source, TableFilter, TablePattern};
+use crate::storage::{
+    FilesystemStorage, S3CredentialSource, S3Encryption, S3StorageOptions,
+    SecretCredentialProvider, StorageManager,
+};
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::catalog::CatalogProvider;
+use datafusion::datasource::listing::ListingTableUrl;
+use datafusion::execution::runtime_env::{RuntimeEnv, RuntimeEnvBuilder};
+use datafusion::execution::session_state::SessionStateBuilder;
+use datafusion::parquet::arrow::async_reader::ParquetObjectReader;
+use datafusion::parquet::arrow::ParquetRecordBatchStreamBuilder;
+use datafusion::parquet::errors::ParquetError;
 use datafusion::prelude::*;
+use futures::TryStreamExt;
 use log::{info, warn};
-use std::collections::HashSet;
+use object_store::ObjectMeta;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -45,6 +62,10 @@ const DEFAULT_PARALLEL_REFRESH_COUNT: usize = 4;
 /// Default interval (in seconds) between deletion worker runs.
 const DEFAULT_DELETION_WORKER_INTERVAL_SECS: u64 = 30;
 
+/// Default time since its last write before the cache garbage collector reclaims an
+/// unreferenced version directory. Long enough that no fetch is still writing into it.
+const DEFAULT_CACHE_GC_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
 /// Connection ID used for internal runtimedb storage (results, etc.)
 const INTERNAL_CONNECTION_ID: i32 = 0;
 
@@ -55,6 +76,14 @@ pub struct QueryResponse {
     pub execution_time: Duration,
 }
 
+/// A directory of cache files that no catalog row references.
+struct UnreferencedDir {
+    path: String,
+    size_bytes: u64,
+    /// When its newest file was written
+    last_modified: DateTime<Utc>,
+}
+
 /// Internal result from writing parquet file, containing the handle for finalization.
 struct ParquetWriteResult {
     handle: crate::storage::CacheWriteHandle,
@@ -64,16 +93,25 @@ struct ParquetWriteResult {
 pub struct RuntimeEngine {
     catalog: Arc<dyn CatalogManager>,
     df_ctx: SessionContext,
+    /// Resolves connection catalogs for `df_ctx`
+    catalog_list: Arc<RuntimeCatalogProviderList>,
     storage: Arc<dyn StorageManager>,
     orchestrator: Arc<FetchOrchestrator>,
     secret_manager: Arc<SecretManager>,
+    metadata_cache: Arc<ParquetMetadataCache>,
     shutdown_token: CancellationToken,
     deletion_worker_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
     deletion_grace_period: Duration,
     /// Stored for potential future restart capability; interval is passed to worker at start
     #[allow(dead_code)]
     deletion_worker_interval: Duration,
+    cache_gc_worker_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    cache_gc_min_age: Duration,
+    access_flush_worker_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
     parallel_refresh_count: usize,
+    /// Latest warm-up progress per connection id, updated by the warm-up tasks
+    warm_up_progress: Arc<std::sync::Mutex<HashMap<i32, WarmUpProgress>>>,
+    warm_up_handles: Mutex<Vec<tokio::task::JoinHandle<()>>>,
 }
 
 impl RuntimeEngine {
@@ -122,12 +160,51 @@ impl RuntimeEngine {
         }
 
         // Only create explicit storage for non-filesystem backends
+        let credentials_secret = config
+            .storage
+            .credentials_secret
+            .as_ref()
+            .map(|name| Arc::new(SecretCredentialProvider::new(name)));
         if config.storage.storage_type != "filesystem" {
-            let storage = Self::create_storage_from_config(config).await?;
+            let storage =
+                Self::create_storage_from_config(config, credentials_secret.clone()).await?;
             builder = builder.storage(storage);
         }
 
-        builder.build().await
+        if let Some(secs) = config.cache.gc_interval_secs {
+            builder = builder.cache_gc_interval(Duration::from_secs(secs));
+        }
+        if let Some(secs) = config.cache.gc_min_age_secs {
+            builder = builder.cache_gc_min_age(Duration::from_secs(secs));
+        }
+
+        let engine = builder.build().await?;
+
+        // Storage credentials kept as a secret can only be read once secrets are available
+        if let Some(provider) = credentials_secret {
+            provider.bind(engine.secret_manager.clone());
+            provider.load().await.map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to read S3 credentials from secret '{}': {}",
+                    provider.secret_name(),
+                    e
+                )
+            })?;
+        }
+
+        if config.cache.verify_on_startup {
+            match engine.verify_cache(config.cache.delete_orphans).await {
+                Ok(result) => info!(
+                    "Cache verified: {} tables checked, {} reset, {} orphaned directories",
+                    result.tables_checked,
+                    result.tables_reset.len(),
+                    result.orphaned_paths.len()
+                ),
+                Err(e) => warn!("Cache verification failed: {}", e),
+            }
+        }
+
+        Ok(engine)
     }
 
     /// Create a catalog manager from config (for non-sqlite backends).
@@ -174,7 +251,13 @@ impl RuntimeEngine {
     /// Create a storage manager from config (for non-filesystem backends).
     async fn create_storage_from_config(
         config: &crate::config::AppConfig,
+        credentials_secret: Option<Arc<SecretCredentialProvider>>,
     ) -> Result<Arc<dyn StorageManager>> {
+        let local_cache_size = config
+            .storage
+            .local_cache_size
+            .unwrap_or(crate::storage::DEFAULT_LOCAL_CACHE_SIZE);
+
         match config.storage.storage_type.as_str() {
             "s3" => {
                 let bucket = config
@@ -183,30 +266,78 @@ impl RuntimeEngine {
                     .as_ref()
                     .ok_or_else(|| anyhow::anyhow!("S3 storage requires bucket"))?;
 
-                // Check if we have custom endpoint (for MinIO/localstack)
-                if let Some(endpoint) = &config.storage.endpoint {
-                    // For custom endpoint, we need credentials from environment
-                    let access_key = std::env::var("AWS_ACCESS_KEY_ID")
-                        .or_else(|_| std::env::var("RUNTIMEDB_STORAGE_ACCESS_KEY_ID"))
-                        .unwrap_or_else(|_| "minioadmin".to_string());
-                    let secret_key = std::env::var("AWS_SECRET_ACCESS_KEY")
-                        .or_else(|_| std::env::var("RUNTIMEDB_STORAGE_SECRET_ACCESS_KEY"))
-                        .unwrap_or_else(|_| "minioadmin".to_string());
-
-                    // Allow HTTP for local MinIO
-                    let allow_http = endpoint.starts_with("http://");
-
-                    Ok(Arc::new(crate::storage::S3Storage::new_with_config(
-                        bucket,
+                let options = s3_options_from_config(&config.storage, credentials_secret)?;
+                let storage = crate::storage::S3Storage::with_options(bucket, options)?;
+
+                // Optionally keep cache files on local disk in front of the bucket
+                let storage = match &config.storage.local_cache_dir {
+                    Some(dir) => storage.with_local_cache(dir, local_cache_size)?,
+                    None => storage,
+                };
+                Ok(Arc::new(storage))
+            }
+            "gcs" => {
+                let bucket = config
+                    .storage
+                    .bucket
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("GCS storage requires bucket"))?;
+
+                // A custom endpoint means an emulator such as fake-gcs-server
+                let storage = match &config.storage.endpoint {
+                    Some(endpoint) => {
+                        crate::storage::GcsStorage::new_with_endpoint(bucket, endpoint)?
+                    }
+                    None => crate::storage::GcsStorage::new(bucket)?,
+                };
+
+                let storage = match &config.storage.local_cache_dir {
+                    Some(dir) => storage.with_local_cache(dir, local_cache_size)?,
+                    None => storage,
+                };
+                Ok(Arc::new(storage))
+            }
+            "azure" => {
+                let container =
+                    config.storage.bucket.as_ref().ok_or_else(|| {
+                        anyhow::anyhow!("Azure storage requires bucket (container)")
+                    })?;
+
+                // Check if we have custom endpoint (for Azurite)
+                let storage = if let Some(endpoint) = &config.storage.endpoint {
+                    let account = config
+                        .storage
+                        .account
+                        .clone()
+                        .or_else(|| std::env::var("AZURE_STORAGE_ACCOUNT_NAME").ok())
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("Azure storage with endpoint requires account")
+                        })?;
+                    let access_key = std::env::var("AZURE_STORAGE_ACCOUNT_KEY")
+                        .or_else(|_| std::env::var("RUNTIMEDB_STORAGE_ACCOUNT_KEY"))
+                        .map_err(|_| {
+                            anyhow::anyhow!(
+                                "Azure storage with endpoint requires AZURE_STORAGE_ACCOUNT_KEY"
+                            )
+                        })?;
+
+                    crate::storage::AzureStorage::new_with_config(
+                        container,
                         endpoint,
+                        &account,
                         &access_key,
-                        &secret_key,
-                        allow_http,
-                    )?))
+                        endpoint.starts_with("http://"),
+                    )?
                 } else {
-                    // Use AWS credentials from environment
-                    Ok(Arc::new(crate::storage::S3Storage::new(bucket)?))
-                }
+                    // Use Azure credentials from environment
+                    crate::storage::AzureStorage::new(container, config.storage.account.as_deref())?
+                };
+
+                let storage = match &config.storage.local_cache_dir {
+                    Some(dir) => storage.with_local_cache(dir, local_cache_size)?,
+                    None => storage,
+                };
+                Ok(Arc::new(storage))
             }
             _ => anyhow::bail!("Unsupported storage type: {}", config.storage.storage_type),
         }
@@ -219,6 +350,7 @@ impl RuntimeEngine {
     /// Delete cache and state directories for a connection.
     async fn delete_connection_files(&self, connection_id: i32) -> Result<()> {
         let cache_prefix = self.storage.cache_prefix(connection_id);
+        self.metadata_cache.invalidate(&cache_prefix);
 
         if let Err(e) = self.storage.delete_prefix(&cache_prefix).await {
             warn!("Failed to delete cache prefix {}: {}", cache_prefix, e);
@@ -227,10 +359,14 @@ impl RuntimeEngine {
         Ok(())
     }
 
-    /// Delete cache directory for a specific table.
+    /// Delete cache directory for a specific table, unless a snapshot pins it.
     async fn delete_table_files(&self, table_info: &TableInfo) -> Result<()> {
         // Delete versioned cache directory if it exists
         if let Some(parquet_path) = &table_info.parquet_path {
+            if self.pinned_paths().await?.contains(parquet_path) {
+                return Ok(());
+            }
+            self.metadata_cache.invalidate(parquet_path);
             if let Err(e) = self.storage.delete_prefix(parquet_path).await {
                 warn!("Failed to delete cache directory {}: {}", parquet_path, e);
             }
@@ -239,6 +375,17 @@ impl RuntimeEngine {
         Ok(())
     }
 
+    /// Cache directories pinned by snapshots.
+    async fn pinned_paths(&self) -> Result<HashSet<String>> {
+        Ok(self
+            .catalog
+            .list_snapshot_tables(None)
+            .await?
+            .into_iter()
+            .map(|t| t.parquet_path)
+            .collect())
+    }
+
     /// Get a reference to the storage manager
     pub fn storage(&self) -> &Arc<dyn StorageManager> {
         &self.storage
@@ -254,34 +401,26 @@ impl RuntimeEngine {
         &self.secret_manager
     }
 
-    /// Register all connections from the catalog store as DataFusion catalogs.
-    async fn register_existing_connections(&mut self) -> Result<()> {
-        let connections = self.catalog.list_connections().await?;
-
-        for conn in connections {
-            let source: Source = serde_json::from_str(&conn.config_json)?;
-
-            let catalog_provider = Arc::new(RuntimeCatalogProvider::new(
-                conn.id,
-                conn.name.clone(),
-                Arc::new(source),
-                self.catalog.clone(),
-                self.orchestrator.clone(),
-            )) as Arc<dyn CatalogProvider>;
-
-            self.df_ctx.register_catalog(&conn.name, catalog_provider);
-        }
+    /// Hit-rate and size counters of the parquet metadata cache shared by all queries.
+    pub fn metadata_cache_stats(&self) -> MetadataCacheStats {
+        self.metadata_cache.stats()
+    }
 
+    /// Drop a connection's DataFusion catalog so the next query builds a fresh one,
+    /// releasing any open file handles and cached table providers.
+    fn reregister_connection(&self, conn: &ConnectionInfo) -> Result<()> {
+        self.catalog_list.invalidate(conn.id);
         Ok(())
     }
 
     /// Register a connection without discovering tables.
     ///
-    /// This persists the connection config to the catalog and registers it with DataFusion,
-    /// but does not attempt to connect to the remote database or discover tables.
+    /// This persists the connection config to the catalog, which makes it visible to
+    /// DataFusion, but does not attempt to connect to the remote database or discover tables.
     /// Use `refresh_schema()` to discover tables after registration.
     pub async fn register_connection(&self, name: &str, source: Source) -> Result<i32> {
         let source_type = source.source_type();
+        source.table_filter().matcher()?;
 
         // Store config as JSON (includes "type" from serde tag)
         let config_json = serde_json::to_string(&source)?;
@@ -289,17 +428,7 @@ impl RuntimeEngine {
             .catalog
             .add_connection(name, source_type, &config_json)
             .await?;
-
-        // Register with DataFusion (empty catalog - no tables yet)
-        let catalog_provider = Arc::new(RuntimeCatalogProvider::new(
-            conn_id,
-            name.to_string(),
-            Arc::new(source),
-            self.catalog.clone(),
-            self.orchestrator.clone(),
-        )) as Arc<dyn CatalogProvider>;
-
-        self.df_ctx.register_catalog(name, catalog_provider);
+        self.catalog_list.invalidate(conn_id);
 
         info!("Connection '{}' registered (discovery pending)", name);
 
@@ -340,10 +469,75 @@ impl RuntimeEngine {
         self.catalog.list_tables(connection_id).await
     }
 
+    /// Set how breaking schema changes are handled for a connection.
+    pub async fn set_schema_change_policy(
+        &self,
+        connection_name: &str,
+        policy: SchemaChangePolicy,
+    ) -> Result<()> {
+        let conn = self
+            .catalog
+            .get_connection(connection_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' not found", connection_name))?;
+        self.catalog.set_schema_change_policy(conn.id, policy).await
+    }
+
+    /// Set the tables fetched into the cache in the background after discovery and on
+    /// startup. An empty list disables warm-up.
+    pub async fn set_warm_up(
+        &self,
+        connection_name: &str,
+        patterns: &[TablePattern],
+    ) -> Result<()> {
+        let conn = self
+            .catalog
+            .get_connection(connection_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' not found", connection_name))?;
+        warm_up_filter(patterns).matcher()?;
+        let warm_up_json = if patterns.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(patterns)?)
+        };
+        self.catalog
+            .set_warm_up_json(conn.id, warm_up_json.as_deref())
+            .await
+    }
+
+    /// The warm-up patterns of a connection.
+    pub async fn warm_up_patterns(&self, connection_id: i32) -> Result<Vec<TablePattern>> {
+        match self.catalog.get_warm_up_json(connection_id).await? {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Progress of the latest warm-up of a connection, if one has run since startup.
+    pub fn warm_up_progress(&self, connection_id: i32) -> Option<WarmUpProgress> {
+        self.warm_up_progress
+            .lock()
+            .unwrap()
+            .get(&connection_id)
+            .cloned()
+    }
+
+    /// Schema versions of every table in a connection, oldest first per table.
+    pub async fn schema_history(&self, connection_name: &str) -> Result<Vec<SchemaVersion>> {
+        let conn = self
+            .catalog
+            .get_connection(connection_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' not found", connection_name))?;
+        self.catalog.list_schema_versions(Some(conn.id)).await
+    }
+
     /// Execute a SQL query and return the results.
     pub async fn execute_query(&self, sql: &str) -> Result<QueryResponse> {
         info!("Executing query: {}", sql);
         let start = Instant::now();
+        self.catalog_list.ensure_fresh().await?;
         let df = self.df_ctx.sql(sql).await.map_err(|e| {
             error!("Error executing query: {}", e);
             e
@@ -376,7 +570,9 @@ impl RuntimeEngine {
         let result_id = crate::id::generate_result_id();
 
         // Write to parquet
-        let parquet_path = self.write_results_to_parquet(&result_id, schema, batches)?;
+        let parquet_path = self
+            .write_results_to_parquet(&result_id, schema, batches)
+            .await?;
 
         // Finalize (uploads to S3 if needed) and get directory URL
         let dir_url = self
@@ -441,7 +637,7 @@ impl RuntimeEngine {
     /// Note: Empty results are persisted with schema only. This ensures GET /results/{id}
     /// works for all result IDs returned by POST /query. A future optimization could avoid
     /// persisting empty results entirely if storage space becomes a concern.
-    fn write_results_to_parquet(
+    async fn write_results_to_parquet(
         &self,
         result_id: &str,
         schema: &Arc<Schema>,
@@ -460,15 +656,16 @@ impl RuntimeEngine {
         writer.init(schema)?;
 
         for batch in batches {
-            writer.write_batch(batch)?;
+            writer.write_batch(batch).await?;
         }
 
-        writer.close()?;
+        writer.close().await?;
 
         Ok(ParquetWriteResult { handle })
     }
 
     /// Purge all cached data for a connection (clears parquet files and resets sync state).
+    /// Versions pinned by snapshots are kept.
     pub async fn purge_connection(&self, name: &str) -> Result<()> {
         // Get connection info (validates it exists and gives us the ID)
         let conn = self
@@ -476,27 +673,30 @@ impl RuntimeEngine {
             .get_connection(name)
             .await?
             .ok_or_else(|| anyhow::anyhow!("Connection '{}' not found", name))?;
+        let tables = self.catalog.list_tables(Some(conn.id)).await?;
 
         // Step 1: Clear metadata first (metadata-first ordering)
         self.catalog.clear_connection_cache_metadata(name).await?;
 
         // Step 2: Re-register the connection with fresh state
         // This causes DataFusion to drop any open file handles to the cached files
-        let source: Source = serde_json::from_str(&conn.config_json)?;
-
-        let catalog_provider = Arc::new(RuntimeCatalogProvider::new(
-            conn.id,
-            conn.name.clone(),
-            Arc::new(source),
-            self.catalog.clone(),
-            self.orchestrator.clone(),
-        )) as Arc<dyn CatalogProvider>;
-
-        // register_catalog replaces existing catalog with same name
-        self.df_ctx.register_catalog(name, catalog_provider);
+        self.reregister_connection(&conn)?;
 
         // Step 3: Delete the physical files (now that DataFusion has released file handles)
-        self.delete_connection_files(conn.id).await?;
+        let connection_dir = format!("{}/", cache_url(&self.storage.cache_prefix(conn.id)));
+        let has_pins = self
+            .pinned_paths()
+            .await?
+            .iter()
+            .any(|path| path.starts_with(&connection_dir));
+        if has_pins {
+            // Other unreferenced versions are left to pending deletions and garbage collection
+            for table in &tables {
+                self.delete_table_files(table).await?;
+            }
+        } else {
+            self.delete_connection_files(conn.id).await?;
+        }
 
         Ok(())
     }
@@ -524,19 +724,7 @@ impl RuntimeEngine {
 
         // Step 2: Re-register the connection with fresh state
         // This causes DataFusion to drop any open file handles to the cached files
-        let source: Source = serde_json::from_str(&conn.config_json)?;
-
-        let catalog_provider = Arc::new(RuntimeCatalogProvider::new(
-            conn.id,
-            conn.name.clone(),
-            Arc::new(source),
-            self.catalog.clone(),
-            self.orchestrator.clone(),
-        )) as Arc<dyn CatalogProvider>;
-
-        // register_catalog replaces existing catalog with same name
-        self.df_ctx
-            .register_catalog(connection_name, catalog_provider);
+        self.reregister_connection(&conn)?;
 
         // Step 3: Delete the physical files (now that DataFusion has released file handles)
         self.delete_table_files(&table_info).await?;
@@ -544,7 +732,400 @@ impl RuntimeEngine {
         Ok(())
     }
 
-    /// Remove a connection entirely (removes from catalog and deletes all data).
+    /// Remove the metadata of a connection's stale tables and schedule their cached files
+    /// for deletion. Returns the pruned tables.
+    pub async fn prune_stale_tables(&self, connection_name: &str) -> Result<Vec<TableInfo>> {
+        let stale: Vec<TableInfo> = self
+            .list_tables(Some(connection_name))
+            .await?
+            .into_iter()
+            .filter(|t| t.stale_since.is_some())
+            .collect();
+
+        for table in &stale {
+            self.catalog.delete_table(table.id).await?;
+
+            if let Some(path) = &table.parquet_path {
+                self.metadata_cache.invalidate(path);
+                if let Err(e) = self.schedule_file_deletion(path).await {
+                    tracing::warn!(
+                        schema = %table.schema_name,
+                        table = %table.table_name,
+                        path = %path,
+                        error = %e,
+                        "Failed to schedule deletion of stale table cache"
+                    );
+                }
+            }
+        }
+
+        Ok(stale)
+    }
+
+    /// Check that every cached table's files exist and have readable parquet footers.
+    ///
+    /// Tables whose files are missing or corrupt are reset to uncached, so the next query
+    /// fetches them again. Cache directories no table references are reported, and
+    /// scheduled for deletion when `delete_orphans` is set. Persisted query results, files
+    /// already scheduled for deletion and files written within the deletion grace period
+    /// (whose write may still be in progress) are never reported.
+    pub async fn verify_cache(&self, delete_orphans: bool) -> Result<CacheVerificationResult> {
+        let connections: HashMap<i32, ConnectionInfo> = self
+            .catalog
+            .list_connections()
+            .await?
+            .into_iter()
+            .map(|c| (c.id, c))
+            .collect();
+        let mut result = CacheVerificationResult::default();
+        let mut referenced: Vec<String> = self.pinned_paths().await?.into_iter().collect();
+        let mut repaired = HashSet::new();
+
+        for table in self.catalog.list_tables(None).await? {
+            let Some(path) = table.parquet_path.clone() else {
+                continue;
+            };
+            result.tables_checked += 1;
+            let entry = |error: String| BrokenCacheEntry {
+                connection_id: connections
+                    .get(&table.connection_id)
+                    .map(|c| c.external_id.clone())
+                    .unwrap_or_default(),
+                schema_name: table.schema_name.clone(),
+                table_name: table.table_name.clone(),
+                parquet_path: path.clone(),
+                error,
+            };
+
+            match self.check_cached_files(&path).await {
+                Ok(None) => referenced.push(path.clone()),
+                Ok(Some(error)) => {
+                    warn!(
+                        "Resetting cache of {}.{}: {}",
+                        table.schema_name, table.table_name, error
+                    );
+                    self.catalog
+                        .clear_table_cache_metadata(
+                            table.connection_id,
+                            &table.schema_name,
+                            &table.table_name,
+                        )
+                        .await?;
+                    self.metadata_cache.invalidate(&path);
+                    repaired.insert(table.connection_id);
+                    result.tables_reset.push(entry(error));
+                }
+                Err(e) => {
+                    // Storage could not be read, so the files may well be fine
+                    referenced.push(path.clone());
+                    result.tables_unverified.push(entry(e.to_string()));
+                }
+            }
+        }
+
+        for connection_id in repaired {
+            if let Some(conn) = connections.get(&connection_id) {
+                self.reregister_connection(conn)?;
+            }
+        }
+
+        let scheduled: Vec<String> = self
+            .catalog
+            .list_scheduled_deletions()
+            .await?
+            .into_iter()
+            .map(|d| d.path)
+            .collect();
+        let grace_period = chrono::Duration::from_std(self.deletion_grace_period)
+            .map_err(|e| anyhow::anyhow!("Invalid grace period duration: {}", e))?;
+        // Persisted query results are tracked by their own catalog rows
+        let results_prefix = format!(
+            "{}/",
+            cache_url(&self.storage.cache_prefix(INTERNAL_CONNECTION_ID))
+        );
+        result.orphaned_paths = Self::find_unreferenced_dirs(
+            &self.df_ctx.runtime_env(),
+            &self.storage.cache_root(),
+            &referenced,
+            &scheduled,
+            Utc::now() - grace_period,
+        )
+        .await?
+        .into_iter()
+        .map(|dir| dir.path)
+        .filter(|path| !path.starts_with(&results_prefix))
+        .collect();
+
+        if delete_orphans {
+            for path in &result.orphaned_paths {
+                self.schedule_file_deletion(path).await?;
+                result.orphans_scheduled += 1;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Why the cache at `url` is unusable, if it is. Errors mean storage could not be read.
+    async fn check_cached_files(&self, url: &str) -> Result<Option<String>> {
+        let table_url = ListingTableUrl::parse(url)?;
+        let store = self.df_ctx.runtime_env().object_store(&table_url)?;
+        let files: Vec<ObjectMeta> = match store.list(Some(table_url.prefix())).try_collect().await
+        {
+            Ok(files) => files,
+            Err(object_store::Error::NotFound { .. }) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let files: Vec<ObjectMeta> = files
+            .into_iter()
+            .filter(|f| f.location.extension() == Some("parquet"))
+            .collect();
+        if files.is_empty() {
+            return Ok(Some("no parquet files found".to_string()));
+        }
+
+        for file in files {
+            let reader = ParquetObjectReader::new(store.clone(), file.location.clone())
+                .with_file_size(file.size);
+            match ParquetRecordBatchStreamBuilder::new(reader).await {
+                Ok(_) => {}
+                Err(ParquetError::External(e)) => {
+                    anyhow::bail!("Failed to read {}: {}", file.location, e)
+                }
+                Err(e) => return Ok(Some(format!("{}: {}", file.location, e))),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Directories under `prefix_url` holding files that are not under any of the
+    /// `referenced` or `scheduled` directories, and whose newest file was written before
+    /// `cutoff`.
+    async fn find_unreferenced_dirs(
+        runtime_env: &RuntimeEnv,
+        prefix_url: &str,
+        referenced: &[String],
+        scheduled: &[String],
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<UnreferencedDir>> {
+        let prefix = ListingTableUrl::parse(prefix_url)?;
+        let store = runtime_env.object_store(&prefix)?;
+        let files: Vec<ObjectMeta> = match store.list(Some(prefix.prefix())).try_collect().await {
+            Ok(files) => files,
+            Err(object_store::Error::NotFound { .. }) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let is_under =
+            |url: &str, dir: &String| url.starts_with(&format!("{}/", dir.trim_end_matches('/')));
+
+        let mut dirs: BTreeMap<String, UnreferencedDir> = BTreeMap::new();
+        for file in files {
+            let file_url = format!("{}{}", prefix.object_store().as_str(), file.location);
+            if referenced
+                .iter()
+                .chain(scheduled)
+                .any(|dir| is_under(&file_url, dir))
+            {
+                continue;
+            }
+            let Some((path, _)) = file_url.rsplit_once('/') else {
+                continue;
+            };
+            let dir = dirs
+                .entry(path.to_string())
+                .or_insert_with(|| UnreferencedDir {
+                    path: path.to_string(),
+                    size_bytes: 0,
+                    last_modified: file.last_modified,
+                });
+            dir.size_bytes += file.size;
+            dir.last_modified = dir.last_modified.max(file.last_modified);
+        }
+
+        // A directory written to recently may belong to a write still in progress
+        Ok(dirs
+            .into_values()
+            .filter(|dir| dir.last_modified < cutoff)
+            .collect())
+    }
+
+    /// Find cache versions no table references, such as those left by failed fetches or
+    /// by deletions that were given up on, and unless `dry_run` is set delete them.
+    ///
+    /// Only versions whose files are all older than the engine's garbage collection
+    /// minimum age are considered. Use a dry run to review what would be reclaimed.
+    pub async fn collect_cache_garbage(&self, dry_run: bool) -> Result<CacheGcResult> {
+        Self::run_cache_gc(
+            &self.catalog,
+            &self.storage,
+            &self.df_ctx.runtime_env(),
+            self.cache_gc_min_age,
+            dry_run,
+        )
+        .await
+    }
+
+    async fn run_cache_gc(
+        catalog: &Arc<dyn CatalogManager>,
+        storage: &Arc<dyn StorageManager>,
+        runtime_env: &RuntimeEnv,
+        min_age: Duration,
+        dry_run: bool,
+    ) -> Result<CacheGcResult> {
+        let min_age = chrono::Duration::from_std(min_age)
+            .map_err(|e| anyhow::anyhow!("Invalid garbage collection age: {}", e))?;
+        let cutoff = Utc::now() - min_age;
+
+        // Read the catalog before listing storage, so that a version written in between
+        // is too recent to be collected rather than seemingly unreferenced
+        let mut referenced: Vec<String> = catalog
+            .list_tables(None)
+            .await?
+            .into_iter()
+            .filter_map(|t| t.parquet_path)
+            .collect();
+        referenced.extend(
+            catalog
+                .list_snapshot_tables(None)
+                .await?
+                .into_iter()
+                .map(|t| t.parquet_path),
+        );
+        let scheduled: Vec<String> = catalog
+            .list_scheduled_deletions()
+            .await?
+            .into_iter()
+            .map(|d| d.path)
+            .collect();
+
+        let mut result = CacheGcResult {
+            dry_run,
+            ..Default::default()
+        };
+        for conn in catalog.list_connections().await? {
+            let prefix = cache_url(&storage.cache_prefix(conn.id));
+            let dirs = match Self::find_unreferenced_dirs(
+                runtime_env,
+                &prefix,
+                &referenced,
+                &scheduled,
+                cutoff,
+            )
+            .await
+            {
+                Ok(dirs) => dirs,
+                Err(e) => {
+                    warn!("Failed to list cache files under {}: {}", prefix, e);
+                    result.errors.push(format!("{}: {}", prefix, e));
+                    continue;
+                }
+            };
+
+            for dir in dirs {
+                result.bytes_reclaimable += dir.size_bytes;
+                if !dry_run {
+                    match storage.delete_prefix(&dir.path).await {
+                        Ok(()) => {
+                            result.versions_reclaimed += 1;
+                            result.bytes_reclaimed += dir.size_bytes;
+                        }
+                        Err(e) => {
+                            warn!("Failed to delete unreferenced cache {}: {}", dir.path, e);
+                            result.errors.push(format!("{}: {}", dir.path, e));
+                        }
+                    }
+                }
+                result.versions.push(UnreferencedCacheVersion {
+                    connection_id: conn.external_id.clone(),
+                    path: dir.path,
+                    size_bytes: dir.size_bytes,
+                    last_modified: dir.last_modified,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Pin the cached versions of every cached table, or of one connection's tables, under a
+    /// named snapshot. Refreshes keep pinned versions until the snapshot is expired, and
+    /// `table_at_snapshot` queries them. Tables that are not cached are not included.
+    pub async fn create_snapshot(
+        &self,
+        name: &str,
+        connection_name: Option<&str>,
+    ) -> Result<CacheSnapshot> {
+        if name.trim().is_empty() {
+            anyhow::bail!("Snapshot name cannot be empty");
+        }
+        if self.catalog.get_cache_snapshot(name).await?.is_some() {
+            anyhow::bail!("Snapshot '{}' already exists", name);
+        }
+
+        let connection_id = match connection_name {
+            Some(connection_name) => Some(
+                self.catalog
+                    .get_connection(connection_name)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Connection '{}' not found", connection_name))?
+                    .id,
+            ),
+            None => None,
+        };
+        let tables = self.catalog.list_tables(connection_id).await?;
+        self.catalog.create_cache_snapshot(name, &tables).await?;
+
+        self.catalog
+            .get_cache_snapshot(name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Snapshot '{}' not found", name))
+    }
+
+    /// List snapshots ordered by name.
+    pub async fn list_snapshots(&self) -> Result<Vec<CacheSnapshot>> {
+        self.catalog.list_cache_snapshots().await
+    }
+
+    /// Get a snapshot and the table versions it pins.
+    pub async fn get_snapshot(&self, name: &str) -> Result<(CacheSnapshot, Vec<SnapshotTable>)> {
+        let snapshot = self
+            .catalog
+            .get_cache_snapshot(name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Snapshot '{}' not found", name))?;
+        let tables = self.catalog.list_snapshot_tables(Some(snapshot.id)).await?;
+        Ok((snapshot, tables))
+    }
+
+    /// Expire a snapshot. Versions it pinned that neither a table nor another snapshot
+    /// still uses are scheduled for deletion.
+    pub async fn expire_snapshot(&self, name: &str) -> Result<()> {
+        let (snapshot, pinned) = self.get_snapshot(name).await?;
+        self.catalog.delete_cache_snapshot(snapshot.id).await?;
+
+        let current: HashSet<String> = self
+            .catalog
+            .list_tables(None)
+            .await?
+            .into_iter()
+            .filter_map(|t| t.parquet_path)
+            .collect();
+        for table in pinned {
+            if current.contains(&table.parquet_path) {
+                continue;
+            }
+            self.metadata_cache.invalidate(&table.parquet_path);
+            self.schedule_file_deletion(&table.parquet_path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove a connection entirely (removes from catalog and deletes all data, including
+    /// versions pinned by snapshots, which no longer include its tables).
     pub async fn remove_connection(&self, name: &str) -> Result<()> {
         // Get connection info (validates it exists and gives us the ID)
         let conn = self
@@ -555,17 +1136,73 @@ impl RuntimeEngine {
 
         // Step 1: Delete metadata first
         self.catalog.delete_connection(name).await?;
+        self.warm_up_progress.lock().unwrap().remove(&conn.id);
+
+        // The catalog list no longer finds the connection; drop its kept provider
+        self.reregister_connection(&conn)?;
 
         // Step 2: Delete the physical files
         self.delete_connection_files(conn.id).await?;
 
-        // Note: DataFusion doesn't support deregistering catalogs in v50.3.0
-        // The catalog will remain registered but will return no schemas/tables
-        // since the metadata catalog has been cleaned up
-
         Ok(())
     }
 
+    /// Rename a connection and/or replace its source configuration, keeping its tables and
+    /// cache. Queries resolve the connection by its new name from then on. With
+    /// `invalidate_cache`, cached data is purged so the next query fetches from the updated
+    /// source.
+    pub async fn update_connection(
+        &self,
+        name: &str,
+        new_name: Option<&str>,
+        source: Option<Source>,
+        invalidate_cache: bool,
+    ) -> Result<ConnectionInfo> {
+        let conn = self
+            .catalog
+            .get_connection(name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' not found", name))?;
+
+        let new_name = new_name.unwrap_or(name);
+        if new_name.trim().is_empty() {
+            anyhow::bail!("Connection name cannot be empty");
+        }
+        let renamed = new_name != name;
+        if renamed && self.catalog.get_connection(new_name).await?.is_some() {
+            anyhow::bail!("Connection '{}' already exists", new_name);
+        }
+
+        let (source_type, config_json) = match source {
+            Some(source) => {
+                source.table_filter().matcher()?;
+                (
+                    source.source_type().to_string(),
+                    serde_json::to_string(&source)?,
+                )
+            }
+            None => (conn.source_type.clone(), conn.config_json.clone()),
+        };
+        self.catalog
+            .update_connection(conn.id, new_name, &source_type, &config_json)
+            .await?;
+
+        let updated = self
+            .catalog
+            .get_connection_by_id(conn.id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' not found", new_name))?;
+        if invalidate_cache {
+            // Drops the DataFusion catalog once the cache is cleared
+            self.purge_connection(new_name).await?;
+        } else {
+            self.reregister_connection(&updated)?;
+        }
+
+        info!("Connection '{}' updated", new_name);
+        Ok(updated)
+    }
+
     /// Set the default catalog for queries (allows queries without fully-qualified table names).
     pub async fn set_default_catalog(&self, name: &str) -> Result<()> {
         // Validate connection exists
@@ -597,12 +1234,39 @@ impl RuntimeEngine {
             // We use a timeout to avoid blocking forever if the worker is stuck
             let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
         }
+        if let Some(handle) = self.cache_gc_worker_handle.lock().await.take() {
+            let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+        }
+        // Writes the table accesses recorded since its last run before the catalog closes
+        if let Some(handle) = self.access_flush_worker_handle.lock().await.take() {
+            let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+        }
+        // Warm-ups stop between tables once cancelled
+        for handle in self.warm_up_handles.lock().await.drain(..) {
+            let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+        }
 
         self.catalog.close().await
     }
 
-    /// Schedule file deletion after grace period (persisted to database).
+    /// Drop cached metadata made stale by a refresh: the version it swapped out, or the
+    /// version it appended rows to.
+    fn invalidate_refreshed(&self, new_url: &str, old_path: Option<&str>, rows_synced: usize) {
+        match old_path {
+            Some(old_path) => self.metadata_cache.invalidate(old_path),
+            None if rows_synced > 0 => self.metadata_cache.invalidate(new_url),
+            None => {}
+        }
+    }
+
+    /// Schedule file deletion after grace period (persisted to database). Versions pinned by
+    /// a snapshot are kept.
     async fn schedule_file_deletion(&self, path: &str) -> Result<()> {
+        if self.pinned_paths().await?.contains(path) {
+            info!("Keeping {}, which is pinned by a snapshot", path);
+            return Ok(());
+        }
+
         let grace_period = chrono::Duration::from_std(self.deletion_grace_period)
             .map_err(|e| anyhow::anyhow!("Invalid grace period duration: {}", e))?;
         let delete_after = Utc::now() + grace_period;
@@ -614,11 +1278,19 @@ impl RuntimeEngine {
     /// Refresh schema for a connection. Re-discovers tables from remote,
     /// preserving cached data for existing tables.
     ///
-    /// Tables that no longer exist in the remote source will remain in the catalog
-    /// (they are not automatically deleted).
+    /// Tables that no longer exist in the remote source, or that the connection's table
+    /// filter now excludes, are marked stale rather than deleted, and unmarked if they
+    /// reappear. Use `prune_stale_tables()` to remove them.
+    ///
+    /// Schema changes are recorded in each table's schema history. Breaking changes
+    /// (dropped or retyped columns) follow the connection's `SchemaChangePolicy`.
     ///
-    /// Returns counts of (added, modified).
-    pub async fn refresh_schema(&self, connection_id: i32) -> Result<(usize, usize)> {
+    /// Returns counts of (added, modified) and the connection's stale tables. Blocked
+    /// changes are not counted as modified.
+    pub async fn refresh_schema(
+        &self,
+        connection_id: i32,
+    ) -> Result<(usize, usize, Vec<TableInfo>)> {
         let conn = self
             .catalog
             .get_connection_by_id(connection_id)
@@ -641,19 +1313,98 @@ impl RuntimeEngine {
 
         let added_count = current_set.difference(&existing_set).count();
 
+        let policy = self.catalog.get_schema_change_policy(connection_id).await?;
+
         let mut modified = 0;
         for table in &discovered {
-            let schema_json = serde_json::to_string(&table.to_arrow_schema())?;
-
-            if let Some(existing) = existing_tables
+            let arrow_schema = table.to_arrow_schema();
+            let schema_json = serde_json::to_string(&arrow_schema)?;
+            let estimates = (
+                table.row_count.and_then(|n| i64::try_from(n).ok()),
+                table.size_bytes.and_then(|n| i64::try_from(n).ok()),
+            );
+
+            let Some(existing) = existing_tables
                 .iter()
                 .find(|t| t.schema_name == table.schema_name && t.table_name == table.table_name)
-            {
-                if existing.arrow_schema_json.as_ref() != Some(&schema_json) {
-                    modified += 1;
+            else {
+                let table_id = self
+                    .catalog
+                    .add_table(
+                        connection_id,
+                        &table.schema_name,
+                        &table.table_name,
+                        &schema_json,
+                    )
+                    .await?;
+                self.catalog
+                    .add_schema_version(table_id, &schema_json, "[]", SchemaVersionStatus::Applied)
+                    .await?;
+                let kind = TableKind::from_source(&table.table_type);
+                if kind != TableKind::default() {
+                    self.catalog.set_table_type(table_id, kind).await?;
+                }
+                if estimates != (None, None) {
+                    self.catalog
+                        .update_table_estimates(table_id, estimates.0, estimates.1)
+                        .await?;
+                }
+                continue;
+            };
+
+            if existing.stale_since.is_some() {
+                self.catalog.set_table_stale(existing.id, false).await?;
+            }
+
+            if (existing.estimated_rows, existing.estimated_bytes) != estimates {
+                self.catalog
+                    .update_table_estimates(existing.id, estimates.0, estimates.1)
+                    .await?;
+            }
+
+            let kind = TableKind::from_source(&table.table_type);
+            if existing.kind() != kind {
+                self.catalog.set_table_type(existing.id, kind).await?;
+                // A cached copy of a table that is now read live would never be used again
+                if existing.parquet_path.is_some()
+                    && self.orchestrator.cache_policy(kind) == CachePolicy::Live
+                {
+                    self.purge_table(&conn.name, &table.schema_name, &table.table_name)
+                        .await?;
                 }
             }
 
+            if existing.arrow_schema_json.as_ref() == Some(&schema_json) {
+                continue;
+            }
+
+            let changes = existing
+                .arrow_schema_json
+                .as_deref()
+                .and_then(|json| deserialize_arrow_schema(json).ok())
+                .map(|old| diff_schemas(&old, &arrow_schema))
+                .unwrap_or_default();
+            let breaking = is_breaking(&changes);
+
+            if breaking && policy == SchemaChangePolicy::Block {
+                warn!(
+                    "Breaking schema change to {}.{}.{} blocked by policy: {}",
+                    conn.name,
+                    table.schema_name,
+                    table.table_name,
+                    serde_json::to_string(&changes)?
+                );
+                record_schema_version(
+                    self.catalog.as_ref(),
+                    existing,
+                    &schema_json,
+                    &changes,
+                    SchemaVersionStatus::Blocked,
+                )
+                .await?;
+                continue;
+            }
+
             self.catalog
                 .add_table(
                     connection_id,
@@ -662,9 +1413,53 @@ impl RuntimeEngine {
                     &schema_json,
                 )
                 .await?;
+            record_schema_version(
+                self.catalog.as_ref(),
+                existing,
+                &schema_json,
+                &changes,
+                SchemaVersionStatus::Applied,
+            )
+            .await?;
+            modified += 1;
+
+            // Cached files still have the old columns; drop them so the next query refetches
+            if breaking
+                && policy == SchemaChangePolicy::ApplyAndPurge
+                && existing.parquet_path.is_some()
+            {
+                self.purge_table(&conn.name, &table.schema_name, &table.table_name)
+                    .await?;
+            }
         }
 
-        Ok((added_count, modified))
+        let mut stale = Vec::new();
+        for table in existing_tables {
+            if current_set.contains(&(table.schema_name.clone(), table.table_name.clone())) {
+                continue;
+            }
+            if table.stale_since.is_none() {
+                warn!(
+                    "Table {}.{}.{} is no longer discovered, marking stale",
+                    conn.name, table.schema_name, table.table_name
+                );
+                self.catalog.set_table_stale(table.id, true).await?;
+            }
+            stale.push(table);
+        }
+        if stale.iter().any(|t| t.stale_since.is_none()) {
+            // Pick up the timestamps of newly stale tables
+            let stale_ids: HashSet<i32> = stale.iter().map(|t| t.id).collect();
+            stale = self
+                .catalog
+                .list_tables(Some(connection_id))
+                .await?
+                .into_iter()
+                .filter(|t| stale_ids.contains(&t.id))
+                .collect();
+        }
+
+        Ok((added_count, modified, stale))
     }
 
     /// Refresh schema for all connections.
@@ -677,15 +1472,26 @@ impl RuntimeEngine {
             tables_discovered: 0,
             tables_added: 0,
             tables_modified: 0,
+            tables_stale: 0,
+            stale_tables: Vec::new(),
             errors: Vec::new(),
         };
 
         for conn in connections {
             match self.refresh_schema(conn.id).await {
-                Ok((added, modified)) => {
+                Ok((added, modified, stale)) => {
                     result.connections_refreshed += 1;
                     result.tables_added += added;
                     result.tables_modified += modified;
+                    result.tables_stale += stale.len();
+                    result
+                        .stale_tables
+                        .extend(stale.into_iter().map(|t| StaleTable {
+                            connection_id: conn.external_id.clone(),
+                            schema_name: t.schema_name,
+                            table_name: t.table_name,
+                            stale_since: t.stale_since,
+                        }));
                 }
                 Err(e) => {
                     tracing::warn!(
@@ -703,7 +1509,13 @@ impl RuntimeEngine {
             }
         }
 
-        result.tables_discovered = self.catalog.list_tables(None).await?.len();
+        result.tables_discovered = self
+            .catalog
+            .list_tables(None)
+            .await?
+            .iter()
+            .filter(|t| t.stale_since.is_none())
+            .count();
         Ok(result)
     }
 
@@ -725,10 +1537,11 @@ impl RuntimeEngine {
             .ok_or_else(|| anyhow::anyhow!("Connection not found"))?;
         let source: Source = serde_json::from_str(&conn.config_json)?;
 
-        let (_, old_path, rows_synced) = self
+        let (new_url, old_path, rows_synced) = self
             .orchestrator
             .refresh_table(&source, connection_id, schema_name, table_name)
             .await?;
+        self.invalidate_refreshed(&new_url, old_path.as_deref(), rows_synced);
 
         if let Some(path) = old_path {
             if let Err(e) = self.schedule_file_deletion(&path).await {
@@ -770,15 +1583,13 @@ impl RuntimeEngine {
         let start = std::time::Instant::now();
         let all_tables = self.catalog.list_tables(Some(connection_id)).await?;
 
-        // By default, only refresh tables that already have cached data
-        let tables: Vec<_> = if include_uncached {
-            all_tables
-        } else {
-            all_tables
-                .into_iter()
-                .filter(|t| t.parquet_path.is_some())
-                .collect()
-        };
+        // By default, only refresh tables that already have cached data. Tables read live
+        // are never cached.
+        let tables: Vec<_> = all_tables
+            .into_iter()
+            .filter(|t| include_uncached || t.parquet_path.is_some())
+            .filter(|t| self.orchestrator.cache_policy(t.kind()) != CachePolicy::Live)
+            .collect();
 
         let conn = self
             .catalog
@@ -820,7 +1631,8 @@ impl RuntimeEngine {
         for handle in handles {
             let (schema_name, table_name, refresh_result) = handle.await?;
             match refresh_result {
-                Ok((_, old_path, rows_synced)) => {
+                Ok((new_url, old_path, rows_synced)) => {
+                    self.invalidate_refreshed(&new_url, old_path.as_deref(), rows_synced);
                     result.tables_refreshed += 1;
                     result.total_rows += rows_synced;
                     if let Some(path) = old_path {
@@ -855,12 +1667,160 @@ impl RuntimeEngine {
         Ok(result)
     }
 
+    /// Start fetching the connection's uncached warm-up tables in the background, using
+    /// the same parallelism as a connection refresh. Returns false when there is nothing
+    /// to warm up.
+    pub async fn start_warm_up(&self, connection_id: i32) -> Result<bool> {
+        let patterns = self.warm_up_patterns(connection_id).await?;
+        if patterns.is_empty() {
+            return Ok(false);
+        }
+        let matcher = warm_up_filter(&patterns).matcher()?;
+
+        let tables: Vec<_> = self
+            .catalog
+            .list_tables(Some(connection_id))
+            .await?
+            .into_iter()
+            .filter(|t| t.parquet_path.is_none())
+            .filter(|t| self.orchestrator.cache_policy(t.kind()) != CachePolicy::Live)
+            .filter(|t| matcher.matches(&t.schema_name, &t.table_name))
+            .map(|t| (t.schema_name, t.table_name))
+            .collect();
+        if tables.is_empty() {
+            return Ok(false);
+        }
+
+        let conn = self
+            .catalog
+            .get_connection_by_id(connection_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Connection not found"))?;
+        let source: Source = serde_json::from_str(&conn.config_json)?;
+
+        info!(
+            "Warming up {} table(s) for connection '{}'",
+            tables.len(),
+            conn.name
+        );
+        self.warm_up_progress.lock().unwrap().insert(
+            connection_id,
+            WarmUpProgress {
+                state: WarmUpState::Running,
+                tables_total: tables.len(),
+                tables_cached: 0,
+                tables_failed: 0,
+                errors: vec![],
+            },
+        );
+
+        let handle = tokio::spawn(Self::run_warm_up(
+            self.orchestrator.clone(),
+            self.warm_up_progress.clone(),
+            self.shutdown_token.clone(),
+            self.parallel_refresh_count,
+            connection_id,
+            source,
+            tables,
+        ));
+        let mut handles = self.warm_up_handles.lock().await;
+        handles.retain(|h| !h.is_finished());
+        handles.push(handle);
+        Ok(true)
+    }
+
+    /// Start warm-ups for every connection with uncached warm-up tables.
+    async fn start_warm_ups(&self) -> Result<()> {
+        for conn in self.catalog.list_connections().await? {
+            if let Err(e) = self.start_warm_up(conn.id).await {
+                warn!(
+                    "Failed to start warm-up for connection '{}': {}",
+                    conn.name, e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_warm_up(
+        orchestrator: Arc<FetchOrchestrator>,
+        progress: Arc<std::sync::Mutex<HashMap<i32, WarmUpProgress>>>,
+        shutdown_token: CancellationToken,
+        parallel_refresh_count: usize,
+        connection_id: i32,
+        source: Source,
+        tables: Vec<(String, String)>,
+    ) {
+        let semaphore = Arc::new(Semaphore::new(parallel_refresh_count));
+        let mut handles = vec![];
+        let mut cancelled = false;
+
+        for (schema_name, table_name) in tables {
+            let permit = tokio::select! {
+                _ = shutdown_token.cancelled() => {
+                    cancelled = true;
+                    break;
+                }
+                permit = semaphore.clone().acquire_owned() => match permit {
+                    Ok(permit) => permit,
+                    Err(_) => break,
+                },
+            };
+            let orchestrator = orchestrator.clone();
+            let progress = progress.clone();
+            let source = source.clone();
+
+            handles.push(tokio::spawn(async move {
+                // A query or another warm-up may be caching the table too; only one
+                // of them fetches it
+                let result = orchestrator
+                    .ensure_cached(&source, connection_id, &schema_name, &table_name)
+                    .await
+                    .map(|_| ());
+                drop(permit);
+
+                let mut progress = progress.lock().unwrap();
+                if let Some(progress) = progress.get_mut(&connection_id) {
+                    match result {
+                        Ok(()) => progress.tables_cached += 1,
+                        Err(e) => {
+                            warn!("Warm-up of {}.{} failed: {}", schema_name, table_name, e);
+                            progress.tables_failed += 1;
+                            progress.errors.push(TableRefreshError {
+                                schema_name,
+                                table_name,
+                                error: e.to_string(),
+                            });
+                        }
+                    }
+                }
+            }));
+        }
+
+        for handle in handles {
+            let _ = handle.await;
+        }
+        if let Some(progress) = progress.lock().unwrap().get_mut(&connection_id) {
+            progress.state = if cancelled {
+                WarmUpState::Cancelled
+            } else {
+                WarmUpState::Completed
+            };
+        }
+    }
+
     /// Process any pending directory deletions that are due.
     pub async fn process_pending_deletions(&self) -> Result<usize> {
         let pending = self.catalog.get_pending_deletions().await?;
+        let pinned = self.pinned_paths().await?;
         let mut deleted = 0;
 
         for deletion in pending {
+            // Pinned by a snapshot created after the deletion was scheduled
+            if pinned.contains(&deletion.path) {
+                self.catalog.remove_pending_deletion(deletion.id).await?;
+                continue;
+            }
             match self.storage.delete_prefix(&deletion.path).await {
                 Ok(_) => {
                     self.catalog.remove_pending_deletion(deletion.id).await?;
@@ -899,8 +1859,25 @@ impl RuntimeEngine {
                                 continue;
                             }
                         };
+                        if pending.is_empty() {
+                            continue;
+                        }
+                        let pinned: HashSet<String> = match catalog.list_snapshot_tables(None).await {
+                            Ok(tables) => tables.into_iter().map(|t| t.parquet_path).collect(),
+                            Err(e) => {
+                                warn!("Failed to get snapshot pins: {}", e);
+                                continue;
+                            }
+                        };
 
                         for deletion in pending {
+                            // Pinned by a snapshot created after the deletion was scheduled
+                            if pinned.contains(&deletion.path) {
+                                if let Err(e) = catalog.remove_pending_deletion(deletion.id).await {
+                                    warn!("Failed to remove deletion record {}: {}", deletion.id, e);
+                                }
+                                continue;
+                            }
                             match storage.delete_prefix(&deletion.path).await {
                                 Ok(_) => {
                                     // Successfully deleted - remove the record
@@ -943,6 +1920,82 @@ impl RuntimeEngine {
             }
         })
     }
+
+    /// Start background task that writes recorded table accesses to the catalog
+    /// periodically, and once more on shutdown.
+    fn start_access_flush_worker(
+        orchestrator: Arc<FetchOrchestrator>,
+        shutdown_token: CancellationToken,
+        interval_duration: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval_duration);
+            loop {
+                tokio::select! {
+                    _ = shutdown_token.cancelled() => {
+                        if let Err(e) = orchestrator.flush_table_accesses().await {
+                            warn!("Failed to record table accesses: {}", e);
+                        }
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        if let Err(e) = orchestrator.flush_table_accesses().await {
+                            warn!("Failed to record table accesses: {}", e);
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Start background task that collects unreferenced cache versions periodically.
+    fn start_cache_gc_worker(
+        catalog: Arc<dyn CatalogManager>,
+        storage: Arc<dyn StorageManager>,
+        runtime_env: Arc<RuntimeEnv>,
+        shutdown_token: CancellationToken,
+        interval_duration: Duration,
+        min_age: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval_duration);
+            loop {
+                tokio::select! {
+                    _ = shutdown_token.cancelled() => {
+                        info!("Cache garbage collector received shutdown signal");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        match Self::run_cache_gc(&catalog, &storage, &runtime_env, min_age, false).await {
+                            Ok(result) if result.versions_reclaimed > 0 => info!(
+                                "Reclaimed {} unreferenced cache versions ({} bytes)",
+                                result.versions_reclaimed, result.bytes_reclaimed
+                            ),
+                            Ok(_) => {}
+                            Err(e) => warn!("Cache garbage collection failed: {}", e),
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Warm-up patterns as a filter that keeps tables matching any of them.
+fn warm_up_filter(patterns: &[TablePattern]) -> TableFilter {
+    TableFilter {
+        include: patterns.to_vec(),
+        exclude: vec![],
+    }
+}
+
+/// Storage prefixes of local backends are plain paths; listing needs a URL.
+fn cache_url(prefix: &str) -> String {
+    if prefix.contains("://") {
+        prefix.to_string()
+    } else {
+        format!("file://{}", prefix)
+    }
 }
 
 impl Drop for RuntimeEngine {
@@ -990,7 +2043,13 @@ pub struct RuntimeEngineBuilder {
     secret_key: Option<String>,
     deletion_grace_period: Duration,
     deletion_worker_interval: Duration,
+    cache_gc_interval: Option<Duration>,
+    cache_gc_min_age: Duration,
     parallel_refresh_count: usize,
+    cache_policies: Vec<(TableKind, CachePolicy)>,
+    metadata_cache_size: usize,
+    catalog_refresh_interval: Duration,
+    access_flush_interval: Duration,
 }
 
 impl Default for RuntimeEngineBuilder {
@@ -1002,6 +2061,12 @@ impl Default for RuntimeEngineBuilder {
 /// Default grace period for file deletion (60 seconds).
 const DEFAULT_DELETION_GRACE_PERIOD: Duration = Duration::from_secs(60);
 
+/// Default interval for re-reading connections from the catalog store (5 seconds).
+const DEFAULT_CATALOG_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default interval for writing recorded table accesses to the catalog (30 seconds).
+const DEFAULT_ACCESS_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
 impl RuntimeEngineBuilder {
     pub fn new() -> Self {
         Self {
@@ -1012,7 +2077,13 @@ impl RuntimeEngineBuilder {
             secret_key: std::env::var("RUNTIMEDB_SECRET_KEY").ok(),
             deletion_grace_period: DEFAULT_DELETION_GRACE_PERIOD,
             deletion_worker_interval: Duration::from_secs(DEFAULT_DELETION_WORKER_INTERVAL_SECS),
+            cache_gc_interval: None,
+            cache_gc_min_age: DEFAULT_CACHE_GC_MIN_AGE,
             parallel_refresh_count: DEFAULT_PARALLEL_REFRESH_COUNT,
+            cache_policies: Vec::new(),
+            metadata_cache_size: DEFAULT_METADATA_CACHE_SIZE,
+            catalog_refresh_interval: DEFAULT_CATALOG_REFRESH_INTERVAL,
+            access_flush_interval: DEFAULT_ACCESS_FLUSH_INTERVAL,
         }
     }
 
@@ -1069,6 +2140,21 @@ impl RuntimeEngineBuilder {
         self
     }
 
+    /// Run the cache garbage collector at this interval, deleting cache versions no table
+    /// references. Disabled by default; `RuntimeEngine::collect_cache_garbage` can still
+    /// be called directly.
+    pub fn cache_gc_interval(mut self, duration: Duration) -> Self {
+        self.cache_gc_interval = Some(duration);
+        self
+    }
+
+    /// Set how long after its last write an unreferenced cache version may be collected.
+    /// Defaults to one hour.
+    pub fn cache_gc_min_age(mut self, duration: Duration) -> Self {
+        self.cache_gc_min_age = duration;
+        self
+    }
+
     /// Set the number of parallel table refreshes for connection-wide data refresh.
     /// Higher values may speed up refresh for connections with many small tables,
     /// but could overwhelm the source database. Defaults to 4. Minimum value is 1
@@ -1078,6 +2164,38 @@ impl RuntimeEngineBuilder {
         self
     }
 
+    /// Override how tables of one kind are cached. By default views are read live,
+    /// materialized views are refetched only after the source refreshes them, and all
+    /// other tables are cached.
+    pub fn cache_policy(mut self, kind: TableKind, policy: CachePolicy) -> Self {
+        self.cache_policies.push((kind, policy));
+        self
+    }
+
+    /// Set the memory budget, in bytes, for cached file listings and parquet statistics
+    /// of cached tables. Defaults to 64 MiB; 0 disables the cache.
+    pub fn metadata_cache_size(mut self, bytes: usize) -> Self {
+        self.metadata_cache_size = bytes;
+        self
+    }
+
+    /// Set how long connections read from the catalog store are reused before queries
+    /// read them again. Changes made through this instance are seen immediately; changes
+    /// made by other instances sharing the catalog store within this interval.
+    /// Defaults to 5 seconds.
+    pub fn catalog_refresh_interval(mut self, interval: Duration) -> Self {
+        self.catalog_refresh_interval = interval;
+        self
+    }
+
+    /// Set how often table accesses recorded by queries are written to the catalog, which
+    /// is how far `last_accessed_at` and `access_count` may lag behind. Defaults to 30
+    /// seconds.
+    pub fn access_flush_interval(mut self, interval: Duration) -> Self {
+        self.access_flush_interval = interval;
+        self
+    }
+
     /// Resolve the base directory, using default if not set.
     fn resolve_base_dir(&self) -> PathBuf {
         self.base_dir.clone().unwrap_or_else(|| {
@@ -1131,12 +2249,9 @@ impl RuntimeEngineBuilder {
             }
         };
 
-        // Step 5: Run migrations and set up DataFusion
+        // Step 5: Run migrations
         catalog.run_migrations().await?;
 
-        let df_ctx = SessionContext::new();
-        storage.register_with_datafusion(&df_ctx)?;
-
         // Step 6: Initialize secret manager
         let (secret_key, using_default_key) = match self.secret_key {
             Some(key) => (key, false),
@@ -1173,12 +2288,39 @@ impl RuntimeEngineBuilder {
 
         // Step 7: Create fetch orchestrator (needs secret_manager)
         let fetcher = Arc::new(NativeFetcher::new());
-        let orchestrator = Arc::new(FetchOrchestrator::new(
-            fetcher,
-            storage.clone(),
+        let orchestrator = self.cache_policies.into_iter().fold(
+            FetchOrchestrator::new(
+                fetcher,
+                storage.clone(),
+                catalog.clone(),
+                secret_manager.clone(),
+            ),
+            |orchestrator, (kind, policy)| orchestrator.with_cache_policy(kind, policy),
+        );
+        let orchestrator = Arc::new(orchestrator);
+
+        // Step 8: Set up DataFusion. Connection catalogs are resolved from the catalog store,
+        // so connections added or removed elsewhere are seen within the refresh interval.
+        let metadata_cache = Arc::new(ParquetMetadataCache::new(self.metadata_cache_size));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_cache_manager(metadata_cache.cache_manager_config())
+            .build_arc()?;
+        let catalog_list = Arc::new(RuntimeCatalogProviderList::new(
             catalog.clone(),
-            secret_manager.clone(),
+            orchestrator.clone(),
+            self.catalog_refresh_interval,
         ));
+        let state = SessionStateBuilder::new()
+            .with_config(SessionConfig::new())
+            .with_runtime_env(runtime)
+            .with_catalog_list(catalog_list.clone())
+            .with_default_features()
+            .build();
+        let mut df_ctx = SessionContext::new_with_state(state);
+        storage.register_with_datafusion(&df_ctx)?;
+
+        // JSON columns are stored as text; json_get, json_contains, `->` etc. query them
+        datafusion_functions_json::register_all(&mut df_ctx)?;
 
         // Create shutdown token for graceful shutdown
         let shutdown_token = CancellationToken::new();
@@ -1191,41 +2333,144 @@ impl RuntimeEngineBuilder {
             self.deletion_worker_interval,
         );
 
-        let mut engine = RuntimeEngine {
+        let cache_gc_worker_handle = self.cache_gc_interval.map(|interval| {
+            RuntimeEngine::start_cache_gc_worker(
+                catalog.clone(),
+                storage.clone(),
+                df_ctx.runtime_env(),
+                shutdown_token.clone(),
+                interval,
+                self.cache_gc_min_age,
+            )
+        });
+
+        let access_flush_worker_handle = RuntimeEngine::start_access_flush_worker(
+            orchestrator.clone(),
+            shutdown_token.clone(),
+            self.access_flush_interval,
+        );
+
+        let engine = RuntimeEngine {
             catalog,
             df_ctx,
+            catalog_list,
             storage,
             orchestrator,
             secret_manager,
+            metadata_cache,
             shutdown_token,
             deletion_worker_handle: Mutex::new(Some(deletion_worker_handle)),
             deletion_grace_period: self.deletion_grace_period,
             deletion_worker_interval: self.deletion_worker_interval,
+            cache_gc_worker_handle: Mutex::new(cache_gc_worker_handle),
+            cache_gc_min_age: self.cache_gc_min_age,
+            access_flush_worker_handle: Mutex::new(Some(access_flush_worker_handle)),
             parallel_refresh_count: self.parallel_refresh_count,
+            warm_up_progress: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            warm_up_handles: Mutex::new(Vec::new()),
         };
 
-        // Register all existing connections as DataFusion catalogs
-        engine.register_existing_connections().await?;
-
         // Register the runtimedb virtual catalog for system metadata
         let runtimedb_catalog = Arc::new(RuntimeDbCatalogProvider::new());
         runtimedb_catalog.register_schema(
             "information_schema",
-            Arc::new(InformationSchemaProvider::new(engine.catalog.clone())),
+            Arc::new(InformationSchemaProvider::new(
+                engine.catalog.clone(),
+                engine.orchestrator.clone(),
+            )),
         )?;
         engine
             .df_ctx
             .register_catalog("runtimedb", runtimedb_catalog);
 
+        // Register time travel for snapshot-versioned sources
+        engine.df_ctx.register_udtf(
+            TABLE_AS_OF_FUNCTION,
+            Arc::new(TableAsOfFunction::new(
+                engine.catalog.clone(),
+                engine.orchestrator.clone(),
+            )),
+        );
+        engine.df_ctx.register_udtf(
+            TABLE_AT_SNAPSHOT_FUNCTION,
+            Arc::new(TableAtSnapshotFunction::new(engine.catalog.clone())),
+        );
+
         // Process any pending deletions from previous runs
         if let Err(e) = engine.process_pending_deletions().await {
             warn!("Failed to process pending deletions on startup: {}", e);
         }
 
+        // Fetch warm-up tables that are missing from the cache
+        if let Err(e) = engine.start_warm_ups().await {
+            warn!("Failed to start cache warm-up on startup: {}", e);
+        }
+
         Ok(engine)
     }
 }
 
+/// S3 options from the storage config. Credentials come from, in order: the configured
+/// secret, `RUNTIMEDB_STORAGE_ACCESS_KEY_ID`/`RUNTIMEDB_STORAGE_SECRET_ACCESS_KEY`, the
+/// configured profile, or the standard AWS chain. Conflicting or partial settings are
+/// errors rather than falling back to another source.
+fn s3_options_from_config(
+    storage: &crate::config::StorageConfig,
+    credentials_secret: Option<Arc<SecretCredentialProvider>>,
+) -> Result<S3StorageOptions> {
+    let access_key = std::env::var("RUNTIMEDB_STORAGE_ACCESS_KEY_ID").ok();
+    let secret_key = std::env::var("RUNTIMEDB_STORAGE_SECRET_ACCESS_KEY").ok();
+    let static_keys = match (access_key, secret_key) {
+        (Some(access_key), Some(secret_key)) => Some(S3CredentialSource::Static {
+            access_key,
+            secret_key,
+            session_token: std::env::var("RUNTIMEDB_STORAGE_SESSION_TOKEN").ok(),
+        }),
+        (None, None) => None,
+        _ => anyhow::bail!(
+            "S3 storage requires both RUNTIMEDB_STORAGE_ACCESS_KEY_ID and \
+             RUNTIMEDB_STORAGE_SECRET_ACCESS_KEY, or neither"
+        ),
+    };
+
+    let credentials = match (credentials_secret, static_keys, &storage.profile) {
+        (Some(provider), None, None) => S3CredentialSource::Secret(provider),
+        (None, Some(keys), None) => keys,
+        (None, None, Some(profile)) => S3CredentialSource::Profile(profile.clone()),
+        (None, None, None) => S3CredentialSource::Chain,
+        _ => anyhow::bail!(
+            "S3 credentials are configured more than once; use only one of \
+             'credentials_secret', 'profile' or RUNTIMEDB_STORAGE_ACCESS_KEY_ID"
+        ),
+    };
+
+    let encryption = match storage.encryption.as_deref() {
+        None => None,
+        Some("sse-s3") => Some(S3Encryption::S3),
+        Some("sse-kms") => Some(S3Encryption::Kms {
+            key_id: storage.kms_key_id.clone(),
+        }),
+        Some(other) => anyhow::bail!("Invalid S3 encryption: {}", other),
+    };
+    if storage.kms_key_id.is_some() && !matches!(encryption, Some(S3Encryption::Kms { .. })) {
+        anyhow::bail!("S3 'kms_key_id' requires encryption = \"sse-kms\"");
+    }
+
+    Ok(S3StorageOptions {
+        region: storage.region.clone(),
+        endpoint: storage.endpoint.clone(),
+        // Allow HTTP for local MinIO
+        allow_http: storage
+            .endpoint
+            .as_deref()
+            .is_some_and(|e| e.starts_with("http://")),
+        prefix: storage.prefix.clone(),
+        credentials,
+        encryption,
+        virtual_hosted_style: storage.virtual_hosted_style,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1437,7 +2682,8 @@ mod tests {
     #[tokio::test(flavor = "multi_thread")]
     async fn test_from_config_sqlite_filesystem() {
         use crate::config::{
-            AppConfig, CatalogConfig, PathsConfig, SecretsConfig, ServerConfig, StorageConfig,
+            AppConfig, CacheConfig, CatalogConfig, PathsConfig, SecretsConfig, ServerConfig,
+            StorageConfig,
         };
 
         let temp_dir = TempDir::new().unwrap();
@@ -1461,6 +2707,15 @@ mod tests {
                 bucket: None,
                 region: None,
                 endpoint: None,
+                account: None,
+                prefix: None,
+                encryption: None,
+                kms_key_id: None,
+                profile: None,
+                credentials_secret: None,
+                virtual_hosted_style: None,
+                local_cache_dir: None,
+                local_cache_size: None,
             },
             paths: PathsConfig {
                 base_dir: Some(base_dir.to_str().unwrap().to_string()),
@@ -1469,6 +2724,7 @@ mod tests {
             secrets: SecretsConfig {
                 encryption_key: Some(test_secret_key()),
             },
+            cache: CacheConfig::default(),
         };
 
         let engine = RuntimeEngine::from_config(&config).await;
@@ -1485,6 +2741,50 @@ mod tests {
         assert!(connections.is_ok(), "Should be able to list connections");
     }
 
+    #[test]
+    fn test_s3_options_from_config() {
+        let storage = |profile: Option<&str>, encryption: Option<&str>, kms: Option<&str>| {
+            crate::config::StorageConfig {
+                storage_type: "s3".to_string(),
+                bucket: Some("bucket".to_string()),
+                region: Some("eu-west-1".to_string()),
+                endpoint: None,
+                account: None,
+                prefix: Some("tenant-a".to_string()),
+                encryption: encryption.map(String::from),
+                kms_key_id: kms.map(String::from),
+                profile: profile.map(String::from),
+                credentials_secret: None,
+                virtual_hosted_style: None,
+                local_cache_dir: None,
+                local_cache_size: None,
+            }
+        };
+
+        let options = s3_options_from_config(
+            &storage(Some("analytics"), Some("sse-kms"), Some("key")),
+            None,
+        )
+        .unwrap();
+        assert!(
+            matches!(options.credentials, S3CredentialSource::Profile(ref p) if p == "analytics")
+        );
+        assert_eq!(
+            options.encryption,
+            Some(S3Encryption::Kms {
+                key_id: Some("key".to_string())
+            })
+        );
+        assert_eq!(options.region.as_deref(), Some("eu-west-1"));
+        assert_eq!(options.prefix.as_deref(), Some("tenant-a"));
+
+        // Misconfiguration is an error, not a fallback
+        let secret = Some(Arc::new(SecretCredentialProvider::new("s3")));
+        assert!(s3_options_from_config(&storage(Some("analytics"), None, None), secret).is_err());
+        assert!(s3_options_from_config(&storage(None, Some("sse-s3"), Some("key")), None).is_err());
+        assert!(s3_options_from_config(&storage(None, Some("aes"), None), None).is_err());
+    }
+
     #[test]
     fn test_parallel_refresh_count_clamps_to_minimum_one() {
         // Test that parallel_refresh_count is clamped to at least 1
@@ -1,12 +1,16 @@
 use crate::http::handlers::{
-    create_connection_handler, create_secret_handler, delete_connection_handler,
-    delete_secret_handler, get_connection_handler, get_result_handler, get_secret_handler,
-    health_handler, information_schema_handler, list_connections_handler, list_results_handler,
-    list_secrets_handler, purge_connection_cache_handler, purge_table_cache_handler, query_handler,
-    refresh_handler, update_secret_handler,
+    cache_gc_handler, cache_gc_report_handler, create_connection_handler, create_secret_handler,
+    create_snapshot_handler, delete_connection_handler, delete_secret_handler,
+    expire_snapshot_handler, get_connection_handler, get_result_handler,
+    get_schema_history_handler, get_secret_handler, get_snapshot_handler, health_handler,
+    information_schema_handler, list_connections_handler, list_results_handler,
+    list_secrets_handler, list_snapshots_handler, metadata_cache_stats_handler,
+    prune_stale_tables_handler, purge_connection_cache_handler, purge_table_cache_handler,
+    query_handler, refresh_handler, update_connection_handler, update_schema_policy_handler,
+    update_secret_handler, verify_cache_handler,
 };
 use crate::RuntimeEngine;
-use axum::routing::{delete, get, post};
+use axum::routing::{delete, get, post, put};
 use axum::Router;
 use std::sync::Arc;
 
@@ -22,11 +26,19 @@ pub const PATH_REFRESH: &str = "/refresh";
 pub const PATH_CONNECTIONS: &str = "/connections";
 pub const PATH_CONNECTION: &str = "/connections/{connection_id}";
 pub const PATH_CONNECTION_CACHE: &str = "/connections/{connection_id}/cache";
+pub const PATH_CONNECTION_SCHEMA_HISTORY: &str = "/connections/{connection_id}/schema_history";
+pub const PATH_CONNECTION_SCHEMA_POLICY: &str = "/connections/{connection_id}/schema_policy";
+pub const PATH_CONNECTION_STALE_TABLES: &str = "/connections/{connection_id}/stale_tables";
 pub const PATH_TABLE_CACHE: &str = "/connections/{connection_id}/tables/{schema}/{table}/cache";
 pub const PATH_SECRETS: &str = "/secrets";
 pub const PATH_SECRET: &str = "/secrets/{name}";
 pub const PATH_RESULTS: &str = "/results";
 pub const PATH_RESULT: &str = "/results/{id}";
+pub const PATH_METADATA_CACHE: &str = "/cache/metadata";
+pub const PATH_CACHE_VERIFY: &str = "/cache/verify";
+pub const PATH_CACHE_GC: &str = "/cache/gc";
+pub const PATH_SNAPSHOTS: &str = "/snapshots";
+pub const PATH_SNAPSHOT: &str = "/snapshots/{name}";
 
 impl AppServer {
     pub fn new(engine: RuntimeEngine) -> Self {
@@ -43,12 +55,26 @@ impl AppServer {
                 )
                 .route(
                     PATH_CONNECTION,
-                    get(get_connection_handler).delete(delete_connection_handler),
+                    get(get_connection_handler)
+                        .patch(update_connection_handler)
+                        .delete(delete_connection_handler),
                 )
                 .route(
                     PATH_CONNECTION_CACHE,
                     delete(purge_connection_cache_handler),
                 )
+                .route(
+                    PATH_CONNECTION_SCHEMA_HISTORY,
+                    get(get_schema_history_handler),
+                )
+                .route(
+                    PATH_CONNECTION_SCHEMA_POLICY,
+                    put(update_schema_policy_handler),
+                )
+                .route(
+                    PATH_CONNECTION_STALE_TABLES,
+                    delete(prune_stale_tables_handler),
+                )
                 .route(PATH_TABLE_CACHE, delete(purge_table_cache_handler))
                 .route(
                     PATH_SECRETS,
@@ -62,6 +88,20 @@ impl AppServer {
                 )
                 .route(PATH_RESULTS, get(list_results_handler))
                 .route(PATH_RESULT, get(get_result_handler))
+                .route(PATH_METADATA_CACHE, get(metadata_cache_stats_handler))
+                .route(PATH_CACHE_VERIFY, post(verify_cache_handler))
+                .route(
+                    PATH_CACHE_GC,
+                    get(cache_gc_report_handler).post(cache_gc_handler),
+                )
+                .route(
+                    PATH_SNAPSHOTS,
+                    post(create_snapshot_handler).get(list_snapshots_handler),
+                )
+                .route(
+                    PATH_SNAPSHOT,
+                    get(get_snapshot_handler).delete(expire_snapshot_handler),
+                )
                 .with_state(engine.clone()),
             engine,
         }
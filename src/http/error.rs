@@ -93,6 +93,7 @@ impl From<anyhow::Error> for ApiError {
                 DataFetchError::UnsupportedDriver(_) | DataFetchError::DriverLoad(_) => {
                     ApiError::bad_request
                 }
+                DataFetchError::SchemaMismatch { .. } => ApiError::conflict,
                 _ => ApiError::internal_error,
             };
             return constructor(err.to_string());
@@ -130,6 +131,7 @@ impl From<crate::datafetch::DataFetchError> for ApiError {
             DataFetchError::UnsupportedDriver(_) | DataFetchError::DriverLoad(_) => {
                 ApiError::bad_request
             }
+            DataFetchError::SchemaMismatch { .. } => ApiError::conflict,
             DataFetchError::Connection(_)
             | DataFetchError::Query(_)
             | DataFetchError::Storage(_)
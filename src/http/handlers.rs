@@ -1,15 +1,18 @@
 use crate::datafetch::deserialize_arrow_schema;
 use crate::http::error::ApiError;
 use crate::http::models::{
-    ColumnInfo, ConnectionInfo, CreateConnectionRequest, CreateConnectionResponse,
-    CreateSecretRequest, CreateSecretResponse, DiscoveryStatus, GetConnectionResponse,
-    GetSecretResponse, InformationSchemaResponse, ListConnectionsResponse, ListResultsResponse,
-    ListSecretsResponse, QueryRequest, QueryResponse, RefreshRequest, RefreshResponse, ResultInfo,
-    SchemaRefreshResult, SecretMetadataResponse, TableInfo, UpdateSecretRequest,
-    UpdateSecretResponse,
+    CacheGcResult, CacheVerificationResult, ColumnInfo, ConnectionInfo, CreateConnectionRequest,
+    CreateConnectionResponse, CreateSecretRequest, CreateSecretResponse, CreateSnapshotRequest,
+    DiscoveryStatus, GetConnectionResponse, GetSecretResponse, InformationSchemaResponse,
+    ListConnectionsResponse, ListResultsResponse, ListSecretsResponse, ListSnapshotsResponse,
+    MetadataCacheStatsResponse, PruneStaleTablesResponse, QueryRequest, QueryResponse,
+    RefreshRequest, RefreshResponse, ResultInfo, SchemaHistoryResponse, SchemaRefreshResult,
+    SchemaVersionInfo, SecretMetadataResponse, SnapshotResponse, SnapshotSummary,
+    SnapshotTableInfo, StaleTable, TableInfo, UpdateConnectionRequest, UpdateSchemaPolicyRequest,
+    UpdateSecretRequest, UpdateSecretResponse, VerifyCacheRequest,
 };
 use crate::http::serialization::{encode_value_at, make_array_encoder};
-use crate::source::Source;
+use crate::source::{Source, TableFilter};
 use crate::RuntimeEngine;
 use axum::{
     extract::{Path, Query as QueryParams, State},
@@ -164,8 +167,10 @@ pub async fn information_schema_handler(
                     connection: conn_name.clone(),
                     schema: t.schema_name,
                     table: t.table_name,
+                    table_type: t.table_type,
                     synced: t.parquet_path.is_some(),
                     last_sync: t.last_sync,
+                    stale: t.stale_since.is_some(),
                     columns,
                 }
             })
@@ -188,6 +193,46 @@ pub async fn health_handler() -> (StatusCode, Json<serde_json::Value>) {
     )
 }
 
+/// Handler for GET /cache/metadata
+pub async fn metadata_cache_stats_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+) -> Json<MetadataCacheStatsResponse> {
+    let stats = engine.metadata_cache_stats();
+    Json(MetadataCacheStatsResponse {
+        hits: stats.hits,
+        misses: stats.misses,
+        hit_rate: stats.hit_rate(),
+        entries: stats.entries,
+        size_bytes: stats.size_bytes,
+        capacity_bytes: stats.capacity_bytes,
+    })
+}
+
+/// Handler for POST /cache/verify
+pub async fn verify_cache_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    QueryParams(params): QueryParams<VerifyCacheRequest>,
+) -> Result<Json<CacheVerificationResult>, ApiError> {
+    let result = engine.verify_cache(params.delete_orphans).await?;
+    Ok(Json(result))
+}
+
+/// Handler for GET /cache/gc: report unreferenced cache versions without deleting them
+pub async fn cache_gc_report_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+) -> Result<Json<CacheGcResult>, ApiError> {
+    let result = engine.collect_cache_garbage(true).await?;
+    Ok(Json(result))
+}
+
+/// Handler for POST /cache/gc
+pub async fn cache_gc_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+) -> Result<Json<CacheGcResult>, ApiError> {
+    let result = engine.collect_cache_garbage(false).await?;
+    Ok(Json(result))
+}
+
 /// Handler for POST /connections
 pub async fn create_connection_handler(
     State(engine): State<Arc<RuntimeEngine>>,
@@ -213,39 +258,15 @@ pub async fn create_connection_handler(
     }
 
     // Build config object, handling password-to-secret conversion
+    let password;
     let config_with_type = {
         let mut obj = match request.config {
             serde_json::Value::Object(m) => m,
             _ => return Err(ApiError::bad_request("Configuration must be a JSON object")),
         };
 
-        // If "password" is provided, auto-create a secret and replace with credential ref
-        if let Some(password_value) = obj.remove("password") {
-            if let Some(password) = password_value.as_str() {
-                if !password.is_empty() {
-                    // Generate secret name from connection name
-                    let secret_name = format!("conn-{}-password", request.name);
-
-                    // Create the secret
-                    engine
-                        .secret_manager()
-                        .create(&secret_name, password.as_bytes())
-                        .await
-                        .map_err(|e| {
-                            ApiError::internal_error(format!("Failed to store password: {}", e))
-                        })?;
-
-                    // Replace password with credential reference
-                    obj.insert(
-                        "credential".to_string(),
-                        serde_json::json!({
-                            "type": "secret_ref",
-                            "name": secret_name
-                        }),
-                    );
-                }
-            }
-        }
+        // If "password" is provided, replace it with a credential ref to a secret
+        password = take_password(&request.name, &mut obj);
 
         obj.insert(
             "type".to_string(),
@@ -257,9 +278,23 @@ pub async fn create_connection_handler(
     // Deserialize to Source enum
     let source: Source = serde_json::from_value(config_with_type)
         .map_err(|e| ApiError::bad_request(format!("Invalid source configuration: {}", e)))?;
+    source
+        .table_filter()
+        .matcher()
+        .map_err(|e| ApiError::bad_request(format!("Invalid table filter: {}", e)))?;
+    TableFilter {
+        include: request.warm_up.clone(),
+        exclude: vec![],
+    }
+    .matcher()
+    .map_err(|e| ApiError::bad_request(format!("Invalid warm-up pattern: {}", e)))?;
 
     let source_type = source.source_type().to_string();
 
+    if let Some(password) = &password {
+        store_password(&engine, password).await?;
+    }
+
     // Step 1: Register the connection
     let conn_id = engine
         .register_connection(&request.name, source)
@@ -269,10 +304,21 @@ pub async fn create_connection_handler(
             ApiError::internal_error(format!("Failed to register connection: {}", e))
         })?;
 
+    if let Some(policy) = request.schema_change_policy {
+        engine
+            .catalog()
+            .set_schema_change_policy(conn_id, policy)
+            .await?;
+    }
+
+    if !request.warm_up.is_empty() {
+        engine.set_warm_up(&request.name, &request.warm_up).await?;
+    }
+
     // Step 2: Attempt discovery - catch errors and return partial success
     let (tables_discovered, discovery_status, discovery_error) =
         match engine.refresh_schema(conn_id).await {
-            Ok((added, _)) => (added, DiscoveryStatus::Success, None),
+            Ok((added, _, _)) => (added, DiscoveryStatus::Success, None),
             Err(e) => {
                 let root_cause = e.root_cause().to_string();
                 let msg = root_cause
@@ -288,6 +334,16 @@ pub async fn create_connection_handler(
             }
         };
 
+    // Step 3: Fetch warm-up tables in the background
+    if discovery_status == DiscoveryStatus::Success {
+        if let Err(e) = engine.start_warm_up(conn_id).await {
+            error!(
+                "Failed to start warm-up for connection '{}': {}",
+                request.name, e
+            );
+        }
+    }
+
     // Fetch the created connection to get external_id
     let conn = engine
         .catalog()
@@ -308,6 +364,76 @@ pub async fn create_connection_handler(
     ))
 }
 
+/// A password taken out of a connection config, stored once the config is known to be valid.
+struct PendingPassword {
+    secret_name: String,
+    password: String,
+}
+
+/// Replace a "password" config field with a reference to the connection's password secret.
+/// The password itself is returned, to be written with `store_password`.
+fn take_password(
+    connection_name: &str,
+    config: &mut serde_json::Map<String, serde_json::Value>,
+) -> Option<PendingPassword> {
+    let password = config.remove("password")?;
+    let password = password.as_str().filter(|p| !p.is_empty())?.to_string();
+
+    // Generate secret name from connection name
+    let secret_name = format!("conn-{}-password", connection_name);
+    config.insert(
+        "credential".to_string(),
+        serde_json::json!({
+            "type": "secret_ref",
+            "name": secret_name
+        }),
+    );
+    Some(PendingPassword {
+        secret_name,
+        password,
+    })
+}
+
+/// Write a password taken by `take_password`, overwriting an existing secret. Returns the
+/// value it replaced, so `restore_password` can undo the write.
+async fn store_password(
+    engine: &RuntimeEngine,
+    pending: &PendingPassword,
+) -> Result<Option<Vec<u8>>, ApiError> {
+    let secrets = engine.secret_manager();
+    let previous = secrets.get(&pending.secret_name).await.ok();
+    let stored = if previous.is_some() {
+        secrets
+            .update(&pending.secret_name, pending.password.as_bytes())
+            .await
+    } else {
+        secrets
+            .create(&pending.secret_name, pending.password.as_bytes())
+            .await
+    };
+    stored.map_err(|e| ApiError::internal_error(format!("Failed to store password: {}", e)))?;
+    Ok(previous)
+}
+
+/// Undo `store_password` after the connection change it belonged to failed.
+async fn restore_password(
+    engine: &RuntimeEngine,
+    pending: &PendingPassword,
+    previous: Option<Vec<u8>>,
+) {
+    let secrets = engine.secret_manager();
+    let restored = match previous {
+        Some(value) => secrets.update(&pending.secret_name, &value).await,
+        None => secrets.delete(&pending.secret_name).await,
+    };
+    if let Err(e) = restored {
+        warn!(
+            "Failed to restore secret '{}' after a failed connection update: {}",
+            pending.secret_name, e
+        );
+    }
+}
+
 /// Handler for GET /connections
 pub async fn list_connections_handler(
     State(engine): State<Arc<RuntimeEngine>>,
@@ -344,6 +470,9 @@ pub async fn get_connection_handler(
     let tables = engine.list_tables(Some(&conn.name)).await?;
     let table_count = tables.len();
     let synced_table_count = tables.iter().filter(|t| t.parquet_path.is_some()).count();
+    let schema_change_policy = engine.catalog().get_schema_change_policy(conn.id).await?;
+    let warm_up = engine.warm_up_patterns(conn.id).await?;
+    let warm_up_progress = engine.warm_up_progress(conn.id);
 
     Ok(Json(GetConnectionResponse {
         id: conn.external_id,
@@ -351,6 +480,169 @@ pub async fn get_connection_handler(
         source_type: conn.source_type,
         table_count,
         synced_table_count,
+        schema_change_policy,
+        warm_up,
+        warm_up_progress,
+    }))
+}
+
+/// Handler for GET /connections/{connection_id}/schema_history
+///
+/// Optional `schema` and `table` query parameters narrow the history to matching tables.
+pub async fn get_schema_history_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    Path(connection_id): Path<String>,
+    QueryParams(params): QueryParams<HashMap<String, String>>,
+) -> Result<Json<SchemaHistoryResponse>, ApiError> {
+    let conn = engine
+        .catalog()
+        .get_connection_by_external_id(&connection_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Connection '{}' not found", connection_id)))?;
+
+    let versions = engine
+        .schema_history(&conn.name)
+        .await?
+        .into_iter()
+        .filter(|v| params.get("schema").is_none_or(|s| *s == v.schema_name))
+        .filter(|v| params.get("table").is_none_or(|t| *t == v.table_name))
+        .map(|v| {
+            let changes = serde_json::from_str(&v.changes_json).map_err(|e| {
+                ApiError::internal_error(format!("Invalid schema change record: {}", e))
+            })?;
+            Ok(SchemaVersionInfo {
+                schema: v.schema_name,
+                table: v.table_name,
+                version: v.version,
+                status: v.status,
+                created_at: v.created_at,
+                changes,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(Json(SchemaHistoryResponse {
+        connection_id: conn.external_id,
+        versions,
+    }))
+}
+
+/// Handler for PUT /connections/{connection_id}/schema_policy
+pub async fn update_schema_policy_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    Path(connection_id): Path<String>,
+    Json(request): Json<UpdateSchemaPolicyRequest>,
+) -> Result<StatusCode, ApiError> {
+    let conn = engine
+        .catalog()
+        .get_connection_by_external_id(&connection_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Connection '{}' not found", connection_id)))?;
+
+    engine
+        .set_schema_change_policy(&conn.name, request.policy)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handler for PATCH /connections/{connection_id}
+///
+/// Renames the connection and/or changes fields of its configuration. Tables and cached
+/// data are kept unless `invalidate_cache` is set.
+pub async fn update_connection_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    Path(connection_id): Path<String>,
+    Json(request): Json<UpdateConnectionRequest>,
+) -> Result<Json<ConnectionInfo>, ApiError> {
+    let conn = engine
+        .catalog()
+        .get_connection_by_external_id(&connection_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Connection '{}' not found", connection_id)))?;
+    let name = request.name.as_deref().unwrap_or(&conn.name);
+    if name != conn.name {
+        if name.trim().is_empty() {
+            return Err(ApiError::bad_request("Connection name cannot be empty"));
+        }
+        if engine.catalog().get_connection(name).await?.is_some() {
+            return Err(ApiError::conflict(format!(
+                "Connection '{}' already exists",
+                name
+            )));
+        }
+    }
+
+    let mut password = None;
+    let source = match request.config {
+        Some(serde_json::Value::Object(changes)) => {
+            let mut config = match serde_json::from_str(&conn.config_json) {
+                Ok(serde_json::Value::Object(config)) => config,
+                _ => return Err(ApiError::internal_error("Stored configuration is invalid")),
+            };
+            for (key, value) in changes {
+                if key == "type" {
+                    return Err(ApiError::bad_request(
+                        "The source type of a connection cannot be changed",
+                    ));
+                }
+                match value {
+                    serde_json::Value::Null => config.remove(&key),
+                    value => config.insert(key, value),
+                };
+            }
+            password = take_password(name, &mut config);
+
+            let source: Source = serde_json::from_value(serde_json::Value::Object(config))
+                .map_err(|e| {
+                    ApiError::bad_request(format!("Invalid source configuration: {}", e))
+                })?;
+            source
+                .table_filter()
+                .matcher()
+                .map_err(|e| ApiError::bad_request(format!("Invalid table filter: {}", e)))?;
+            Some(source)
+        }
+        Some(_) => return Err(ApiError::bad_request("Configuration must be a JSON object")),
+        None => None,
+    };
+
+    // Only write the secret once the update is known to be valid, and undo it if it fails
+    let previous_password = match &password {
+        Some(password) => store_password(&engine, password).await?,
+        None => None,
+    };
+
+    let updated = engine
+        .update_connection(
+            &conn.name,
+            request.name.as_deref(),
+            source,
+            request.invalidate_cache,
+        )
+        .await;
+    if updated.is_err() {
+        if let Some(password) = &password {
+            restore_password(&engine, password, previous_password).await;
+        }
+    }
+    let updated = updated.map_err(|e| {
+        let msg = e.to_string();
+        if msg.contains("not found") {
+            ApiError::not_found(msg)
+        } else if msg.contains("already exists") {
+            ApiError::conflict(msg)
+        } else if msg.contains("cannot be empty") {
+            ApiError::bad_request(msg)
+        } else {
+            ApiError::internal_error(msg)
+        }
+    })?;
+
+    Ok(Json(ConnectionInfo {
+        id: updated.external_id,
+        name: updated.name,
+        source_type: updated.source_type,
     }))
 }
 
@@ -402,6 +694,35 @@ pub async fn purge_connection_cache_handler(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Handler for DELETE /connections/{connection_id}/stale_tables
+pub async fn prune_stale_tables_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    Path(connection_id): Path<String>,
+) -> Result<Json<PruneStaleTablesResponse>, ApiError> {
+    let conn = engine
+        .catalog()
+        .get_connection_by_external_id(&connection_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Connection '{}' not found", connection_id)))?;
+
+    let tables_pruned = engine
+        .prune_stale_tables(&conn.name)
+        .await?
+        .into_iter()
+        .map(|t| StaleTable {
+            connection_id: conn.external_id.clone(),
+            schema_name: t.schema_name,
+            table_name: t.table_name,
+            stale_since: t.stale_since,
+        })
+        .collect();
+
+    Ok(Json(PruneStaleTablesResponse {
+        connection_id: conn.external_id,
+        tables_pruned,
+    }))
+}
+
 /// Path parameters for table cache operations
 #[derive(Deserialize)]
 pub struct TableCachePath {
@@ -527,6 +848,119 @@ pub async fn delete_secret_handler(
     Ok(StatusCode::NO_CONTENT)
 }
 
+// Cache snapshot handlers
+
+/// Map snapshot errors to API errors by message.
+fn snapshot_error(e: anyhow::Error) -> ApiError {
+    let msg = e.to_string();
+    if msg.contains("not found") {
+        ApiError::not_found(msg)
+    } else if msg.contains("already exists") {
+        ApiError::conflict(msg)
+    } else if msg.contains("cannot be empty") {
+        ApiError::bad_request(msg)
+    } else {
+        ApiError::internal_error(msg)
+    }
+}
+
+/// Build the response for a snapshot, with external connection IDs.
+async fn snapshot_response(
+    engine: &RuntimeEngine,
+    name: &str,
+) -> Result<SnapshotResponse, ApiError> {
+    let (snapshot, tables) = engine.get_snapshot(name).await.map_err(snapshot_error)?;
+    let external_ids: HashMap<i32, String> = engine
+        .list_connections()
+        .await?
+        .into_iter()
+        .map(|c| (c.id, c.external_id))
+        .collect();
+
+    Ok(SnapshotResponse {
+        name: snapshot.name,
+        created_at: snapshot.created_at,
+        tables: tables
+            .into_iter()
+            .map(|t| SnapshotTableInfo {
+                connection_id: external_ids
+                    .get(&t.connection_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                schema_name: t.schema_name,
+                table_name: t.table_name,
+                parquet_path: t.parquet_path,
+            })
+            .collect(),
+    })
+}
+
+/// Handler for POST /snapshots
+pub async fn create_snapshot_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    Json(request): Json<CreateSnapshotRequest>,
+) -> Result<(StatusCode, Json<SnapshotResponse>), ApiError> {
+    let connection_name = match &request.connection_id {
+        Some(connection_id) => Some(
+            engine
+                .catalog()
+                .get_connection_by_external_id(connection_id)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::not_found(format!("Connection '{}' not found", connection_id))
+                })?
+                .name,
+        ),
+        None => None,
+    };
+
+    engine
+        .create_snapshot(&request.name, connection_name.as_deref())
+        .await
+        .map_err(snapshot_error)?;
+
+    let response = snapshot_response(&engine, &request.name).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Handler for GET /snapshots
+pub async fn list_snapshots_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+) -> Result<Json<ListSnapshotsResponse>, ApiError> {
+    let snapshots = engine.list_snapshots().await?;
+
+    Ok(Json(ListSnapshotsResponse {
+        snapshots: snapshots
+            .into_iter()
+            .map(|s| SnapshotSummary {
+                name: s.name,
+                created_at: s.created_at,
+            })
+            .collect(),
+    }))
+}
+
+/// Handler for GET /snapshots/{name}
+pub async fn get_snapshot_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    Path(name): Path<String>,
+) -> Result<Json<SnapshotResponse>, ApiError> {
+    Ok(Json(snapshot_response(&engine, &name).await?))
+}
+
+/// Handler for DELETE /snapshots/{name}: expire the snapshot
+pub async fn expire_snapshot_handler(
+    State(engine): State<Arc<RuntimeEngine>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    engine
+        .expire_snapshot(&name)
+        .await
+        .map_err(snapshot_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Handler for POST /refresh
 pub async fn refresh_handler(
     State(engine): State<Arc<RuntimeEngine>>,
@@ -570,15 +1004,25 @@ pub async fn refresh_handler(
         }
 
         // Schema refresh: single connection
-        (Some((conn_id, _)), None, None, false) => {
-            let (added, modified) = engine.refresh_schema(conn_id).await?;
+        (Some((conn_id, external_id)), None, None, false) => {
+            let (added, modified, stale) = engine.refresh_schema(conn_id).await?;
             let tables = engine.catalog().list_tables(Some(conn_id)).await?;
             RefreshResponse::Schema(SchemaRefreshResult {
                 connections_refreshed: 1,
                 connections_failed: 0,
-                tables_discovered: tables.len(),
+                tables_discovered: tables.iter().filter(|t| t.stale_since.is_none()).count(),
                 tables_added: added,
                 tables_modified: modified,
+                tables_stale: stale.len(),
+                stale_tables: stale
+                    .into_iter()
+                    .map(|t| StaleTable {
+                        connection_id: external_id.clone(),
+                        schema_name: t.schema_name,
+                        table_name: t.table_name,
+                        stale_since: t.stale_since,
+                    })
+                    .collect(),
                 errors: Vec::new(),
             })
         }
@@ -1,4 +1,6 @@
+use crate::catalog::{ColumnChange, SchemaChangePolicy};
 use crate::secrets::SecretMetadata;
+use crate::source::TablePattern;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -59,8 +61,12 @@ pub struct TableInfo {
     pub connection: String,
     pub schema: String,
     pub table: String,
+    /// Source table type, e.g. "BASE TABLE", "VIEW" or "MATERIALIZED VIEW"
+    pub table_type: String,
     pub synced: bool,
     pub last_sync: Option<String>,
+    /// True when the table was last seen missing from its source
+    pub stale: bool,
     pub columns: Vec<ColumnInfo>,
 }
 
@@ -76,6 +82,12 @@ pub struct CreateConnectionRequest {
     pub name: String,
     pub source_type: String,
     pub config: serde_json::Value,
+    /// How breaking schema changes are handled (defaults to auto_apply)
+    #[serde(default)]
+    pub schema_change_policy: Option<SchemaChangePolicy>,
+    /// Tables fetched into the cache in the background after discovery and on startup
+    #[serde(default)]
+    pub warm_up: Vec<TablePattern>,
 }
 
 /// Discovery status for connection creation
@@ -124,6 +136,74 @@ pub struct GetConnectionResponse {
     pub source_type: String,
     pub table_count: usize,
     pub synced_table_count: usize,
+    pub schema_change_policy: SchemaChangePolicy,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warm_up: Vec<TablePattern>,
+    /// Progress of the latest warm-up, if one has run since startup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warm_up_progress: Option<WarmUpProgress>,
+}
+
+/// State of a connection's background cache warm-up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmUpState {
+    Running,
+    Completed,
+    /// Stopped by shutdown before every table was fetched
+    Cancelled,
+}
+
+/// Progress of a connection's background cache warm-up
+#[derive(Debug, Clone, Serialize)]
+pub struct WarmUpProgress {
+    pub state: WarmUpState,
+    /// Matching tables that were missing from the cache when the warm-up started
+    pub tables_total: usize,
+    pub tables_cached: usize,
+    pub tables_failed: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<TableRefreshError>,
+}
+
+/// One version in a table's schema history
+#[derive(Debug, Serialize)]
+pub struct SchemaVersionInfo {
+    pub schema: String,
+    pub table: String,
+    pub version: i32,
+    /// "applied", or "blocked" when the connection's policy refused the change
+    pub status: String,
+    pub created_at: Option<String>,
+    pub changes: Vec<ColumnChange>,
+}
+
+/// Response body for GET /connections/{connection_id}/schema_history
+#[derive(Debug, Serialize)]
+pub struct SchemaHistoryResponse {
+    pub connection_id: String,
+    pub versions: Vec<SchemaVersionInfo>,
+}
+
+/// Request body for PUT /connections/{connection_id}/schema_policy
+#[derive(Debug, Deserialize)]
+pub struct UpdateSchemaPolicyRequest {
+    pub policy: SchemaChangePolicy,
+}
+
+/// Request body for PATCH /connections/{connection_id}
+#[derive(Debug, Deserialize)]
+pub struct UpdateConnectionRequest {
+    /// New connection name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Configuration fields to change, merged into the current configuration.
+    /// A null value removes the field.
+    #[serde(default)]
+    pub config: Option<serde_json::Value>,
+    /// Purge cached data so queries fetch from the updated source
+    #[serde(default)]
+    pub invalidate_cache: bool,
 }
 
 // Secret management models
@@ -226,10 +306,134 @@ pub struct SchemaRefreshResult {
     pub tables_discovered: usize,
     pub tables_added: usize,
     pub tables_modified: usize,
+    /// Tables missing from their source, kept until pruned
+    pub tables_stale: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stale_tables: Vec<StaleTable>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub errors: Vec<ConnectionSchemaError>,
 }
 
+/// A table that schema refresh no longer found at its source
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleTable {
+    pub connection_id: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub stale_since: Option<String>,
+}
+
+/// Response body for DELETE /connections/{connection_id}/stale_tables
+#[derive(Debug, Serialize)]
+pub struct PruneStaleTablesResponse {
+    pub connection_id: String,
+    pub tables_pruned: Vec<StaleTable>,
+}
+
+/// Response body for GET /cache/metadata
+#[derive(Debug, Serialize)]
+pub struct MetadataCacheStatsResponse {
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+    pub entries: usize,
+    pub size_bytes: usize,
+    pub capacity_bytes: usize,
+}
+
+/// A table whose cached files could not be verified
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokenCacheEntry {
+    pub connection_id: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub parquet_path: String,
+    pub error: String,
+}
+
+/// Query parameters for POST /cache/verify
+#[derive(Debug, Default, Deserialize)]
+pub struct VerifyCacheRequest {
+    /// Schedule orphaned cache directories for deletion after the grace period
+    #[serde(default)]
+    pub delete_orphans: bool,
+}
+
+/// Response body for POST /cache/verify
+#[derive(Debug, Default, Serialize)]
+pub struct CacheVerificationResult {
+    pub tables_checked: usize,
+    /// Tables whose files were missing or unreadable; they were reset to uncached
+    pub tables_reset: Vec<BrokenCacheEntry>,
+    /// Tables skipped because storage could not be read
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tables_unverified: Vec<BrokenCacheEntry>,
+    /// Cache directories no table references
+    pub orphaned_paths: Vec<String>,
+    pub orphans_scheduled: usize,
+}
+
+/// A cache version directory no table references.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnreferencedCacheVersion {
+    pub connection_id: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Response body for GET and POST /cache/gc
+#[derive(Debug, Default, Serialize)]
+pub struct CacheGcResult {
+    /// True when nothing was deleted
+    pub dry_run: bool,
+    pub versions: Vec<UnreferencedCacheVersion>,
+    pub bytes_reclaimable: u64,
+    pub versions_reclaimed: usize,
+    pub bytes_reclaimed: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Request body for POST /snapshots
+#[derive(Debug, Deserialize)]
+pub struct CreateSnapshotRequest {
+    pub name: String,
+    /// Pin only this connection's tables instead of every cached table
+    #[serde(default)]
+    pub connection_id: Option<String>,
+}
+
+/// A table version pinned by a snapshot.
+#[derive(Debug, Serialize)]
+pub struct SnapshotTableInfo {
+    pub connection_id: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub parquet_path: String,
+}
+
+/// Response body for POST /snapshots and GET /snapshots/{name}
+#[derive(Debug, Serialize)]
+pub struct SnapshotResponse {
+    pub name: String,
+    pub created_at: Option<String>,
+    pub tables: Vec<SnapshotTableInfo>,
+}
+
+/// Snapshot summary for listing
+#[derive(Debug, Serialize)]
+pub struct SnapshotSummary {
+    pub name: String,
+    pub created_at: Option<String>,
+}
+
+/// Response body for GET /snapshots
+#[derive(Debug, Serialize)]
+pub struct ListSnapshotsResponse {
+    pub snapshots: Vec<SnapshotSummary>,
+}
+
 /// Non-fatal warning that occurred during a refresh operation.
 /// Used to report issues like failed deletion scheduling that don't
 /// prevent the refresh from succeeding.
@@ -255,7 +459,7 @@ pub struct TableRefreshResult {
 }
 
 /// Error details for a failed table refresh
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TableRefreshError {
     pub schema_name: String,
     pub table_name: String,
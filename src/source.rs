@@ -24,6 +24,28 @@ pub enum IcebergCatalogType {
         #[serde(default)]
         credential: Credential,
     },
+    /// JDBC-style catalog backed by a SQL database (Postgres, MySQL or SQLite)
+    Sql {
+        /// Database URL (e.g., "postgres://user@host/db" or "sqlite:/path/catalog.db")
+        uri: String,
+        /// Optional database password, applied to the URL at connect time
+        #[serde(default)]
+        credential: Credential,
+    },
+    /// Hadoop-style catalog: tables are directories under a filesystem warehouse,
+    /// laid out as `{path}/{namespace...}/{table}/metadata/`
+    Filesystem { path: String },
+}
+
+/// Message encoding for Kafka topics.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaFormat {
+    /// UTF-8 JSON objects, one per message
+    #[default]
+    Json,
+    /// Confluent wire-format Avro, with schemas resolved from a schema registry
+    Avro,
 }
 
 /// Credential storage - either no credential or a reference to a stored secret.
@@ -55,6 +77,146 @@ impl Credential {
     }
 }
 
+/// How a [`TablePattern`] is interpreted.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternSyntax {
+    /// `*` matches any run of characters and `?` a single character
+    #[default]
+    Glob,
+    /// Regular expression that must match the whole name
+    Regex,
+}
+
+/// Pattern over schema and table names. A missing part matches any name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct TablePattern {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    #[serde(default)]
+    pub syntax: PatternSyntax,
+}
+
+/// Include/exclude patterns limiting which tables discovery registers.
+///
+/// A table is kept when it matches any include pattern (or there are none) and no
+/// exclude pattern.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct TableFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<TablePattern>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<TablePattern>,
+}
+
+impl TableFilter {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Compile the patterns. Fails if a regex pattern is invalid.
+    pub fn matcher(&self) -> anyhow::Result<TableMatcher> {
+        let compile = |patterns: &[TablePattern]| {
+            patterns
+                .iter()
+                .map(CompiledPattern::new)
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        Ok(TableMatcher {
+            include: compile(&self.include)?,
+            exclude: compile(&self.exclude)?,
+        })
+    }
+}
+
+/// Compiled form of a [`TableFilter`].
+#[derive(Debug, Clone)]
+pub struct TableMatcher {
+    include: Vec<CompiledPattern>,
+    exclude: Vec<CompiledPattern>,
+}
+
+impl TableMatcher {
+    /// Matcher keeping only `schema.table`, for discovering a single table.
+    pub fn only(schema: &str, table: &str) -> Self {
+        let exact = |name: &str| {
+            Some(regex::Regex::new(&format!("^{}$", regex::escape(name))).expect("escaped regex"))
+        };
+        Self {
+            include: vec![CompiledPattern {
+                schema: exact(schema),
+                table: exact(table),
+            }],
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Whether the filter keeps `schema.table`.
+    pub fn matches(&self, schema: &str, table: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches(schema, table)))
+            && !self.exclude.iter().any(|p| p.matches(schema, table))
+    }
+
+    /// Whether the filter may keep any table of `schema`. Lets discovery skip listing the
+    /// tables of schemas it would drop entirely.
+    pub fn matches_schema(&self, schema: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches_schema(schema)))
+            && !self
+                .exclude
+                .iter()
+                .any(|p| p.table.is_none() && p.matches_schema(schema))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompiledPattern {
+    schema: Option<regex::Regex>,
+    table: Option<regex::Regex>,
+}
+
+impl CompiledPattern {
+    fn new(pattern: &TablePattern) -> anyhow::Result<Self> {
+        let compile = |part: &Option<String>| {
+            part.as_deref()
+                .map(|p| {
+                    let source = match pattern.syntax {
+                        PatternSyntax::Glob => glob_to_regex(p),
+                        PatternSyntax::Regex => p.to_string(),
+                    };
+                    regex::Regex::new(&format!("^(?:{})$", source))
+                        .map_err(|e| anyhow::anyhow!("invalid table pattern '{}': {}", p, e))
+                })
+                .transpose()
+        };
+        Ok(Self {
+            schema: compile(&pattern.schema)?,
+            table: compile(&pattern.table)?,
+        })
+    }
+
+    fn matches(&self, schema: &str, table: &str) -> bool {
+        self.matches_schema(schema) && self.table.as_ref().is_none_or(|re| re.is_match(table))
+    }
+
+    fn matches_schema(&self, schema: &str) -> bool {
+        self.schema.as_ref().is_none_or(|re| re.is_match(schema))
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::new();
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex
+}
+
 /// Represents a data source connection with typed configuration.
 /// The `type` field is used as the JSON discriminator via serde's tag attribute.
 ///
@@ -70,6 +232,9 @@ pub enum Source {
         database: String,
         #[serde(default)]
         credential: Credential,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
     },
     Snowflake {
         account: String,
@@ -82,14 +247,23 @@ pub enum Source {
         role: Option<String>,
         #[serde(default)]
         credential: Credential,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
     },
     Motherduck {
         database: String,
         #[serde(default)]
         credential: Credential,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
     },
     Duckdb {
         path: String,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
     },
     Iceberg {
         catalog_type: IcebergCatalogType,
@@ -98,6 +272,9 @@ pub enum Source {
         /// Optional namespace filter (e.g., "db.schema")
         #[serde(skip_serializing_if = "Option::is_none")]
         namespace: Option<String>,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
     },
     Mysql {
         host: String,
@@ -106,11 +283,60 @@ pub enum Source {
         database: String,
         #[serde(default)]
         credential: Credential,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
+    },
+    Kafka {
+        /// Comma-separated list of bootstrap brokers (e.g., "localhost:9092")
+        bootstrap_servers: String,
+        #[serde(default)]
+        format: KafkaFormat,
+        /// Schema registry base URL (required for Avro topics)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        schema_registry_url: Option<String>,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
+    },
+    Delta {
+        /// Table or lake root (e.g., s3://bucket/lake or /data/lake). Either a single
+        /// Delta table or a directory whose subdirectories are Delta tables.
+        location: String,
+        /// Storage credential (AWS credentials JSON for s3:// locations)
+        #[serde(default)]
+        credential: Credential,
+        /// Optional time travel: read every table as of this table version
+        #[serde(skip_serializing_if = "Option::is_none")]
+        version: Option<i64>,
+        /// Optional time travel: read every table as of this RFC 3339 timestamp
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timestamp: Option<String>,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
+    },
+    Bigquery {
+        /// GCP project that owns the dataset; read sessions are billed to it
+        project: String,
+        dataset: String,
+        /// Service account key JSON. Leave unset to connect without auth (emulator).
+        #[serde(default)]
+        credential: Credential,
+        /// REST API endpoint override (e.g., "http://localhost:9050" for the emulator)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        api_endpoint: Option<String>,
+        /// Storage Read API (gRPC) endpoint override (e.g., "http://localhost:9060")
+        #[serde(skip_serializing_if = "Option::is_none")]
+        storage_endpoint: Option<String>,
+        /// Include/exclude patterns applied to discovered tables
+        #[serde(default, skip_serializing_if = "TableFilter::is_empty")]
+        table_filter: TableFilter,
     },
 }
 
 impl Source {
-    /// Returns the source type as a string (e.g., "postgres", "snowflake", "motherduck", "duckdb", "iceberg", "mysql")
+    /// Returns the source type as a string (e.g., "postgres", "snowflake", "motherduck", "duckdb", "iceberg", "mysql", "kafka", "delta", "bigquery")
     pub fn source_type(&self) -> &'static str {
         match self {
             Source::Postgres { .. } => "postgres",
@@ -119,6 +345,9 @@ impl Source {
             Source::Duckdb { .. } => "duckdb",
             Source::Iceberg { .. } => "iceberg",
             Source::Mysql { .. } => "mysql",
+            Source::Kafka { .. } => "kafka",
+            Source::Delta { .. } => "delta",
+            Source::Bigquery { .. } => "bigquery",
         }
     }
 
@@ -141,10 +370,44 @@ impl Source {
             Source::Iceberg { catalog_type, .. } => match catalog_type {
                 IcebergCatalogType::Rest { credential, .. } => credential,
                 IcebergCatalogType::Glue { credential, .. } => credential,
+                IcebergCatalogType::Sql { credential, .. } => credential,
+                IcebergCatalogType::Filesystem { .. } => &Credential::None,
             },
             Source::Mysql { credential, .. } => credential,
+            Source::Kafka { .. } => &Credential::None,
+            Source::Delta { credential, .. } => credential,
+            Source::Bigquery { credential, .. } => credential,
+        }
+    }
+
+    /// Include/exclude patterns applied to discovered tables.
+    pub fn table_filter(&self) -> &TableFilter {
+        match self {
+            Source::Postgres { table_filter, .. }
+            | Source::Snowflake { table_filter, .. }
+            | Source::Motherduck { table_filter, .. }
+            | Source::Duckdb { table_filter, .. }
+            | Source::Iceberg { table_filter, .. }
+            | Source::Mysql { table_filter, .. }
+            | Source::Kafka { table_filter, .. }
+            | Source::Delta { table_filter, .. }
+            | Source::Bigquery { table_filter, .. } => table_filter,
         }
     }
+
+    /// Whether this source is an append-only stream.
+    /// Streaming sources are refreshed incrementally from stored offsets rather than
+    /// being re-fetched in full.
+    pub fn is_streaming(&self) -> bool {
+        matches!(self, Source::Kafka { .. })
+    }
+
+    /// Whether this source versions tables as snapshots.
+    /// Snapshot sources record the snapshot they cached, so refreshes can be skipped when
+    /// nothing changed and limited to new data files when the table was only appended to.
+    pub fn has_snapshots(&self) -> bool {
+        matches!(self, Source::Iceberg { .. })
+    }
 }
 
 #[cfg(test)]
@@ -161,6 +424,7 @@ mod tests {
             credential: Credential::SecretRef {
                 name: "my-pg-secret".to_string(),
             },
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -184,6 +448,7 @@ mod tests {
             credential: Credential::SecretRef {
                 name: "snowflake-secret".to_string(),
             },
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -207,6 +472,7 @@ mod tests {
             credential: Credential::SecretRef {
                 name: "secret".to_string(),
             },
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -221,6 +487,7 @@ mod tests {
             credential: Credential::SecretRef {
                 name: "md-token".to_string(),
             },
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -236,11 +503,13 @@ mod tests {
         let motherduck = Source::Motherduck {
             database: "my_database".to_string(),
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert_eq!(motherduck.catalog(), Some("my_database"));
 
         let duckdb = Source::Duckdb {
             path: "/path/to/db".to_string(),
+            table_filter: Default::default(),
         };
         assert_eq!(duckdb.catalog(), None);
 
@@ -250,6 +519,7 @@ mod tests {
             user: "u".to_string(),
             database: "d".to_string(),
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert_eq!(postgres.catalog(), None);
 
@@ -261,6 +531,7 @@ mod tests {
             schema: None,
             role: None,
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert_eq!(snowflake.catalog(), None);
     }
@@ -273,6 +544,7 @@ mod tests {
             user: "u".to_string(),
             database: "d".to_string(),
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert_eq!(postgres.source_type(), "postgres");
 
@@ -284,12 +556,14 @@ mod tests {
             schema: None,
             role: None,
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert_eq!(snowflake.source_type(), "snowflake");
 
         let motherduck = Source::Motherduck {
             database: "d".to_string(),
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert_eq!(motherduck.source_type(), "motherduck");
     }
@@ -304,6 +578,7 @@ mod tests {
             credential: Credential::SecretRef {
                 name: "my-secret".to_string(),
             },
+            table_filter: Default::default(),
         };
         assert!(matches!(
             with_secret.credential(),
@@ -312,6 +587,7 @@ mod tests {
 
         let duckdb = Source::Duckdb {
             path: "/p".to_string(),
+            table_filter: Default::default(),
         };
         assert!(matches!(duckdb.credential(), Credential::None));
     }
@@ -327,6 +603,7 @@ mod tests {
             },
             warehouse: "s3://my-bucket/warehouse".to_string(),
             namespace: Some("my_database".to_string()),
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -352,6 +629,7 @@ mod tests {
             },
             warehouse: "s3://data-lake/iceberg".to_string(),
             namespace: Some("analytics.events".to_string()),
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -365,6 +643,51 @@ mod tests {
         assert_eq!(source, parsed);
     }
 
+    #[test]
+    fn test_iceberg_sql_serialization() {
+        let source = Source::Iceberg {
+            catalog_type: IcebergCatalogType::Sql {
+                uri: "postgres://iceberg@localhost/catalog".to_string(),
+                credential: Credential::SecretRef {
+                    name: "catalog-db".to_string(),
+                },
+            },
+            warehouse: "s3://data-lake/iceberg".to_string(),
+            namespace: None,
+            table_filter: Default::default(),
+        };
+
+        let json = serde_json::to_string(&source).unwrap();
+        assert!(json.contains(r#""type":"sql""#));
+        assert!(json.contains(r#""uri":"postgres://iceberg@localhost/catalog""#));
+
+        let parsed: Source = serde_json::from_str(&json).unwrap();
+        assert_eq!(source, parsed);
+        assert!(matches!(
+            parsed.credential(),
+            Credential::SecretRef { name } if name == "catalog-db"
+        ));
+    }
+
+    #[test]
+    fn test_iceberg_filesystem_serialization() {
+        let json = r#"{
+            "type": "iceberg",
+            "catalog_type": {"type": "filesystem", "path": "/data/warehouse"},
+            "warehouse": "/data/warehouse"
+        }"#;
+
+        let parsed: Source = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            &parsed,
+            Source::Iceberg {
+                catalog_type: IcebergCatalogType::Filesystem { path },
+                ..
+            } if path == "/data/warehouse"
+        ));
+        assert!(matches!(parsed.credential(), Credential::None));
+    }
+
     #[test]
     fn test_iceberg_without_namespace() {
         let source = Source::Iceberg {
@@ -374,6 +697,7 @@ mod tests {
             },
             warehouse: "s3://bucket/path".to_string(),
             namespace: None,
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -392,6 +716,7 @@ mod tests {
             },
             warehouse: "s3://bucket/path".to_string(),
             namespace: None,
+            table_filter: Default::default(),
         };
         assert_eq!(iceberg.source_type(), "iceberg");
     }
@@ -405,6 +730,7 @@ mod tests {
             },
             warehouse: "s3://bucket/path".to_string(),
             namespace: Some("my_ns".to_string()),
+            table_filter: Default::default(),
         };
         // Iceberg doesn't use the catalog() method like Motherduck does
         assert_eq!(iceberg.catalog(), None);
@@ -422,6 +748,7 @@ mod tests {
             },
             warehouse: "s3://b/p".to_string(),
             namespace: None,
+            table_filter: Default::default(),
         };
         assert!(matches!(
             rest_with_cred.credential(),
@@ -438,6 +765,7 @@ mod tests {
             },
             warehouse: "s3://b/p".to_string(),
             namespace: None,
+            table_filter: Default::default(),
         };
         assert!(matches!(
             glue_with_cred.credential(),
@@ -452,6 +780,7 @@ mod tests {
             },
             warehouse: "s3://b/p".to_string(),
             namespace: None,
+            table_filter: Default::default(),
         };
         assert!(matches!(rest_no_cred.credential(), Credential::None));
     }
@@ -498,6 +827,7 @@ mod tests {
             credential: Credential::SecretRef {
                 name: "my-mysql-secret".to_string(),
             },
+            table_filter: Default::default(),
         };
 
         let json = serde_json::to_string(&source).unwrap();
@@ -520,6 +850,7 @@ mod tests {
             user: "u".to_string(),
             database: "d".to_string(),
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert_eq!(mysql.source_type(), "mysql");
     }
@@ -534,6 +865,7 @@ mod tests {
             credential: Credential::SecretRef {
                 name: "mysql-secret".to_string(),
             },
+            table_filter: Default::default(),
         };
         assert!(matches!(
             with_secret.credential(),
@@ -546,7 +878,226 @@ mod tests {
             user: "u".to_string(),
             database: "d".to_string(),
             credential: Credential::None,
+            table_filter: Default::default(),
         };
         assert!(matches!(without_cred.credential(), Credential::None));
     }
+
+    #[test]
+    fn test_kafka_serialization() {
+        let source = Source::Kafka {
+            bootstrap_servers: "localhost:9092".to_string(),
+            format: KafkaFormat::Avro,
+            schema_registry_url: Some("http://localhost:8081".to_string()),
+            table_filter: Default::default(),
+        };
+
+        let json = serde_json::to_string(&source).unwrap();
+        assert!(json.contains(r#""type":"kafka""#));
+        assert!(json.contains(r#""bootstrap_servers":"localhost:9092""#));
+        assert!(json.contains(r#""format":"avro""#));
+        assert!(json.contains(r#""schema_registry_url":"http://localhost:8081""#));
+
+        let parsed: Source = serde_json::from_str(&json).unwrap();
+        assert_eq!(source, parsed);
+    }
+
+    #[test]
+    fn test_kafka_defaults_to_json() {
+        let json = r#"{"type":"kafka","bootstrap_servers":"broker:9092"}"#;
+        let parsed: Source = serde_json::from_str(json).unwrap();
+
+        assert_eq!(parsed.source_type(), "kafka");
+        assert!(parsed.is_streaming());
+        assert!(matches!(parsed.credential(), Credential::None));
+        assert!(matches!(
+            parsed,
+            Source::Kafka {
+                format: KafkaFormat::Json,
+                schema_registry_url: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_delta_serialization() {
+        let source = Source::Delta {
+            location: "s3://lake/tables".to_string(),
+            credential: Credential::SecretRef {
+                name: "aws-creds".to_string(),
+            },
+            version: Some(3),
+            timestamp: None,
+            table_filter: Default::default(),
+        };
+
+        let json = serde_json::to_string(&source).unwrap();
+        assert!(json.contains(r#""type":"delta""#));
+        assert!(json.contains(r#""location":"s3://lake/tables""#));
+        assert!(json.contains(r#""version":3"#));
+        assert!(!json.contains(r#""timestamp""#));
+
+        let parsed: Source = serde_json::from_str(&json).unwrap();
+        assert_eq!(source, parsed);
+    }
+
+    #[test]
+    fn test_delta_minimal_config() {
+        let json = r#"{"type":"delta","location":"/data/lake"}"#;
+        let parsed: Source = serde_json::from_str(json).unwrap();
+
+        assert_eq!(parsed.source_type(), "delta");
+        assert!(!parsed.is_streaming());
+        assert!(matches!(parsed.credential(), Credential::None));
+        assert!(matches!(
+            parsed,
+            Source::Delta {
+                version: None,
+                timestamp: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_bigquery_serialization() {
+        let source = Source::Bigquery {
+            project: "analytics-prod".to_string(),
+            dataset: "sales".to_string(),
+            credential: Credential::SecretRef {
+                name: "bq-service-account".to_string(),
+            },
+            api_endpoint: None,
+            storage_endpoint: None,
+            table_filter: Default::default(),
+        };
+
+        let json = serde_json::to_string(&source).unwrap();
+        assert!(json.contains(r#""type":"bigquery""#));
+        assert!(json.contains(r#""project":"analytics-prod""#));
+        assert!(!json.contains(r#""api_endpoint""#));
+
+        let parsed: Source = serde_json::from_str(&json).unwrap();
+        assert_eq!(source, parsed);
+    }
+
+    #[test]
+    fn test_bigquery_emulator_config() {
+        let json = r#"{"type":"bigquery","project":"test","dataset":"ds","api_endpoint":"http://localhost:9050","storage_endpoint":"http://localhost:9060"}"#;
+        let parsed: Source = serde_json::from_str(json).unwrap();
+
+        assert_eq!(parsed.source_type(), "bigquery");
+        assert!(matches!(parsed.credential(), Credential::None));
+        assert!(matches!(
+            parsed,
+            Source::Bigquery {
+                api_endpoint: Some(_),
+                storage_endpoint: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_table_filter_serialization() {
+        let json = r#"{
+            "type": "postgres",
+            "host": "localhost",
+            "port": 5432,
+            "user": "u",
+            "database": "d",
+            "table_filter": {
+                "include": [{"schema": "sales"}],
+                "exclude": [{"table": "^tmp_\\d+$", "syntax": "regex"}]
+            }
+        }"#;
+
+        let parsed: Source = serde_json::from_str(json).unwrap();
+        let filter = parsed.table_filter();
+        assert_eq!(filter.include.len(), 1);
+        assert_eq!(filter.exclude[0].syntax, PatternSyntax::Regex);
+
+        let roundtrip: Source =
+            serde_json::from_str(&serde_json::to_string(&parsed).unwrap()).unwrap();
+        assert_eq!(parsed, roundtrip);
+
+        // Empty filters are left out of stored configs
+        let duckdb = Source::Duckdb {
+            path: "/p".to_string(),
+            table_filter: Default::default(),
+        };
+        assert!(!serde_json::to_string(&duckdb)
+            .unwrap()
+            .contains("table_filter"));
+    }
+
+    #[test]
+    fn test_table_filter_matching() {
+        let filter = TableFilter {
+            include: vec![
+                TablePattern {
+                    schema: Some("sales".to_string()),
+                    ..Default::default()
+                },
+                TablePattern {
+                    schema: Some("ref?".to_string()),
+                    table: Some("country_*".to_string()),
+                    ..Default::default()
+                },
+            ],
+            exclude: vec![TablePattern {
+                table: Some(r"tmp_\d+".to_string()),
+                syntax: PatternSyntax::Regex,
+                ..Default::default()
+            }],
+        };
+        let matcher = filter.matcher().unwrap();
+
+        assert!(matcher.matches("sales", "orders"));
+        assert!(!matcher.matches("sales", "tmp_42"));
+        assert!(matcher.matches("sales", "tmp_old"));
+        assert!(matcher.matches("ref1", "country_codes"));
+        assert!(!matcher.matches("ref1", "currencies"));
+        assert!(!matcher.matches("reference", "country_codes"));
+        assert!(!matcher.matches("hr", "employees"));
+
+        assert!(matcher.matches_schema("sales"));
+        assert!(matcher.matches_schema("ref1"));
+        assert!(!matcher.matches_schema("hr"));
+
+        let only = TableMatcher::only("sales", "orders.v1");
+        assert!(only.matches("sales", "orders.v1"));
+        assert!(!only.matches("sales", "orders_v1"));
+        assert!(!only.matches("sales", "orders"));
+        assert!(!only.matches_schema("hr"));
+
+        // Glob metacharacters other than * and ? are literal
+        let dotted = TableFilter {
+            include: vec![TablePattern {
+                table: Some("a.b".to_string()),
+                ..Default::default()
+            }],
+            exclude: vec![],
+        };
+        assert!(!dotted.matcher().unwrap().matches("s", "axb"));
+
+        assert!(TableFilter::default()
+            .matcher()
+            .unwrap()
+            .matches("any", "table"));
+    }
+
+    #[test]
+    fn test_table_filter_rejects_invalid_regex() {
+        let filter = TableFilter {
+            include: vec![],
+            exclude: vec![TablePattern {
+                table: Some("(unclosed".to_string()),
+                syntax: PatternSyntax::Regex,
+                ..Default::default()
+            }],
+        };
+        assert!(filter.matcher().is_err());
+    }
 }
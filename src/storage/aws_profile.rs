@@ -0,0 +1,174 @@
+// src/storage/aws_profile.rs
+//! Profiles from the shared AWS config files (`~/.aws/credentials` and `~/.aws/config`),
+//! which object_store does not read itself.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Static credentials and region of one profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AwsProfile {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+    pub region: Option<String>,
+}
+
+/// Load a named profile. Fails if the profile does not exist or has no static keys.
+pub(crate) fn load(name: &str) -> Result<AwsProfile> {
+    let credentials = read_file("AWS_SHARED_CREDENTIALS_FILE", "credentials");
+    let config = read_file("AWS_CONFIG_FILE", "config");
+    from_files(name, credentials.as_deref(), config.as_deref())?
+        .ok_or_else(|| anyhow::anyhow!("AWS profile '{}' was not found", name))
+}
+
+/// Load the profile the standard chain falls back to (`AWS_PROFILE`, or `default`), if the
+/// shared files define it with static keys. Profiles without keys, e.g. ones that only set
+/// a region, leave credentials to the rest of the chain.
+pub(crate) fn load_default() -> Option<AwsProfile> {
+    let name = std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
+    let credentials = read_file("AWS_SHARED_CREDENTIALS_FILE", "credentials");
+    let config = read_file("AWS_CONFIG_FILE", "config");
+    from_files(&name, credentials.as_deref(), config.as_deref())
+        .ok()
+        .flatten()
+}
+
+fn read_file(env_var: &str, default_name: &str) -> Option<String> {
+    let path = match std::env::var(env_var) {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => {
+            let home = std::env::var("HOME")
+                .or_else(|_| std::env::var("USERPROFILE"))
+                .ok()?;
+            PathBuf::from(home).join(".aws").join(default_name)
+        }
+    };
+    std::fs::read_to_string(path).ok()
+}
+
+/// Keys may be in either file; the credentials file wins. In the config file, profiles
+/// other than `default` are named `[profile {name}]`.
+fn from_files(
+    name: &str,
+    credentials: Option<&str>,
+    config: Option<&str>,
+) -> Result<Option<AwsProfile>> {
+    let from_credentials = credentials.and_then(|c| parse_sections(c).remove(name));
+    let config_section = if name == "default" {
+        "default".to_string()
+    } else {
+        format!("profile {}", name)
+    };
+    let from_config = config.and_then(|c| parse_sections(c).remove(&config_section));
+
+    if from_credentials.is_none() && from_config.is_none() {
+        return Ok(None);
+    }
+    let get = |key: &str| {
+        from_credentials
+            .as_ref()
+            .and_then(|s| s.get(key))
+            .or_else(|| from_config.as_ref().and_then(|s| s.get(key)))
+            .cloned()
+    };
+
+    match (get("aws_access_key_id"), get("aws_secret_access_key")) {
+        (Some(access_key_id), Some(secret_access_key)) => Ok(Some(AwsProfile {
+            access_key_id,
+            secret_access_key,
+            session_token: get("aws_session_token"),
+            region: get("region"),
+        })),
+        _ => anyhow::bail!(
+            "AWS profile '{}' has no static keys; role, SSO and credential_process profiles \
+             are not supported, use web identity or static keys instead",
+            name
+        ),
+    }
+}
+
+/// Minimal INI parsing: `[section]` headers and `key = value` lines, `#`/`;` comments.
+fn parse_sections(contents: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let section = section.trim().to_string();
+            sections.entry(section.clone()).or_default();
+            current = Some(section);
+        } else if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREDENTIALS: &str = "
+[default]
+aws_access_key_id = AKIADEFAULT
+aws_secret_access_key = secret-default
+
+# comment
+[analytics]
+aws_access_key_id=AKIAANALYTICS
+aws_secret_access_key=secret-analytics
+aws_session_token = token
+";
+
+    const CONFIG: &str = "
+[default]
+region = us-east-1
+
+[profile analytics]
+region = eu-west-1
+
+[profile sso]
+sso_start_url = https://example.awsapps.com/start
+";
+
+    #[test]
+    fn test_profile_combines_credentials_and_config() {
+        let profile = from_files("analytics", Some(CREDENTIALS), Some(CONFIG))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            profile,
+            AwsProfile {
+                access_key_id: "AKIAANALYTICS".to_string(),
+                secret_access_key: "secret-analytics".to_string(),
+                session_token: Some("token".to_string()),
+                region: Some("eu-west-1".to_string()),
+            }
+        );
+
+        let default = from_files("default", Some(CREDENTIALS), Some(CONFIG))
+            .unwrap()
+            .unwrap();
+        assert_eq!(default.region.as_deref(), Some("us-east-1"));
+    }
+
+    #[test]
+    fn test_missing_and_unsupported_profiles() {
+        assert_eq!(
+            from_files("other", Some(CREDENTIALS), Some(CONFIG)).unwrap(),
+            None
+        );
+        assert!(from_files("sso", Some(CREDENTIALS), Some(CONFIG))
+            .unwrap_err()
+            .to_string()
+            .contains("no static keys"));
+    }
+}
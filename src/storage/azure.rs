@@ -0,0 +1,54 @@
+// src/storage/azure.rs
+use anyhow::Result;
+use object_store::azure::MicrosoftAzureBuilder;
+use std::sync::Arc;
+
+use super::remote::{remote_storage, RemoteStore};
+
+/// Cache storage in an Azure Blob Storage container, addressed as
+/// `az://{container}/cache/...`.
+#[derive(Debug)]
+pub struct AzureStorage {
+    remote: RemoteStore,
+}
+
+impl AzureStorage {
+    /// Create AzureStorage with credentials from the environment (`AZURE_STORAGE_ACCOUNT_NAME`,
+    /// `AZURE_STORAGE_ACCOUNT_KEY`, SAS tokens or managed identity). `account` overrides
+    /// the environment's account name.
+    pub fn new(container: &str, account: Option<&str>) -> Result<Self> {
+        let mut builder = MicrosoftAzureBuilder::from_env().with_container_name(container);
+        if let Some(account) = account {
+            builder = builder.with_account(account);
+        }
+        let store = builder.build()?;
+
+        Ok(Self {
+            remote: RemoteStore::new("az", container, Arc::new(store)),
+        })
+    }
+
+    /// Create AzureStorage with an account key against a custom endpoint, e.g. Azurite.
+    /// The endpoint is the account URL, such as `http://127.0.0.1:10000/devstoreaccount1`.
+    pub fn new_with_config(
+        container: &str,
+        endpoint: &str,
+        account: &str,
+        access_key: &str,
+        allow_http: bool,
+    ) -> Result<Self> {
+        let store = MicrosoftAzureBuilder::new()
+            .with_container_name(container)
+            .with_endpoint(endpoint.to_string())
+            .with_account(account)
+            .with_access_key(access_key)
+            .with_allow_http(allow_http)
+            .build()?;
+
+        Ok(Self {
+            remote: RemoteStore::new("az", container, Arc::new(store)),
+        })
+    }
+}
+
+remote_storage!(AzureStorage);
@@ -18,6 +18,37 @@ impl FilesystemStorage {
             cache_base: PathBuf::from(cache_base),
         }
     }
+
+    /// Handle for `file_name` in a new version directory:
+    /// `{cache_base}/{conn_id}/{schema}/{table}/{version}/{file_name}`
+    fn prepare_write(
+        &self,
+        connection_id: i32,
+        schema: &str,
+        table: &str,
+        file_name: &str,
+        base_version: Option<&str>,
+    ) -> CacheWriteHandle {
+        let version = nanoid::nanoid!(8);
+        let local_path = self
+            .cache_base
+            .join(connection_id.to_string())
+            .join(schema)
+            .join(table)
+            .join(&version)
+            .join(file_name);
+
+        CacheWriteHandle {
+            local_path,
+            version,
+            connection_id,
+            schema: schema.to_string(),
+            table: table.to_string(),
+            file_name: file_name.to_string(),
+            upload: None,
+            base_version: base_version.map(str::to_string),
+        }
+    }
 }
 
 #[async_trait]
@@ -40,6 +71,10 @@ impl StorageManager for FilesystemStorage {
             .to_string()
     }
 
+    fn cache_root(&self) -> String {
+        format!("file://{}", self.cache_base.display())
+    }
+
     async fn read(&self, url: &str) -> Result<Vec<u8>> {
         let path = url
             .strip_prefix("file://")
@@ -111,33 +146,42 @@ impl StorageManager for FilesystemStorage {
         // By using versioned directories with a fixed filename, we ensure only
         // the active version is read after catalog update.
         // Path: {cache_base}/{conn_id}/{schema}/{table}/{version}/data.parquet
-        let version = nanoid::nanoid!(8);
-        let local_path = self
-            .cache_base
-            .join(connection_id.to_string())
-            .join(schema)
-            .join(table)
-            .join(&version)
-            .join("data.parquet");
+        self.prepare_write(connection_id, schema, table, "data.parquet", None)
+    }
 
-        CacheWriteHandle {
-            local_path,
-            version,
-            connection_id,
-            schema: schema.to_string(),
-            table: table.to_string(),
-        }
+    fn prepare_append_write(
+        &self,
+        connection_id: i32,
+        schema: &str,
+        table: &str,
+        base_version: &str,
+    ) -> CacheWriteHandle {
+        let file_name = format!("part-{}.parquet", nanoid::nanoid!(8));
+        self.prepare_write(connection_id, schema, table, &file_name, Some(base_version))
     }
 
     async fn finalize_cache_write(&self, handle: &CacheWriteHandle) -> Result<String> {
         // For local storage, the file is already in place.
         // Return the versioned directory URL (for ListingTable compatibility).
-        let version_dir = self
+        let table_dir = self
             .cache_base
             .join(handle.connection_id.to_string())
             .join(&handle.schema)
-            .join(&handle.table)
-            .join(&handle.version);
+            .join(&handle.table);
+        let version_dir = table_dir.join(&handle.version);
+
+        if let Some(base_version) = &handle.base_version {
+            fs::create_dir_all(&version_dir)?;
+            for entry in fs::read_dir(table_dir.join(base_version))? {
+                let entry = entry?;
+                let target = version_dir.join(entry.file_name());
+                // Files are never modified once written, so versions can share them
+                if fs::hard_link(entry.path(), &target).is_err() {
+                    fs::copy(entry.path(), &target)?;
+                }
+            }
+        }
+
         Ok(format!("file://{}", version_dir.display()))
     }
 }
@@ -214,4 +258,40 @@ mod tests {
             "Both should be under same table dir"
         );
     }
+
+    #[tokio::test]
+    async fn test_append_write_creates_new_version_with_base_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage = FilesystemStorage::new(temp_dir.path().to_str().unwrap());
+
+        let initial = storage.prepare_cache_write(1, "topics", "events");
+        std::fs::create_dir_all(initial.local_path.parent().unwrap()).unwrap();
+        std::fs::write(&initial.local_path, b"first").unwrap();
+        let initial_url = storage.finalize_cache_write(&initial).await.unwrap();
+
+        let append = storage.prepare_append_write(1, "topics", "events", &initial.version);
+        assert_ne!(append.version, initial.version);
+        assert!(append.file_name.starts_with("part-"));
+        assert!(append.local_path.ends_with(&append.file_name));
+
+        std::fs::create_dir_all(append.local_path.parent().unwrap()).unwrap();
+        std::fs::write(&append.local_path, b"second").unwrap();
+        let append_url = storage.finalize_cache_write(&append).await.unwrap();
+        assert_ne!(append_url, initial_url);
+
+        let files = |url: &str| {
+            let mut names: Vec<_> = std::fs::read_dir(url.strip_prefix("file://").unwrap())
+                .unwrap()
+                .map(|e| e.unwrap().file_name().into_string().unwrap())
+                .collect();
+            names.sort();
+            names
+        };
+        // The base version is left as it was
+        assert_eq!(files(&initial_url), vec!["data.parquet"]);
+        assert_eq!(
+            files(&append_url),
+            vec!["data.parquet".to_string(), append.file_name.clone()]
+        );
+    }
 }
@@ -0,0 +1,52 @@
+// src/storage/gcs.rs
+use anyhow::Result;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::ClientOptions;
+use std::sync::Arc;
+
+use super::remote::{remote_storage, RemoteStore};
+
+/// Cache storage in a Google Cloud Storage bucket, addressed as `gs://{bucket}/cache/...`.
+#[derive(Debug)]
+pub struct GcsStorage {
+    remote: RemoteStore,
+}
+
+impl GcsStorage {
+    /// Create GcsStorage with credentials from the environment
+    /// (`GOOGLE_SERVICE_ACCOUNT`, `GOOGLE_APPLICATION_CREDENTIALS` or application defaults).
+    pub fn new(bucket: &str) -> Result<Self> {
+        let store = GoogleCloudStorageBuilder::from_env()
+            .with_bucket_name(bucket)
+            .build()?;
+
+        Ok(Self {
+            remote: RemoteStore::new("gs", bucket, Arc::new(store)),
+        })
+    }
+
+    /// Create GcsStorage against a custom endpoint without authentication, for emulators
+    /// such as fake-gcs-server.
+    pub fn new_with_endpoint(bucket: &str, endpoint: &str) -> Result<Self> {
+        let service_account = serde_json::json!({
+            "gcs_base_url": endpoint.trim_end_matches('/'),
+            "disable_oauth": true,
+            "client_email": "",
+            "private_key": "",
+            "private_key_id": "",
+        });
+        let store = GoogleCloudStorageBuilder::new()
+            .with_bucket_name(bucket)
+            .with_service_account_key(service_account.to_string())
+            .with_client_options(
+                ClientOptions::new().with_allow_http(endpoint.starts_with("http://")),
+            )
+            .build()?;
+
+        Ok(Self {
+            remote: RemoteStore::new("gs", bucket, Arc::new(store)),
+        })
+    }
+}
+
+remote_storage!(GcsStorage);
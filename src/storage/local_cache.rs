@@ -0,0 +1,600 @@
+// src/storage/local_cache.rs
+//! Local disk tier in front of a remote object store.
+//!
+//! Versioned cache directories are never rewritten, so once a parquet file has been read
+//! or written through this process it can be served from local disk. Files are streamed to
+//! disk on the first read (one download per file at a time), kept when uploaded, and evicted
+//! least-recently-read first once the byte budget is exceeded. Deleting an object from the remote store (pending deletions,
+//! purges) also drops its local copy.
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::TryStreamExt;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOptions, PutOptions, PutPayload, PutResult,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Default disk budget for the local tier (10 GiB).
+pub const DEFAULT_LOCAL_CACHE_SIZE: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Only versioned table caches are immutable; everything else is always read remotely.
+const CACHEABLE_PREFIX: &str = "cache/";
+
+struct LocalEntry {
+    size: u64,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct LocalIndex {
+    entries: HashMap<Path, LocalEntry>,
+    /// Locations by `last_used`, least recently used first
+    by_use: BTreeMap<u64, Path>,
+    size: u64,
+    clock: u64,
+}
+
+impl LocalIndex {
+    fn touch(&mut self, location: &Path) -> bool {
+        let Some(entry) = self.entries.get_mut(location) else {
+            return false;
+        };
+        self.clock += 1;
+        self.by_use.remove(&entry.last_used);
+        entry.last_used = self.clock;
+        self.by_use.insert(self.clock, location.clone());
+        true
+    }
+
+    fn insert(&mut self, location: Path, size: u64) {
+        self.remove(&location);
+        self.clock += 1;
+        self.size += size;
+        self.by_use.insert(self.clock, location.clone());
+        self.entries.insert(
+            location,
+            LocalEntry {
+                size,
+                last_used: self.clock,
+            },
+        );
+    }
+
+    fn remove(&mut self, location: &Path) -> bool {
+        match self.entries.remove(location) {
+            Some(entry) => {
+                self.by_use.remove(&entry.last_used);
+                self.size -= entry.size;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove least recently used entries until the index fits `capacity`, returning them.
+    fn evict_to(&mut self, capacity: u64) -> Vec<Path> {
+        let mut evicted = Vec::new();
+        while self.size > capacity {
+            let Some((_, oldest)) = self.by_use.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.size -= entry.size;
+            }
+            evicted.push(oldest);
+        }
+        evicted
+    }
+}
+
+/// Object store that serves immutable cache files from a local directory and everything
+/// else from the wrapped remote store.
+pub struct LocalCachedStore {
+    remote: Arc<dyn ObjectStore>,
+    local: LocalFileSystem,
+    root: PathBuf,
+    capacity: u64,
+    index: Mutex<LocalIndex>,
+    /// Per-location locks, so concurrent misses download a file once
+    downloads: Mutex<HashMap<Path, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl fmt::Debug for LocalCachedStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalCachedStore")
+            .field("remote", &self.remote)
+            .field("root", &self.root)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+impl fmt::Display for LocalCachedStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LocalCached({}, {})", self.remote, self.root.display())
+    }
+}
+
+impl LocalCachedStore {
+    /// Wrap `remote`, keeping up to `capacity` bytes of cache files under `root`.
+    /// Files left in `root` by a previous run are reused.
+    pub fn new(
+        remote: Arc<dyn ObjectStore>,
+        root: impl Into<PathBuf>,
+        capacity: u64,
+    ) -> anyhow::Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+        let local = LocalFileSystem::new_with_prefix(&root)?;
+
+        let mut existing = Vec::new();
+        scan_local_files(&root, &root, &mut existing)?;
+        existing.sort_by_key(|(_, _, modified)| *modified);
+
+        let mut index = LocalIndex::default();
+        for (location, size, _) in existing {
+            index.insert(location, size);
+        }
+        let store = Self {
+            remote,
+            local,
+            root,
+            capacity,
+            index: Mutex::new(index),
+            downloads: Mutex::new(HashMap::new()),
+        };
+        let evicted = store.index.lock().unwrap().evict_to(capacity);
+        for location in evicted {
+            let _ = std::fs::remove_file(store.root.join(location.as_ref()));
+        }
+
+        Ok(store)
+    }
+
+    /// Bytes of cache files currently kept on local disk.
+    pub fn local_size(&self) -> u64 {
+        self.index.lock().unwrap().size
+    }
+
+    fn is_cacheable(location: &Path) -> bool {
+        location.as_ref().starts_with(CACHEABLE_PREFIX)
+    }
+
+    /// Keep a copy of an object on local disk, evicting older copies over the budget.
+    async fn store_local(&self, location: &Path, payload: PutPayload) {
+        let size = payload.content_length() as u64;
+        if size > self.capacity {
+            return;
+        }
+        if let Err(e) = self.local.put(location, payload).await {
+            warn!(location = %location, error = %e, "Failed to keep local copy of cache file");
+            return;
+        }
+        self.keep(location, size).await;
+    }
+
+    /// Record a local copy in the index, evicting older copies over the budget.
+    async fn keep(&self, location: &Path, size: u64) {
+        let evicted = {
+            let mut index = self.index.lock().unwrap();
+            index.insert(location.clone(), size);
+            index.evict_to(self.capacity)
+        };
+        for location in evicted {
+            self.delete_local(&location).await;
+        }
+    }
+
+    async fn evict(&self, location: &Path) {
+        if self.index.lock().unwrap().remove(location) {
+            self.delete_local(location).await;
+        }
+    }
+
+    async fn delete_local(&self, location: &Path) {
+        match self.local.delete(location).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => {}
+            Err(e) => warn!(location = %location, error = %e, "Failed to evict local cache file"),
+        }
+    }
+
+    /// Read from the local copy, downloading the object first on a miss. Returns `None`
+    /// when the object has to be read remotely.
+    async fn get_local(
+        &self,
+        location: &Path,
+        options: &GetOptions,
+    ) -> object_store::Result<Option<GetResult>> {
+        if !self.index.lock().unwrap().touch(location)
+            && (options.head || !self.download(location).await?)
+        {
+            return Ok(None);
+        }
+
+        match self.local.get_opts(location, options.clone()).await {
+            Ok(result) => Ok(Some(result)),
+            Err(object_store::Error::NotFound { .. }) => {
+                // Removed from disk behind our back, or evicted by a concurrent read
+                self.index.lock().unwrap().remove(location);
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn lock_download(&self, location: &Path) -> tokio::sync::OwnedMutexGuard<()> {
+        let lock = {
+            let mut downloads = self.downloads.lock().unwrap();
+            // Forget locks that nobody holds or waits for
+            downloads.retain(|_, lock| Arc::strong_count(lock) > 1);
+            downloads.entry(location.clone()).or_default().clone()
+        };
+        lock.lock_owned().await
+    }
+
+    /// Stream an object to a staging file and rename it into place, unless a concurrent
+    /// read already did. Returns false when the object is not kept locally, because it
+    /// exceeds the budget or cannot be written to disk.
+    async fn download(&self, location: &Path) -> object_store::Result<bool> {
+        let _download = self.lock_download(location).await;
+        if self.index.lock().unwrap().touch(location) {
+            return Ok(true);
+        }
+
+        let result = self.remote.get(location).await?;
+        let size = result.meta.size;
+        if size > self.capacity {
+            return Ok(false);
+        }
+        let path = self.local.path_to_filesystem(location)?;
+        // Staging files are named like LocalFileSystem's, so a restart skips leftovers
+        let staging = PathBuf::from(format!("{}#{}", path.display(), Uuid::new_v4()));
+        let mut stream = result.into_stream();
+
+        let written: std::io::Result<()> = async {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let mut file = tokio::fs::File::create(&staging).await?;
+            while let Some(chunk) = stream.try_next().await.map_err(std::io::Error::other)? {
+                file.write_all(&chunk).await?;
+            }
+            file.flush().await?;
+            tokio::fs::rename(&staging, &path).await
+        }
+        .await;
+        if let Err(e) = written {
+            warn!(location = %location, error = %e, "Failed to download cache file");
+            let _ = tokio::fs::remove_file(&staging).await;
+            return Ok(false);
+        }
+
+        self.keep(location, size).await;
+        Ok(true)
+    }
+}
+
+/// Collect `(location, size, modified)` for the files under `dir`, skipping the staging
+/// files `LocalFileSystem` writes before renaming them into place.
+fn scan_local_files(
+    root: &std::path::Path,
+    dir: &std::path::Path,
+    files: &mut Vec<(Path, u64, SystemTime)>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            scan_local_files(root, &path, files)?;
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy();
+        if relative.contains('#') {
+            continue;
+        }
+        let Ok(location) = Path::parse(relative.as_ref()) else {
+            continue;
+        };
+        files.push((location, metadata.len(), metadata.modified()?));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl ObjectStore for LocalCachedStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        let result = self
+            .remote
+            .put_opts(location, payload.clone(), opts)
+            .await?;
+        if Self::is_cacheable(location) {
+            self.store_local(location, payload).await;
+        }
+        Ok(result)
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.evict(location).await;
+        self.remote.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        // Preconditions refer to the remote object's version, so they are checked remotely
+        let conditional = options.if_match.is_some()
+            || options.if_none_match.is_some()
+            || options.if_modified_since.is_some()
+            || options.if_unmodified_since.is_some()
+            || options.version.is_some();
+
+        if Self::is_cacheable(location) && !conditional {
+            if let Some(result) = self.get_local(location, &options).await? {
+                return Ok(result);
+            }
+        }
+        self.remote.get_opts(location, options).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.evict(location).await;
+        self.remote.delete(location).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.remote.list(prefix)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> object_store::Result<ListResult> {
+        self.remote.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.evict(to).await;
+        self.remote.copy(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.remote.copy_if_not_exists(from, to).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Remote store that counts reads of object contents.
+    #[derive(Debug, Default)]
+    struct CountingStore {
+        inner: InMemory,
+        gets: AtomicUsize,
+    }
+
+    impl fmt::Display for CountingStore {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Counting({})", self.inner)
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for CountingStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOptions,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            if !options.head {
+                self.gets.fetch_add(1, Ordering::SeqCst);
+            }
+            // Give concurrent readers the chance to miss as well
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.inner.get_opts(location, options).await
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            self.inner.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    fn location(name: &str) -> Path {
+        Path::from(format!("cache/1/public/orders/v1/{}", name))
+    }
+
+    #[tokio::test]
+    async fn test_reads_are_served_locally_after_first_download() {
+        let dir = TempDir::new().unwrap();
+        let remote = Arc::new(InMemory::new());
+        remote
+            .put(&location("data.parquet"), b"parquet".to_vec().into())
+            .await
+            .unwrap();
+        let store = LocalCachedStore::new(remote.clone(), dir.path(), 1024).unwrap();
+
+        let first = store.get(&location("data.parquet")).await.unwrap();
+        assert_eq!(first.bytes().await.unwrap().as_ref(), b"parquet");
+        assert_eq!(store.local_size(), 7);
+
+        // Gone remotely, still readable (including ranges) from the local copy
+        remote.delete(&location("data.parquet")).await.unwrap();
+        let range = store
+            .get_range(&location("data.parquet"), 2..5)
+            .await
+            .unwrap();
+        assert_eq!(range.as_ref(), b"rqu");
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_kept_and_deletes_evict() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalCachedStore::new(Arc::new(InMemory::new()), dir.path(), 1024).unwrap();
+
+        store
+            .put(&location("data.parquet"), b"parquet".to_vec().into())
+            .await
+            .unwrap();
+        assert!(dir
+            .path()
+            .join("cache/1/public/orders/v1/data.parquet")
+            .exists());
+
+        store.delete(&location("data.parquet")).await.unwrap();
+        assert!(!dir
+            .path()
+            .join("cache/1/public/orders/v1/data.parquet")
+            .exists());
+        assert_eq!(store.local_size(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_read_files_are_evicted() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalCachedStore::new(Arc::new(InMemory::new()), dir.path(), 10).unwrap();
+
+        for name in ["a", "b"] {
+            store
+                .put(&location(name), b"12345".to_vec().into())
+                .await
+                .unwrap();
+        }
+        store.get(&location("a")).await.unwrap();
+        store
+            .put(&location("c"), b"12345".to_vec().into())
+            .await
+            .unwrap();
+
+        let local = |name: &str| dir.path().join("cache/1/public/orders/v1").join(name);
+        assert!(local("a").exists());
+        assert!(!local("b").exists());
+        assert!(local("c").exists());
+        assert_eq!(store.local_size(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_existing_local_files_are_reused() {
+        let dir = TempDir::new().unwrap();
+        {
+            let store = LocalCachedStore::new(Arc::new(InMemory::new()), dir.path(), 1024).unwrap();
+            store
+                .put(&location("data.parquet"), b"parquet".to_vec().into())
+                .await
+                .unwrap();
+        }
+
+        // A fresh remote no longer has the file; the reopened tier still serves it
+        let store = LocalCachedStore::new(Arc::new(InMemory::new()), dir.path(), 1024).unwrap();
+        assert_eq!(store.local_size(), 7);
+        let result = store.get(&location("data.parquet")).await.unwrap();
+        assert_eq!(result.bytes().await.unwrap().as_ref(), b"parquet");
+    }
+
+    #[tokio::test]
+    async fn test_files_outside_cache_prefix_are_not_kept() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalCachedStore::new(Arc::new(InMemory::new()), dir.path(), 1024).unwrap();
+
+        store
+            .put(&Path::from("results/r1/data.parquet"), b"x".to_vec().into())
+            .await
+            .unwrap();
+        assert_eq!(store.local_size(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_download_once() {
+        let dir = TempDir::new().unwrap();
+        let remote = Arc::new(CountingStore::default());
+        remote
+            .put(&location("data.parquet"), b"parquet".to_vec().into())
+            .await
+            .unwrap();
+        let store = Arc::new(LocalCachedStore::new(remote.clone(), dir.path(), 1024).unwrap());
+
+        let reads = (0..4).map(|_| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                let result = store.get(&location("data.parquet")).await.unwrap();
+                result.bytes().await.unwrap()
+            })
+        });
+        for data in futures::future::join_all(reads).await {
+            assert_eq!(data.unwrap().as_ref(), b"parquet");
+        }
+        assert_eq!(remote.gets.load(Ordering::SeqCst), 1);
+        assert_eq!(store.local_size(), 7);
+
+        // No staging files are left next to the downloaded copy
+        let files: Vec<_> = std::fs::read_dir(dir.path().join("cache/1/public/orders/v1"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, vec!["data.parquet"]);
+    }
+}
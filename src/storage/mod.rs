@@ -5,12 +5,25 @@ use datafusion::prelude::SessionContext;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+mod aws_profile;
+pub mod azure;
 pub mod filesystem;
+pub mod gcs;
+pub mod local_cache;
+mod remote;
 pub mod s3;
+pub mod upload;
 
 // Re-exports
+pub use azure::AzureStorage;
 pub use filesystem::FilesystemStorage;
-pub use s3::S3Storage;
+pub use gcs::GcsStorage;
+pub use local_cache::{LocalCachedStore, DEFAULT_LOCAL_CACHE_SIZE};
+pub use s3::{
+    S3CredentialSource, S3Encryption, S3Storage, S3StorageOptions, SecretCredentialProvider,
+    DEFAULT_CREDENTIAL_TTL,
+};
+pub use upload::{CacheUpload, UploadWriter};
 
 /// S3 credentials for passing to sync scripts
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +47,14 @@ pub struct CacheWriteHandle {
     pub schema: String,
     /// Table name
     pub table: String,
+    /// File name within the versioned directory (`data.parquet` for full writes)
+    pub file_name: String,
+    /// Version whose files are carried into this one when it is finalized. Set for
+    /// appends; see [`StorageManager::prepare_append_write`].
+    pub base_version: Option<String>,
+    /// Multipart upload the parquet file is streamed into instead of `local_path`, when
+    /// the backend supports it. See [`StorageManager::begin_cache_upload`].
+    pub upload: Option<CacheUpload>,
 }
 
 #[async_trait]
@@ -41,6 +62,8 @@ pub trait StorageManager: Debug + Send + Sync {
     // Path construction
     fn cache_url(&self, connection_id: i32, schema: &str, table: &str) -> String;
     fn cache_prefix(&self, connection_id: i32) -> String;
+    /// URL of the directory holding every connection's cache files
+    fn cache_root(&self) -> String;
 
     // File operations
     async fn read(&self, url: &str) -> Result<Vec<u8>>;
@@ -76,8 +99,32 @@ pub trait StorageManager: Debug + Send + Sync {
         table: &str,
     ) -> CacheWriteHandle;
 
+    /// Prepares a write that appends a new parquet file to the files of `base_version`.
+    /// A written version never changes, since readers, caches and snapshots rely on it,
+    /// so the file goes into a new version and finalizing carries the base version's
+    /// files into it. Used by streaming and snapshot sources, which add one file per
+    /// micro-batch.
+    ///
+    /// Path structure: `{base}/{conn_id}/{schema}/{table}/{version}/part-{id}.parquet`
+    fn prepare_append_write(
+        &self,
+        connection_id: i32,
+        schema: &str,
+        table: &str,
+        base_version: &str,
+    ) -> CacheWriteHandle;
+
+    /// Starts a multipart upload for the handle's file so it can be streamed to storage
+    /// while it is written, instead of through a local temp file.
+    /// Returns None for backends that write locally.
+    async fn begin_cache_upload(&self, _handle: &CacheWriteHandle) -> Result<Option<CacheUpload>> {
+        Ok(None)
+    }
+
     /// Finalizes the cache write after Parquet file is written.
     /// For local storage: no-op (file already in place), returns URL.
-    /// For remote storage: uploads temp file to storage, cleans up temp, returns URL.
+    /// For remote storage: completes the handle's upload if the file was streamed,
+    /// otherwise uploads temp file to storage and cleans up temp; returns URL.
+    /// Appends first copy the files of the handle's base version into the new version.
     async fn finalize_cache_write(&self, handle: &CacheWriteHandle) -> Result<String>;
 }
@@ -0,0 +1,392 @@
+// src/storage/remote.rs
+//! Storage operations shared by the object-store backed cloud backends.
+//!
+//! Cache files are addressed as `{scheme}://{bucket}/cache/...` URLs and streamed into a
+//! multipart upload, or written to a local temp file and uploaded on finalize. Each
+//! backend only builds its object store; [`remote_storage!`] implements the rest.
+
+use anyhow::Result;
+use datafusion::prelude::SessionContext;
+use futures::TryStreamExt;
+use object_store::{path::Path as ObjectPath, ObjectStore};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::warn;
+use url::Url;
+
+use super::{CacheUpload, CacheWriteHandle, LocalCachedStore};
+
+/// Implement [`StorageManager`](super::StorageManager) and `with_local_cache` for a
+/// backend whose `remote` field is a [`RemoteStore`]. Backend-specific trait methods,
+/// such as `get_s3_credentials`, can be passed in braces.
+macro_rules! remote_storage {
+    ($storage:ty) => {
+        $crate::storage::remote::remote_storage!($storage, {});
+    };
+    ($storage:ty, { $($extra:tt)* }) => {
+        impl $storage {
+            /// Serve cached parquet files from a local directory: files are kept when
+            /// uploaded and downloaded on their first read, up to `capacity_bytes` of disk.
+            pub fn with_local_cache(
+                mut self,
+                dir: impl Into<::std::path::PathBuf>,
+                capacity_bytes: u64,
+            ) -> ::anyhow::Result<Self> {
+                self.remote = self.remote.with_local_cache(dir, capacity_bytes)?;
+                Ok(self)
+            }
+        }
+
+        #[::async_trait::async_trait]
+        impl $crate::storage::StorageManager for $storage {
+            fn cache_url(&self, connection_id: i32, schema: &str, table: &str) -> String {
+                self.remote.cache_url(connection_id, schema, table)
+            }
+
+            fn cache_prefix(&self, connection_id: i32) -> String {
+                self.remote.cache_prefix(connection_id)
+            }
+
+            fn cache_root(&self) -> String {
+                self.remote.cache_root()
+            }
+
+            async fn read(&self, url: &str) -> ::anyhow::Result<Vec<u8>> {
+                self.remote.read(url).await
+            }
+
+            async fn write(&self, url: &str, data: &[u8]) -> ::anyhow::Result<()> {
+                self.remote.write(url, data).await
+            }
+
+            async fn delete(&self, url: &str) -> ::anyhow::Result<()> {
+                self.remote.delete(url).await
+            }
+
+            async fn delete_prefix(&self, prefix: &str) -> ::anyhow::Result<()> {
+                self.remote.delete_prefix(prefix).await
+            }
+
+            async fn exists(&self, url: &str) -> ::anyhow::Result<bool> {
+                self.remote.exists(url).await
+            }
+
+            fn register_with_datafusion(
+                &self,
+                ctx: &::datafusion::prelude::SessionContext,
+            ) -> ::anyhow::Result<()> {
+                self.remote.register_with_datafusion(ctx)
+            }
+
+            fn prepare_cache_write(
+                &self,
+                connection_id: i32,
+                schema: &str,
+                table: &str,
+            ) -> $crate::storage::CacheWriteHandle {
+                self.remote.prepare_cache_write(connection_id, schema, table)
+            }
+
+            fn prepare_append_write(
+                &self,
+                connection_id: i32,
+                schema: &str,
+                table: &str,
+                base_version: &str,
+            ) -> $crate::storage::CacheWriteHandle {
+                self.remote
+                    .prepare_append_write(connection_id, schema, table, base_version)
+            }
+
+            async fn begin_cache_upload(
+                &self,
+                handle: &$crate::storage::CacheWriteHandle,
+            ) -> ::anyhow::Result<Option<$crate::storage::CacheUpload>> {
+                Ok(Some(self.remote.begin_cache_upload(handle).await?))
+            }
+
+            async fn finalize_cache_write(
+                &self,
+                handle: &$crate::storage::CacheWriteHandle,
+            ) -> ::anyhow::Result<String> {
+                self.remote.finalize_cache_write(handle).await
+            }
+
+            $($extra)*
+        }
+    };
+}
+
+pub(crate) use remote_storage;
+
+#[derive(Debug)]
+pub(crate) struct RemoteStore {
+    scheme: &'static str,
+    bucket: String,
+    store: Arc<dyn ObjectStore>,
+}
+
+impl RemoteStore {
+    pub(crate) fn new(scheme: &'static str, bucket: &str, store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            scheme,
+            bucket: bucket.to_string(),
+            store,
+        }
+    }
+
+    pub(crate) fn with_local_cache(
+        mut self,
+        dir: impl Into<PathBuf>,
+        capacity_bytes: u64,
+    ) -> Result<Self> {
+        let store = LocalCachedStore::new(self.store.clone(), dir, capacity_bytes)?;
+        self.store = Arc::new(store);
+        Ok(self)
+    }
+
+    fn bucket_url(&self) -> String {
+        format!("{}://{}", self.scheme, self.bucket)
+    }
+
+    fn url_to_path(&self, url: &str) -> Result<ObjectPath> {
+        let url = Url::parse(url)?;
+        let path = url.path().trim_start_matches('/');
+        Ok(ObjectPath::from(path))
+    }
+
+    pub(crate) fn cache_url(&self, connection_id: i32, schema: &str, table: &str) -> String {
+        format!(
+            "{}/cache/{}/{}/{}",
+            self.bucket_url(),
+            connection_id,
+            schema,
+            table
+        )
+    }
+
+    pub(crate) fn cache_prefix(&self, connection_id: i32) -> String {
+        format!("{}/cache/{}", self.bucket_url(), connection_id)
+    }
+
+    pub(crate) fn cache_root(&self) -> String {
+        format!("{}/cache", self.bucket_url())
+    }
+
+    pub(crate) async fn read(&self, url: &str) -> Result<Vec<u8>> {
+        let path = self.url_to_path(url)?;
+        let bytes = self.store.get(&path).await?.bytes().await?;
+        Ok(bytes.to_vec())
+    }
+
+    pub(crate) async fn write(&self, url: &str, data: &[u8]) -> Result<()> {
+        let path = self.url_to_path(url)?;
+        self.store.put(&path, data.to_vec().into()).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn delete(&self, url: &str) -> Result<()> {
+        let path = self.url_to_path(url)?;
+        self.store.delete(&path).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+        let prefix_path = self.url_to_path(prefix)?;
+        let objects: Vec<_> = self.store.list(Some(&prefix_path)).try_collect().await?;
+
+        for obj in objects {
+            self.store.delete(&obj.location).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn exists(&self, url: &str) -> Result<bool> {
+        let path = self.url_to_path(url)?;
+        match self.store.head(&path).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub(crate) fn register_with_datafusion(&self, ctx: &SessionContext) -> Result<()> {
+        let url = Url::parse(&self.bucket_url())?;
+        ctx.runtime_env()
+            .register_object_store(&url, self.store.clone());
+        Ok(())
+    }
+
+    pub(crate) fn prepare_cache_write(
+        &self,
+        connection_id: i32,
+        schema: &str,
+        table: &str,
+    ) -> CacheWriteHandle {
+        self.prepare_write(connection_id, schema, table, "data.parquet", None)
+    }
+
+    pub(crate) fn prepare_append_write(
+        &self,
+        connection_id: i32,
+        schema: &str,
+        table: &str,
+        base_version: &str,
+    ) -> CacheWriteHandle {
+        let file_name = format!("part-{}.parquet", nanoid::nanoid!(8));
+        self.prepare_write(connection_id, schema, table, &file_name, Some(base_version))
+    }
+
+    /// Handle for `file_name` in a new version, with a local temp path mirroring the
+    /// remote layout: `{temp_dir}/{conn_id}/{schema}/{table}/{version}/{file_name}`
+    fn prepare_write(
+        &self,
+        connection_id: i32,
+        schema: &str,
+        table: &str,
+        file_name: &str,
+        base_version: Option<&str>,
+    ) -> CacheWriteHandle {
+        let version = nanoid::nanoid!(8);
+        let local_path = std::env::temp_dir()
+            .join(connection_id.to_string())
+            .join(schema)
+            .join(table)
+            .join(&version)
+            .join(file_name);
+
+        CacheWriteHandle {
+            local_path,
+            version,
+            connection_id,
+            schema: schema.to_string(),
+            table: table.to_string(),
+            file_name: file_name.to_string(),
+            upload: None,
+            base_version: base_version.map(str::to_string),
+        }
+    }
+
+    /// Object path of a file in one of a table's version directories.
+    fn version_path(handle: &CacheWriteHandle, version: &str, file_name: &str) -> ObjectPath {
+        ObjectPath::from(format!(
+            "cache/{}/{}/{}/{}/{}",
+            handle.connection_id, handle.schema, handle.table, version, file_name
+        ))
+    }
+
+    /// Copy the files of the handle's base version into its new version.
+    async fn carry_base_version(&self, handle: &CacheWriteHandle) -> Result<()> {
+        let Some(base_version) = &handle.base_version else {
+            return Ok(());
+        };
+        let base_dir = ObjectPath::from(format!(
+            "cache/{}/{}/{}/{}",
+            handle.connection_id, handle.schema, handle.table, base_version
+        ));
+        let files: Vec<_> = self.store.list(Some(&base_dir)).try_collect().await?;
+
+        for file in files {
+            let Some(file_name) = file.location.filename() else {
+                continue;
+            };
+            let target = Self::version_path(handle, &handle.version, file_name);
+            self.store.copy(&file.location, &target).await?;
+        }
+        Ok(())
+    }
+
+    /// Start a multipart upload at the handle's place in its versioned directory.
+    pub(crate) async fn begin_cache_upload(
+        &self,
+        handle: &CacheWriteHandle,
+    ) -> Result<CacheUpload> {
+        let path = Self::version_path(handle, &handle.version, &handle.file_name);
+        Ok(CacheUpload::new(self.store.clone(), path))
+    }
+
+    /// Complete the handle's upload, or upload the temp file if it was written locally,
+    /// and return the versioned directory URL. Appends also get their base version's files.
+    pub(crate) async fn finalize_cache_write(&self, handle: &CacheWriteHandle) -> Result<String> {
+        let versioned_dir_url = format!(
+            "{}/{}",
+            self.cache_url(handle.connection_id, &handle.schema, &handle.table),
+            handle.version
+        );
+        self.carry_base_version(handle).await?;
+
+        if let Some(upload) = &handle.upload {
+            if upload.finish().await? {
+                return Ok(versioned_dir_url);
+            }
+        }
+
+        let data = std::fs::read(&handle.local_path)?;
+        let file_url = format!("{}/{}", versioned_dir_url, handle.file_name);
+
+        self.write(&file_url, &data).await?;
+
+        // The upload succeeded, so a leftover temp file is only worth a warning
+        if let Err(e) = std::fs::remove_file(&handle.local_path) {
+            warn!(
+                path = %handle.local_path.display(),
+                error = %e,
+                "Failed to remove local temp file after upload; file may be orphaned"
+            );
+        }
+
+        Ok(versioned_dir_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn test_finalize_uploads_into_versioned_directory() {
+        let store = Arc::new(InMemory::new());
+        let remote = RemoteStore::new("gs", "test-bucket", store.clone());
+
+        let handle = remote.prepare_cache_write(7, "public", "orders");
+        std::fs::create_dir_all(handle.local_path.parent().unwrap()).unwrap();
+        std::fs::write(&handle.local_path, b"parquet").unwrap();
+
+        let url = remote.finalize_cache_write(&handle).await.unwrap();
+        assert_eq!(
+            url,
+            format!("gs://test-bucket/cache/7/public/orders/{}", handle.version)
+        );
+        assert!(!handle.local_path.exists());
+
+        let path = ObjectPath::from(format!(
+            "cache/7/public/orders/{}/data.parquet",
+            handle.version
+        ));
+        assert!(store.head(&path).await.is_ok());
+
+        // An append gets a new version holding the base version's file and its own
+        let append = remote.prepare_append_write(7, "public", "orders", &handle.version);
+        std::fs::create_dir_all(append.local_path.parent().unwrap()).unwrap();
+        std::fs::write(&append.local_path, b"more parquet").unwrap();
+        let append_url = remote.finalize_cache_write(&append).await.unwrap();
+        assert_ne!(append_url, url);
+        for file in ["data.parquet", append.file_name.as_str()] {
+            assert!(remote
+                .exists(&format!("{}/{}", append_url, file))
+                .await
+                .unwrap());
+        }
+        assert!(!remote
+            .exists(&format!("{}/{}", url, append.file_name))
+            .await
+            .unwrap());
+
+        remote.delete_prefix(&remote.cache_prefix(7)).await.unwrap();
+        assert!(!remote
+            .exists(&format!("{}/data.parquet", url))
+            .await
+            .unwrap());
+    }
+}
@@ -1,15 +1,17 @@
 // src/storage/s3.rs
 use anyhow::Result;
 use async_trait::async_trait;
-use datafusion::prelude::SessionContext;
-use futures::TryStreamExt;
-use object_store::aws::AmazonS3Builder;
-use object_store::{path::Path as ObjectPath, ObjectStore};
-use std::sync::Arc;
-use tracing::warn;
-use url::Url;
-
-use super::{CacheWriteHandle, S3Credentials, StorageManager};
+use object_store::aws::{AmazonS3Builder, AmazonS3ConfigKey, AwsCredential};
+use object_store::prefix::PrefixStore;
+use object_store::{CredentialProvider, ObjectStore};
+use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::aws_profile;
+use super::remote::{remote_storage, RemoteStore};
+use super::S3Credentials;
+use crate::secrets::SecretManager;
 
 #[derive(Debug, Clone)]
 struct S3Config {
@@ -18,129 +20,315 @@ struct S3Config {
     secret_key: String,
 }
 
+/// Where `S3Storage` gets its credentials.
+#[derive(Debug, Clone, Default)]
+pub enum S3CredentialSource {
+    /// The standard AWS chain: `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`, web identity
+    /// (`AWS_WEB_IDENTITY_TOKEN_FILE` and `AWS_ROLE_ARN`), the `AWS_PROFILE` or `default`
+    /// profile of the shared config files, then container and instance metadata.
+    #[default]
+    Chain,
+    /// A named profile from `~/.aws/credentials` and `~/.aws/config`
+    Profile(String),
+    /// Fixed keys
+    Static {
+        access_key: String,
+        secret_key: String,
+        session_token: Option<String>,
+    },
+    /// Keys stored in the secret manager
+    Secret(Arc<SecretCredentialProvider>),
+}
+
+/// Server-side encryption applied to written objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum S3Encryption {
+    /// SSE-S3, keys managed by S3
+    S3,
+    /// SSE-KMS, with the bucket's default key when no key ID is given
+    Kms { key_id: Option<String> },
+}
+
+/// Options for [`S3Storage::with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct S3StorageOptions {
+    pub region: Option<String>,
+    /// Custom endpoint for MinIO/S3-compatible storage
+    pub endpoint: Option<String>,
+    pub allow_http: bool,
+    /// Key prefix within the bucket. Cache URLs stay `s3://{bucket}/cache/...`; objects
+    /// are stored under `{prefix}/cache/...`.
+    pub prefix: Option<String>,
+    pub credentials: S3CredentialSource,
+    pub encryption: Option<S3Encryption>,
+    /// Address the bucket as `{bucket}.{host}` rather than `{host}/{bucket}`. With a
+    /// custom endpoint, the endpoint must then include the bucket.
+    pub virtual_hosted_style: Option<bool>,
+}
+
+/// S3 credentials read from a secret holding
+/// `{"access_key_id": ..., "secret_access_key": ..., "session_token": ...}`.
+///
+/// Storage is created before the secret manager, so the provider is bound to it once the
+/// engine is built. The secret is read on first use and again once the keys read are older
+/// than the provider's TTL, so rotated keys reach the running client without a restart.
 #[derive(Debug)]
-pub struct S3Storage {
-    bucket: String,
-    store: Arc<dyn ObjectStore>,
-    config: Option<S3Config>,
+pub struct SecretCredentialProvider {
+    secret_name: String,
+    secret_manager: OnceLock<Arc<SecretManager>>,
+    ttl: Duration,
+    /// The keys last read and when the secret was last read. Locked while the secret is
+    /// read, so concurrent requests wait for a single read.
+    credential: tokio::sync::Mutex<Option<(Arc<AwsCredential>, Instant)>>,
 }
 
-impl S3Storage {
-    pub fn new(bucket: &str) -> Result<Self> {
-        let store = AmazonS3Builder::from_env()
-            .with_bucket_name(bucket)
-            .build()?;
+/// Default time S3 keys read from a secret are used before the secret is read again.
+pub const DEFAULT_CREDENTIAL_TTL: Duration = Duration::from_secs(5 * 60);
 
-        Ok(Self {
-            bucket: bucket.to_string(),
-            store: Arc::new(store),
-            config: None,
-        })
+#[derive(Deserialize)]
+struct SecretKeys {
+    access_key_id: String,
+    secret_access_key: String,
+    #[serde(default)]
+    session_token: Option<String>,
+}
+
+impl SecretCredentialProvider {
+    pub fn new(secret_name: impl Into<String>) -> Self {
+        Self {
+            secret_name: secret_name.into(),
+            secret_manager: OnceLock::new(),
+            ttl: DEFAULT_CREDENTIAL_TTL,
+            credential: tokio::sync::Mutex::new(None),
+        }
     }
 
-    /// Create S3Storage with custom endpoint for MinIO/S3-compatible storage
-    pub fn new_with_config(
-        bucket: &str,
-        endpoint: &str,
-        access_key: &str,
-        secret_key: &str,
-        allow_http: bool,
-    ) -> Result<Self> {
-        let mut builder = AmazonS3Builder::new()
-            .with_bucket_name(bucket)
-            .with_endpoint(endpoint)
-            .with_access_key_id(access_key)
-            .with_secret_access_key(secret_key)
-            .with_allow_http(allow_http);
+    /// Read the secret again once the keys read are older than `ttl`.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
 
-        // For MinIO, we need to use path-style URLs
-        builder = builder.with_virtual_hosted_style_request(false);
+    pub fn secret_name(&self) -> &str {
+        &self.secret_name
+    }
 
-        let store = builder.build()?;
+    /// Read credentials through `secret_manager` from now on.
+    pub fn bind(&self, secret_manager: Arc<SecretManager>) {
+        let _ = self.secret_manager.set(secret_manager);
+    }
 
-        Ok(Self {
-            bucket: bucket.to_string(),
-            store: Arc::new(store),
-            config: Some(S3Config {
-                endpoint: endpoint.to_string(),
-                access_key: access_key.to_string(),
-                secret_key: secret_key.to_string(),
-            }),
-        })
+    /// The current keys, reading the secret when it has not been read within the TTL.
+    /// Called at startup so misconfiguration is reported there. If the secret cannot be
+    /// read again later, the previous keys are kept until the next attempt after the TTL.
+    pub async fn load(&self) -> Result<Arc<AwsCredential>> {
+        let mut cached = self.credential.lock().await;
+        if let Some((credential, read_at)) = cached.as_ref() {
+            if read_at.elapsed() < self.ttl {
+                return Ok(credential.clone());
+            }
+        }
+
+        match self.read().await {
+            Ok(credential) => {
+                *cached = Some((credential.clone(), Instant::now()));
+                Ok(credential)
+            }
+            Err(e) => match cached.as_mut() {
+                Some((credential, read_at)) => {
+                    tracing::warn!(
+                        "Failed to reload S3 credentials from secret '{}', keeping the previous keys: {}",
+                        self.secret_name,
+                        e
+                    );
+                    *read_at = Instant::now();
+                    Ok(credential.clone())
+                }
+                None => Err(e),
+            },
+        }
     }
 
-    fn url_to_path(&self, url: &str) -> Result<ObjectPath> {
-        // Convert s3://bucket/path/to/file to ObjectPath
-        let url = Url::parse(url)?;
-        let path = url.path().trim_start_matches('/');
-        Ok(ObjectPath::from(path))
+    async fn read(&self) -> Result<Arc<AwsCredential>> {
+        let secret_manager = self.secret_manager.get().ok_or_else(|| {
+            anyhow::anyhow!("S3 credentials secret read before secrets are available")
+        })?;
+        let value = secret_manager.get_string(&self.secret_name).await?;
+        let keys: SecretKeys = serde_json::from_str(&value).map_err(|e| {
+            anyhow::anyhow!(
+                "Secret '{}' is not valid S3 credentials JSON: {}",
+                self.secret_name,
+                e
+            )
+        })?;
+        Ok(Arc::new(AwsCredential {
+            key_id: keys.access_key_id,
+            secret_key: keys.secret_access_key,
+            token: keys.session_token,
+        }))
     }
 }
 
 #[async_trait]
-impl StorageManager for S3Storage {
-    fn cache_url(&self, connection_id: i32, schema: &str, table: &str) -> String {
-        // For S3, return directory path (DLT creates <table>/*.parquet files)
-        format!(
-            "s3://{}/cache/{}/{}/{}",
-            self.bucket, connection_id, schema, table
-        )
-    }
+impl CredentialProvider for SecretCredentialProvider {
+    type Credential = AwsCredential;
 
-    fn cache_prefix(&self, connection_id: i32) -> String {
-        format!("s3://{}/cache/{}", self.bucket, connection_id)
+    async fn get_credential(&self) -> object_store::Result<Arc<AwsCredential>> {
+        self.load().await.map_err(|e| object_store::Error::Generic {
+            store: "S3",
+            source: e.into(),
+        })
     }
+}
 
-    async fn read(&self, url: &str) -> Result<Vec<u8>> {
-        let path = self.url_to_path(url)?;
-        let result = self.store.get(&path).await?;
-        let bytes = result.bytes().await?;
-        Ok(bytes.to_vec())
-    }
+#[derive(Debug)]
+pub struct S3Storage {
+    remote: RemoteStore,
+    config: Option<S3Config>,
+}
 
-    async fn write(&self, url: &str, data: &[u8]) -> Result<()> {
-        let path = self.url_to_path(url)?;
-        self.store.put(&path, data.to_vec().into()).await?;
-        Ok(())
+impl S3Storage {
+    /// Create S3Storage with credentials from the standard AWS chain
+    pub fn new(bucket: &str) -> Result<Self> {
+        Self::with_options(bucket, S3StorageOptions::default())
     }
 
-    async fn delete(&self, url: &str) -> Result<()> {
-        let path = self.url_to_path(url)?;
-        self.store.delete(&path).await?;
-        Ok(())
+    /// Create S3Storage with custom endpoint for MinIO/S3-compatible storage
+    pub fn new_with_config(
+        bucket: &str,
+        endpoint: &str,
+        access_key: &str,
+        secret_key: &str,
+        allow_http: bool,
+    ) -> Result<Self> {
+        Self::with_options(
+            bucket,
+            S3StorageOptions {
+                endpoint: Some(endpoint.to_string()),
+                allow_http,
+                credentials: S3CredentialSource::Static {
+                    access_key: access_key.to_string(),
+                    secret_key: secret_key.to_string(),
+                    session_token: None,
+                },
+                ..Default::default()
+            },
+        )
     }
 
-    async fn delete_prefix(&self, prefix: &str) -> Result<()> {
-        let url = Url::parse(prefix)?;
-        let prefix_path = url.path().trim_start_matches('/');
-        let prefix_path = ObjectPath::from(prefix_path);
-
-        // List all objects with prefix and delete them
-        let list = self.store.list(Some(&prefix_path));
-        let objects: Vec<_> = list.try_collect().await?;
+    pub fn with_options(bucket: &str, options: S3StorageOptions) -> Result<Self> {
+        // Fixed keys must not be mixed with whatever the environment holds
+        let mut builder = match &options.credentials {
+            S3CredentialSource::Static { .. } => AmazonS3Builder::new(),
+            _ => AmazonS3Builder::from_env(),
+        }
+        .with_bucket_name(bucket);
+
+        let mut region = options.region.clone();
+        let mut keys = None;
+        match &options.credentials {
+            S3CredentialSource::Chain => {
+                // object_store covers the chain except for the shared config files
+                let from_env = std::env::var_os("AWS_ACCESS_KEY_ID").is_some()
+                    || std::env::var_os("AWS_WEB_IDENTITY_TOKEN_FILE").is_some();
+                if !from_env {
+                    keys = aws_profile::load_default();
+                }
+            }
+            S3CredentialSource::Profile(name) => {
+                keys = Some(aws_profile::load(name)?);
+            }
+            S3CredentialSource::Static {
+                access_key,
+                secret_key,
+                session_token,
+            } => {
+                builder = builder
+                    .with_access_key_id(access_key)
+                    .with_secret_access_key(secret_key);
+                if let Some(token) = session_token {
+                    builder = builder.with_token(token);
+                }
+            }
+            S3CredentialSource::Secret(provider) => {
+                builder = builder.with_credentials(provider.clone());
+            }
+        }
+        if let Some(profile) = keys {
+            // An explicit or environment region wins over the profile's
+            let env_region = std::env::var("AWS_REGION")
+                .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+                .ok();
+            region = region.or(env_region).or(profile.region);
+            builder = builder
+                .with_access_key_id(profile.access_key_id)
+                .with_secret_access_key(profile.secret_access_key);
+            if let Some(token) = profile.session_token {
+                builder = builder.with_token(token);
+            }
+        }
 
-        for obj in objects {
-            self.store.delete(&obj.location).await?;
+        if let Some(region) = &region {
+            builder = builder.with_region(region);
+        }
+        if let Some(endpoint) = &options.endpoint {
+            // For MinIO, we need to use path-style URLs unless told otherwise
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(options.allow_http)
+                .with_virtual_hosted_style_request(false);
+        }
+        if let Some(virtual_hosted_style) = options.virtual_hosted_style {
+            builder = builder.with_virtual_hosted_style_request(virtual_hosted_style);
         }
-        Ok(())
-    }
 
-    async fn exists(&self, url: &str) -> Result<bool> {
-        let path = self.url_to_path(url)?;
-        match self.store.head(&path).await {
-            Ok(_) => Ok(true),
-            Err(object_store::Error::NotFound { .. }) => Ok(false),
-            Err(e) => Err(e.into()),
+        match &options.encryption {
+            Some(S3Encryption::S3) => {
+                builder = builder.with_config(sse_config_key()?, "AES256");
+            }
+            Some(S3Encryption::Kms {
+                key_id: Some(key_id),
+            }) => {
+                builder = builder.with_sse_kms_encryption(key_id);
+            }
+            Some(S3Encryption::Kms { key_id: None }) => {
+                builder = builder.with_config(sse_config_key()?, "aws:kms");
+            }
+            None => {}
         }
-    }
 
-    fn register_with_datafusion(&self, ctx: &SessionContext) -> Result<()> {
-        let url = Url::parse(&format!("s3://{}", self.bucket))?;
-        ctx.runtime_env()
-            .register_object_store(&url, self.store.clone());
-        Ok(())
+        let store = builder.build()?;
+        let store: Arc<dyn ObjectStore> =
+            match options.prefix.as_deref().map(|p| p.trim_matches('/')) {
+                Some(prefix) if !prefix.is_empty() => Arc::new(PrefixStore::new(store, prefix)),
+                _ => Arc::new(store),
+            };
+
+        // Sync scripts only get credentials for custom endpoints with fixed keys
+        let config = match (&options.endpoint, &options.credentials) {
+            (
+                Some(endpoint),
+                S3CredentialSource::Static {
+                    access_key,
+                    secret_key,
+                    ..
+                },
+            ) => Some(S3Config {
+                endpoint: endpoint.clone(),
+                access_key: access_key.clone(),
+                secret_key: secret_key.clone(),
+            }),
+            _ => None,
+        };
+
+        Ok(Self {
+            remote: RemoteStore::new("s3", bucket, store),
+            config,
+        })
     }
+}
 
+remote_storage!(S3Storage, {
     fn get_s3_credentials(&self) -> Option<S3Credentials> {
         self.config.as_ref().map(|c| S3Credentials {
             aws_access_key_id: c.access_key.clone(),
@@ -148,70 +336,90 @@ impl StorageManager for S3Storage {
             endpoint_url: c.endpoint.clone(),
         })
     }
+});
+
+fn sse_config_key() -> Result<AmazonS3ConfigKey> {
+    Ok("aws_server_side_encryption".parse()?)
+}
 
-    fn prepare_cache_write(
-        &self,
-        connection_id: i32,
-        schema: &str,
-        table: &str,
-    ) -> CacheWriteHandle {
-        // Use versioned DIRECTORIES to avoid duplicate reads during grace period.
-        // Path structure: {temp_dir}/{conn_id}/{schema}/{table}/{version}/data.parquet
-        // This matches the S3 destination structure for consistency.
-        let version = nanoid::nanoid!(8);
-        let local_path = std::env::temp_dir()
-            .join(connection_id.to_string())
-            .join(schema)
-            .join(table)
-            .join(&version)
-            .join("data.parquet");
-
-        CacheWriteHandle {
-            local_path,
-            version,
-            connection_id,
-            schema: schema.to_string(),
-            table: table.to_string(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{CacheWriteHandle, StorageManager};
+    use object_store::path::Path as ObjectPath;
+
+    fn test_storage(store: Arc<dyn ObjectStore>) -> S3Storage {
+        S3Storage {
+            remote: RemoteStore::new("s3", "test-bucket", store),
+            config: None,
         }
     }
 
-    async fn finalize_cache_write(&self, handle: &CacheWriteHandle) -> Result<String> {
-        let data = std::fs::read(&handle.local_path)?;
+    #[test]
+    fn test_with_options_builds_configured_store() {
+        let storage = S3Storage::with_options(
+            "test-bucket",
+            S3StorageOptions {
+                region: Some("eu-west-1".to_string()),
+                endpoint: Some("http://localhost:9000".to_string()),
+                allow_http: true,
+                prefix: Some("/tenant-a/".to_string()),
+                credentials: S3CredentialSource::Static {
+                    access_key: "key".to_string(),
+                    secret_key: "secret".to_string(),
+                    session_token: Some("token".to_string()),
+                },
+                encryption: Some(S3Encryption::Kms {
+                    key_id: Some("alias/runtimedb".to_string()),
+                }),
+                virtual_hosted_style: None,
+            },
+        )
+        .unwrap();
 
-        // Build S3 URL using the version from the handle
-        let versioned_dir_url = format!(
-            "s3://{}/cache/{}/{}/{}/{}",
-            self.bucket, handle.connection_id, handle.schema, handle.table, handle.version
+        // The prefix only applies to object keys
+        assert_eq!(
+            storage.cache_url(1, "public", "orders"),
+            "s3://test-bucket/cache/1/public/orders"
         );
-        let file_url = format!("{}/data.parquet", versioned_dir_url);
-
-        self.write(&file_url, &data).await?;
-
-        // Clean up local temp file - log warning but don't fail if removal fails
-        // since the S3 upload succeeded and that's what matters
-        if let Err(e) = std::fs::remove_file(&handle.local_path) {
-            warn!(
-                path = %handle.local_path.display(),
-                error = %e,
-                "Failed to remove local temp file after S3 upload; file may be orphaned"
-            );
-        }
+        assert_eq!(
+            storage.get_s3_credentials().unwrap().aws_access_key_id,
+            "key"
+        );
+    }
 
-        Ok(versioned_dir_url)
+    #[test]
+    fn test_missing_profile_is_an_error() {
+        let err = S3Storage::with_options(
+            "test-bucket",
+            S3StorageOptions {
+                credentials: S3CredentialSource::Profile("runtimedb-missing-profile".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("runtimedb-missing-profile"));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[tokio::test]
+    async fn test_prefix_applies_to_object_keys() {
+        let inner: Arc<dyn ObjectStore> = Arc::new(object_store::memory::InMemory::new());
+        let storage = test_storage(Arc::new(PrefixStore::new(inner.clone(), "tenant-a")));
+
+        storage
+            .write(
+                "s3://test-bucket/cache/1/public/orders/v1/data.parquet",
+                b"data",
+            )
+            .await
+            .unwrap();
+        let path = ObjectPath::from("tenant-a/cache/1/public/orders/v1/data.parquet");
+        assert!(inner.head(&path).await.is_ok());
+    }
 
     #[test]
     fn test_cache_write_handle_unique_versions() {
-        let storage = S3Storage {
-            bucket: "test-bucket".to_string(),
-            store: Arc::new(object_store::memory::InMemory::new()),
-            config: None,
-        };
+        let storage = test_storage(Arc::new(object_store::memory::InMemory::new()));
 
         let handle1 = storage.prepare_cache_write(1, "main", "orders");
         let handle2 = storage.prepare_cache_write(1, "main", "orders");
@@ -227,11 +435,7 @@ mod tests {
 
     #[test]
     fn test_cache_write_handle_structure() {
-        let storage = S3Storage {
-            bucket: "test-bucket".to_string(),
-            store: Arc::new(object_store::memory::InMemory::new()),
-            config: None,
-        };
+        let storage = test_storage(Arc::new(object_store::memory::InMemory::new()));
 
         let handle = storage.prepare_cache_write(42, "public", "users");
         let path_str = handle.local_path.to_string_lossy();
@@ -262,11 +466,7 @@ mod tests {
 
     #[test]
     fn test_versioned_directories_are_separate() {
-        let storage = S3Storage {
-            bucket: "test-bucket".to_string(),
-            store: Arc::new(object_store::memory::InMemory::new()),
-            config: None,
-        };
+        let storage = test_storage(Arc::new(object_store::memory::InMemory::new()));
 
         let handle1 = storage.prepare_cache_write(1, "main", "orders");
         let handle2 = storage.prepare_cache_write(1, "main", "orders");
@@ -316,11 +516,7 @@ mod tests {
     #[tokio::test]
     async fn test_finalize_cache_write_uploads_to_correct_s3_path() {
         let store = Arc::new(object_store::memory::InMemory::new());
-        let storage = S3Storage {
-            bucket: "test-bucket".to_string(),
-            store: store.clone(),
-            config: None,
-        };
+        let storage = test_storage(store.clone());
 
         // Create a handle with known version
         let version = "testver1";
@@ -341,6 +537,9 @@ mod tests {
             connection_id: 1,
             schema: "public".to_string(),
             table: "orders".to_string(),
+            file_name: "data.parquet".to_string(),
+            upload: None,
+            base_version: None,
         };
 
         let result_url = storage.finalize_cache_write(&handle).await.unwrap();
@@ -364,4 +563,43 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_dir_all(&temp_base);
     }
+
+    #[tokio::test]
+    async fn test_secret_credentials_are_reloaded_after_ttl() {
+        use crate::catalog::{CatalogManager, SqliteCatalogManager};
+        use crate::secrets::{EncryptedCatalogBackend, ENCRYPTED_PROVIDER_TYPE};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let catalog = Arc::new(
+            SqliteCatalogManager::new(dir.path().join("catalog.db").to_str().unwrap())
+                .await
+                .unwrap(),
+        );
+        catalog.run_migrations().await.unwrap();
+        let backend = Arc::new(EncryptedCatalogBackend::new([7; 32], catalog.clone()));
+        let secrets = Arc::new(SecretManager::new(
+            backend,
+            catalog,
+            ENCRYPTED_PROVIDER_TYPE,
+        ));
+        let keys = |id: &str| {
+            format!(r#"{{"access_key_id": "{id}", "secret_access_key": "secret"}}"#).into_bytes()
+        };
+        secrets.create("s3", &keys("old")).await.unwrap();
+
+        let cached = SecretCredentialProvider::new("s3");
+        let reloaded = SecretCredentialProvider::new("s3").with_ttl(Duration::ZERO);
+        for provider in [&cached, &reloaded] {
+            provider.bind(secrets.clone());
+            assert_eq!(provider.load().await.unwrap().key_id, "old");
+        }
+
+        secrets.update("s3", &keys("rotated")).await.unwrap();
+        assert_eq!(cached.load().await.unwrap().key_id, "old");
+        assert_eq!(reloaded.load().await.unwrap().key_id, "rotated");
+
+        // An unreadable secret keeps the previous keys rather than failing requests
+        secrets.update("s3", b"not json").await.unwrap();
+        assert_eq!(reloaded.load().await.unwrap().key_id, "rotated");
+    }
 }
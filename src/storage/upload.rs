@@ -0,0 +1,263 @@
+// src/storage/upload.rs
+//! Multipart uploads that cache files are streamed into.
+//!
+//! Remote backends can stream parquet straight into an object store multipart upload
+//! instead of writing a local temp file and uploading it afterwards, so a fetch needs no
+//! local disk for the table and uploads while it fetches.
+
+use anyhow::Result;
+use bytes::Bytes;
+use datafusion::parquet::arrow::async_writer::AsyncFileWriter;
+use datafusion::parquet::errors::ParquetError;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use object_store::buffered::BufWriter;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::fmt;
+use std::io;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tokio::runtime::Handle;
+use tracing::warn;
+
+/// Size of each uploaded part. S3 requires at least 5 MiB for all parts but the last.
+const PART_SIZE: usize = 10 * 1024 * 1024;
+
+/// Parts in flight before writes wait for one to complete.
+const MAX_CONCURRENT_PARTS: usize = 8;
+
+/// A multipart upload shared by a [`CacheWriteHandle`](super::CacheWriteHandle) and the
+/// writer streaming into it.
+///
+/// The writer borrows the upload through [`writer`](Self::writer) and hands it back when
+/// the file is written; the upload completes on [`finish`](Self::finish). Dropping it
+/// before that, e.g. when a fetch fails, aborts it.
+#[derive(Clone)]
+pub struct CacheUpload {
+    state: Arc<Mutex<UploadState>>,
+}
+
+struct UploadState {
+    /// The upload, while no [`UploadWriter`] has it
+    writer: Option<BufWriter>,
+    /// Set when the writer chose to write a local file instead
+    abandoned: bool,
+    written: u64,
+    /// Bytes written since `begin_footer`
+    tail: Option<Vec<u8>>,
+}
+
+impl Drop for UploadState {
+    fn drop(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            abort_in_background(writer);
+        }
+    }
+}
+
+fn abort_in_background(mut writer: BufWriter) {
+    match Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move {
+                if let Err(e) = writer.abort().await {
+                    warn!(error = %e, "Failed to abort multipart upload");
+                }
+            });
+        }
+        Err(_) => warn!("Multipart upload dropped outside a runtime; it was not aborted"),
+    }
+}
+
+impl fmt::Debug for CacheUpload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("CacheUpload")
+            .field("written", &state.written)
+            .field("abandoned", &state.abandoned)
+            .finish()
+    }
+}
+
+impl CacheUpload {
+    /// Upload to `path` in `store`. Parts are uploaded as they fill, with at most
+    /// `MAX_CONCURRENT_PARTS` in flight before writes wait for one to complete.
+    pub fn new(store: Arc<dyn ObjectStore>, path: Path) -> Self {
+        let writer = BufWriter::with_capacity(store, path, PART_SIZE)
+            .with_max_concurrency(MAX_CONCURRENT_PARTS);
+        Self {
+            state: Arc::new(Mutex::new(UploadState {
+                writer: Some(writer),
+                abandoned: false,
+                written: 0,
+                tail: None,
+            })),
+        }
+    }
+
+    /// Borrow the upload to stream a parquet file into. It is handed back once the
+    /// parquet writer completes the file.
+    pub fn writer(&self) -> io::Result<UploadWriter> {
+        let writer = self
+            .state
+            .lock()
+            .unwrap()
+            .writer
+            .take()
+            .ok_or_else(|| io::Error::other("upload is already in use or closed"))?;
+        Ok(UploadWriter {
+            writer: Some(writer),
+            upload: self.clone(),
+        })
+    }
+
+    /// Keep a copy of everything written from now on. Called before a parquet writer
+    /// writes its footer, so statistics can be read without downloading the file.
+    pub fn begin_footer(&self) {
+        self.state.lock().unwrap().tail = Some(Vec::new());
+    }
+
+    /// The bytes written since [`begin_footer`](Self::begin_footer): the end of the file.
+    pub fn tail(&self) -> Option<Vec<u8>> {
+        self.state.lock().unwrap().tail.clone()
+    }
+
+    /// Give the upload up because the file is written locally instead; it is aborted and
+    /// [`finish`](Self::finish) reports that nothing was uploaded.
+    pub fn abandon(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.abandoned = true;
+        if let Some(writer) = state.writer.take() {
+            abort_in_background(writer);
+        }
+    }
+
+    /// Whether the writer streams into this upload rather than a local file.
+    pub fn is_abandoned(&self) -> bool {
+        self.state.lock().unwrap().abandoned
+    }
+
+    /// Upload the remaining data and complete the upload. Returns false if the upload was
+    /// abandoned for a local file, which then still has to be uploaded.
+    pub async fn finish(&self) -> Result<bool> {
+        let writer = {
+            let mut state = self.state.lock().unwrap();
+            if state.abandoned {
+                return Ok(false);
+            }
+            state.writer.take()
+        };
+        let mut writer =
+            writer.ok_or_else(|| anyhow::anyhow!("Upload is already finished or still written"))?;
+        writer.shutdown().await?;
+        Ok(true)
+    }
+
+    fn record_write(&self, buf: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+        state.written += buf.len() as u64;
+        if let Some(tail) = state.tail.as_mut() {
+            tail.extend_from_slice(buf);
+        }
+    }
+}
+
+/// The end of a [`CacheUpload`] a parquet writer streams into. Completing the file hands
+/// the upload back without completing it; dropping the writer before that aborts it.
+pub struct UploadWriter {
+    writer: Option<BufWriter>,
+    upload: CacheUpload,
+}
+
+impl AsyncFileWriter for UploadWriter {
+    fn write(&mut self, bs: Bytes) -> BoxFuture<'_, datafusion::parquet::errors::Result<()>> {
+        async move {
+            let writer = self
+                .writer
+                .as_mut()
+                .ok_or_else(|| ParquetError::General("upload is already complete".into()))?;
+            self.upload.record_write(&bs);
+            writer
+                .put(bs)
+                .await
+                .map_err(|e| ParquetError::External(Box::new(e)))
+        }
+        .boxed()
+    }
+
+    fn complete(&mut self) -> BoxFuture<'_, datafusion::parquet::errors::Result<()>> {
+        async move {
+            if let Some(mut writer) = self.writer.take() {
+                writer.flush().await?;
+                self.upload.state.lock().unwrap().writer = Some(writer);
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+impl Drop for UploadWriter {
+    fn drop(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            abort_in_background(writer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn test_finish_uploads_written_bytes() {
+        let store = Arc::new(InMemory::new());
+        let path = Path::from("cache/1/public/orders/v1/data.parquet");
+        let upload = CacheUpload::new(store.clone(), path.clone());
+
+        let mut writer = upload.writer().unwrap();
+        writer.write(Bytes::from_static(b"head")).await.unwrap();
+        upload.begin_footer();
+        writer.write(Bytes::from_static(b"tail")).await.unwrap();
+        writer.complete().await.unwrap();
+        assert!(
+            store.head(&path).await.is_err(),
+            "The file only appears once the upload finishes"
+        );
+        assert!(upload.finish().await.unwrap());
+
+        let data = store.get(&path).await.unwrap().bytes().await.unwrap();
+        assert_eq!(data.as_ref(), b"headtail");
+        assert_eq!(upload.tail().unwrap(), b"tail");
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_upload_writes_nothing() {
+        let store = Arc::new(InMemory::new());
+        let path = Path::from("cache/1/public/orders/v1/data.parquet");
+        let upload = CacheUpload::new(store.clone(), path.clone());
+
+        upload.abandon();
+        assert!(upload.writer().is_err());
+        assert!(!upload.finish().await.unwrap());
+        assert!(store.head(&path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload_is_lent_to_one_writer() {
+        let store = Arc::new(InMemory::new());
+        let path = Path::from("cache/1/public/orders/v1/data.parquet");
+        let upload = CacheUpload::new(store.clone(), path.clone());
+
+        let mut writer = upload.writer().unwrap();
+        assert!(upload.writer().is_err());
+        assert!(upload.finish().await.is_err());
+
+        // A written file that is never finished does not appear
+        writer.write(Bytes::from_static(b"data")).await.unwrap();
+        writer.complete().await.unwrap();
+        drop((writer, upload));
+        assert!(store.head(&path).await.is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Azure Blob storage integration tests using Azurite via testcontainers.
+//!
+//! These tests automatically start an Azurite container, no manual setup required.
+//!
+//! Run these tests with: cargo test --test azure_storage_tests
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rand::RngCore;
+use runtimedb::source::Source;
+use runtimedb::storage::{AzureStorage, StorageManager};
+use runtimedb::RuntimeEngine;
+use std::process::Command;
+use std::sync::Arc;
+use testcontainers::{runners::AsyncRunner, ContainerAsync};
+use testcontainers_modules::azurite::{Azurite, BLOB_PORT};
+
+/// Azurite's well-known development account
+const AZURITE_ACCOUNT: &str = "devstoreaccount1";
+const AZURITE_KEY: &str =
+    "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+const AZURE_CONTAINER: &str = "test-container";
+
+/// Generate a test secret key (base64-encoded 32 bytes)
+fn generate_test_secret_key() -> String {
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    STANDARD.encode(key)
+}
+
+/// Create a blob container in Azurite using the Azure CLI via Docker
+fn create_azure_container(account_url: &str, container: &str) {
+    let connection_string = format!(
+        "DefaultEndpointsProtocol=http;AccountName={};AccountKey={};BlobEndpoint={};",
+        AZURITE_ACCOUNT, AZURITE_KEY, account_url
+    );
+
+    let output = Command::new("docker")
+        .args([
+            "run",
+            "--rm",
+            "--network=host",
+            "mcr.microsoft.com/azure-cli",
+            "az",
+            "storage",
+            "container",
+            "create",
+            "--name",
+            container,
+            "--connection-string",
+            &connection_string,
+        ])
+        .output()
+        .expect("Failed to run docker az command - is Docker running?");
+
+    if !output.status.success() {
+        eprintln!(
+            "Warning: az storage container create failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+/// Test infrastructure that manages Azurite container lifecycle
+struct AzuriteTestInfra {
+    #[allow(dead_code)]
+    azurite: ContainerAsync<Azurite>,
+    storage: Arc<AzureStorage>,
+}
+
+impl AzuriteTestInfra {
+    async fn start() -> Self {
+        let azurite = Azurite::default()
+            .start()
+            .await
+            .expect("Failed to start Azurite");
+
+        let host = azurite.get_host().await.unwrap();
+        let port = azurite.get_host_port_ipv4(BLOB_PORT).await.unwrap();
+        let account_url = format!("http://{}:{}/{}", host, port, AZURITE_ACCOUNT);
+
+        create_azure_container(&account_url, AZURE_CONTAINER);
+
+        let storage = AzureStorage::new_with_config(
+            AZURE_CONTAINER,
+            &account_url,
+            AZURITE_ACCOUNT,
+            AZURITE_KEY,
+            true,
+        )
+        .expect("Failed to create AzureStorage");
+
+        Self {
+            azurite,
+            storage: Arc::new(storage),
+        }
+    }
+}
+
+#[tokio::test]
+async fn azure_storage_write_read_delete() {
+    let infra = AzuriteTestInfra::start().await;
+    let storage = &infra.storage;
+
+    let url = format!("az://{}/cache/1/public/users/test.parquet", AZURE_CONTAINER);
+    let data = b"test data";
+
+    storage.write(&url, data).await.unwrap();
+    assert!(storage.exists(&url).await.unwrap());
+    assert_eq!(storage.read(&url).await.unwrap(), data);
+
+    storage.delete(&url).await.unwrap();
+    assert!(!storage.exists(&url).await.unwrap());
+}
+
+#[tokio::test]
+async fn azure_storage_path_construction() {
+    let infra = AzuriteTestInfra::start().await;
+    let storage = &infra.storage;
+
+    assert_eq!(
+        storage.cache_url(1, "public", "users"),
+        format!("az://{}/cache/1/public/users", AZURE_CONTAINER)
+    );
+    assert_eq!(
+        storage.cache_prefix(1),
+        format!("az://{}/cache/1", AZURE_CONTAINER)
+    );
+}
+
+/// finalize_cache_write uploads into a versioned directory, which delete_prefix removes
+/// the way the deletion worker does.
+#[tokio::test]
+async fn azure_storage_versioned_write_and_delete_prefix() {
+    let infra = AzuriteTestInfra::start().await;
+    let storage = &infra.storage;
+
+    let handle = storage.prepare_cache_write(99, "test_schema", "test_table");
+    std::fs::create_dir_all(handle.local_path.parent().unwrap()).unwrap();
+    std::fs::write(&handle.local_path, b"test parquet data").unwrap();
+
+    let versioned_dir_url = storage.finalize_cache_write(&handle).await.unwrap();
+    assert_eq!(
+        versioned_dir_url,
+        format!(
+            "az://{}/cache/99/test_schema/test_table/{}",
+            AZURE_CONTAINER, handle.version
+        )
+    );
+
+    let file_url = format!("{}/data.parquet", versioned_dir_url);
+    assert!(storage.exists(&file_url).await.unwrap());
+
+    storage
+        .delete_prefix(&storage.cache_prefix(99))
+        .await
+        .unwrap();
+    assert!(!storage.exists(&file_url).await.unwrap());
+}
+
+/// The first query caches the table in the bucket and the second is served from its
+/// `az://` cache URL.
+#[tokio::test(flavor = "multi_thread")]
+async fn azure_storage_serves_cached_queries() {
+    let infra = AzuriteTestInfra::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("source.duckdb");
+    {
+        let conn = duckdb::Connection::open(&db_path).unwrap();
+        conn.execute("CREATE TABLE orders (id INTEGER, total DOUBLE)", [])
+            .unwrap();
+        conn.execute("INSERT INTO orders VALUES (1, 9.5), (2, 20.0)", [])
+            .unwrap();
+    }
+
+    let engine = RuntimeEngine::builder()
+        .base_dir(dir.path())
+        .secret_key(generate_test_secret_key())
+        .storage(infra.storage.clone())
+        .build()
+        .await
+        .unwrap();
+    engine
+        .connect(
+            "shop",
+            Source::Duckdb {
+                path: db_path.to_str().unwrap().to_string(),
+                table_filter: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        let result = engine
+            .execute_query("SELECT * FROM shop.main.orders")
+            .await
+            .unwrap();
+        let rows: usize = result.results.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 2);
+    }
+
+    let tables = engine.list_tables(Some("shop")).await.unwrap();
+    let cached = tables[0].parquet_path.as_deref().unwrap();
+    assert!(
+        cached.starts_with("az://"),
+        "unexpected cache URL: {}",
+        cached
+    );
+
+    engine.shutdown().await.unwrap();
+}
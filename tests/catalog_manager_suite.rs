@@ -1,4 +1,6 @@
-use runtimedb::catalog::{CatalogManager, PostgresCatalogManager, SqliteCatalogManager};
+use runtimedb::catalog::{
+    CatalogManager, PostgresCatalogManager, SqliteCatalogManager, TableAccess,
+};
 use sqlx::{PgPool, SqlitePool};
 use tempfile::TempDir;
 use testcontainers::{runners::AsyncRunner, ImageExt};
@@ -453,6 +455,530 @@ macro_rules! catalog_manager_tests {
                 assert!(table_after.last_sync.is_none());
             }
 
+            #[tokio::test]
+            async fn table_offsets_upsert_and_reset() {
+                use runtimedb::catalog::PartitionOffset;
+
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let config = r#"{"bootstrap_servers": "localhost:9092"}"#;
+                let conn_id = catalog
+                    .add_connection("stream", "kafka", config)
+                    .await
+                    .unwrap();
+                let table_id = catalog
+                    .add_table(conn_id, "topics", "events", "")
+                    .await
+                    .unwrap();
+
+                assert!(catalog
+                    .get_table_offsets(table_id)
+                    .await
+                    .unwrap()
+                    .is_empty());
+
+                let first = [
+                    PartitionOffset {
+                        partition_id: 0,
+                        next_offset: 10,
+                    },
+                    PartitionOffset {
+                        partition_id: 1,
+                        next_offset: 5,
+                    },
+                ];
+                catalog
+                    .update_table_offsets(table_id, &first)
+                    .await
+                    .unwrap();
+
+                // Updating one partition leaves the other untouched
+                catalog
+                    .update_table_offsets(
+                        table_id,
+                        &[PartitionOffset {
+                            partition_id: 0,
+                            next_offset: 42,
+                        }],
+                    )
+                    .await
+                    .unwrap();
+
+                let offsets = catalog.get_table_offsets(table_id).await.unwrap();
+                assert_eq!(
+                    offsets,
+                    vec![
+                        PartitionOffset {
+                            partition_id: 0,
+                            next_offset: 42
+                        },
+                        PartitionOffset {
+                            partition_id: 1,
+                            next_offset: 5
+                        },
+                    ]
+                );
+
+                // Purging the cache resets offsets so the stream is re-read from the start
+                catalog
+                    .clear_table_cache_metadata(conn_id, "topics", "events")
+                    .await
+                    .unwrap();
+                assert!(catalog
+                    .get_table_offsets(table_id)
+                    .await
+                    .unwrap()
+                    .is_empty());
+            }
+
+            #[tokio::test]
+            async fn table_snapshot_id_roundtrip_and_reset() {
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let config = r#"{"catalog_type": {"type": "rest", "uri": "http://localhost:8181"}, "warehouse": "s3://wh"}"#;
+                let conn_id = catalog
+                    .add_connection("lake", "iceberg", config)
+                    .await
+                    .unwrap();
+                let table_id = catalog
+                    .add_table(conn_id, "db", "events", "")
+                    .await
+                    .unwrap();
+
+                assert_eq!(catalog.get_table_snapshot_id(table_id).await.unwrap(), None);
+
+                catalog
+                    .update_table_snapshot_id(table_id, Some(4358109269873137077))
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    catalog.get_table_snapshot_id(table_id).await.unwrap(),
+                    Some(4358109269873137077)
+                );
+
+                // Purging the cache forgets the snapshot so the next fetch is a full read
+                catalog
+                    .clear_table_cache_metadata(conn_id, "db", "events")
+                    .await
+                    .unwrap();
+                assert_eq!(catalog.get_table_snapshot_id(table_id).await.unwrap(), None);
+            }
+
+            #[tokio::test]
+            async fn schema_change_policy_defaults_and_updates() {
+                use runtimedb::catalog::SchemaChangePolicy;
+
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    catalog.get_schema_change_policy(conn_id).await.unwrap(),
+                    SchemaChangePolicy::AutoApply
+                );
+
+                catalog
+                    .set_schema_change_policy(conn_id, SchemaChangePolicy::Block)
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    catalog.get_schema_change_policy(conn_id).await.unwrap(),
+                    SchemaChangePolicy::Block
+                );
+            }
+
+            #[tokio::test]
+            async fn update_connection_renames_and_keeps_tables() {
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", r#"{"host":"a"}"#)
+                    .await
+                    .unwrap();
+                catalog
+                    .add_table(conn_id, "public", "orders", "{}")
+                    .await
+                    .unwrap();
+                catalog
+                    .add_connection("lake", "duckdb", "{}")
+                    .await
+                    .unwrap();
+
+                catalog
+                    .update_connection(conn_id, "analytics", "postgres", r#"{"host":"b"}"#)
+                    .await
+                    .unwrap();
+                assert!(catalog.get_connection("warehouse").await.unwrap().is_none());
+                let conn = catalog.get_connection("analytics").await.unwrap().unwrap();
+                assert_eq!(conn.id, conn_id);
+                assert_eq!(conn.config_json, r#"{"host":"b"}"#);
+                assert_eq!(catalog.list_tables(Some(conn_id)).await.unwrap().len(), 1);
+
+                // Names stay unique
+                assert!(catalog
+                    .update_connection(conn_id, "lake", "postgres", "{}")
+                    .await
+                    .is_err());
+                assert!(catalog
+                    .update_connection(conn_id + 100, "missing", "postgres", "{}")
+                    .await
+                    .is_err());
+            }
+
+            #[tokio::test]
+            async fn warm_up_json_round_trips() {
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                assert_eq!(catalog.get_warm_up_json(conn_id).await.unwrap(), None);
+
+                let patterns = r#"[{"table":"orders*"}]"#;
+                catalog
+                    .set_warm_up_json(conn_id, Some(patterns))
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    catalog.get_warm_up_json(conn_id).await.unwrap().as_deref(),
+                    Some(patterns)
+                );
+
+                catalog.set_warm_up_json(conn_id, None).await.unwrap();
+                assert_eq!(catalog.get_warm_up_json(conn_id).await.unwrap(), None);
+            }
+
+            #[tokio::test]
+            async fn schema_versions_are_numbered_per_table() {
+                use runtimedb::catalog::SchemaVersionStatus;
+
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                let orders = catalog
+                    .add_table(conn_id, "public", "orders", "v1")
+                    .await
+                    .unwrap();
+                let users = catalog
+                    .add_table(conn_id, "public", "users", "u1")
+                    .await
+                    .unwrap();
+
+                let changes = r#"[{"change":"added","column":"email","new_type":"Utf8"}]"#;
+                assert_eq!(
+                    catalog
+                        .add_schema_version(orders, "v1", "[]", SchemaVersionStatus::Applied)
+                        .await
+                        .unwrap(),
+                    1
+                );
+                assert_eq!(
+                    catalog
+                        .add_schema_version(users, "u1", "[]", SchemaVersionStatus::Applied)
+                        .await
+                        .unwrap(),
+                    1
+                );
+                assert_eq!(
+                    catalog
+                        .add_schema_version(orders, "v2", changes, SchemaVersionStatus::Blocked)
+                        .await
+                        .unwrap(),
+                    2
+                );
+
+                let versions = catalog.list_schema_versions(Some(conn_id)).await.unwrap();
+                let summary: Vec<_> = versions
+                    .iter()
+                    .map(|v| (v.table_name.as_str(), v.version, v.status.as_str()))
+                    .collect();
+                assert_eq!(
+                    summary,
+                    vec![
+                        ("orders", 1, "applied"),
+                        ("orders", 2, "blocked"),
+                        ("users", 1, "applied"),
+                    ]
+                );
+                assert_eq!(versions[1].changes_json, changes);
+                assert_eq!(versions[1].arrow_schema_json, "v2");
+
+                // History goes away with the connection
+                catalog.delete_connection("warehouse").await.unwrap();
+                assert!(catalog.list_schema_versions(None).await.unwrap().is_empty());
+            }
+
+            #[tokio::test]
+            async fn stale_tables_are_marked_and_deleted() {
+                use runtimedb::catalog::{PartitionOffset, SchemaVersionStatus};
+
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                let table_id = catalog
+                    .add_table(conn_id, "public", "orders", "{}")
+                    .await
+                    .unwrap();
+                catalog
+                    .add_table(conn_id, "public", "users", "{}")
+                    .await
+                    .unwrap();
+
+                catalog.set_table_stale(table_id, true).await.unwrap();
+                let stale = catalog
+                    .get_table(conn_id, "public", "orders")
+                    .await
+                    .unwrap()
+                    .unwrap();
+                let stale_since = stale.stale_since.clone();
+                assert!(stale_since.is_some());
+
+                // Marking again keeps the original timestamp
+                catalog.set_table_stale(table_id, true).await.unwrap();
+                let again = catalog
+                    .get_table(conn_id, "public", "orders")
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(again.stale_since, stale_since);
+
+                catalog.set_table_stale(table_id, false).await.unwrap();
+                let fresh = catalog
+                    .get_table(conn_id, "public", "orders")
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert!(fresh.stale_since.is_none());
+
+                catalog
+                    .update_table_offsets(
+                        table_id,
+                        &[PartitionOffset {
+                            partition_id: 0,
+                            next_offset: 10,
+                        }],
+                    )
+                    .await
+                    .unwrap();
+                catalog
+                    .add_schema_version(table_id, "{}", "[]", SchemaVersionStatus::Applied)
+                    .await
+                    .unwrap();
+
+                catalog.delete_table(table_id).await.unwrap();
+                let tables = catalog.list_tables(Some(conn_id)).await.unwrap();
+                assert_eq!(tables.len(), 1);
+                assert_eq!(tables[0].table_name, "users");
+                assert!(catalog
+                    .get_table_offsets(table_id)
+                    .await
+                    .unwrap()
+                    .is_empty());
+                assert!(catalog
+                    .list_schema_versions(Some(conn_id))
+                    .await
+                    .unwrap()
+                    .is_empty());
+            }
+
+            #[tokio::test]
+            async fn table_type_defaults_to_base_table_and_updates() {
+                use runtimedb::catalog::TableKind;
+
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                let table_id = catalog
+                    .add_table(conn_id, "public", "active_users", "{}")
+                    .await
+                    .unwrap();
+
+                let table = catalog
+                    .get_table(conn_id, "public", "active_users")
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(table.table_type, "BASE TABLE");
+                assert_eq!(table.kind(), TableKind::BaseTable);
+
+                catalog
+                    .set_table_type(table_id, TableKind::MaterializedView)
+                    .await
+                    .unwrap();
+                // Re-adding the table on a schema change keeps its type
+                catalog
+                    .add_table(conn_id, "public", "active_users", "{\"fields\":[]}")
+                    .await
+                    .unwrap();
+
+                let tables = catalog.list_tables(Some(conn_id)).await.unwrap();
+                assert_eq!(tables[0].table_type, "MATERIALIZED VIEW");
+                assert_eq!(tables[0].kind(), TableKind::MaterializedView);
+            }
+
+            #[tokio::test]
+            async fn table_statistics_are_stored_and_cleared_with_cache() {
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                let table_id = catalog
+                    .add_table(conn_id, "public", "orders", "{}")
+                    .await
+                    .unwrap();
+
+                catalog
+                    .update_table_estimates(table_id, Some(1_000_000), Some(64 << 20))
+                    .await
+                    .unwrap();
+                catalog
+                    .update_table_sync(table_id, "file:///cache/orders/v1")
+                    .await
+                    .unwrap();
+                catalog
+                    .update_table_cache_stats(table_id, Some(r#"{"url":"file:///cache/orders/v1"}"#))
+                    .await
+                    .unwrap();
+
+                let table = catalog
+                    .get_table(conn_id, "public", "orders")
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(table.estimated_rows, Some(1_000_000));
+                assert_eq!(table.estimated_bytes, Some(64 << 20));
+                assert!(table.cache_stats_json.is_some());
+
+                // Purging the cache drops its statistics but keeps the source's estimates
+                catalog
+                    .clear_table_cache_metadata(conn_id, "public", "orders")
+                    .await
+                    .unwrap();
+                let tables = catalog.list_tables(Some(conn_id)).await.unwrap();
+                assert!(tables[0].cache_stats_json.is_none());
+                assert_eq!(tables[0].estimated_rows, Some(1_000_000));
+            }
+
+            #[tokio::test]
+            async fn table_access_is_recorded() {
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                let table_id = catalog
+                    .add_table(conn_id, "public", "orders", "{}")
+                    .await
+                    .unwrap();
+
+                let tables = catalog.list_tables(Some(conn_id)).await.unwrap();
+                assert!(tables[0].last_accessed_at.is_none());
+
+                let accessed_at = chrono::Utc::now();
+                let access = |count, last_accessed_at| TableAccess {
+                    table_id,
+                    count,
+                    last_accessed_at,
+                };
+                catalog
+                    .record_table_accesses(&[access(3, accessed_at)])
+                    .await
+                    .unwrap();
+                // An older batch, e.g. from another instance, adds to the count only
+                catalog
+                    .record_table_accesses(&[access(2, accessed_at - chrono::Duration::hours(1))])
+                    .await
+                    .unwrap();
+
+                let table = catalog
+                    .get_table(conn_id, "public", "orders")
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(table.access_count, 5);
+                let recorded = table.last_accessed_at.expect("access time should be recorded");
+                assert!(recorded.starts_with(&accessed_at.format("%Y-%m-%d %H:%M").to_string()));
+            }
+
+            #[tokio::test]
+            async fn cache_snapshots_pin_cached_tables() {
+                let ctx = super::$setup_fn().await;
+                let catalog = ctx.manager();
+
+                let conn_id = catalog
+                    .add_connection("warehouse", "postgres", "{}")
+                    .await
+                    .unwrap();
+                let cached = catalog
+                    .add_table(conn_id, "public", "orders", "{}")
+                    .await
+                    .unwrap();
+                catalog
+                    .add_table(conn_id, "public", "customers", "{}")
+                    .await
+                    .unwrap();
+                catalog
+                    .update_table_sync(cached, "file:///cache/orders/v1")
+                    .await
+                    .unwrap();
+
+                let tables = catalog.list_tables(Some(conn_id)).await.unwrap();
+                let snapshot_id = catalog
+                    .create_cache_snapshot("month_end", &tables)
+                    .await
+                    .unwrap();
+                assert!(catalog
+                    .create_cache_snapshot("month_end", &tables)
+                    .await
+                    .is_err());
+
+                let snapshot = catalog
+                    .get_cache_snapshot("month_end")
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(snapshot.id, snapshot_id);
+                assert!(snapshot.created_at.is_some());
+
+                // Only the cached table is pinned
+                let pinned = catalog
+                    .list_snapshot_tables(Some(snapshot_id))
+                    .await
+                    .unwrap();
+                assert_eq!(pinned.len(), 1);
+                assert_eq!(pinned[0].table_name, "orders");
+                assert_eq!(pinned[0].parquet_path, "file:///cache/orders/v1");
+
+                catalog.delete_cache_snapshot(snapshot_id).await.unwrap();
+                assert!(catalog.list_cache_snapshots().await.unwrap().is_empty());
+                assert!(catalog.list_snapshot_tables(None).await.unwrap().is_empty());
+            }
+
             #[tokio::test]
             async fn close_is_idempotent() {
                 let ctx = super::$setup_fn().await;
@@ -32,6 +32,7 @@ async fn test_duckdb_discovery_empty() {
     let fetcher = NativeFetcher::new();
     let source = Source::Duckdb {
         path: ":memory:".to_string(),
+        table_filter: Default::default(),
     };
 
     let result = fetcher.discover_tables(&source, &secrets).await;
@@ -67,6 +68,7 @@ async fn test_duckdb_discovery_with_table() {
     let fetcher = NativeFetcher::new();
     let source = Source::Duckdb {
         path: db_path.to_str().unwrap().to_string(),
+        table_filter: Default::default(),
     };
 
     let result = fetcher.discover_tables(&source, &secrets).await;
@@ -103,6 +105,7 @@ async fn test_unsupported_driver() {
         schema: None,
         role: None,
         credential: runtimedb::source::Credential::None,
+        table_filter: Default::default(),
     };
 
     let result = fetcher.discover_tables(&source, &secrets).await;
@@ -169,6 +172,7 @@ mod mysql_container_tests {
             credential: runtimedb::source::Credential::SecretRef {
                 name: "mysql-pass".to_string(),
             },
+            table_filter: Default::default(),
         };
 
         let result = fetcher.discover_tables(&source, &secrets).await;
@@ -250,6 +254,7 @@ mod mysql_container_tests {
             credential: runtimedb::source::Credential::SecretRef {
                 name: "mysql-pass".to_string(),
             },
+            table_filter: Default::default(),
         };
 
         let output_path = temp_dir.path().join("mysql_output.parquet");
@@ -260,7 +265,7 @@ mod mysql_container_tests {
             .await;
         assert!(result.is_ok(), "Fetch should succeed: {:?}", result.err());
 
-        writer.close().unwrap();
+        writer.close().await.unwrap();
 
         // Verify parquet file
         let file = File::open(&output_path).unwrap();
@@ -330,6 +335,7 @@ async fn test_duckdb_fetch_table() {
     let fetcher = NativeFetcher::new();
     let source = Source::Duckdb {
         path: db_path.to_str().unwrap().to_string(),
+        table_filter: Default::default(),
     };
 
     let output_path = temp_dir.path().join("output.parquet");
@@ -347,7 +353,7 @@ async fn test_duckdb_fetch_table() {
         .await;
     assert!(result.is_ok(), "Fetch should succeed: {:?}", result.err());
 
-    writer.close().unwrap();
+    writer.close().await.unwrap();
 
     // Read back the parquet and verify data
     let file = File::open(&output_path).unwrap();
@@ -376,3 +382,372 @@ async fn test_duckdb_fetch_table() {
     assert!(schema.field_with_name("price").is_ok());
     assert!(schema.field_with_name("in_stock").is_ok());
 }
+
+// Kafka tests using testcontainers
+mod kafka_container_tests {
+    use super::*;
+    use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use rskafka::client::partition::{Compression, UnknownTopicHandling};
+    use rskafka::client::ClientBuilder;
+    use rskafka::record::Record;
+    use runtimedb::datafetch::StreamingParquetWriter;
+    use runtimedb::source::KafkaFormat;
+    use std::fs::File;
+    use testcontainers::runners::AsyncRunner;
+    use testcontainers_modules::kafka::apache::{Kafka, KAFKA_PORT};
+
+    async fn produce_json(bootstrap: &str, topic: &str, values: &[serde_json::Value]) {
+        let client = ClientBuilder::new(vec![bootstrap.to_string()])
+            .build()
+            .await
+            .unwrap();
+        let partition = client
+            .partition_client(topic, 0, UnknownTopicHandling::Retry)
+            .await
+            .unwrap();
+        let records = values
+            .iter()
+            .map(|v| Record {
+                key: None,
+                value: Some(serde_json::to_vec(v).unwrap()),
+                headers: Default::default(),
+                timestamp: chrono::Utc::now(),
+            })
+            .collect();
+        partition
+            .produce(records, Compression::NoCompression)
+            .await
+            .unwrap();
+    }
+
+    fn read_rows(path: &std::path::Path) -> usize {
+        let file = File::open(path).unwrap();
+        ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .map(|b| b.unwrap().num_rows())
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_kafka_json_discovery_and_incremental_fetch() {
+        let temp_dir = TempDir::new().unwrap();
+        let secrets = test_secret_manager(&temp_dir).await;
+
+        let container = Kafka::default()
+            .start()
+            .await
+            .expect("Failed to start kafka");
+        let port = container.get_host_port_ipv4(KAFKA_PORT).await.unwrap();
+        let bootstrap = format!("127.0.0.1:{}", port);
+
+        let client = ClientBuilder::new(vec![bootstrap.clone()])
+            .build()
+            .await
+            .unwrap();
+        client
+            .controller_client()
+            .unwrap()
+            .create_topic("events", 1, 1, 5_000)
+            .await
+            .unwrap();
+
+        produce_json(
+            &bootstrap,
+            "events",
+            &[
+                serde_json::json!({"id": 1, "kind": "click"}),
+                serde_json::json!({"id": 2, "kind": "view"}),
+            ],
+        )
+        .await;
+
+        let fetcher = NativeFetcher::new();
+        let source = Source::Kafka {
+            bootstrap_servers: bootstrap.clone(),
+            format: KafkaFormat::Json,
+            schema_registry_url: None,
+            table_filter: Default::default(),
+        };
+
+        let tables = fetcher.discover_tables(&source, &secrets).await.unwrap();
+        let events = tables
+            .iter()
+            .find(|t| t.table_name == "events")
+            .expect("Should discover the events topic");
+        assert_eq!(events.schema_name, "topics");
+        let names: Vec<&str> = events.columns.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"_offset"));
+        assert!(names.contains(&"id"));
+        assert!(names.contains(&"kind"));
+
+        // First fetch reads everything from the earliest offset
+        let first_path = temp_dir.path().join("first.parquet");
+        let mut writer = StreamingParquetWriter::new(first_path.clone());
+        let offsets = fetcher
+            .fetch_table_since(&source, &secrets, "topics", "events", &[], &mut writer)
+            .await
+            .unwrap();
+        writer.close().await.unwrap();
+        assert_eq!(read_rows(&first_path), 2);
+        assert_eq!(offsets.len(), 1);
+        assert_eq!(offsets[0].next_offset, 2);
+
+        // Second fetch resumes after the stored offsets
+        produce_json(
+            &bootstrap,
+            "events",
+            &[serde_json::json!({"id": 3, "kind": "click"})],
+        )
+        .await;
+
+        let second_path = temp_dir.path().join("second.parquet");
+        let mut writer = StreamingParquetWriter::new(second_path.clone());
+        let offsets = fetcher
+            .fetch_table_since(&source, &secrets, "topics", "events", &offsets, &mut writer)
+            .await
+            .unwrap();
+        writer.close().await.unwrap();
+        assert_eq!(read_rows(&second_path), 1);
+        assert_eq!(offsets[0].next_offset, 3);
+    }
+}
+
+// Delta Lake tests using local filesystem tables
+mod delta_tests {
+    use super::*;
+    use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use deltalake::arrow::array::{Int64Array, StringArray};
+    use deltalake::arrow::datatypes::{DataType, Field, Schema};
+    use deltalake::arrow::record_batch::RecordBatch;
+    use deltalake::DeltaOps;
+    use runtimedb::datafetch::StreamingParquetWriter;
+    use runtimedb::source::Credential;
+    use std::fs::File;
+
+    async fn append_events(table_path: &std::path::Path, ids: &[i64], region: &str) {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("region", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(ids.to_vec())),
+                Arc::new(StringArray::from(vec![region; ids.len()])),
+            ],
+        )
+        .unwrap();
+
+        DeltaOps::try_from_uri(url::Url::from_directory_path(table_path).unwrap())
+            .await
+            .unwrap()
+            .write(vec![batch])
+            .with_partition_columns(["region"])
+            .await
+            .unwrap();
+    }
+
+    fn delta_source(location: &std::path::Path, version: Option<i64>) -> Source {
+        Source::Delta {
+            location: location.to_str().unwrap().to_string(),
+            credential: Credential::None,
+            version,
+            timestamp: None,
+            table_filter: Default::default(),
+        }
+    }
+
+    async fn fetch_rows(
+        fetcher: &NativeFetcher,
+        source: &Source,
+        secrets: &SecretManager,
+    ) -> usize {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("events.parquet");
+        let mut writer = StreamingParquetWriter::new(path.clone());
+        fetcher
+            .fetch_table(source, secrets, None, "default", "events", &mut writer)
+            .await
+            .unwrap();
+        writer.close().await.unwrap();
+
+        let file = File::open(&path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        assert!(
+            builder.schema().field_with_name("region").is_ok(),
+            "Partition column should be materialized"
+        );
+
+        builder
+            .build()
+            .unwrap()
+            .map(|b| b.unwrap().num_rows())
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_delta_discovery_and_fetch() {
+        let temp_dir = TempDir::new().unwrap();
+        let secrets = test_secret_manager(&temp_dir).await;
+
+        let lake = temp_dir.path().join("lake");
+        let table_path = lake.join("events");
+        std::fs::create_dir_all(&table_path).unwrap();
+        // Directories without a _delta_log are not tables
+        std::fs::create_dir_all(lake.join("scratch")).unwrap();
+
+        append_events(&table_path, &[1, 2], "us").await;
+        append_events(&table_path, &[3], "eu").await;
+
+        let fetcher = NativeFetcher::new();
+        let source = delta_source(&lake, None);
+
+        let tables = fetcher.discover_tables(&source, &secrets).await.unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].schema_name, "default");
+        assert_eq!(tables[0].table_name, "events");
+        let names: Vec<&str> = tables[0].columns.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"id"));
+        assert!(names.contains(&"region"));
+
+        assert_eq!(fetch_rows(&fetcher, &source, &secrets).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_delta_time_travel_by_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let secrets = test_secret_manager(&temp_dir).await;
+
+        let table_path = temp_dir.path().join("events");
+        std::fs::create_dir_all(&table_path).unwrap();
+
+        append_events(&table_path, &[1, 2], "us").await;
+        append_events(&table_path, &[3], "eu").await;
+
+        let fetcher = NativeFetcher::new();
+
+        // Pointing at the table directory itself exposes just that table
+        let tables = fetcher
+            .discover_tables(&delta_source(&table_path, None), &secrets)
+            .await
+            .unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].table_name, "events");
+
+        let as_of_first_commit = delta_source(&table_path, Some(0));
+        assert_eq!(fetch_rows(&fetcher, &as_of_first_commit, &secrets).await, 2);
+    }
+}
+
+// BigQuery tests against the open-source emulator (REST on 9050, Storage Read API on 9060)
+mod bigquery_emulator_tests {
+    use super::*;
+    use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use runtimedb::datafetch::StreamingParquetWriter;
+    use runtimedb::source::Credential;
+    use std::fs::File;
+    use std::time::Duration;
+    use testcontainers::core::IntoContainerPort;
+    use testcontainers::runners::AsyncRunner;
+    use testcontainers::{GenericImage, ImageExt};
+
+    const PROJECT: &str = "test";
+    const DATASET: &str = "ds";
+
+    /// Run a statement through the emulator's REST API, retrying while it starts up.
+    async fn run_statement(api_endpoint: &str, sql: &str) {
+        let http = reqwest::Client::new();
+        let url = format!("{}/bigquery/v2/projects/{}/queries", api_endpoint, PROJECT);
+        let body = serde_json::json!({ "query": sql, "useLegacySql": false });
+
+        for _ in 0..30 {
+            if let Ok(response) = http.post(&url).json(&body).send().await {
+                if response.status().is_success() {
+                    return;
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        panic!("emulator did not accept statement: {}", sql);
+    }
+
+    #[tokio::test]
+    async fn test_bigquery_discovery_and_fetch() {
+        let temp_dir = TempDir::new().unwrap();
+        let secrets = test_secret_manager(&temp_dir).await;
+
+        let container = GenericImage::new("ghcr.io/goccy/bigquery-emulator", "latest")
+            .with_exposed_port(9050.tcp())
+            .with_exposed_port(9060.tcp())
+            .with_cmd([
+                format!("--project={}", PROJECT),
+                format!("--dataset={}", DATASET),
+            ])
+            .start()
+            .await
+            .expect("Failed to start BigQuery emulator");
+
+        let host = container.get_host().await.unwrap();
+        let api_endpoint = format!(
+            "http://{}:{}",
+            host,
+            container.get_host_port_ipv4(9050.tcp()).await.unwrap()
+        );
+        let storage_endpoint = format!(
+            "http://{}:{}",
+            host,
+            container.get_host_port_ipv4(9060.tcp()).await.unwrap()
+        );
+
+        run_statement(
+            &api_endpoint,
+            "CREATE TABLE ds.orders (id INT64, customer STRING, amount FLOAT64)",
+        )
+        .await;
+        run_statement(
+            &api_endpoint,
+            "INSERT INTO ds.orders (id, customer, amount) VALUES (1, 'alice', 9.5), (2, 'bob', 20.0), (3, 'carol', 3.25)",
+        )
+        .await;
+
+        let source = Source::Bigquery {
+            project: PROJECT.to_string(),
+            dataset: DATASET.to_string(),
+            credential: Credential::None,
+            api_endpoint: Some(api_endpoint),
+            storage_endpoint: Some(storage_endpoint),
+            table_filter: Default::default(),
+        };
+        let fetcher = NativeFetcher::new();
+
+        let tables = fetcher.discover_tables(&source, &secrets).await.unwrap();
+        let orders = tables
+            .iter()
+            .find(|t| t.table_name == "orders")
+            .expect("orders table should be discovered");
+        assert_eq!(orders.schema_name, DATASET);
+        let names: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "customer", "amount"]);
+
+        let output_path = temp_dir.path().join("orders.parquet");
+        let mut writer = StreamingParquetWriter::new(output_path.clone());
+        fetcher
+            .fetch_table(&source, &secrets, None, DATASET, "orders", &mut writer)
+            .await
+            .unwrap();
+        writer.close().await.unwrap();
+
+        let batches: Vec<_> =
+            ParquetRecordBatchReaderBuilder::try_new(File::open(&output_path).unwrap())
+                .unwrap()
+                .build()
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 3);
+        assert!(batches[0].schema().field_with_name("customer").is_ok());
+    }
+}
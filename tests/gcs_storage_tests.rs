@@ -0,0 +1,188 @@
+//! GCS storage integration tests using fake-gcs-server via testcontainers.
+//!
+//! These tests automatically start a fake-gcs-server container, no manual setup required.
+//!
+//! Run these tests with: cargo test --test gcs_storage_tests
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rand::RngCore;
+use runtimedb::source::Source;
+use runtimedb::storage::{GcsStorage, StorageManager};
+use runtimedb::RuntimeEngine;
+use std::sync::Arc;
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::{runners::AsyncRunner, ContainerAsync, GenericImage, ImageExt};
+
+const GCS_BUCKET: &str = "test-bucket";
+const GCS_PORT: u16 = 4443;
+
+/// Generate a test secret key (base64-encoded 32 bytes)
+fn generate_test_secret_key() -> String {
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    STANDARD.encode(key)
+}
+
+/// Create a bucket through fake-gcs-server's JSON API
+async fn create_gcs_bucket(endpoint: &str, bucket: &str) {
+    let response = reqwest::Client::new()
+        .post(format!("{}/storage/v1/b", endpoint))
+        .json(&serde_json::json!({ "name": bucket }))
+        .send()
+        .await
+        .expect("Failed to reach fake-gcs-server");
+
+    if !response.status().is_success() {
+        eprintln!(
+            "Warning: bucket creation failed: {}",
+            response.text().await.unwrap_or_default()
+        );
+    }
+}
+
+/// Test infrastructure that manages fake-gcs-server container lifecycle
+struct GcsTestInfra {
+    #[allow(dead_code)]
+    gcs: ContainerAsync<GenericImage>,
+    storage: Arc<GcsStorage>,
+}
+
+impl GcsTestInfra {
+    async fn start() -> Self {
+        let gcs = GenericImage::new("fsouza/fake-gcs-server", "latest")
+            .with_exposed_port(GCS_PORT.tcp())
+            .with_wait_for(WaitFor::message_on_stderr("server started"))
+            .with_cmd(["-scheme", "http", "-backend", "memory"])
+            .start()
+            .await
+            .expect("Failed to start fake-gcs-server");
+
+        let host = gcs.get_host().await.unwrap();
+        let port = gcs.get_host_port_ipv4(GCS_PORT).await.unwrap();
+        let endpoint = format!("http://{}:{}", host, port);
+
+        create_gcs_bucket(&endpoint, GCS_BUCKET).await;
+
+        let storage = GcsStorage::new_with_endpoint(GCS_BUCKET, &endpoint)
+            .expect("Failed to create GcsStorage");
+
+        Self {
+            gcs,
+            storage: Arc::new(storage),
+        }
+    }
+}
+
+#[tokio::test]
+async fn gcs_storage_write_read_delete() {
+    let infra = GcsTestInfra::start().await;
+    let storage = &infra.storage;
+
+    let url = format!("gs://{}/cache/1/public/users/test.parquet", GCS_BUCKET);
+    let data = b"test data";
+
+    storage.write(&url, data).await.unwrap();
+    assert!(storage.exists(&url).await.unwrap());
+    assert_eq!(storage.read(&url).await.unwrap(), data);
+
+    storage.delete(&url).await.unwrap();
+    assert!(!storage.exists(&url).await.unwrap());
+}
+
+#[tokio::test]
+async fn gcs_storage_path_construction() {
+    let infra = GcsTestInfra::start().await;
+    let storage = &infra.storage;
+
+    assert_eq!(
+        storage.cache_url(1, "public", "users"),
+        format!("gs://{}/cache/1/public/users", GCS_BUCKET)
+    );
+    assert_eq!(
+        storage.cache_prefix(1),
+        format!("gs://{}/cache/1", GCS_BUCKET)
+    );
+}
+
+/// finalize_cache_write uploads into a versioned directory, which delete_prefix removes
+/// the way the deletion worker does.
+#[tokio::test]
+async fn gcs_storage_versioned_write_and_delete_prefix() {
+    let infra = GcsTestInfra::start().await;
+    let storage = &infra.storage;
+
+    let handle = storage.prepare_cache_write(99, "test_schema", "test_table");
+    std::fs::create_dir_all(handle.local_path.parent().unwrap()).unwrap();
+    std::fs::write(&handle.local_path, b"test parquet data").unwrap();
+
+    let versioned_dir_url = storage.finalize_cache_write(&handle).await.unwrap();
+    assert_eq!(
+        versioned_dir_url,
+        format!(
+            "gs://{}/cache/99/test_schema/test_table/{}",
+            GCS_BUCKET, handle.version
+        )
+    );
+
+    let file_url = format!("{}/data.parquet", versioned_dir_url);
+    assert!(storage.exists(&file_url).await.unwrap());
+
+    storage
+        .delete_prefix(&storage.cache_prefix(99))
+        .await
+        .unwrap();
+    assert!(!storage.exists(&file_url).await.unwrap());
+}
+
+/// The first query caches the table in the bucket and the second is served from its
+/// `gs://` cache URL.
+#[tokio::test(flavor = "multi_thread")]
+async fn gcs_storage_serves_cached_queries() {
+    let infra = GcsTestInfra::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("source.duckdb");
+    {
+        let conn = duckdb::Connection::open(&db_path).unwrap();
+        conn.execute("CREATE TABLE orders (id INTEGER, total DOUBLE)", [])
+            .unwrap();
+        conn.execute("INSERT INTO orders VALUES (1, 9.5), (2, 20.0)", [])
+            .unwrap();
+    }
+
+    let engine = RuntimeEngine::builder()
+        .base_dir(dir.path())
+        .secret_key(generate_test_secret_key())
+        .storage(infra.storage.clone())
+        .build()
+        .await
+        .unwrap();
+    engine
+        .connect(
+            "shop",
+            Source::Duckdb {
+                path: db_path.to_str().unwrap().to_string(),
+                table_filter: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        let result = engine
+            .execute_query("SELECT * FROM shop.main.orders")
+            .await
+            .unwrap();
+        let rows: usize = result.results.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 2);
+    }
+
+    let tables = engine.list_tables(Some("shop")).await.unwrap();
+    let cached = tables[0].parquet_path.as_deref().unwrap();
+    assert!(
+        cached.starts_with("gs://"),
+        "unexpected cache URL: {}",
+        cached
+    );
+
+    engine.shutdown().await.unwrap();
+}
@@ -385,6 +385,7 @@ async fn test_iceberg_rest_catalog_discovery() {
         },
         warehouse: "test_warehouse".to_string(),
         namespace: None, // Discover all namespaces
+        table_filter: Default::default(),
     };
 
     // Test table discovery
@@ -446,6 +447,7 @@ async fn test_iceberg_rest_catalog_fetch_empty_table() {
         },
         warehouse: "test_warehouse".to_string(),
         namespace: Some("test_namespace".to_string()),
+        table_filter: Default::default(),
     };
 
     // Test fetching an empty table (no data files)
@@ -471,7 +473,7 @@ async fn test_iceberg_rest_catalog_fetch_empty_table() {
         result.err()
     );
 
-    writer.close().unwrap();
+    writer.close().await.unwrap();
 
     // Verify the parquet file was created with correct schema
     use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
@@ -516,6 +518,7 @@ async fn test_iceberg_rest_catalog_with_namespace_filter() {
         },
         warehouse: "test_warehouse".to_string(),
         namespace: Some("test_namespace".to_string()),
+        table_filter: Default::default(),
     };
 
     // Test table discovery with namespace filter
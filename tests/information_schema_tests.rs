@@ -50,6 +50,7 @@ async fn create_test_engine_with_data(temp_dir: &TempDir) -> RuntimeEngine {
     // Register the DuckDB connection
     let source = Source::Duckdb {
         path: duckdb_path.to_str().unwrap().to_string(),
+        table_filter: Default::default(),
     };
     engine
         .connect("testdb", source)
@@ -126,6 +127,50 @@ async fn test_information_schema_columns() {
     assert_eq!(column_names.value(3), "created_at");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_information_schema_columns_decimal_precision() {
+    use datafusion::arrow::array::Array;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let engine = create_test_engine_with_data(&temp_dir).await;
+
+    let result = engine
+        .execute_query(
+            "SELECT column_name, data_type, numeric_precision, numeric_scale
+             FROM runtimedb.information_schema.columns
+             WHERE table_name = 'orders'
+             ORDER BY ordinal_position",
+        )
+        .await
+        .expect("Query failed");
+
+    let batch = &result.results[0];
+    let data_types = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<datafusion::arrow::array::StringArray>()
+        .expect("data_type should be string");
+    let precision = batch
+        .column(2)
+        .as_any()
+        .downcast_ref::<datafusion::arrow::array::Int32Array>()
+        .expect("numeric_precision should be int32");
+    let scale = batch
+        .column(3)
+        .as_any()
+        .downcast_ref::<datafusion::arrow::array::Int32Array>()
+        .expect("numeric_scale should be int32");
+
+    // amount DECIMAL(10, 2) keeps its declared precision and scale
+    assert_eq!(data_types.value(2), "Decimal128(10, 2)");
+    assert_eq!(precision.value(2), 10);
+    assert_eq!(scale.value(2), 2);
+
+    // Non-decimal columns have no precision
+    assert!(precision.is_null(1));
+    assert!(scale.is_null(1));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_information_schema_columns_all_tables() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -150,6 +195,73 @@ async fn test_information_schema_columns_all_tables() {
     assert_eq!(count.value(0), 7, "Should have 7 columns total");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_information_schema_table_cache() {
+    use datafusion::arrow::array::{Array, BooleanArray, Int64Array, StringArray};
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let engine = create_test_engine_with_data(&temp_dir).await;
+
+    // Caches orders and records the access
+    engine
+        .execute_query("SELECT * FROM testdb.sales.orders")
+        .await
+        .expect("Query failed");
+
+    let result = engine
+        .execute_query(
+            "SELECT table_name, is_cached, sync_mode, last_sync, last_accessed_at, \
+             cached_rows, cached_bytes, file_count, version_id, access_count \
+             FROM runtimedb.information_schema.table_cache ORDER BY table_name",
+        )
+        .await
+        .expect("Query failed");
+    let batch = &result.results[0];
+    assert_eq!(batch.num_rows(), 2);
+
+    let column = |i: usize| batch.column(i).clone();
+    let names = column(0);
+    let names = names.as_any().downcast_ref::<StringArray>().unwrap();
+    let cached = column(1);
+    let cached = cached.as_any().downcast_ref::<BooleanArray>().unwrap();
+    let modes = column(2);
+    let modes = modes.as_any().downcast_ref::<StringArray>().unwrap();
+    let rows = column(5);
+    let rows = rows.as_any().downcast_ref::<Int64Array>().unwrap();
+    let bytes = column(6);
+    let bytes = bytes.as_any().downcast_ref::<Int64Array>().unwrap();
+    let files = column(7);
+    let files = files.as_any().downcast_ref::<Int64Array>().unwrap();
+    let versions = column(8);
+    let versions = versions.as_any().downcast_ref::<StringArray>().unwrap();
+    let accesses = column(9);
+    let accesses = accesses.as_any().downcast_ref::<Int64Array>().unwrap();
+
+    assert_eq!(names.value(0), "orders");
+    assert!(cached.value(0));
+    assert_eq!(modes.value(0), "full");
+    assert!(!column(3).is_null(0), "orders should have a last_sync");
+    assert!(!column(4).is_null(0), "orders should have been accessed");
+    assert_eq!(accesses.value(0), 1);
+    assert_eq!(rows.value(0), 1);
+    assert!(bytes.value(0) > 0);
+    assert_eq!(files.value(0), 1);
+    let parquet_path = engine.list_tables(Some("testdb")).await.unwrap()[0]
+        .parquet_path
+        .clone()
+        .unwrap();
+    assert!(parquet_path.ends_with(versions.value(0)));
+
+    assert_eq!(names.value(1), "products");
+    assert!(!cached.value(1));
+    assert!(column(3).is_null(1));
+    assert!(column(4).is_null(1));
+    assert_eq!(accesses.value(1), 0);
+    assert!(rows.is_null(1));
+    assert!(files.is_null(1));
+    assert!(versions.is_null(1));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_information_schema_empty_catalog() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -171,3 +283,82 @@ async fn test_information_schema_empty_catalog() {
     assert!(!result.results.is_empty(), "Should have result batch");
     assert_eq!(result.results[0].num_rows(), 0, "Should have 0 tables");
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_information_schema_reports_views_and_reads_them_live() {
+    use datafusion::arrow::array::StringArray;
+    use runtimedb::source::Source;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let engine = RuntimeEngine::builder()
+        .base_dir(temp_dir.path().to_path_buf())
+        .secret_key(test_secret_key())
+        .build()
+        .await
+        .expect("Failed to create engine");
+
+    let duckdb_path = temp_dir.path().join("views.duckdb");
+    let db = duckdb::Connection::open(&duckdb_path).expect("Failed to create DuckDB");
+    db.execute_batch(
+        r#"
+        CREATE SCHEMA sales;
+        CREATE TABLE sales.orders (id INTEGER, amount DOUBLE);
+        INSERT INTO sales.orders VALUES (1, 100.0), (2, 200.0);
+        CREATE VIEW sales.large_orders AS SELECT * FROM sales.orders WHERE amount > 150;
+        "#,
+    )
+    .expect("Failed to create test tables");
+    drop(db);
+
+    let source = Source::Duckdb {
+        path: duckdb_path.to_str().unwrap().to_string(),
+        table_filter: Default::default(),
+    };
+    engine
+        .connect("testdb", source)
+        .await
+        .expect("Failed to connect");
+
+    let result = engine
+        .execute_query(
+            "SELECT table_name, table_type FROM runtimedb.information_schema.tables \
+             ORDER BY table_name",
+        )
+        .await
+        .expect("Query failed");
+    let batch = &result.results[0];
+    let types = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .expect("table_type should be string");
+    assert_eq!(types.value(0), "VIEW");
+    assert_eq!(types.value(1), "BASE TABLE");
+
+    // Views are not cached, so every query sees the source's current rows
+    async fn count_large_orders(engine: &RuntimeEngine) -> usize {
+        engine
+            .execute_query("SELECT * FROM testdb.sales.large_orders")
+            .await
+            .expect("Query failed")
+            .results
+            .iter()
+            .map(|b| b.num_rows())
+            .sum()
+    }
+    assert_eq!(count_large_orders(&engine).await, 1);
+
+    let db = duckdb::Connection::open(&duckdb_path).expect("Failed to open DuckDB");
+    db.execute("INSERT INTO sales.orders VALUES (3, 300.0)", [])
+        .expect("Failed to insert");
+    drop(db);
+    assert_eq!(count_large_orders(&engine).await, 2);
+
+    let tables = engine.list_tables(Some("testdb")).await.unwrap();
+    let view = tables
+        .iter()
+        .find(|t| t.table_name == "large_orders")
+        .unwrap();
+    assert_eq!(view.table_type, "VIEW");
+    assert!(view.parquet_path.is_none());
+}
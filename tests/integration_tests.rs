@@ -406,13 +406,14 @@ struct ApiExecutor {
 impl TestExecutor for ApiExecutor {
     async fn connect(&self, name: &str, source: &Source) -> String {
         let (source_type, config) = match source {
-            Source::Duckdb { path } => ("duckdb", json!({ "path": path })),
+            Source::Duckdb { path, .. } => ("duckdb", json!({ "path": path })),
             Source::Postgres {
                 host,
                 port,
                 user,
                 database,
                 credential,
+                ..
             } => {
                 let cred_json = match credential {
                     runtimedb::source::Credential::None => json!({"type": "none"}),
@@ -431,6 +432,7 @@ impl TestExecutor for ApiExecutor {
                 user,
                 database,
                 credential,
+                ..
             } => {
                 let cred_json = match credential {
                     runtimedb::source::Credential::None => json!({"type": "none"}),
@@ -920,6 +922,7 @@ mod fixtures {
             temp_dir,
             Source::Duckdb {
                 path: db_path.to_str().unwrap().to_string(),
+                table_filter: Default::default(),
             },
         )
     }
@@ -948,6 +951,7 @@ mod fixtures {
             temp_dir,
             Source::Duckdb {
                 path: db_path.to_str().unwrap().to_string(),
+                table_filter: Default::default(),
             },
         )
     }
@@ -1008,6 +1012,37 @@ mod postgres_fixtures {
                 credential: runtimedb::source::Credential::SecretRef {
                     name: secret_name.to_string(),
                 },
+                table_filter: Default::default(),
+            },
+        }
+    }
+
+    pub async fn rich_types(secret_name: &str) -> PostgresFixture {
+        let (container, conn_str) = start_container().await;
+        let pool = sqlx::PgPool::connect(&conn_str).await.unwrap();
+
+        sqlx::query(
+            "CREATE TABLE public.events (id INTEGER, payload JSONB, ref UUID, tags TEXT[], scores INTEGER[], starts_at TIME, duration INTERVAL)"
+        ).execute(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO public.events VALUES \
+             (1, '{\"kind\": \"click\", \"count\": 3}', 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', ARRAY['a', 'b'], ARRAY[1, NULL, 3], '09:30:00', '1 month 2 days 03:00:00'), \
+             (2, NULL, NULL, NULL, NULL, NULL, NULL)"
+        ).execute(&pool).await.unwrap();
+        pool.close().await;
+
+        let port = container.get_host_port_ipv4(5432).await.unwrap();
+        PostgresFixture {
+            container,
+            source: Source::Postgres {
+                host: "localhost".into(),
+                port,
+                user: "postgres".into(),
+                database: "postgres".into(),
+                credential: runtimedb::source::Credential::SecretRef {
+                    name: secret_name.to_string(),
+                },
+                table_filter: Default::default(),
             },
         }
     }
@@ -1053,6 +1088,7 @@ mod postgres_fixtures {
                 credential: runtimedb::source::Credential::SecretRef {
                     name: secret_name.to_string(),
                 },
+                table_filter: Default::default(),
             },
         }
     }
@@ -1112,6 +1148,7 @@ mod mysql_fixtures {
                 credential: runtimedb::source::Credential::SecretRef {
                     name: secret_name.to_string(),
                 },
+                table_filter: Default::default(),
             },
         }
     }
@@ -1244,6 +1281,69 @@ mod postgres_tests {
         let fixture = postgres_fixtures::multi_schema(PG_SECRET_NAME).await;
         run_multi_schema_test(harness.api(), &fixture.source, "pg_conn").await;
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_engine_rich_types() {
+        use datafusion::arrow::array::{Array, Int64Array, StringArray};
+        use datafusion::arrow::datatypes::{DataType, IntervalUnit, TimeUnit};
+
+        let harness = TestHarness::new().await;
+        harness
+            .store_secret(PG_SECRET_NAME, postgres_fixtures::TEST_PASSWORD)
+            .await;
+        let fixture = postgres_fixtures::rich_types(PG_SECRET_NAME).await;
+        let engine = &harness.engine_executor.engine;
+        engine
+            .connect("pg_conn", fixture.source.clone())
+            .await
+            .unwrap();
+
+        let response = engine
+            .execute_query("SELECT * FROM pg_conn.public.events ORDER BY id")
+            .await
+            .unwrap();
+        let schema = response.results[0].schema();
+        assert!(matches!(
+            schema.field_with_name("scores").unwrap().data_type(),
+            DataType::List(item) if item.data_type() == &DataType::Int32
+        ));
+        assert_eq!(
+            schema.field_with_name("ref").unwrap().data_type(),
+            &DataType::FixedSizeBinary(16)
+        );
+        assert_eq!(
+            schema.field_with_name("starts_at").unwrap().data_type(),
+            &DataType::Time64(TimeUnit::Microsecond)
+        );
+        assert_eq!(
+            schema.field_with_name("duration").unwrap().data_type(),
+            &DataType::Interval(IntervalUnit::MonthDayNano)
+        );
+
+        // JSON stays queryable through the registered json functions
+        let response = engine
+            .execute_query(
+                "SELECT json_get_str(payload, 'kind') AS kind, json_get_int(payload, 'count') AS count, \
+                 array_length(scores) AS n \
+                 FROM pg_conn.public.events WHERE id = 1",
+            )
+            .await
+            .unwrap();
+        let batch = &response.results[0];
+        let kind = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let count = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(kind.value(0), "click");
+        assert_eq!(count.value(0), 3);
+        assert!(!batch.column(2).is_null(0));
+    }
 }
 
 // ============================================================================
@@ -10,7 +10,11 @@ use axum::{
 };
 use base64::{engine::general_purpose::STANDARD, Engine};
 use rand::RngCore;
-use runtimedb::http::app_server::{AppServer, PATH_CONNECTIONS, PATH_REFRESH};
+use runtimedb::http::app_server::{
+    AppServer, PATH_CACHE_GC, PATH_CACHE_VERIFY, PATH_CONNECTION, PATH_CONNECTIONS,
+    PATH_CONNECTION_SCHEMA_HISTORY, PATH_CONNECTION_SCHEMA_POLICY, PATH_CONNECTION_STALE_TABLES,
+    PATH_METADATA_CACHE, PATH_REFRESH, PATH_SNAPSHOT, PATH_SNAPSHOTS,
+};
 use runtimedb::RuntimeEngine;
 use serde_json::json;
 use std::sync::Arc;
@@ -110,6 +114,73 @@ impl RefreshTestHarness {
         .expect("Failed to create table");
     }
 
+    /// Run a DDL statement against an existing DuckDB
+    fn alter_duckdb(db_path: &str, sql: &str) {
+        let conn = duckdb::Connection::open(db_path).expect("Failed to open DuckDB");
+        conn.execute(sql, []).expect("Failed to alter table");
+    }
+
+    /// Refresh the schema of one connection via API and return the response body
+    async fn refresh_connection_schema(&self, connection_id: &str) -> Result<serde_json::Value> {
+        let response = self
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(PATH_REFRESH)
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_string(&json!({
+                        "connection_id": connection_id
+                    }))?))?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Fetch a connection's schema history via API
+    async fn schema_history(&self, connection_id: &str) -> Result<serde_json::Value> {
+        let response = self
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(PATH_CONNECTION_SCHEMA_HISTORY.replace("{connection_id}", connection_id))
+                    .body(Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Set a connection's schema change policy via API
+    async fn set_schema_policy(&self, connection_id: &str, policy: &str) -> Result<()> {
+        let response = self
+            .router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(PATH_CONNECTION_SCHEMA_POLICY.replace("{connection_id}", connection_id))
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_string(&json!({
+                        "policy": policy
+                    }))?))?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        Ok(())
+    }
+
     /// Create a connection via API and return the connection_id
     async fn create_connection(&self, name: &str, db_path: &str) -> Result<String> {
         let response = self
@@ -321,6 +392,767 @@ async fn test_refresh_all_schemas_multiple_connections() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_refresh_schema_records_schema_history() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("history_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+
+    RefreshTestHarness::alter_duckdb(&db_path, "ALTER TABLE sales.orders DROP COLUMN amount");
+    RefreshTestHarness::alter_duckdb(&db_path, "ALTER TABLE sales.orders ADD COLUMN note VARCHAR");
+
+    let json = harness.refresh_connection_schema(&connection_id).await?;
+    assert_eq!(json["tables_modified"], 1);
+
+    let history = harness.schema_history(&connection_id).await?;
+    let versions = history["versions"].as_array().unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0]["version"], 1);
+    assert_eq!(versions[0]["changes"], json!([]));
+    assert_eq!(versions[1]["version"], 2);
+    assert_eq!(versions[1]["status"], "applied");
+    assert_eq!(
+        versions[1]["changes"],
+        json!([
+            {"change": "dropped", "column": "amount", "old_type": "Float64"},
+            {"change": "added", "column": "note", "new_type": "Utf8"},
+        ])
+    );
+
+    let result = harness
+        .engine
+        .execute_query(
+            "SELECT change_type, column_name FROM runtimedb.information_schema.schema_changes \
+             WHERE table_catalog = 'test_conn' AND table_name = 'orders' ORDER BY column_name",
+        )
+        .await?;
+    let batch = &result.results[0];
+    assert_eq!(batch.num_rows(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_refresh_schema_block_policy_keeps_schema() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("block_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+    harness.set_schema_policy(&connection_id, "block").await?;
+
+    let before = harness.engine.list_tables(Some("test_conn")).await?[0]
+        .arrow_schema_json
+        .clone();
+
+    RefreshTestHarness::alter_duckdb(&db_path, "ALTER TABLE sales.orders DROP COLUMN amount");
+
+    // Refreshing twice records the blocked change only once
+    for _ in 0..2 {
+        let json = harness.refresh_connection_schema(&connection_id).await?;
+        assert_eq!(json["tables_modified"], 0);
+    }
+
+    let after = harness.engine.list_tables(Some("test_conn")).await?[0]
+        .arrow_schema_json
+        .clone();
+    assert_eq!(before, after);
+
+    let history = harness.schema_history(&connection_id).await?;
+    let versions = history["versions"].as_array().unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[1]["status"], "blocked");
+
+    // Additive changes are never blocked
+    RefreshTestHarness::alter_duckdb(
+        &db_path,
+        "ALTER TABLE sales.orders ADD COLUMN amount DOUBLE",
+    );
+    RefreshTestHarness::alter_duckdb(&db_path, "ALTER TABLE sales.orders ADD COLUMN note VARCHAR");
+    let json = harness.refresh_connection_schema(&connection_id).await?;
+    assert_eq!(json["tables_modified"], 1);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_refresh_schema_apply_and_purge_drops_cache() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("purge_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+    harness
+        .set_schema_policy(&connection_id, "apply_and_purge")
+        .await?;
+
+    harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+    assert!(harness.engine.list_tables(Some("test_conn")).await?[0]
+        .parquet_path
+        .is_some());
+
+    RefreshTestHarness::alter_duckdb(&db_path, "ALTER TABLE sales.orders DROP COLUMN amount");
+    harness.refresh_connection_schema(&connection_id).await?;
+
+    assert!(harness.engine.list_tables(Some("test_conn")).await?[0]
+        .parquet_path
+        .is_none());
+
+    let result = harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+    assert_eq!(result.results[0].num_columns(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_refresh_schema_marks_missing_tables_stale() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb_multi_table("stale_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+
+    RefreshTestHarness::alter_duckdb(&db_path, "DROP TABLE sales.products");
+
+    let json = harness.refresh_connection_schema(&connection_id).await?;
+    assert_eq!(json["tables_discovered"], 2);
+    assert_eq!(json["tables_stale"], 1);
+    assert_eq!(json["stale_tables"][0]["table_name"], "products");
+    assert!(json["stale_tables"][0]["stale_since"].is_string());
+
+    // A table that comes back is no longer stale
+    RefreshTestHarness::add_table_to_duckdb(&db_path, "sales", "products");
+    let json = harness.refresh_connection_schema(&connection_id).await?;
+    assert_eq!(json["tables_stale"], 0);
+    assert!(json.get("stale_tables").is_none());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prune_stale_tables_removes_metadata_and_schedules_files() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb_multi_table("prune_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+
+    // Cache the table that is about to disappear
+    harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.products")
+        .await?;
+    let cache_path = harness
+        .engine
+        .list_tables(Some("test_conn"))
+        .await?
+        .into_iter()
+        .find(|t| t.table_name == "products")
+        .and_then(|t| t.parquet_path)
+        .expect("products should be cached");
+
+    RefreshTestHarness::alter_duckdb(&db_path, "DROP TABLE sales.products");
+    harness.refresh_connection_schema(&connection_id).await?;
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(PATH_CONNECTION_STALE_TABLES.replace("{connection_id}", &connection_id))
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    let pruned = json["tables_pruned"].as_array().unwrap();
+    assert_eq!(pruned.len(), 1);
+    assert_eq!(pruned[0]["table_name"], "products");
+
+    let tables = harness.engine.list_tables(Some("test_conn")).await?;
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].table_name, "orders");
+
+    // Cached files are removed by pending deletions once the grace period passes
+    assert!(std::path::Path::new(cache_path.strip_prefix("file://").unwrap()).exists());
+    let due = harness.engine.catalog().get_pending_deletions().await?;
+    assert!(due.is_empty(), "deletion waits for the grace period");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_verify_cache_resets_broken_tables_and_finds_orphans() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("verify_test");
+    harness.create_connection("test_conn", &db_path).await?;
+
+    harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+    let cache_path = harness.engine.list_tables(Some("test_conn")).await?[0]
+        .parquet_path
+        .clone()
+        .expect("orders should be cached");
+    let cache_dir = std::path::PathBuf::from(cache_path.strip_prefix("file://").unwrap());
+
+    // Lose the cached file, and leave a directory no table references
+    std::fs::remove_dir_all(&cache_dir)?;
+    let stray_dir = cache_dir.parent().unwrap().join("stray");
+    std::fs::create_dir_all(&stray_dir)?;
+    let stray_file = std::fs::File::create(stray_dir.join("data.parquet"))?;
+    stray_file
+        .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(86400))?;
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("{}?delete_orphans=true", PATH_CACHE_VERIFY))
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    assert_eq!(json["tables_checked"], 1);
+    let reset = json["tables_reset"].as_array().unwrap();
+    assert_eq!(reset.len(), 1);
+    assert_eq!(reset[0]["table_name"], "orders");
+    assert_eq!(
+        json["orphaned_paths"],
+        json!([format!("file://{}", stray_dir.display())])
+    );
+    assert_eq!(json["orphans_scheduled"], 1);
+
+    let scheduled = harness.engine.catalog().list_scheduled_deletions().await?;
+    assert!(scheduled
+        .iter()
+        .any(|d| d.path == format!("file://{}", stray_dir.display())));
+
+    // The reset table is fetched again on the next query
+    let tables = harness.engine.list_tables(Some("test_conn")).await?;
+    assert!(tables[0].parquet_path.is_none());
+    let result = harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+    assert_eq!(result.results[0].num_rows(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cache_gc_reports_then_reclaims_unreferenced_versions() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("gc_test");
+    harness.create_connection("test_conn", &db_path).await?;
+
+    harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+    let cache_path = harness.engine.list_tables(Some("test_conn")).await?[0]
+        .parquet_path
+        .clone()
+        .expect("orders should be cached");
+    let cache_dir = std::path::PathBuf::from(cache_path.strip_prefix("file://").unwrap());
+    let table_dir = cache_dir.parent().unwrap();
+
+    // A version left by a failed fetch, and one a fetch is still writing
+    let failed_dir = table_dir.join("failed");
+    std::fs::create_dir_all(&failed_dir)?;
+    std::fs::write(failed_dir.join("data.parquet"), b"partial")?;
+    std::fs::File::options()
+        .write(true)
+        .open(failed_dir.join("data.parquet"))?
+        .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(7200))?;
+    let in_progress_dir = table_dir.join("in_progress");
+    std::fs::create_dir_all(&in_progress_dir)?;
+    std::fs::write(in_progress_dir.join("data.parquet"), b"partial")?;
+
+    let gc = |method: &'static str| {
+        let router = harness.router.clone();
+        async move {
+            let response = router
+                .oneshot(
+                    Request::builder()
+                        .method(method)
+                        .uri(PATH_CACHE_GC)
+                        .body(Body::empty())?,
+                )
+                .await?;
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+            anyhow::Ok(serde_json::from_slice::<serde_json::Value>(&body)?)
+        }
+    };
+
+    // The dry run only reports
+    let report = gc("GET").await?;
+    assert_eq!(report["dry_run"], true);
+    let versions = report["versions"].as_array().unwrap();
+    assert_eq!(versions.len(), 1);
+    assert_eq!(
+        versions[0]["path"],
+        format!("file://{}", failed_dir.display())
+    );
+    assert_eq!(report["bytes_reclaimable"], 7);
+    assert_eq!(report["versions_reclaimed"], 0);
+    assert!(failed_dir.exists());
+
+    let result = gc("POST").await?;
+    assert_eq!(result["dry_run"], false);
+    assert_eq!(result["versions_reclaimed"], 1);
+    assert_eq!(result["bytes_reclaimed"], 7);
+    assert!(!failed_dir.exists());
+    assert!(in_progress_dir.exists());
+    assert!(cache_dir.exists());
+
+    let result = harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+    assert_eq!(result.results[0].num_rows(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_filter_limits_discovery() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb_multi_table("filter_test");
+    RefreshTestHarness::add_table_to_duckdb(&db_path, "sales", "orders_archive");
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(PATH_CONNECTIONS)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&json!({
+                    "name": "test_conn",
+                    "source_type": "duckdb",
+                    "config": {
+                        "path": db_path,
+                        "table_filter": {
+                            "include": [{"table": "orders*"}],
+                            "exclude": [{"table": ".*_archive", "syntax": "regex"}]
+                        }
+                    }
+                }))?))?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    assert_eq!(json["tables_discovered"], 1);
+
+    let tables = harness.engine.list_tables(Some("test_conn")).await?;
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].table_name, "orders");
+
+    // Tables created later are filtered on refresh too
+    RefreshTestHarness::add_table_to_duckdb(&db_path, "sales", "orders_2024");
+    RefreshTestHarness::add_table_to_duckdb(&db_path, "sales", "customers");
+    let json = harness
+        .refresh_connection_schema(json["id"].as_str().unwrap())
+        .await?;
+    assert_eq!(json["tables_discovered"], 2);
+    assert_eq!(json["tables_added"], 1);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invalid_table_filter_is_rejected() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("bad_filter_test");
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(PATH_CONNECTIONS)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&json!({
+                    "name": "test_conn",
+                    "source_type": "duckdb",
+                    "config": {
+                        "path": db_path,
+                        "table_filter": {"exclude": [{"table": "(", "syntax": "regex"}]}
+                    }
+                }))?))?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(harness.engine.list_connections().await?.is_empty());
+
+    Ok(())
+}
+
+/// PATCH a connection and return the response status and body
+async fn patch_connection(
+    router: &Router,
+    connection_id: &str,
+    body: serde_json::Value,
+) -> Result<(StatusCode, serde_json::Value)> {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PATCH")
+                .uri(PATH_CONNECTION.replace("{connection_id}", connection_id))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&body)?))?,
+        )
+        .await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body).unwrap_or_default()))
+}
+
+async fn count_rows(engine: &RuntimeEngine, sql: &str) -> Result<usize> {
+    let result = engine.execute_query(sql).await?;
+    Ok(result.results.iter().map(|b| b.num_rows()).sum())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_connection_renames_and_keeps_cache() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("rename_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+
+    count_rows(&harness.engine, "SELECT * FROM test_conn.sales.orders").await?;
+    let cached_path = harness.engine.list_tables(Some("test_conn")).await?[0]
+        .parquet_path
+        .clone()
+        .expect("orders should be cached");
+
+    let (status, json) = patch_connection(
+        &harness.router,
+        &connection_id,
+        json!({"name": "renamed_conn"}),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["id"], connection_id.as_str());
+    assert_eq!(json["name"], "renamed_conn");
+
+    let tables = harness.engine.list_tables(Some("renamed_conn")).await?;
+    assert_eq!(
+        tables[0].parquet_path.as_deref(),
+        Some(cached_path.as_str())
+    );
+    assert_eq!(
+        count_rows(&harness.engine, "SELECT * FROM renamed_conn.sales.orders").await?,
+        2
+    );
+    assert!(harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await
+        .is_err());
+
+    // Names stay unique and the source type is fixed
+    harness.create_connection("other_conn", &db_path).await?;
+    let (status, _) = patch_connection(
+        &harness.router,
+        &connection_id,
+        json!({"name": "other_conn"}),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = patch_connection(
+        &harness.router,
+        &connection_id,
+        json!({"config": {"type": "postgres"}}),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Rejected updates leave no password secret behind
+    let (status, _) = patch_connection(
+        &harness.router,
+        &connection_id,
+        json!({"name": "other_conn", "config": {"password": "hunter2"}}),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = patch_connection(
+        &harness.router,
+        &connection_id,
+        json!({"config": {"password": "hunter2", "path": 42}}),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(harness.engine.secret_manager().list().await?.is_empty());
+    let (status, _) = patch_connection(&harness.router, "missing", json!({"name": "x"})).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_connection_config_can_invalidate_cache() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("config_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+    count_rows(&harness.engine, "SELECT * FROM test_conn.sales.orders").await?;
+
+    let new_path = harness.create_duckdb("config_test_moved");
+    RefreshTestHarness::alter_duckdb(
+        &new_path,
+        "INSERT INTO sales.orders VALUES (3, 'Carol', 300.0)",
+    );
+
+    // Without invalidation the cached data keeps serving queries
+    let (status, _) = patch_connection(
+        &harness.router,
+        &connection_id,
+        json!({"config": {"path": new_path}}),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::OK);
+    let conn = harness
+        .engine
+        .catalog()
+        .get_connection("test_conn")
+        .await?
+        .unwrap();
+    assert!(conn.config_json.contains("config_test_moved"));
+    assert_eq!(
+        count_rows(&harness.engine, "SELECT * FROM test_conn.sales.orders").await?,
+        2
+    );
+
+    let (status, _) = patch_connection(
+        &harness.router,
+        &connection_id,
+        json!({"invalidate_cache": true}),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(harness.engine.list_tables(Some("test_conn")).await?[0]
+        .parquet_path
+        .is_none());
+    assert_eq!(
+        count_rows(&harness.engine, "SELECT * FROM test_conn.sales.orders").await?,
+        3
+    );
+
+    Ok(())
+}
+
+/// Poll GET /connections/{id} until the connection's warm-up has completed
+async fn wait_for_warm_up(router: &Router, connection_id: &str) -> Result<serde_json::Value> {
+    for _ in 0..200 {
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(PATH_CONNECTION.replace("{connection_id}", connection_id))
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        if json["warm_up_progress"]["state"] == "completed" {
+            return Ok(json);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    anyhow::bail!("warm-up of {} did not complete", connection_id)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_warm_up_caches_matching_tables_after_discovery_and_on_startup() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb_multi_table("warm_up_test");
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(PATH_CONNECTIONS)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&json!({
+                    "name": "test_conn",
+                    "source_type": "duckdb",
+                    "config": {"path": db_path},
+                    "warm_up": [{"schema": "sales", "table": "ord*"}]
+                }))?))?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    let connection_id = json["id"].as_str().unwrap().to_string();
+
+    let json = wait_for_warm_up(&harness.router, &connection_id).await?;
+    assert_eq!(json["warm_up"][0]["table"], "ord*");
+    assert_eq!(json["warm_up_progress"]["tables_total"], 1);
+    assert_eq!(json["warm_up_progress"]["tables_cached"], 1);
+    assert_eq!(json["warm_up_progress"]["tables_failed"], 0);
+    assert_eq!(json["synced_table_count"], 1);
+
+    let tables = harness.engine.list_tables(Some("test_conn")).await?;
+    let cached: Vec<_> = tables
+        .iter()
+        .filter(|t| t.parquet_path.is_some())
+        .map(|t| t.table_name.as_str())
+        .collect();
+    assert_eq!(cached, vec!["orders"]);
+
+    // A restarted engine fetches warm-up tables that went missing from the cache
+    harness
+        .engine
+        .purge_table("test_conn", "sales", "orders")
+        .await?;
+    harness.engine.shutdown().await?;
+
+    let engine = RuntimeEngine::builder()
+        .base_dir(harness.temp_dir.path())
+        .secret_key(generate_test_secret_key())
+        .build()
+        .await?;
+    let app = AppServer::new(engine);
+
+    let json = wait_for_warm_up(&app.router, &connection_id).await?;
+    assert_eq!(json["warm_up_progress"]["tables_cached"], 1);
+    let orders = app
+        .engine
+        .list_tables(Some("test_conn"))
+        .await?
+        .into_iter()
+        .find(|t| t.table_name == "orders")
+        .unwrap();
+    assert!(orders.parquet_path.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invalid_warm_up_pattern_is_rejected() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("bad_warm_up_test");
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(PATH_CONNECTIONS)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&json!({
+                    "name": "test_conn",
+                    "source_type": "duckdb",
+                    "config": {"path": db_path},
+                    "warm_up": [{"table": "(", "syntax": "regex"}]
+                }))?))?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(harness.engine.list_connections().await?.is_empty());
+
+    Ok(())
+}
+
+// ============================================================================
+// Catalog Resolution Tests
+// ============================================================================
+
+fn catalog_names(engine: &RuntimeEngine) -> Vec<String> {
+    engine.session_context().catalog_names()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_removed_connection_is_deregistered() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("removed_test");
+    harness.create_connection("test_conn", &db_path).await?;
+
+    assert_eq!(
+        count_rows(&harness.engine, "SELECT * FROM test_conn.sales.orders").await?,
+        2
+    );
+    assert!(catalog_names(&harness.engine).contains(&"test_conn".to_string()));
+
+    harness.engine.remove_connection("test_conn").await?;
+    assert!(!catalog_names(&harness.engine).contains(&"test_conn".to_string()));
+    assert!(harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await
+        .is_err());
+
+    // A new connection with the same name resolves to the new source
+    let new_db_path = harness.create_duckdb_multi_table("recreated_test");
+    harness.create_connection("test_conn", &new_db_path).await?;
+    assert_eq!(
+        count_rows(&harness.engine, "SELECT * FROM test_conn.sales.orders").await?,
+        1
+    );
+    assert_eq!(
+        count_rows(&harness.engine, "SELECT * FROM test_conn.sales.products").await?,
+        1
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_connection_changes_are_visible_to_other_instances() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let other = RuntimeEngine::builder()
+        .base_dir(harness.temp_dir.path())
+        .secret_key(generate_test_secret_key())
+        .catalog_refresh_interval(std::time::Duration::ZERO)
+        .build()
+        .await?;
+
+    // Added through one instance, queryable from the other without a restart
+    let db_path = harness.create_duckdb("shared_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+    assert!(catalog_names(&other).contains(&"test_conn".to_string()));
+    assert_eq!(
+        count_rows(&other, "SELECT * FROM test_conn.sales.orders").await?,
+        2
+    );
+
+    // Schema changes discovered by one instance rebuild the other's table providers
+    RefreshTestHarness::alter_duckdb(&db_path, "ALTER TABLE sales.orders ADD COLUMN note VARCHAR");
+    harness.refresh_connection_schema(&connection_id).await?;
+    assert_eq!(
+        count_rows(&other, "SELECT note FROM test_conn.sales.orders").await?,
+        2
+    );
+
+    harness.engine.remove_connection("test_conn").await?;
+    assert!(!catalog_names(&other).contains(&"test_conn".to_string()));
+    assert!(other
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await
+        .is_err());
+
+    other.shutdown().await?;
+    Ok(())
+}
+
 // ============================================================================
 // Data Refresh Tests
 // ============================================================================
@@ -1109,6 +1941,116 @@ async fn test_pending_deletions_respect_timing() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snapshot_pins_version_across_refresh() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("snapshot_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+
+    harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+    let pinned_path = harness.engine.list_tables(Some("test_conn")).await?[0]
+        .parquet_path
+        .clone()
+        .expect("orders should be cached");
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(PATH_SNAPSHOTS)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&json!({
+                    "name": "before_refresh",
+                    "connection_id": connection_id
+                }))?))?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    assert_eq!(json["tables"].as_array().unwrap().len(), 1);
+    assert_eq!(json["tables"][0]["parquet_path"], pinned_path.as_str());
+    assert_eq!(json["tables"][0]["connection_id"], connection_id.as_str());
+
+    // Refresh replaces the cached version, but keeps the pinned one
+    RefreshTestHarness::alter_duckdb(
+        &db_path,
+        "INSERT INTO sales.orders VALUES (3, 'Carol', 300.0)",
+    );
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(PATH_REFRESH)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&json!({
+                    "connection_id": connection_id,
+                    "schema_name": "sales",
+                    "table_name": "orders",
+                    "data": true
+                }))?))?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let scheduled = harness.engine.catalog().list_scheduled_deletions().await?;
+    assert!(scheduled.iter().all(|d| d.path != pinned_path));
+
+    let current = harness
+        .engine
+        .execute_query("SELECT COUNT(*) AS n FROM test_conn.sales.orders")
+        .await?;
+    let pinned = harness
+        .engine
+        .execute_query(
+            "SELECT COUNT(*) AS n FROM table_at_snapshot('before_refresh', 'test_conn', 'sales', 'orders')",
+        )
+        .await?;
+    let count = |batches: &[datafusion::arrow::record_batch::RecordBatch]| {
+        batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<datafusion::arrow::array::Int64Array>()
+            .unwrap()
+            .value(0)
+    };
+    assert_eq!(count(&current.results), 3);
+    assert_eq!(count(&pinned.results), 2);
+
+    // Expiring the snapshot releases the pinned version
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(PATH_SNAPSHOT.replace("{name}", "before_refresh"))
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let scheduled = harness.engine.catalog().list_scheduled_deletions().await?;
+    assert!(scheduled.iter().any(|d| d.path == pinned_path));
+    assert!(harness.engine.list_snapshots().await?.is_empty());
+    assert!(harness
+        .engine
+        .execute_query(
+            "SELECT * FROM table_at_snapshot('before_refresh', 'test_conn', 'sales', 'orders')"
+        )
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 // ============================================================================
 // Response Format Tests
 // ============================================================================
@@ -1858,3 +2800,97 @@ async fn test_connection_refresh_omits_empty_warnings() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_statistics_from_discovery_and_cache() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("stats_test");
+    harness.create_connection("test_conn", &db_path).await?;
+
+    // DuckDB reports its row count estimate at discovery
+    let tables = harness.engine.list_tables(Some("test_conn")).await?;
+    assert_eq!(tables[0].estimated_rows, Some(2));
+    assert!(tables[0].cache_stats_json.is_none());
+
+    harness
+        .engine
+        .execute_query("SELECT * FROM test_conn.sales.orders")
+        .await?;
+
+    // Caching records exact statistics for the cached file
+    let tables = harness.engine.list_tables(Some("test_conn")).await?;
+    let stats: runtimedb::datafetch::CacheStatistics =
+        serde_json::from_str(tables[0].cache_stats_json.as_deref().unwrap())?;
+    assert_eq!(Some(stats.url.as_str()), tables[0].parquet_path.as_deref());
+    assert_eq!(stats.num_rows, 2);
+    let amount = stats.columns.iter().find(|c| c.name == "amount").unwrap();
+    let bound = |value: &Option<String>| value.as_deref().map(|v| v.parse::<f64>().unwrap());
+    assert_eq!(bound(&amount.min), Some(100.0));
+    assert_eq!(bound(&amount.max), Some(200.0));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_metadata_cache_serves_repeat_queries_until_refresh() -> Result<()> {
+    let harness = RefreshTestHarness::new().await?;
+    let db_path = harness.create_duckdb("metadata_cache_test");
+    let connection_id = harness.create_connection("test_conn", &db_path).await?;
+    let query = "SELECT * FROM test_conn.sales.orders";
+
+    harness.engine.execute_query(query).await?;
+    let first = harness.engine.metadata_cache_stats();
+    assert!(first.entries > 0);
+
+    // The second scan of the same version reuses its listing and statistics
+    harness.engine.execute_query(query).await?;
+    let second = harness.engine.metadata_cache_stats();
+    assert!(second.hits > first.hits);
+
+    // Refreshing swaps the table to a new version, dropping the old version's entries
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(PATH_REFRESH)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&json!({
+                    "connection_id": connection_id,
+                    "schema_name": "sales",
+                    "table_name": "orders",
+                    "data": true
+                }))?))?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(harness.engine.metadata_cache_stats().entries, 0);
+
+    let result = harness.engine.execute_query(query).await?;
+    assert_eq!(
+        result.results.iter().map(|b| b.num_rows()).sum::<usize>(),
+        2
+    );
+    assert!(harness.engine.metadata_cache_stats().misses > second.misses);
+
+    let response = harness
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(PATH_METADATA_CACHE)
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    let stats = harness.engine.metadata_cache_stats();
+    assert_eq!(json["hits"], stats.hits);
+    assert_eq!(json["entries"], stats.entries);
+    assert!(json["hit_rate"].as_f64().unwrap() > 0.0);
+
+    Ok(())
+}
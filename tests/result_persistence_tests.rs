@@ -86,6 +86,10 @@ impl StorageManager for FailingStorage {
         self.inner.cache_prefix(connection_id)
     }
 
+    fn cache_root(&self) -> String {
+        self.inner.cache_root()
+    }
+
     async fn read(&self, url: &str) -> Result<Vec<u8>> {
         self.inner.read(url).await
     }
@@ -127,6 +131,17 @@ impl StorageManager for FailingStorage {
         handle
     }
 
+    fn prepare_append_write(
+        &self,
+        connection_id: i32,
+        schema: &str,
+        table: &str,
+        base_version: &str,
+    ) -> CacheWriteHandle {
+        self.inner
+            .prepare_append_write(connection_id, schema, table, base_version)
+    }
+
     async fn finalize_cache_write(&self, handle: &CacheWriteHandle) -> Result<String> {
         if self.config.fail_finalize.load(Ordering::SeqCst) {
             anyhow::bail!("Injected storage failure at finalize")
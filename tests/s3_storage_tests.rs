@@ -4,8 +4,14 @@
 //!
 //! Run these tests with: cargo test --test s3_storage_tests
 
+use datafusion::arrow::array::Int64Array;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use runtimedb::datafetch::{CacheStatistics, StreamingParquetWriter};
 use runtimedb::storage::{S3Credentials, S3Storage, StorageManager};
 use std::process::Command;
+use std::sync::Arc;
 use std::time::Duration;
 use testcontainers::{runners::AsyncRunner, ContainerAsync};
 use testcontainers_modules::minio::MinIO;
@@ -194,6 +200,90 @@ async fn s3_storage_delete_prefix_removes_versioned_directory() {
     );
 }
 
+/// Parquet written through a cache upload lands in its versioned directory without a
+/// local temp file, split into several parts.
+#[tokio::test(flavor = "multi_thread")]
+async fn s3_storage_streams_cache_write_as_multipart_upload() {
+    let infra = MinioTestInfra::start().await;
+    let storage = &infra.storage;
+
+    let mut handle = storage.prepare_cache_write(7, "public", "events");
+    handle.upload = storage.begin_cache_upload(&handle).await.unwrap();
+    assert!(handle.upload.is_some());
+
+    // Values that barely compress, so the file spans more than one part
+    let schema = Arc::new(Schema::new(vec![Field::new("v", DataType::Int64, false)]));
+    let mut writer = StreamingParquetWriter::new(handle.local_path.clone())
+        .with_upload(handle.upload.clone().unwrap());
+    writer.init(&schema).unwrap();
+    let mut seed: i64 = 42;
+    for _ in 0..4 {
+        let values: Vec<i64> = (0..1_000_000)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                seed
+            })
+            .collect();
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(Int64Array::from(values))]).unwrap();
+        writer.write_batch(&batch).await.unwrap();
+    }
+    let (_, row_count) = writer.close().await.unwrap();
+    assert_eq!(row_count, 4_000_000);
+
+    let tail = handle.upload.as_ref().unwrap().tail().unwrap();
+    let stats = CacheStatistics::from_parquet_footer(&tail).unwrap();
+    assert_eq!(stats.num_rows, 4_000_000);
+
+    let versioned_dir_url = storage.finalize_cache_write(&handle).await.unwrap();
+    assert!(!handle.local_path.exists());
+
+    let data = storage
+        .read(&format!("{}/data.parquet", versioned_dir_url))
+        .await
+        .unwrap();
+    assert!(data.len() > 10 * 1024 * 1024);
+    let dir = tempfile::tempdir().unwrap();
+    let downloaded = dir.path().join("data.parquet");
+    std::fs::write(&downloaded, data).unwrap();
+    let reader =
+        ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&downloaded).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+    let rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+    assert_eq!(rows, 4_000_000);
+}
+
+/// A cache write that fails before it is finalized leaves nothing behind.
+#[tokio::test(flavor = "multi_thread")]
+async fn s3_storage_dropped_upload_is_aborted() {
+    use datafusion::parquet::arrow::async_writer::AsyncFileWriter;
+
+    let infra = MinioTestInfra::start().await;
+    let storage = &infra.storage;
+
+    let mut handle = storage.prepare_cache_write(8, "public", "events");
+    handle.upload = storage.begin_cache_upload(&handle).await.unwrap();
+    let mut writer = handle.upload.as_ref().unwrap().writer().unwrap();
+    writer
+        .write(bytes::Bytes::from_static(b"PAR1partial"))
+        .await
+        .unwrap();
+    drop(writer);
+    let file_url = format!(
+        "{}/{}/{}",
+        storage.cache_url(8, "public", "events"),
+        handle.version,
+        handle.file_name
+    );
+    drop(handle);
+
+    assert!(!storage.exists(&file_url).await.unwrap());
+}
+
 /// Test that S3Storage created with new_with_config returns credentials via get_s3_credentials
 #[test]
 fn s3_storage_get_credentials_with_config() {